@@ -10,7 +10,7 @@ use penumbra_dex::{
 };
 use penumbra_fee::Fee;
 use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
 use penumbra_proof_params::{DummyWitness, SWAPCLAIM_PROOF_PROVING_KEY};
 use penumbra_sct::Nullifier;
 use penumbra_tct as tct;
@@ -70,6 +70,8 @@ fn swap_claim_proving_time(c: &mut Criterion) {
         height: height.into(),
         trading_pair: swap_plaintext.trading_pair,
         sct_position_prefix: position,
+        limit_price_1_for_2: U128x128::default(),
+        limit_price_2_for_1: U128x128::default(),
     };
     let (lambda_1, lambda_2) = output_data.pro_rata_outputs((delta_1_i, delta_2_i));
 
@@ -0,0 +1,260 @@
+//! Support for authorizing transactions on an air-gapped machine.
+//!
+//! The online machine plans a transaction and exports it with
+//! `pcli tx --export-plan`, the offline machine authorizes it with its custody
+//! backend using `pcli tx authorize`, and the online machine then witnesses,
+//! proves and broadcasts it with `pcli tx import`.
+
+use std::{future::Future, io::Write, path::Path, pin::Pin};
+
+use anyhow::{Context, Result};
+use futures::FutureExt;
+use penumbra_asset::{asset::Cache, Value, STAKING_TOKEN_ASSET_ID};
+use penumbra_custody::{AuthorizeRequest, CustodyClient};
+use penumbra_keys::FullViewingKey;
+use penumbra_proto::{
+    core::transaction::v1 as pb, custody::v1::AuthorizeResponse, DomainType as _,
+};
+use penumbra_transaction::{ActionPlan, AuthorizationData, TransactionPlan};
+use serde::{Deserialize, Serialize};
+
+/// A transaction plan exported for authorization on another machine.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedPlan {
+    /// A human-readable summary of the transaction's effects.
+    pub summary: Vec<String>,
+    /// The hex-encoded effect hash of the transaction, which is what the
+    /// authorization signs.
+    pub effect_hash: String,
+    /// The plan of the transaction.
+    pub plan: TransactionPlan,
+}
+
+impl ExportedPlan {
+    pub fn new(fvk: &FullViewingKey, plan: TransactionPlan, asset_cache: &Cache) -> Result<Self> {
+        Ok(Self {
+            summary: summarize(&plan, asset_cache),
+            effect_hash: hex::encode(plan.effect_hash(fvk)?.as_bytes()),
+            plan,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::read(path)
+            .with_context(|| format!("could not read exported plan {}", path.display()))?;
+        serde_json::from_slice(&file).context("could not parse exported plan")
+    }
+
+    /// Saves the plan to `path`, which must not exist yet.
+    ///
+    /// Refusing to overwrite files ensures that commands which submit several
+    /// transactions can't silently replace the plans they've exported.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("could not create exported plan {}", path.display()))?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Checks that the exported effect hash is the one `fvk` computes for the
+    /// plan, so that a plan built for another wallet, or a plan modified after
+    /// it was exported, is caught before it's authorized or built.
+    ///
+    /// The exported summary isn't checked: it's only a preview, and the summary
+    /// shown before authorizing is recomputed from the plan itself.
+    pub fn check_effect_hash(&self, fvk: &FullViewingKey) -> Result<()> {
+        let effect_hash = hex::encode(self.plan.effect_hash(fvk)?.as_bytes());
+        anyhow::ensure!(
+            effect_hash == self.effect_hash,
+            "exported effect hash {} does not match the plan's effect hash {}",
+            self.effect_hash,
+            effect_hash
+        );
+        Ok(())
+    }
+}
+
+/// Loads authorization data saved by `pcli tx authorize`.
+pub fn load_authorization_data(path: &Path) -> Result<AuthorizationData> {
+    let file = std::fs::read(path)
+        .with_context(|| format!("could not read authorization data {}", path.display()))?;
+    let auth_data: pb::AuthorizationData =
+        serde_json::from_slice(&file).context("could not parse authorization data")?;
+    auth_data.try_into()
+}
+
+/// Saves authorization data for `pcli tx import`.
+pub fn save_authorization_data(path: &Path, auth_data: AuthorizationData) -> Result<()> {
+    let auth_data = auth_data.to_proto();
+    std::fs::write(path, serde_json::to_vec_pretty(&auth_data)?)
+        .with_context(|| format!("could not write authorization data {}", path.display()))
+}
+
+/// A custody client that hands out authorization data produced on another
+/// machine, as long as it authorizes the requested plan.
+pub struct PreAuthorized {
+    pub fvk: FullViewingKey,
+    pub auth_data: AuthorizationData,
+}
+
+impl CustodyClient for PreAuthorized {
+    fn authorize(
+        &mut self,
+        request: AuthorizeRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<AuthorizeResponse>> + Send + 'static>> {
+        let effect_hash = request.plan.effect_hash(&self.fvk);
+        let auth_data = self.auth_data.clone();
+        async move {
+            anyhow::ensure!(
+                auth_data.effect_hash == Some(effect_hash?),
+                "authorization data was produced for a different transaction"
+            );
+            Ok(AuthorizeResponse {
+                data: Some(auth_data.into()),
+            })
+        }
+        .boxed()
+    }
+}
+
+/// Describes the effects of `plan`, one line per effect.
+pub fn summarize(plan: &TransactionPlan, cache: &Cache) -> Vec<String> {
+    let staking_value = |amount| {
+        Value {
+            amount,
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        }
+        .format(cache)
+    };
+
+    let mut summary = vec![format!(
+        "fee: {}",
+        plan.transaction_parameters.fee.0.format(cache)
+    )];
+    if plan.transaction_parameters.expiry_height != 0 {
+        summary.push(format!(
+            "expires after height {}",
+            plan.transaction_parameters.expiry_height
+        ));
+    }
+    if let Some(memo) = &plan.memo {
+        summary.push(format!("memo: {:?}", memo.plaintext.text()));
+    }
+
+    for action in &plan.actions {
+        summary.push(match action {
+            ActionPlan::Spend(spend) => format!("spend {}", spend.note.value().format(cache)),
+            ActionPlan::Output(output) => format!(
+                "output {} to {}",
+                output.value.format(cache),
+                output.dest_address
+            ),
+            ActionPlan::Swap(swap) => {
+                let swap = &swap.swap_plaintext;
+                let pair = &swap.trading_pair;
+                let (input, output) = if swap.delta_1_i.value() > 0 {
+                    (
+                        Value {
+                            amount: swap.delta_1_i,
+                            asset_id: pair.asset_1(),
+                        },
+                        pair.asset_2(),
+                    )
+                } else {
+                    (
+                        Value {
+                            amount: swap.delta_2_i,
+                            asset_id: pair.asset_2(),
+                        },
+                        pair.asset_1(),
+                    )
+                };
+                let output = cache
+                    .get(&output)
+                    .map(|metadata| metadata.to_string())
+                    .unwrap_or_else(|| output.to_string());
+                format!(
+                    "swap {} for {}, prepaying a claim fee of {}",
+                    input.format(cache),
+                    output,
+                    swap.claim_fee.0.format(cache)
+                )
+            }
+            ActionPlan::SwapClaim(claim) => {
+                format!("claim swap {}", claim.swap_plaintext.swap_commitment())
+            }
+            ActionPlan::Delegate(delegate) => format!(
+                "delegate {} to {}",
+                staking_value(delegate.unbonded_amount),
+                delegate.validator_identity
+            ),
+            ActionPlan::Undelegate(undelegate) => format!(
+                "undelegate {} from {}",
+                staking_value(undelegate.unbonded_amount),
+                undelegate.validator_identity
+            ),
+            ActionPlan::Redelegate(redelegate) => format!(
+                "redelegate {} from {} to {}",
+                staking_value(redelegate.unbonded_amount),
+                redelegate.from_validator,
+                redelegate.to_validator
+            ),
+            ActionPlan::RedelegateClaim(claim) => format!(
+                "claim a redelegation from {} to {}",
+                claim.from_validator, claim.to_validator
+            ),
+            ActionPlan::UndelegateClaim(claim) => format!(
+                "claim {} undelegated from {}",
+                staking_value(claim.unbonding_amount),
+                claim.validator_identity
+            ),
+            ActionPlan::ValidatorDefinition(_) => "update a validator definition".to_string(),
+            ActionPlan::IbcAction(_) => "relay an IBC message".to_string(),
+            ActionPlan::ProposalSubmit(_) => "submit a governance proposal".to_string(),
+            ActionPlan::ProposalWithdraw(_) => "withdraw a governance proposal".to_string(),
+            ActionPlan::DelegatorVote(_) => "vote on a proposal as a delegator".to_string(),
+            ActionPlan::ValidatorVote(_) => "vote on a proposal as a validator".to_string(),
+            ActionPlan::ProposalDepositClaim(_) => "claim a proposal deposit".to_string(),
+            ActionPlan::PositionOpen(_) => "open a liquidity position".to_string(),
+            ActionPlan::PositionClose(_) => "close a liquidity position".to_string(),
+            ActionPlan::PositionWithdraw(_) => "withdraw a liquidity position".to_string(),
+            ActionPlan::CommunityPoolSpend(_) => "spend from the community pool".to_string(),
+            ActionPlan::CommunityPoolOutput(_) => "output from the community pool".to_string(),
+            ActionPlan::CommunityPoolDeposit(_) => "deposit to the community pool".to_string(),
+            ActionPlan::Ics20Withdrawal(withdrawal) => format!(
+                "withdraw {} over IBC to {}",
+                withdrawal.value().format(cache),
+                withdrawal.destination_chain_address
+            ),
+            ActionPlan::ActionDutchAuctionSchedule(_) => "schedule a Dutch auction".to_string(),
+            ActionPlan::ActionDutchAuctionEnd(_) => "end a Dutch auction".to_string(),
+            ActionPlan::ActionDutchAuctionWithdraw(_) => {
+                "withdraw from a Dutch auction".to_string()
+            }
+        });
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exported_plan_effect_hash() {
+        let fvk = penumbra_keys::test_keys::FULL_VIEWING_KEY.clone();
+        let exported = ExportedPlan::new(&fvk, TransactionPlan::default(), &Cache::default())
+            .expect("can export an empty plan");
+
+        let json = serde_json::to_vec(&exported).unwrap();
+        let mut exported: ExportedPlan = serde_json::from_slice(&json).unwrap();
+        exported.check_effect_hash(&fvk).unwrap();
+
+        exported.plan.transaction_parameters.chain_id = "another-chain".to_string();
+        assert!(exported.check_effect_hash(&fvk).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 pub use debug::DebugCmd;
 pub use init::InitCmd;
 pub use query::QueryCmd;
-pub use threshold::ThresholdCmd;
+pub use threshold::{RelayOptions, ThresholdCmd};
 pub use tx::TxCmd;
 pub use validator::ValidatorCmd;
 pub use view::ViewCmd;
@@ -5,13 +5,14 @@ use std::{
 
 use anyhow::Result;
 use camino::Utf8PathBuf;
-use penumbra_custody::threshold;
+use penumbra_custody::threshold::{self, RelayRole};
 use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
 use rand_core::OsRng;
 use termion::screen::IntoAlternateScreen;
 use url::Url;
 
 use crate::{
+    command::RelayOptions,
     config::{CustodyConfig, GovernanceCustodyConfig, PcliConfig},
     terminal::ActualTerminal,
 };
@@ -182,6 +183,8 @@ pub enum ThresholdInitCmd {
         /// The maximum number of signers that can make a signature
         #[clap(short, long)]
         num_participants: u16,
+        #[clap(flatten)]
+        relay: RelayOptions,
     },
 }
 
@@ -324,12 +327,19 @@ impl InitCmd {
                 InitSubCmd::Threshold(ThresholdInitCmd::Dkg {
                     threshold,
                     num_participants,
+                    relay,
                 }),
                 false,
             ) => {
-                let config =
-                    threshold::dkg(*threshold, *num_participants, &ActualTerminal::default())
-                        .await?;
+                let config = match relay.terminal(ActualTerminal::default(), RelayRole::Peer)? {
+                    Some(terminal) => {
+                        threshold::dkg(*threshold, *num_participants, &terminal).await?
+                    }
+                    None => {
+                        threshold::dkg(*threshold, *num_participants, &ActualTerminal::default())
+                            .await?
+                    }
+                };
                 let fvk = config.fvk().clone();
                 let custody_config = if self.encrypted {
                     let password = ActualTerminal::get_confirmed_password().await?;
@@ -1,5 +1,9 @@
-use anyhow::Result;
-use penumbra_custody::threshold::Terminal;
+use std::{net::SocketAddr, time::Duration};
+
+use anyhow::{Context, Result};
+use penumbra_custody::threshold::{RelayRole, RelayServer, RelayTerminal, Terminal};
+use penumbra_proto::custody::threshold::v1::relay_service_server::RelayServiceServer;
+use url::Url;
 
 use crate::{
     config::{CustodyConfig, GovernanceCustodyConfig},
@@ -10,18 +14,89 @@ use crate::{
 #[derive(Debug, clap::Subcommand)]
 pub enum ThresholdCmd {
     /// Contribute to signing a transaction with threshold custody
-    Sign,
+    Sign {
+        #[clap(flatten)]
+        relay: RelayOptions,
+    },
+    /// Run a relay, which threshold signers can exchange their messages through
+    Relay {
+        /// The address to listen on.
+        #[clap(long, default_value = "127.0.0.1:8089")]
+        bind: SocketAddr,
+    },
+}
+
+/// Options for exchanging the messages of the threshold protocols through a relay,
+/// instead of copying and pasting them between participants.
+#[derive(Debug, Clone, clap::Args)]
+pub struct RelayOptions {
+    /// The URL of a relay to exchange threshold signing messages through.
+    ///
+    /// A relay can be run with `pcli threshold relay`.
+    #[clap(long = "relay", value_name = "URL", parse(try_from_str = Url::parse))]
+    pub url: Option<Url>,
+    /// The relay session to use, which all participants must agree on.
+    ///
+    /// Each session can only be used once, to produce a single signature or key.
+    #[clap(long = "relay-session", value_name = "SESSION", requires = "url")]
+    pub session: Option<String>,
+    /// The name identifying this participant to the others on the relay.
+    #[clap(long = "relay-name", value_name = "NAME", requires = "url")]
+    pub name: Option<String>,
+    /// How long to wait on each request to the relay, in seconds.
+    #[clap(long = "relay-timeout", value_name = "SECONDS", default_value = "10")]
+    pub timeout: u64,
+}
+
+impl RelayOptions {
+    /// Wrap `terminal` to exchange messages through the relay, if one was given.
+    pub fn terminal<T: Terminal>(
+        &self,
+        terminal: T,
+        role: RelayRole,
+    ) -> Result<Option<RelayTerminal<T>>> {
+        let Some(url) = &self.url else {
+            return Ok(None);
+        };
+        let session = self
+            .session
+            .clone()
+            .context("a --relay-session is required to use a relay")?;
+        let name = match (&self.name, role) {
+            (Some(name), _) => name.clone(),
+            (None, RelayRole::Coordinator) => "coordinator".to_string(),
+            (None, _) => anyhow::bail!("a --relay-name is required to use a relay"),
+        };
+        Ok(Some(RelayTerminal::new(
+            terminal,
+            url.to_string(),
+            session,
+            name,
+            role,
+            Duration::from_secs(self.timeout),
+        )?))
+    }
 }
 
 impl ThresholdCmd {
     pub fn offline(&self) -> bool {
         match self {
-            ThresholdCmd::Sign => true,
+            ThresholdCmd::Sign { .. } => true,
+            ThresholdCmd::Relay { .. } => true,
         }
     }
 
     #[tracing::instrument(skip(self, app))]
     pub async fn exec(&self, app: &mut App) -> Result<()> {
+        if let ThresholdCmd::Relay { bind } = self {
+            println!("Running threshold signing relay on {bind}");
+            return tonic::transport::Server::builder()
+                .add_service(RelayServiceServer::new(RelayServer::new()))
+                .serve(*bind)
+                .await
+                .context("threshold signing relay failed");
+        }
+
         let config = match app.config.custody.clone() {
             CustodyConfig::Threshold(config) => Some(config),
             CustodyConfig::Encrypted(config) => {
@@ -38,14 +113,27 @@ impl ThresholdCmd {
             _ => None,              // If not threshold, we can't sign using governance config
         };
         match self {
-            ThresholdCmd::Sign => {
-                penumbra_custody::threshold::follow(
-                    config.as_ref(),
-                    governance_config.as_ref(),
-                    &ActualTerminal::default(),
-                )
-                .await
+            ThresholdCmd::Sign { relay } => {
+                match relay.terminal(ActualTerminal::default(), RelayRole::Follower)? {
+                    Some(terminal) => {
+                        penumbra_custody::threshold::follow(
+                            config.as_ref(),
+                            governance_config.as_ref(),
+                            &terminal,
+                        )
+                        .await
+                    }
+                    None => {
+                        penumbra_custody::threshold::follow(
+                            config.as_ref(),
+                            governance_config.as_ref(),
+                            &ActualTerminal::default(),
+                        )
+                        .await
+                    }
+                }
             }
+            ThresholdCmd::Relay { .. } => unreachable!("relay command already executed"),
         }
     }
 }
@@ -2,13 +2,14 @@ use std::{
     collections::BTreeMap,
     fs::{self, File},
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{ensure, Context, Result};
 use decaf377::{Fq, Fr};
+use dialoguer::Confirm;
 use ibc_proto::ibc::core::client::v1::{
     query_client::QueryClient as IbcClientQueryClient, QueryClientStateRequest,
 };
@@ -26,7 +27,11 @@ use rand_core::OsRng;
 use regex::Regex;
 
 use liquidity_position::PositionCmd;
-use penumbra_asset::{asset, asset::Metadata, Value, STAKING_TOKEN_ASSET_ID};
+use penumbra_asset::{
+    asset::{self, Cache, Metadata},
+    Value, STAKING_TOKEN_ASSET_ID,
+};
+use penumbra_custody::{AuthorizeRequest, CustodyClient};
 use penumbra_dex::{lp::position, swap_claim::SwapClaimPlan};
 use penumbra_fee::FeeTier;
 use penumbra_governance::{proposal::ProposalToml, proposal_state::State as ProposalState, Vote};
@@ -55,12 +60,16 @@ use penumbra_proto::{
 };
 use penumbra_shielded_pool::Ics20Withdrawal;
 use penumbra_stake::rate::RateData;
-use penumbra_stake::{DelegationToken, IdentityKey, Penalty, UnbondingToken, UndelegateClaimPlan};
-use penumbra_transaction::{gas::swap_claim_gas_cost, Transaction};
+use penumbra_stake::{
+    DelegationToken, IdentityKey, Penalty, RedelegateClaim, RedelegationToken, UnbondingToken,
+    UndelegateClaimPlan,
+};
+use penumbra_transaction::{gas::swap_claim_gas_cost, AuthorizationData, Transaction};
 use penumbra_view::{SpendableNoteRecord, ViewClient};
 use penumbra_wallet::plan::{self, Planner};
 use proposal::ProposalCmd;
 
+use crate::airgap::{self, ExportedPlan};
 use crate::command::tx::auction::AuctionCmd;
 use crate::App;
 use clap::Parser;
@@ -80,6 +89,12 @@ pub struct TxCmdWithOptions {
     /// If present, a file to save the transaction to instead of broadcasting it
     #[clap(long)]
     pub offline: Option<PathBuf>,
+    /// If present, a file to export the transaction plan to instead of building it.
+    ///
+    /// The exported plan can be authorized on an air-gapped machine with
+    /// `pcli tx authorize`, then built and broadcast with `pcli tx import`.
+    #[clap(long, conflicts_with = "offline")]
+    pub export_plan: Option<PathBuf>,
     #[clap(subcommand)]
     pub cmd: TxCmd,
 }
@@ -92,6 +107,7 @@ impl TxCmdWithOptions {
 
     pub async fn exec(&self, app: &mut App) -> Result<()> {
         app.save_transaction_here_instead = self.offline.clone();
+        app.export_plan_here_instead = self.export_plan.clone();
         self.cmd.exec(app).await
     }
 }
@@ -146,6 +162,25 @@ pub enum TxCmd {
         #[clap(short, long, default_value_t)]
         fee_tier: FeeTier,
     },
+    /// Move stake from one validator's delegation pool to another's, without unbonding.
+    ///
+    /// The stake remains liable for slashing penalties applied to the original
+    /// validator until the unbonding delay has passed, after which it can be
+    /// claimed with `pcli tx redelegate-claim`.
+    #[clap(display_order = 200)]
+    Redelegate {
+        /// The identity key of the validator to redelegate to.
+        #[clap(long, display_order = 100)]
+        to: String,
+        /// The amount of delegation tokens to redelegate.
+        amount: String,
+        /// Only spend funds originally received by the given account.
+        #[clap(long, default_value = "0", display_order = 300)]
+        source: u32,
+        /// The selected fee tier to multiply the fee amount by.
+        #[clap(short, long, default_value_t)]
+        fee_tier: FeeTier,
+    },
     /// Claim any undelegations that have finished unbonding.
     #[clap(display_order = 200)]
     UndelegateClaim {
@@ -153,6 +188,14 @@ pub enum TxCmd {
         #[clap(short, long, default_value_t)]
         fee_tier: FeeTier,
     },
+    /// Claim any redelegations that have finished unbonding, converting them
+    /// into delegation tokens of the validator they were redelegated to.
+    #[clap(display_order = 200)]
+    RedelegateClaim {
+        /// The selected fee tier to multiply the fee amount by.
+        #[clap(short, long, default_value_t)]
+        fee_tier: FeeTier,
+    },
     /// Swap tokens of one denomination for another using the DEX.
     ///
     /// Swaps are batched and executed at the market-clearing price.
@@ -168,6 +211,11 @@ pub enum TxCmd {
         /// The denomination to swap the input into, e.g. `gm`
         #[clap(long, display_order = 100)]
         into: String,
+        /// The minimum output to accept, written as a typed value in the `into`
+        /// denomination, e.g. 10gm. If the batch would pay out less, the input is
+        /// returned instead.
+        #[clap(long, display_order = 200)]
+        min_output: Option<String>,
         /// Only spend funds originally received by the given account.
         #[clap(long, default_value = "0", display_order = 300)]
         source: u32,
@@ -260,6 +308,31 @@ pub enum TxCmd {
         /// The transaction to be broadcast
         transaction: PathBuf,
     },
+    /// Authorize a transaction plan exported with `--export-plan`.
+    ///
+    /// This runs offline, so it can be used on an air-gapped machine holding
+    /// the custody configuration. The authorization data it saves can then be
+    /// imported with `pcli tx import` on the machine that exported the plan.
+    #[clap(display_order = 1100)]
+    Authorize {
+        /// The exported transaction plan to authorize.
+        plan: PathBuf,
+        /// The file to save the authorization data to.
+        #[clap(long)]
+        output: PathBuf,
+        /// Authorize the plan without asking for confirmation.
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Build and broadcast an exported transaction plan, using authorization
+    /// data produced for it with `pcli tx authorize`.
+    #[clap(display_order = 1200)]
+    Import {
+        /// The exported transaction plan.
+        plan: PathBuf,
+        /// The authorization data for the plan.
+        authorization: PathBuf,
+    },
 }
 
 /// Vote on a governance proposal.
@@ -307,7 +380,9 @@ impl TxCmd {
             TxCmd::Swap { .. } => false,
             TxCmd::Delegate { .. } => false,
             TxCmd::Undelegate { .. } => false,
+            TxCmd::Redelegate { .. } => false,
             TxCmd::UndelegateClaim { .. } => false,
+            TxCmd::RedelegateClaim { .. } => false,
             TxCmd::Vote { .. } => false,
             TxCmd::Proposal(proposal_cmd) => proposal_cmd.offline(),
             TxCmd::CommunityPoolDeposit { .. } => false,
@@ -315,10 +390,17 @@ impl TxCmd {
             TxCmd::Withdraw { .. } => false,
             TxCmd::Auction(_) => false,
             TxCmd::Broadcast { .. } => false,
+            TxCmd::Authorize { .. } => true,
+            TxCmd::Import { .. } => false,
         }
     }
 
     pub async fn exec(&self, app: &mut App) -> Result<()> {
+        // Authorization happens offline, without a view service to query.
+        if let TxCmd::Authorize { plan, output, yes } = self {
+            return authorize_exported_plan(app, plan, output, *yes).await;
+        }
+
         // TODO: use a command line flag to determine the fee token,
         // and pull the appropriate GasPrices out of this rpc response,
         // the rest should follow
@@ -419,11 +501,23 @@ impl TxCmd {
             TxCmd::Swap {
                 input,
                 into,
+                min_output,
                 source,
                 fee_tier,
             } => {
                 let input = input.parse::<Value>()?;
                 let into = asset::REGISTRY.parse_unit(into.as_str()).base();
+                let min_output = match min_output {
+                    Some(min_output) => {
+                        let min_output = min_output.parse::<Value>()?;
+                        ensure!(
+                            min_output.asset_id == into.id(),
+                            "minimum output must be denominated in the output asset"
+                        );
+                        min_output.amount
+                    }
+                    None => Amount::zero(),
+                };
                 let fee_tier: FeeTier = (*fee_tier).into();
 
                 let fvk = app.config.full_viewing_key.clone();
@@ -444,7 +538,13 @@ impl TxCmd {
                     .fee(&swap_claim_gas_cost())
                     .apply_tier(fee_tier.into());
 
-                planner.swap(input, into.id(), estimated_claim_fee, claim_address)?;
+                planner.swap_with_min_output(
+                    input,
+                    into.id(),
+                    min_output,
+                    estimated_claim_fee,
+                    claim_address,
+                )?;
 
                 let plan = planner
                     .plan(app.view(), AddressIndex::new(*source))
@@ -461,7 +561,11 @@ impl TxCmd {
 
                 // Submit the `Swap` transaction, waiting for confirmation,
                 // at which point the swap will be available for claiming.
-                app.build_and_submit_transaction(plan).await?;
+                if app.build_and_submit_transaction(plan).await?.is_none() {
+                    // The swap was exported rather than submitted, so it
+                    // can't be claimed yet.
+                    return Ok(());
+                }
 
                 // Fetch the SwapRecord with the claimable swap.
                 let swap_record = app
@@ -471,9 +575,10 @@ impl TxCmd {
 
                 let asset_cache = app.view().assets().await?;
 
-                let pro_rata_outputs = swap_record
-                    .output_data
-                    .pro_rata_outputs((swap_plaintext.delta_1_i, swap_plaintext.delta_2_i));
+                let pro_rata_outputs = swap_record.output_data.swap_outputs(
+                    (swap_plaintext.delta_1_i, swap_plaintext.delta_2_i),
+                    swap_plaintext.min_output,
+                );
                 println!("Swap submitted and batch confirmed!");
                 println!(
                     "You will receive outputs of {} and {}. Claiming now...",
@@ -625,6 +730,75 @@ impl TxCmd {
 
                 app.build_and_submit_transaction(plan).await?;
             }
+            TxCmd::Redelegate {
+                to,
+                amount,
+                source,
+                fee_tier,
+            } => {
+                let delegation_value @ Value {
+                    amount: _,
+                    asset_id,
+                } = amount.parse::<Value>()?;
+
+                let delegation_token: DelegationToken = app
+                    .view()
+                    .assets()
+                    .await?
+                    .get(&asset_id)
+                    .ok_or_else(|| anyhow::anyhow!("unknown asset id {}", asset_id))?
+                    .clone()
+                    .try_into()
+                    .context("could not parse supplied denomination as a delegation token")?;
+
+                let from = delegation_token.validator();
+                let to = to.parse::<IdentityKey>()?;
+                if from == to {
+                    anyhow::bail!("cannot redelegate to the validator the stake is delegated to");
+                }
+
+                let mut stake_client = StakeQueryServiceClient::new(app.pd_channel().await?);
+                let from_rate_data: RateData = stake_client
+                    .current_validator_rate(tonic::Request::new(from.into()))
+                    .await?
+                    .into_inner()
+                    .try_into()?;
+                let to_rate_data: RateData = stake_client
+                    .current_validator_rate(tonic::Request::new(to.into()))
+                    .await?
+                    .into_inner()
+                    .try_into()?;
+
+                let mut sct_client = SctQueryServiceClient::new(app.pd_channel().await?);
+                let latest_sync_height = app.view().status().await?.full_sync_height;
+                let epoch = sct_client
+                    .epoch_by_height(EpochByHeightRequest {
+                        height: latest_sync_height,
+                    })
+                    .await?
+                    .into_inner()
+                    .epoch
+                    .expect("epoch must be available")
+                    .into();
+
+                let mut planner = Planner::new(OsRng);
+                planner
+                    .set_gas_prices(gas_prices)
+                    .set_fee_tier((*fee_tier).into());
+
+                let plan = planner
+                    .redelegate(epoch, delegation_value.amount, from_rate_data, to_rate_data)
+                    .plan(
+                        app.view
+                            .as_mut()
+                            .context("view service must be initialized")?,
+                        AddressIndex::new(*source),
+                    )
+                    .await
+                    .context("can't build redelegate plan")?;
+
+                app.build_and_submit_transaction(plan).await?;
+            }
             TxCmd::UndelegateClaim { fee_tier } => {
                 let channel = app.pd_channel().await?;
                 let view: &mut dyn ViewClient = app
@@ -740,6 +914,118 @@ impl TxCmd {
                     }
                 }
             }
+            TxCmd::RedelegateClaim { fee_tier } => {
+                let channel = app.pd_channel().await?;
+                let view: &mut dyn ViewClient = app
+                    .view
+                    .as_mut()
+                    .context("view service must be initialized")?;
+
+                let current_height = view.status().await?.full_sync_height;
+                let mut client = SctQueryServiceClient::new(channel.clone());
+                let current_epoch = client
+                    .epoch_by_height(EpochByHeightRequest {
+                        height: current_height,
+                    })
+                    .await?
+                    .into_inner()
+                    .epoch
+                    .context("unable to get epoch for current height")?;
+                let asset_cache = view.assets().await?;
+
+                // As for undelegation claims, claim the redelegation tokens into the
+                // same address index that currently holds them.
+                let notes = view.unspent_notes_by_address_and_asset().await?;
+
+                let notes: Vec<(
+                    AddressIndex,
+                    Vec<(RedelegationToken, Vec<SpendableNoteRecord>)>,
+                )> = notes
+                    .into_iter()
+                    .map(|(address_index, notes_by_asset)| {
+                        let mut filtered_notes: Vec<(
+                            RedelegationToken,
+                            Vec<SpendableNoteRecord>,
+                        )> = notes_by_asset
+                            .into_iter()
+                            .filter_map(|(asset_id, notes)| {
+                                // Filter for notes that are redelegation tokens.
+                                let denom = asset_cache
+                                    .get(&asset_id)
+                                    .expect("asset ID should exist in asset cache")
+                                    .clone();
+                                match RedelegationToken::try_from(denom) {
+                                    Ok(token) => Some((token, notes)),
+                                    Err(_) => None,
+                                }
+                            })
+                            .collect();
+
+                        filtered_notes.sort_by_key(|(token, _)| token.redelegation_start_height());
+
+                        (address_index, filtered_notes)
+                    })
+                    .collect();
+
+                for (address_index, notes_by_asset) in notes.into_iter() {
+                    for (token, notes) in notes_by_asset.into_iter() {
+                        println!("claiming {}", token.denom().default_unit());
+
+                        let from_validator = token.from_validator();
+                        let end_epoch_index = current_epoch.index;
+
+                        let mut sct_client = SctQueryServiceClient::new(channel.clone());
+                        let epoch_start = sct_client
+                            .epoch_by_height(EpochByHeightRequest {
+                                height: token.redelegation_start_height(),
+                            })
+                            .await
+                            .expect("can get epoch by height")
+                            .into_inner()
+                            .epoch
+                            .context("unable to get epoch for redelegation start height")?;
+
+                        let mut stake_client = StakeQueryServiceClient::new(channel.clone());
+                        let penalty: Penalty = stake_client
+                            .validator_penalty(tonic::Request::new(ValidatorPenaltyRequest {
+                                identity_key: Some(from_validator.into()),
+                                start_epoch_index: epoch_start.index,
+                                end_epoch_index,
+                            }))
+                            .await?
+                            .into_inner()
+                            .penalty
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "no penalty returned for validator {}",
+                                    from_validator
+                                )
+                            })?
+                            .try_into()?;
+
+                        let mut planner = Planner::new(OsRng);
+                        planner
+                            .set_gas_prices(gas_prices.clone())
+                            .set_fee_tier((*fee_tier).into());
+                        let redelegation_amount = notes.iter().map(|n| n.note.amount()).sum();
+
+                        let plan = planner
+                            .redelegate_claim(RedelegateClaim::new(
+                                &token,
+                                penalty,
+                                redelegation_amount,
+                            ))
+                            .plan(
+                                app.view
+                                    .as_mut()
+                                    .context("view service must be initialized")?,
+                                address_index,
+                            )
+                            .await?;
+                        app.build_and_submit_transaction(plan).await?;
+                    }
+                }
+            }
             TxCmd::Proposal(ProposalCmd::Submit {
                 file,
                 source,
@@ -1304,9 +1590,6 @@ impl TxCmd {
 
                 app.build_and_submit_transaction(plan).await?;
             }
-            TxCmd::Position(PositionCmd::RewardClaim {}) => {
-                unimplemented!("deprecated, remove this")
-            }
             TxCmd::Position(PositionCmd::Replicate(replicate_cmd)) => {
                 replicate_cmd.exec(app).await?;
             }
@@ -1317,7 +1600,66 @@ impl TxCmd {
                 let transaction: Transaction = serde_json::from_slice(&fs::read(transaction)?)?;
                 app.submit_transaction(transaction).await?;
             }
+            TxCmd::Authorize { .. } => unreachable!("authorize command already executed"),
+            TxCmd::Import {
+                plan,
+                authorization,
+            } => {
+                let exported = ExportedPlan::load(plan)?;
+                exported.check_effect_hash(&app.config.full_viewing_key)?;
+                let auth_data = airgap::load_authorization_data(authorization)?;
+
+                let transaction = app
+                    .build_authorized_transaction(exported.plan, auth_data)
+                    .await?;
+                app.submit_transaction(transaction).await?;
+            }
         }
         Ok(())
     }
 }
+
+/// Authorizes an exported transaction plan with the configured custody
+/// backend, saving the authorization data to `output`.
+async fn authorize_exported_plan(
+    app: &mut App,
+    plan: &Path,
+    output: &Path,
+    yes: bool,
+) -> Result<()> {
+    let exported = ExportedPlan::load(plan)?;
+    exported.check_effect_hash(&app.config.full_viewing_key)?;
+
+    // Describe the plan itself, rather than trusting the exported summary.
+    println!("Transaction to authorize:");
+    for line in airgap::summarize(&exported.plan, &Cache::with_known_assets()) {
+        println!("  {line}");
+    }
+    println!("Effect hash: {}", exported.effect_hash);
+    if !yes
+        && !Confirm::new()
+            .with_prompt("Do you wish to authorize this transaction")
+            .interact()?
+    {
+        anyhow::bail!("authorization declined");
+    }
+
+    let auth_data: AuthorizationData = CustodyClient::authorize(
+        &mut app.custody,
+        AuthorizeRequest {
+            plan: exported.plan,
+            pre_authorizations: Vec::new(),
+        },
+    )
+    .await?
+    .data
+    .ok_or_else(|| anyhow::anyhow!("empty AuthorizeResponse message"))?
+    .try_into()?;
+
+    airgap::save_authorization_data(output, auth_data)?;
+    println!(
+        "saved authorization data to disk, path: {}",
+        output.to_string_lossy()
+    );
+    Ok(())
+}
@@ -347,13 +347,7 @@ impl DutchCmd {
                     .await
                     .context("can't build send transaction")?;
 
-                let tx = app.build_transaction(plan.clone()).await?;
-                let fee_fmt = tx
-                    .transaction_body
-                    .transaction_parameters
-                    .fee
-                    .0
-                    .format(&asset_cache);
+                let fee_fmt = plan.transaction_parameters.fee.0.format(&asset_cache);
 
                 println!("Total fee: {fee_fmt}");
 
@@ -390,7 +384,7 @@ async fn fetch_dutch_auction_states(
     include_inactive: bool,
 ) -> Result<Vec<(AuctionId, DutchAuction, u64)>> {
     let auctions = view_client
-        .auctions(Some(source.into()), include_inactive, true)
+        .auctions(Some(source.into()), include_inactive, true, Vec::new())
         .await?
         .into_iter()
         .filter_map(|(id, _, local_seq, state, _)| {
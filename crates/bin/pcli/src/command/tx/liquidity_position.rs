@@ -64,9 +64,6 @@ pub enum PositionCmd {
         fee_tier: FeeTier,
     },
 
-    /// Debits a withdrawn position NFT and credits a claimed position NFT and any liquidity incentives.
-    #[clap(hide(true))] // remove when reward claims exist
-    RewardClaim {},
     /// Replicate a trading function
     #[clap(subcommand)]
     Replicate(ReplicateCmd),
@@ -80,7 +77,6 @@ impl PositionCmd {
             PositionCmd::CloseAll { .. } => false,
             PositionCmd::Withdraw { .. } => false,
             PositionCmd::WithdrawAll { .. } => false,
-            PositionCmd::RewardClaim { .. } => false,
             PositionCmd::Replicate(replicate) => replicate.offline(),
         }
     }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use comfy_table::{presets, Cell, ContentArrangement, Table};
-use penumbra_auction::auction::dutch::DutchAuction;
+use penumbra_auction::auction::{dutch::DutchAuction, AuctionId};
 use penumbra_keys::FullViewingKey;
 use penumbra_proto::{core::component::auction::v1 as pb_auction, DomainType, Name};
 use penumbra_view::ViewClient;
@@ -15,6 +15,9 @@ pub struct AuctionCmd {
     /// If set, make the view server query an RPC and pcli render the full auction state
     #[clap(long, default_value_t = true)]
     pub query_latest_state: bool,
+    /// If set, only shows the auctions with these ids.
+    #[clap(long = "auction-id")]
+    pub auction_ids: Vec<AuctionId>,
 }
 
 impl AuctionCmd {
@@ -28,13 +31,18 @@ impl AuctionCmd {
         _fvk: &FullViewingKey,
     ) -> Result<()> {
         let auctions: Vec<(
-            penumbra_auction::auction::AuctionId,
+            AuctionId,
             penumbra_view::SpendableNoteRecord,
             u64,
             Option<pbjson_types::Any>,
             Vec<penumbra_dex::lp::position::Position>,
         )> = view_client
-            .auctions(None, self.include_inactive, self.query_latest_state)
+            .auctions(
+                None,
+                self.include_inactive,
+                self.query_latest_state,
+                self.auction_ids.clone(),
+            )
             .await?;
 
         for (auction_id, _, local_seq, maybe_auction_state, positions) in auctions.into_iter() {
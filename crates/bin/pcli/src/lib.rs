@@ -18,6 +18,7 @@ pub mod config;
 pub mod opt;
 pub mod warning;
 
+mod airgap;
 mod dex_utils;
 mod network;
 mod terminal;
@@ -37,6 +38,9 @@ pub struct App {
     pub config: PcliConfig,
     /// If present, save the transaction here instead of broadcasting it.
     pub save_transaction_here_instead: Option<PathBuf>,
+    /// If present, export the transaction plan here, to be authorized on
+    /// another machine, instead of building the transaction.
+    pub export_plan_here_instead: Option<PathBuf>,
 }
 
 impl App {
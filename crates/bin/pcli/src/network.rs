@@ -1,6 +1,7 @@
 use anyhow::Context;
 use decaf377_rdsa::{Signature, SpendAuth};
 use futures::{FutureExt, TryStreamExt};
+use penumbra_custody::CustodyClient;
 use penumbra_governance::ValidatorVoteBody;
 use penumbra_proto::{
     custody::v1::{AuthorizeValidatorDefinitionRequest, AuthorizeValidatorVoteRequest},
@@ -9,32 +10,71 @@ use penumbra_proto::{
     DomainType,
 };
 use penumbra_stake::validator::Validator;
-use penumbra_transaction::{txhash::TransactionId, Transaction, TransactionPlan};
+use penumbra_transaction::{
+    txhash::TransactionId, AuthorizationData, Transaction, TransactionPlan,
+};
 use penumbra_view::ViewClient;
 use std::{fs, future::Future};
 use tonic::transport::{Channel, ClientTlsConfig};
 use tracing::instrument;
 
-use crate::App;
+use crate::{
+    airgap::{ExportedPlan, PreAuthorized},
+    App,
+};
 
 impl App {
+    /// Builds and submits the transaction described by `plan`, returning its
+    /// ID, or `None` if the plan was exported to be authorized elsewhere.
     pub async fn build_and_submit_transaction(
         &mut self,
         plan: TransactionPlan,
-    ) -> anyhow::Result<TransactionId> {
+    ) -> anyhow::Result<Option<TransactionId>> {
         let asset_cache = self.view().assets().await?;
         println!(
             "including transaction fee of {}...",
             plan.transaction_parameters.fee.0.format(&asset_cache)
         );
 
+        if let Some(file) = &self.export_plan_here_instead {
+            println!(
+                "exporting transaction plan to disk, path: {}",
+                file.to_string_lossy()
+            );
+            ExportedPlan::new(&self.config.full_viewing_key, plan, &asset_cache)?.save(file)?;
+            return Ok(None);
+        }
+
         let transaction = self.build_transaction(plan).await?;
-        self.submit_transaction(transaction).await
+        self.submit_transaction(transaction).await.map(Some)
     }
 
     pub fn build_transaction(
         &mut self,
         plan: TransactionPlan,
+    ) -> impl Future<Output = anyhow::Result<Transaction>> + '_ {
+        let custody = self.custody.clone();
+        self.build_transaction_with(plan, custody)
+    }
+
+    /// Builds the transaction described by `plan`, using authorization data
+    /// produced for it on another machine.
+    pub fn build_authorized_transaction(
+        &mut self,
+        plan: TransactionPlan,
+        auth_data: AuthorizationData,
+    ) -> impl Future<Output = anyhow::Result<Transaction>> + '_ {
+        let custody = PreAuthorized {
+            fvk: self.config.full_viewing_key.clone(),
+            auth_data,
+        };
+        self.build_transaction_with(plan, custody)
+    }
+
+    fn build_transaction_with(
+        &mut self,
+        plan: TransactionPlan,
+        mut custody: impl CustodyClient + 'static,
     ) -> impl Future<Output = anyhow::Result<Transaction>> + '_ {
         println!(
             "building transaction [{} actions, {} proofs]...",
@@ -42,14 +82,30 @@ impl App {
             plan.num_proofs(),
         );
         let start = std::time::Instant::now();
-        let tx = penumbra_wallet::build_transaction(
-            &self.config.full_viewing_key,
-            self.view.as_mut().expect("view service initialized"),
-            &mut self.custody,
-            plan,
-        );
+
+        use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+        let progress_bar =
+            ProgressBar::with_draw_target(plan.actions.len() as u64, ProgressDrawTarget::stdout())
+                .with_style(
+                    ProgressStyle::default_bar()
+                        .template("[{elapsed}] {bar:50.cyan/blue} {pos:>3}/{len:3} actions built"),
+                );
+        progress_bar.set_position(0);
+
+        let progress_bar2 = progress_bar.clone();
+        let fvk = &self.config.full_viewing_key;
+        let view = self.view.as_mut().expect("view service initialized");
         async move {
-            let tx = tx.await?;
+            let tx = penumbra_wallet::build_transaction_with_progress(
+                fvk,
+                view,
+                &mut custody,
+                plan,
+                move |progress| progress_bar2.set_position(progress.built_actions as u64),
+            )
+            .await;
+            progress_bar.finish_and_clear();
+            let tx = tx?;
             let elapsed = start.elapsed();
             println!(
                 "finished proving in {}.{:03} seconds [{} actions, {} proofs, {} bytes]",
@@ -115,7 +171,11 @@ impl App {
         }
 
         println!("broadcasting transaction and awaiting confirmation...");
-        let mut rsp = self.view().broadcast_transaction(transaction, true).await?;
+        let mut rsp = self
+            .view()
+            .broadcast_transaction(transaction, true)
+            .await
+            .map_err(with_rejection_hint)?;
 
         let id = async move {
             while let Some(rsp) = rsp.try_next().await? {
@@ -157,7 +217,8 @@ impl App {
         }
         .boxed()
         .await
-        .context("error broadcasting transaction")?;
+        .context("error broadcasting transaction")
+        .map_err(with_rejection_hint)?;
 
         Ok(id)
     }
@@ -199,3 +260,22 @@ impl App {
         Ok(TendermintProxyServiceClient::new(channel))
     }
 }
+
+/// Explains why the node rejected a broadcast transaction, if it reported a reason.
+fn with_rejection_hint(error: anyhow::Error) -> anyhow::Error {
+    let Some(rejection) = error
+        .downcast_ref::<tonic::Status>()
+        .and_then(penumbra_view::check_tx_error)
+    else {
+        return error;
+    };
+    let message = format!(
+        "transaction rejected: {rejection} (code {}, codespace {})",
+        rejection.code(),
+        rejection.codespace()
+    );
+    match rejection.hint() {
+        Some(hint) => error.context(format!("{message}; {hint}")),
+        None => error.context(message),
+    }
+}
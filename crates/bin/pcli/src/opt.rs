@@ -1,4 +1,5 @@
 use crate::{
+    command::RelayOptions,
     config::{CustodyConfig, GovernanceCustodyConfig, PcliConfig},
     terminal::ActualTerminal,
     App, Command,
@@ -7,7 +8,12 @@ use anyhow::Result;
 use camino::Utf8PathBuf;
 use clap::Parser;
 use directories::ProjectDirs;
-use penumbra_custody::{null_kms::NullKms, soft_kms::SoftKms};
+use penumbra_custody::{
+    encrypted::Encrypted,
+    null_kms::NullKms,
+    soft_kms::SoftKms,
+    threshold::{RelayRole, Threshold},
+};
 use penumbra_proto::box_grpc_svc;
 use penumbra_proto::{
     custody::v1::{
@@ -33,6 +39,10 @@ pub struct Opt {
     /// By default, this URL is provided by pcli's config. See `pcli init` for more information.
     #[clap(long, parse(try_from_str = Url::parse))]
     pub grpc_url: Option<Url>,
+    /// When coordinating threshold signing, exchange messages with the other signers
+    /// through a relay, rather than through the terminal.
+    #[clap(flatten)]
+    pub relay: RelayOptions,
 }
 
 impl Opt {
@@ -82,25 +92,41 @@ impl Opt {
             }
             CustodyConfig::Threshold(config) => {
                 tracing::info!("using manual threshold custody service");
-                let threshold_kms = penumbra_custody::threshold::Threshold::new(
-                    config.clone(),
-                    ActualTerminal {
-                        fvk: Some(fvk.clone()),
-                    },
-                );
-                let custody_svc = CustodyServiceServer::new(threshold_kms);
-                CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+                let terminal = ActualTerminal {
+                    fvk: Some(fvk.clone()),
+                };
+                let custody_svc = match self
+                    .relay
+                    .terminal(terminal.clone(), RelayRole::Coordinator)?
+                {
+                    Some(terminal) => box_grpc_svc::local(CustodyServiceServer::new(
+                        Threshold::new(config.clone(), terminal),
+                    )),
+                    None => box_grpc_svc::local(CustodyServiceServer::new(Threshold::new(
+                        config.clone(),
+                        terminal,
+                    ))),
+                };
+                CustodyServiceClient::new(custody_svc)
             }
             CustodyConfig::Encrypted(config) => {
                 tracing::info!("using encrypted custody service");
-                let encrypted_kms = penumbra_custody::encrypted::Encrypted::new(
-                    config.clone(),
-                    ActualTerminal {
-                        fvk: Some(fvk.clone()),
-                    },
-                );
-                let custody_svc = CustodyServiceServer::new(encrypted_kms);
-                CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+                let terminal = ActualTerminal {
+                    fvk: Some(fvk.clone()),
+                };
+                let custody_svc = match self
+                    .relay
+                    .terminal(terminal.clone(), RelayRole::Coordinator)?
+                {
+                    Some(terminal) => box_grpc_svc::local(CustodyServiceServer::new(
+                        Encrypted::new(config.clone(), terminal),
+                    )),
+                    None => box_grpc_svc::local(CustodyServiceServer::new(Encrypted::new(
+                        config.clone(),
+                        terminal,
+                    ))),
+                };
+                CustodyServiceClient::new(custody_svc)
             }
         };
 
@@ -119,21 +145,37 @@ impl Opt {
                     tracing::info!(
                         "using separate manual threshold custody service for validator voting"
                     );
-                    let threshold_kms = penumbra_custody::threshold::Threshold::new(
-                        config.clone(),
-                        ActualTerminal { fvk: Some(fvk) },
-                    );
-                    let custody_svc = CustodyServiceServer::new(threshold_kms);
-                    CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+                    let terminal = ActualTerminal { fvk: Some(fvk) };
+                    let custody_svc = match self
+                        .relay
+                        .terminal(terminal.clone(), RelayRole::Coordinator)?
+                    {
+                        Some(terminal) => box_grpc_svc::local(CustodyServiceServer::new(
+                            Threshold::new(config.clone(), terminal),
+                        )),
+                        None => box_grpc_svc::local(CustodyServiceServer::new(Threshold::new(
+                            config.clone(),
+                            terminal,
+                        ))),
+                    };
+                    CustodyServiceClient::new(custody_svc)
                 }
                 GovernanceCustodyConfig::Encrypted { config, .. } => {
                     tracing::info!("using separate encrypted custody service for validator voting");
-                    let encrypted_kms = penumbra_custody::encrypted::Encrypted::new(
-                        config.clone(),
-                        ActualTerminal { fvk: Some(fvk) },
-                    );
-                    let custody_svc = CustodyServiceServer::new(encrypted_kms);
-                    CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+                    let terminal = ActualTerminal { fvk: Some(fvk) };
+                    let custody_svc = match self
+                        .relay
+                        .terminal(terminal.clone(), RelayRole::Coordinator)?
+                    {
+                        Some(terminal) => box_grpc_svc::local(CustodyServiceServer::new(
+                            Encrypted::new(config.clone(), terminal),
+                        )),
+                        None => box_grpc_svc::local(CustodyServiceServer::new(Encrypted::new(
+                            config.clone(),
+                            terminal,
+                        ))),
+                    };
+                    CustodyServiceClient::new(custody_svc)
                 }
             },
             None => custody.clone(), // If no separate custody for validator voting, use the same one
@@ -174,6 +216,7 @@ impl Opt {
             governance_custody,
             config,
             save_transaction_here_instead: None,
+            export_plan_here_instead: None,
         };
         Ok((app, self.cmd))
     }
@@ -148,6 +148,8 @@ fn pretty_print_transaction_plan(
             ActionPlan::Delegate(_) => None,
             ActionPlan::Undelegate(_) => None,
             ActionPlan::UndelegateClaim(_) => None,
+            ActionPlan::Redelegate(_) => None,
+            ActionPlan::RedelegateClaim(_) => None,
             ActionPlan::Ics20Withdrawal(_) => None,
             ActionPlan::CommunityPoolSpend(_) => None,
             ActionPlan::CommunityPoolOutput(_) => None,
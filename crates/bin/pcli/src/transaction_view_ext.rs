@@ -378,6 +378,8 @@ impl TransactionViewExt for TransactionView {
                 penumbra_transaction::ActionView::Delegate(_) => ["Delegation", ""],
                 penumbra_transaction::ActionView::Undelegate(_) => ["Undelegation", ""],
                 penumbra_transaction::ActionView::UndelegateClaim(_) => ["Undelegation Claim", ""],
+                penumbra_transaction::ActionView::Redelegate(_) => ["Redelegation", ""],
+                penumbra_transaction::ActionView::RedelegateClaim(_) => ["Redelegation Claim", ""],
                 penumbra_transaction::ActionView::ActionDutchAuctionSchedule(_) => todo!(),
                 penumbra_transaction::ActionView::ActionDutchAuctionEnd(_) => {
                     todo!()
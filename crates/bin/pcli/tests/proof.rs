@@ -15,7 +15,7 @@ use penumbra_governance::{
     DelegatorVoteProof, DelegatorVoteProofPrivate, DelegatorVoteProofPublic,
 };
 use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
 use penumbra_proof_params::{
     CONVERT_PROOF_PROVING_KEY, CONVERT_PROOF_VERIFICATION_KEY, DELEGATOR_VOTE_PROOF_PROVING_KEY,
     DELEGATOR_VOTE_PROOF_VERIFICATION_KEY, NULLIFIER_DERIVATION_PROOF_PROVING_KEY,
@@ -278,6 +278,8 @@ fn swap_claim_parameters_vs_current_swap_claim_circuit() {
         height: height.into(),
         trading_pair: swap_plaintext.trading_pair,
         sct_position_prefix: position,
+        limit_price_1_for_2: U128x128::default(),
+        limit_price_2_for_1: U128x128::default(),
     };
     let (lambda_1, lambda_2) = output_data.pro_rata_outputs((delta_1_i, delta_2_i));
 
@@ -16,7 +16,9 @@ use penumbra_proto::{
     core::app::v1::{
         query_service_client::QueryServiceClient as AppQueryServiceClient, AppParametersRequest,
     },
-    custody::v1::custody_service_server::CustodyServiceServer,
+    custody::v1::{
+        custody_service_client::CustodyServiceClient, custody_service_server::CustodyServiceServer,
+    },
     view::v1::view_service_server::ViewServiceServer,
 };
 use penumbra_view::{Storage, ViewServer};
@@ -45,12 +47,27 @@ pub struct PclientdConfig {
     /// FVK for both view and custody modes
     #[serde_as(as = "DisplayFromStr")]
     pub full_viewing_key: FullViewingKey,
+    /// FVKs of further wallets to serve in view mode.
+    ///
+    /// Each wallet has its own storage, and is selected by its wallet ID in
+    /// view service requests, see [`penumbra_view::WALLET_ID_METADATA_KEY`].
+    /// Requests that don't select a wallet are served by `full_viewing_key`.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_full_viewing_keys: Vec<FullViewingKey>,
     /// The URL of the gRPC endpoint used to talk to pd.
     pub grpc_url: Url,
     /// The address to bind to serve gRPC.
     pub bind_addr: SocketAddr,
     /// Optional KMS config for custody mode
     pub kms_config: Option<soft_kms::Config>,
+    /// Whether to claim the swaps of `full_viewing_key` automatically, in custody mode.
+    ///
+    /// Each swap is claimed as soon as its outputs are known, paying the claim
+    /// fee prepaid by the swap. The KMS auth policy must allow these claims
+    /// without pre-authorization.
+    #[serde(default)]
+    pub auto_claim_swaps: bool,
 }
 
 impl PclientdConfig {
@@ -127,6 +144,30 @@ impl Opt {
         path
     }
 
+    /// The storage path of one of the `additional_full_viewing_keys`.
+    fn wallet_sqlite_path(&self, fvk: &FullViewingKey) -> Utf8PathBuf {
+        let mut path = self.home.clone();
+        path.push(format!("pclientd-db-{}.sqlite", fvk.wallet_id()));
+        path
+    }
+
+    /// All storage files in the home directory, for every wallet ever served.
+    fn all_sqlite_paths(&self) -> Result<Vec<Utf8PathBuf>> {
+        if !self.home.exists() {
+            return Ok(Vec::new());
+        }
+        let mut paths = Vec::new();
+        for entry in self.home.read_dir_utf8()? {
+            let path = entry?.into_path();
+            let name = path.file_name().unwrap_or_default();
+            if name.starts_with("pclientd-db") && name.ends_with(".sqlite") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
     fn check_home_nonempty(&self) -> Result<()> {
         if self.home.exists() {
             if !self.home.is_dir() {
@@ -148,7 +189,12 @@ impl Opt {
         Ok(())
     }
 
-    async fn init_sqlite(&self, fvk: &FullViewingKey, grpc_url: &Url) -> Result<Storage> {
+    async fn init_sqlite(
+        &self,
+        path: Utf8PathBuf,
+        fvk: &FullViewingKey,
+        grpc_url: &Url,
+    ) -> Result<Storage> {
         // Initialize client and storage
         let mut client = AppQueryServiceClient::connect(grpc_url.to_string()).await?;
 
@@ -158,14 +204,19 @@ impl Opt {
             .into_inner()
             .try_into()?;
 
-        Storage::initialize(Some(self.sqlite_path()), fvk.clone(), params).await
+        Storage::initialize(Some(path), fvk.clone(), params).await
     }
 
-    async fn load_or_init_sqlite(&self, fvk: &FullViewingKey, grpc_url: &Url) -> Result<Storage> {
-        if self.sqlite_path().exists() {
-            Ok(Storage::load(self.sqlite_path()).await?)
+    async fn load_or_init_sqlite(
+        &self,
+        path: Utf8PathBuf,
+        fvk: &FullViewingKey,
+        grpc_url: &Url,
+    ) -> Result<Storage> {
+        if path.exists() {
+            Ok(Storage::load(path).await?)
         } else {
-            self.init_sqlite(fvk, grpc_url).await
+            self.init_sqlite(path, fvk, grpc_url).await
         }
     }
 
@@ -173,12 +224,14 @@ impl Opt {
         let opt = self;
         match &opt.cmd {
             Command::Reset {} => {
-                if opt.sqlite_path().exists() {
-                    fs::remove_file(opt.sqlite_path())?;
-                    println!("Deleted local storage at: {:?}", opt.sqlite_path());
-                } else {
+                let paths = opt.all_sqlite_paths()?;
+                if paths.is_empty() {
                     println!("No local storage at: {:?} (have you started pclientd, so it would have data to store?)", opt.sqlite_path());
                 }
+                for path in paths {
+                    fs::remove_file(&path)?;
+                    println!("Deleted local storage at: {:?}", path);
+                }
 
                 Ok(())
             }
@@ -271,8 +324,10 @@ impl Opt {
                 let client_config = PclientdConfig {
                     kms_config,
                     full_viewing_key,
+                    additional_full_viewing_keys: vec![],
                     grpc_url: grpc_url.clone(),
                     bind_addr: *bind_addr,
+                    auto_claim_swaps: false,
                 };
 
                 let encoded = toml::to_string_pretty(&client_config)
@@ -294,9 +349,20 @@ impl Opt {
                 )?;
 
                 tracing::info!(?opt.home, ?config.bind_addr, %config.grpc_url, "starting pclientd");
-                let storage = opt
-                    .load_or_init_sqlite(&config.full_viewing_key, &config.grpc_url)
-                    .await?;
+                let mut storages = vec![
+                    opt.load_or_init_sqlite(
+                        opt.sqlite_path(),
+                        &config.full_viewing_key,
+                        &config.grpc_url,
+                    )
+                    .await?,
+                ];
+                for fvk in &config.additional_full_viewing_keys {
+                    storages.push(
+                        opt.load_or_init_sqlite(opt.wallet_sqlite_path(fvk), fvk, &config.grpc_url)
+                            .await?,
+                    );
+                }
 
                 let proxy_channel =
                     tonic::transport::Channel::from_shared(config.grpc_url.to_string())
@@ -316,12 +382,26 @@ impl Opt {
                 let compact_block_query_proxy = CompactBlockQueryProxy(proxy_channel.clone());
                 let tendermint_proxy_proxy = TendermintProxyProxy(proxy_channel.clone());
 
-                let view_service =
-                    ViewServiceServer::new(ViewServer::new(storage, config.grpc_url).await?);
                 let custody_service = config.kms_config.as_ref().map(|kms_config| {
                     CustodyServiceServer::new(SoftKms::new(kms_config.spend_key.clone().into()))
                 });
 
+                // In custody mode, the view service can also authorize transactions
+                // for the default wallet, serving `AuthorizeAndBuild` requests.
+                let mut view_server = ViewServer::with_wallets(storages, config.grpc_url).await?;
+                if let Some(custody_service) = &custody_service {
+                    view_server = view_server
+                        .with_custody(CustodyServiceClient::new(custody_service.clone()));
+                    if config.auto_claim_swaps {
+                        view_server = view_server.with_auto_claim()?;
+                    }
+                } else if config.auto_claim_swaps {
+                    tracing::warn!(
+                        "not claiming swaps automatically, since custody mode is disabled"
+                    );
+                }
+                let view_service = ViewServiceServer::new(view_server);
+
                 let server = Server::builder()
                     .accept_http1(true)
                     .add_service(tonic_web::enable(view_service))
@@ -34,6 +34,7 @@ use penumbra_view::ViewClient;
 fn generate_config() -> anyhow::Result<PclientdConfig> {
     Ok(PclientdConfig {
         full_viewing_key: test_keys::FULL_VIEWING_KEY.clone(),
+        additional_full_viewing_keys: vec![],
         grpc_url: std::env::var("PENUMBRA_NODE_PD_URL")
             .unwrap_or_else(|_| "http://127.0.0.1:8080".to_owned())
             .parse()?,
@@ -42,6 +43,7 @@ fn generate_config() -> anyhow::Result<PclientdConfig> {
             spend_key: test_keys::SPEND_KEY.clone(),
             auth_policy: Vec::new(),
         }),
+        auto_claim_swaps: false,
     })
 }
 
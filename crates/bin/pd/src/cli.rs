@@ -89,6 +89,47 @@ pub enum RootCommand {
         /// Enable expensive RPCs, currently a no-op.
         #[clap(short, long, display_order = 500)]
         enable_expensive_rpc: bool,
+        /// Take a state sync snapshot every this many blocks, serving it to
+        /// peers joining the network via CometBFT state sync.
+        ///
+        /// Snapshots are stored in the `snapshots` directory under the `pd`
+        /// home directory. If set to 0, no snapshots are taken, although
+        /// the node can still restore from a snapshot offered by its peers.
+        #[clap(
+            long,
+            env = "PENUMBRA_PD_SNAPSHOT_INTERVAL",
+            default_value = "0",
+            display_order = 600
+        )]
+        snapshot_interval: u64,
+        /// The number of recent state sync snapshots to keep on disk.
+        /// Older snapshots are deleted as new ones are taken.
+        #[clap(
+            long,
+            env = "PENUMBRA_PD_SNAPSHOT_KEEP_RECENT",
+            default_value = "2",
+            display_order = 601
+        )]
+        snapshot_keep_recent: usize,
+        /// Discard historical versions of the chain state, keeping only this many
+        /// recent versions on disk.
+        ///
+        /// If unset, `pd` runs as an archive node and keeps every version.
+        #[clap(long, env = "PENUMBRA_PD_PRUNING_KEEP_RECENT", display_order = 700)]
+        pruning_keep_recent: Option<u64>,
+        /// When pruning, also keep every version that is a multiple of this value.
+        /// Has no effect unless `--pruning-keep-recent` is set.
+        #[clap(long, env = "PENUMBRA_PD_PRUNING_KEEP_EVERY", display_order = 701)]
+        pruning_keep_every: Option<u64>,
+        /// When pruning, run a pruning pass every this many blocks.
+        /// Has no effect unless `--pruning-keep-recent` is set.
+        #[clap(
+            long,
+            env = "PENUMBRA_PD_PRUNING_INTERVAL",
+            default_value = "100",
+            display_order = 702
+        )]
+        pruning_interval: u64,
     },
 
     /// Generate, join, or reset a network.
@@ -113,7 +154,8 @@ pub enum RootCommand {
         /// node state, e.g. ~/pd-backup.tar.gz.
         #[clap(long, display_order = 200)]
         export_archive: Option<PathBuf>,
-        /// Whether to prune the JMT tree.
+        /// Whether to prune the JMT tree, keeping only the latest version
+        /// of the chain state in the exported node state.
         #[clap(long, display_order = 300)]
         prune: bool,
     },
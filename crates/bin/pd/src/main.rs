@@ -8,18 +8,18 @@ use metrics_tracing_context::{MetricsLayer, TracingContextLayer};
 use metrics_util::layers::Stack;
 
 use anyhow::{anyhow, Context};
-use cnidarium::Storage;
+use cnidarium::{PruningMode, Storage};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use pd::{
     cli::{NetworkCommand, Opt, RootCommand},
-    migrate::Migration::{ReadyToStart, Testnet78},
+    migrate::Migration::{ReadyToStart, Testnet79},
     network::{
         config::{get_network_dir, parse_tm_address, url_has_necessary_parts},
         generate::NetworkConfig,
         join::network_join,
     },
 };
-use penumbra_app::SUBSTORE_PREFIXES;
+use penumbra_app::{server::snapshot::SnapshotStore, SUBSTORE_PREFIXES};
 use rand::Rng;
 use rand_core::OsRng;
 use tendermint_config::net::Address as TendermintAddress;
@@ -61,6 +61,11 @@ async fn main() -> anyhow::Result<()> {
             metrics_bind,
             cometbft_addr,
             enable_expensive_rpc,
+            snapshot_interval,
+            snapshot_keep_recent,
+            pruning_keep_recent,
+            pruning_keep_every,
+            pruning_interval,
         } => {
             // Use the given `grpc_bind` address if one was specified. If not, we will choose a
             // default depending on whether or not `grpc_auto_https` was set. See the
@@ -97,11 +102,24 @@ async fn main() -> anyhow::Result<()> {
             };
             let rocksdb_home = pd_home.join("rocksdb");
 
-            let storage = Storage::load(rocksdb_home, SUBSTORE_PREFIXES.to_vec())
-                .await
-                .context(
-                    "Unable to initialize RocksDB storage - is there another `pd` process running?",
-                )?;
+            let pruning = match pruning_keep_recent {
+                Some(keep_recent) => PruningMode::Pruned {
+                    keep_recent,
+                    keep_every: pruning_keep_every,
+                    interval: pruning_interval,
+                },
+                None => PruningMode::Archive,
+            };
+
+            let storage = Storage::load_with_pruning(
+                rocksdb_home,
+                SUBSTORE_PREFIXES.to_vec(),
+                pruning,
+            )
+            .await
+            .context(
+                "Unable to initialize RocksDB storage - is there another `pd` process running?",
+            )?;
 
             tracing::info!(
                 ?abci_bind,
@@ -111,6 +129,9 @@ async fn main() -> anyhow::Result<()> {
                 ?metrics_bind,
                 %cometbft_addr,
                 ?enable_expensive_rpc,
+                ?snapshot_interval,
+                ?snapshot_keep_recent,
+                ?pruning,
                 "starting pd"
             );
 
@@ -121,8 +142,18 @@ async fn main() -> anyhow::Result<()> {
                 exit(0)
             }
 
+            let snapshot_store = SnapshotStore::new(pd_home.join("snapshots"))?;
+            if snapshot_interval > 0 {
+                tokio::task::spawn(penumbra_app::server::snapshot::run_snapshot_worker(
+                    storage.clone(),
+                    snapshot_store.clone(),
+                    snapshot_interval,
+                    snapshot_keep_recent,
+                ));
+            }
+
             let abci_server = tokio::task::spawn(
-                penumbra_app::server::new(storage.clone()).listen_tcp(abci_bind),
+                penumbra_app::server::new(storage.clone(), snapshot_store).listen_tcp(abci_bind),
             );
 
             let tm_proxy = penumbra_tendermint_proxy::TendermintProxy::new(cometbft_addr);
@@ -421,7 +452,20 @@ async fn main() -> anyhow::Result<()> {
             // to compressing. So we'll just mandate the presence of the --export-directory arg
             // always.
             if prune {
-                unimplemented!("storage pruning is unimplemented (for now)")
+                tracing::info!("pruning exported node state");
+                let storage = Storage::load_with_pruning(
+                    dst_rocksdb_dir.clone(),
+                    SUBSTORE_PREFIXES.to_vec(),
+                    PruningMode::Pruned {
+                        keep_recent: 1,
+                        keep_every: None,
+                        interval: 1,
+                    },
+                )
+                .await?;
+                let stats = storage.prune().await?;
+                tracing::info!(?stats, "finished pruning node state");
+                storage.release().await;
             }
 
             // Compress to tarball if requested.
@@ -468,7 +512,7 @@ async fn main() -> anyhow::Result<()> {
 
             let genesis_start = pd::migrate::last_block_timestamp(pd_home.clone()).await?;
             tracing::info!(?genesis_start, "last block timestamp");
-            Testnet78
+            Testnet79
                 .migrate(pd_home.clone(), comet_home, Some(genesis_start), force)
                 .instrument(pd_migrate_span)
                 .await
@@ -11,6 +11,7 @@ mod testnet74;
 mod testnet76;
 mod testnet77;
 mod testnet78;
+mod testnet79;
 
 use anyhow::{ensure, Context};
 use penumbra_governance::StateReadExt;
@@ -52,6 +53,9 @@ pub enum Migration {
     /// - Truncate various user-supplied `String` fields to a maximum length.
     /// - Populate the DEX NV price idnexes with position data
     Testnet78,
+    /// Testnet-79 migration:
+    /// - Switch to the regenerated swap and swap claim SNARK parameters
+    Testnet79,
 }
 
 impl Migration {
@@ -101,6 +105,9 @@ impl Migration {
                     tracing::warn!("cometbft home not specified, update 'max_txs_bytes' value manually before restarting");
                 }
             }
+            Migration::Testnet79 => {
+                testnet79::migrate(storage, pd_home.clone(), genesis_start).await?
+            }
             // We keep historical migrations around for now, this will help inform an abstracted
             // design. Feel free to remove it if it's causing you trouble.
             _ => unimplemented!("the specified migration is unimplemented"),
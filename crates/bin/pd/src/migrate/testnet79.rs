@@ -0,0 +1,255 @@
+//! Contains functions related to the migration script of Testnet79.
+use anyhow::Context;
+use cnidarium::{Snapshot, StateDelta, StateRead, Storage};
+use futures::{StreamExt as _, TryStreamExt as _};
+use jmt::RootHash;
+use penumbra_app::app::StateReadExt as _;
+use penumbra_app::genesis::AppState;
+use penumbra_asset::asset;
+use penumbra_auction::auction::id::AuctionId;
+use penumbra_auction::component::AuctionStoreRead as _;
+use penumbra_auction::state_key as auction_state_key;
+use penumbra_dex::DirectedTradingPair;
+use penumbra_governance::StateWriteExt as _;
+use penumbra_ibc::component::state_key as ibc_state_key;
+use penumbra_num::Amount;
+use penumbra_proto::{StateReadProto as _, StateWriteProto as _};
+use penumbra_sct::component::clock::{EpochManager, EpochRead};
+use penumbra_shielded_pool::component::AssetRegistryRead as _;
+use penumbra_shielded_pool::state_key as shielded_pool_state_key;
+use penumbra_stake::component::validator_handler::ValidatorDataRead as _;
+use penumbra_stake::{state_key as stake_state_key, validator::Validator, DelegationToken};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::instrument;
+
+use crate::network::generate::NetworkConfig;
+
+/// Run the full migration, given an export path and a start time for genesis.
+///
+/// This upgrade replaces the SNARK parameters of the swap and swap claim circuits,
+/// which gained public inputs for swap minimum outputs and batch limit price cutoffs.
+/// Proofs made against the previous parameters no longer verify, so every node must
+/// switch over at the same height.
+///
+/// Menu:
+/// - Seed the per-asset token supply, which this release starts tracking.
+/// - Index the open Dutch auctions by trading pair, which this release starts doing.
+/// - Reset the halt bit and the application height.
+#[instrument]
+pub async fn migrate(
+    storage: Storage,
+    pd_home: PathBuf,
+    genesis_start: Option<tendermint::time::Time>,
+) -> anyhow::Result<()> {
+    /* `Migration::prepare`: collect basic migration data, logging, initialize alt-storage if needed */
+    let initial_state = storage.latest_snapshot();
+
+    let chain_id = initial_state.get_chain_id().await?;
+    let root_hash = initial_state
+        .root_hash()
+        .await
+        .expect("chain state has a root hash");
+
+    let pre_upgrade_height = initial_state
+        .get_block_height()
+        .await
+        .expect("chain state has a block height");
+    let post_upgrade_height = pre_upgrade_height.wrapping_add(1);
+
+    let pre_upgrade_root_hash: RootHash = root_hash.into();
+
+    /* `Migration::migrate`: reach into the chain state and perform an offline state transition */
+    let mut delta = StateDelta::new(initial_state);
+
+    let (migration_duration, post_upgrade_root_hash) = {
+        let start_time = std::time::SystemTime::now();
+
+        // Seed the token supply table. This must run before the genesis file is overwritten.
+        seed_token_supply(&mut delta, &pd_home).await?;
+
+        // Index the open Dutch auctions by trading pair.
+        index_dutch_auctions_by_pair(&mut delta).await?;
+
+        // Reset the application height and halt flag.
+        delta.ready_to_start();
+        delta.put_block_height(0u64);
+
+        // Finally, commit the changes to the chain state.
+        let post_upgrade_root_hash = storage.commit_in_place(delta).await?;
+        tracing::info!(?post_upgrade_root_hash, "post-migration root hash");
+
+        (
+            start_time.elapsed().expect("start is set"),
+            post_upgrade_root_hash,
+        )
+    };
+
+    tracing::info!("migration completed, generating genesis and signing state...");
+
+    /* `Migration::complete`: the state transition has been performed, we prepare the checkpointed genesis and signing state */
+    let app_state = penumbra_app::genesis::Content {
+        chain_id,
+        ..Default::default()
+    };
+    let mut genesis = NetworkConfig::make_genesis(app_state.clone()).expect("can make genesis");
+    genesis.app_hash = post_upgrade_root_hash
+        .0
+        .to_vec()
+        .try_into()
+        .expect("infaillible conversion");
+
+    genesis.initial_height = post_upgrade_height as i64;
+    genesis.genesis_time = genesis_start.unwrap_or_else(|| {
+        let now = tendermint::time::Time::now();
+        tracing::info!(%now, "no genesis time provided, detecting a testing setup");
+        now
+    });
+
+    tracing::info!("generating checkpointed genesis");
+    let checkpoint = post_upgrade_root_hash.0.to_vec();
+    let genesis = NetworkConfig::make_checkpoint(genesis, Some(checkpoint));
+
+    tracing::info!("writing genesis to disk");
+    let genesis_json = serde_json::to_string(&genesis).expect("can serialize genesis");
+    tracing::info!("genesis: {}", genesis_json);
+    let genesis_path = pd_home.join("genesis.json");
+    std::fs::write(genesis_path, genesis_json).expect("can write genesis");
+
+    tracing::info!("updating signing state");
+    let validator_state_path = pd_home.join("priv_validator_state.json");
+    let fresh_validator_state = crate::network::generate::NetworkValidator::initial_state();
+    std::fs::write(validator_state_path, fresh_validator_state).expect("can write validator state");
+
+    tracing::info!(
+        pre_upgrade_height,
+        post_upgrade_height,
+        ?pre_upgrade_root_hash,
+        ?post_upgrade_root_hash,
+        duration = migration_duration.as_secs(),
+        "migration fully complete"
+    );
+
+    Ok(())
+}
+
+/// Seed the per-asset token supply for a chain that ran without supply tracking.
+///
+/// The supply is reconstructed from what the chain state and the genesis file still record:
+/// - the allocations of the original genesis, if `pd_home` holds a genesis with content
+///   rather than a checkpoint left by an earlier migration,
+/// - the delegation tokens issued by each validator, i.e. the size of its delegation pool,
+/// - the ICS-20 vouchers minted for inbound transfers, i.e. the value balance of every
+///   channel for the assets that it is not the source of.
+///
+/// Value minted or burned by other means before the upgrade (e.g. staking rewards, fees,
+/// LP and auction NFTs) isn't recorded anywhere, so those supplies start from the values
+/// above or from zero, and `decrease_token_supply` saturates instead of failing on them.
+/// Assets that already have a supply entry are left untouched.
+async fn seed_token_supply(delta: &mut StateDelta<Snapshot>, pd_home: &Path) -> anyhow::Result<()> {
+    let mut supplies: BTreeMap<asset::Id, Amount> = BTreeMap::new();
+
+    // Genesis allocations.
+    let genesis_path = pd_home.join("genesis.json");
+    let genesis_json =
+        std::fs::read_to_string(&genesis_path).context("error reading genesis file")?;
+    let genesis: tendermint::Genesis<AppState> =
+        serde_json::from_str(&genesis_json).context("error parsing genesis file")?;
+    match genesis.app_state {
+        AppState::Content(content) => {
+            for allocation in content.shielded_pool_content.allocations {
+                let value = allocation.value();
+                *supplies.entry(value.asset_id).or_default() += value.amount;
+            }
+        }
+        AppState::Checkpoint(_) => {
+            tracing::warn!("genesis is a checkpoint, not seeding supply from genesis allocations")
+        }
+    }
+
+    // Delegation token issuance.
+    let validators: Vec<Validator> = delta
+        .prefix::<Validator>(stake_state_key::validators::definitions::prefix())
+        .map(|entry| entry.map(|(_, validator)| validator))
+        .try_collect()
+        .await?;
+    for validator in validators {
+        let pool_size = delta
+            .get_validator_pool_size(&validator.identity_key)
+            .await
+            .unwrap_or_default();
+        let asset_id = DelegationToken::new(validator.identity_key).id();
+        *supplies.entry(asset_id).or_default() += pool_size;
+    }
+
+    // ICS-20 vouchers.
+    let value_balances: Vec<(String, Amount)> = delta
+        .prefix::<Amount>(ibc_state_key::ics20_value_balance::prefix())
+        .try_collect()
+        .await?;
+    for (key, amount) in value_balances {
+        // Keys are of the form `{prefix}{channel_id}/{asset_id}`.
+        let (channel_id, asset_id) = key
+            .strip_prefix(ibc_state_key::ics20_value_balance::prefix())
+            .and_then(|rest| rest.split_once('/'))
+            .context("malformed ics20 value balance key")?;
+        let asset_id = asset_id.parse::<asset::Id>()?;
+        let is_voucher = match delta.denom_metadata_by_asset(&asset_id).await {
+            Some(denom) => denom
+                .base_denom()
+                .denom
+                .starts_with(&format!("transfer/{channel_id}/")),
+            None => false,
+        };
+        if is_voucher {
+            *supplies.entry(asset_id).or_default() += amount;
+        }
+    }
+
+    for (asset_id, amount) in supplies {
+        let key = shielded_pool_state_key::token_supply::by_asset_id(&asset_id);
+        if delta.get_raw(&key).await?.is_some() {
+            continue;
+        }
+        tracing::info!(?asset_id, ?amount, "seeding token supply");
+        delta.put(key, amount);
+    }
+
+    Ok(())
+}
+
+/// Index the Dutch auctions that are still open by the trading pair they sell along.
+///
+/// Auctions scheduled from this release on are indexed when they are scheduled, and
+/// removed from the index when they close, so only the open auctions need backfilling.
+async fn index_dutch_auctions_by_pair(delta: &mut StateDelta<Snapshot>) -> anyhow::Result<()> {
+    let prefix = auction_state_key::auction_store::prefix();
+    let auction_ids: Vec<AuctionId> = delta
+        .prefix_raw(prefix)
+        .map(|entry| {
+            let (key, _) = entry?;
+            key[prefix.len()..]
+                .parse()
+                .with_context(|| format!("error parsing auction id from key {key}"))
+        })
+        .try_collect()
+        .await?;
+
+    let mut indexed = 0usize;
+    for auction_id in auction_ids {
+        let Some(auction) = delta.get_dutch_auction_by_id(auction_id).await? else {
+            continue;
+        };
+        if auction.state.sequence > 0 {
+            continue;
+        }
+        let description = auction.description;
+        let pair = DirectedTradingPair::new(description.input.asset_id, description.output_id);
+        let index_path = auction_state_key::dutch::by_pair::auction_for_pair(&pair, auction_id);
+        delta.nonverifiable_put(index_path.as_bytes().to_vec(), auction_id);
+        indexed += 1;
+    }
+    tracing::info!(indexed, "indexed open dutch auctions by trading pair");
+
+    Ok(())
+}
@@ -0,0 +1,400 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::{
+    async_trait,
+    sqlx::{self, Executor as _},
+    AppView, ContextualizedEvent, PgTransaction,
+};
+use penumbra_asset::asset;
+use penumbra_dex::{
+    lp::position, BatchSwapOutputData, DirectedTradingPair, SwapExecution, TradingPair,
+};
+use penumbra_num::Amount;
+use penumbra_proto::{core::component::dex::v1 as pb, event::ProtoEvent};
+
+use crate::parsing::{numeric, required};
+
+const EVENT_SWAP: &str = "penumbra.core.component.dex.v1.EventSwap";
+const EVENT_POSITION_OPEN: &str = "penumbra.core.component.dex.v1.EventPositionOpen";
+const EVENT_POSITION_CLOSE: &str = "penumbra.core.component.dex.v1.EventPositionClose";
+const EVENT_POSITION_WITHDRAW: &str = "penumbra.core.component.dex.v1.EventPositionWithdraw";
+const EVENT_POSITION_EXECUTION: &str = "penumbra.core.component.dex.v1.EventPositionExecution";
+const EVENT_BATCH_SWAP: &str = "penumbra.core.component.dex.v1.EventBatchSwap";
+const EVENT_ARB_EXECUTION: &str = "penumbra.core.component.dex.v1.EventArbExecution";
+
+/// Materializes DEX events into tables of positions, swaps, batch swaps, arbitrage
+/// executions and per-block candlesticks.
+#[derive(Debug)]
+pub struct Component {}
+
+#[async_trait]
+impl AppView for Component {
+    fn name(&self) -> String {
+        "dex".to_string()
+    }
+
+    async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut().execute(include_str!("dex/dex.sql")).await?;
+        Ok(())
+    }
+
+    async fn reset(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut()
+            .execute(
+                "DROP TABLE IF EXISTS dex_positions, dex_position_history, dex_swaps, \
+                 dex_batch_swaps, dex_arb_executions, dex_candlesticks",
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [
+            EVENT_SWAP,
+            EVENT_POSITION_OPEN,
+            EVENT_POSITION_CLOSE,
+            EVENT_POSITION_WITHDRAW,
+            EVENT_POSITION_EXECUTION,
+            EVENT_BATCH_SWAP,
+            EVENT_ARB_EXECUTION,
+        ]
+        .contains(&type_str)
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+    ) -> Result<(), anyhow::Error> {
+        match event.event.kind.as_str() {
+            EVENT_SWAP => index_swap(dbtx, event).await,
+            EVENT_POSITION_OPEN => index_position_open(dbtx, event).await,
+            EVENT_POSITION_CLOSE => index_position_close(dbtx, event).await,
+            EVENT_POSITION_WITHDRAW => index_position_withdraw(dbtx, event).await,
+            EVENT_POSITION_EXECUTION => index_position_execution(dbtx, event).await,
+            EVENT_BATCH_SWAP => index_batch_swap(dbtx, event).await,
+            EVENT_ARB_EXECUTION => index_arb_execution(dbtx, event).await,
+            other => Err(anyhow!("unexpected event type {other}")),
+        }
+        .with_context(|| format!("indexing {} event", event.event.kind))
+    }
+}
+
+fn asset_bytes(id: asset::Id) -> Vec<u8> {
+    id.to_bytes().to_vec()
+}
+
+async fn index_swap(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventSwap::from_event(event.as_ref())?;
+    let trading_pair: TradingPair = required(pe.trading_pair, "trading_pair")?;
+    let delta_1: Amount = required(pe.delta_1_i, "delta_1_i")?;
+    let delta_2: Amount = required(pe.delta_2_i, "delta_2_i")?;
+    let swap_commitment = pe
+        .swap_commitment
+        .ok_or_else(|| anyhow!("event missing swap_commitment"))?
+        .inner;
+
+    sqlx::query(
+        "
+        INSERT INTO dex_swaps (height, tx_hash, asset_1, asset_2, delta_1, delta_2, swap_commitment)
+        VALUES ($1, $2, $3, $4, $5::NUMERIC, $6::NUMERIC, $7)
+        ",
+    )
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(asset_bytes(trading_pair.asset_1()))
+    .bind(asset_bytes(trading_pair.asset_2()))
+    .bind(numeric(delta_1))
+    .bind(numeric(delta_2))
+    .bind(swap_commitment)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_position_open(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventPositionOpen::from_event(event.as_ref())?;
+    let position_id: position::Id = required(pe.position_id, "position_id")?;
+    let trading_pair: TradingPair = required(pe.trading_pair, "trading_pair")?;
+    let reserves_1: Amount = required(pe.reserves_1, "reserves_1")?;
+    let reserves_2: Amount = required(pe.reserves_2, "reserves_2")?;
+
+    sqlx::query(
+        "
+        INSERT INTO dex_positions (
+            position_id, asset_1, asset_2, fee, state, reserves_1, reserves_2,
+            opening_height, opening_tx
+        )
+        VALUES ($1, $2, $3, $4, 'opened', $5::NUMERIC, $6::NUMERIC, $7, $8)
+        ",
+    )
+    .bind(position_id.0.to_vec())
+    .bind(asset_bytes(trading_pair.asset_1()))
+    .bind(asset_bytes(trading_pair.asset_2()))
+    .bind(pe.trading_fee as i32)
+    .bind(numeric(reserves_1))
+    .bind(numeric(reserves_2))
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    record_history(
+        dbtx,
+        event,
+        position_id,
+        "open",
+        Some((reserves_1, reserves_2)),
+        None,
+    )
+    .await
+}
+
+async fn index_position_close(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventPositionClose::from_event(event.as_ref())?;
+    let position_id: position::Id = required(pe.position_id, "position_id")?;
+
+    sqlx::query(
+        "
+        UPDATE dex_positions
+        SET state = 'closed', closing_height = $2
+        WHERE position_id = $1
+        ",
+    )
+    .bind(position_id.0.to_vec())
+    .bind(event.block_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    record_history(dbtx, event, position_id, "close", None, None).await
+}
+
+async fn index_position_withdraw(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventPositionWithdraw::from_event(event.as_ref())?;
+    let position_id: position::Id = required(pe.position_id, "position_id")?;
+    let reserves_1: Amount = required(pe.reserves_1, "reserves_1")?;
+    let reserves_2: Amount = required(pe.reserves_2, "reserves_2")?;
+
+    sqlx::query(
+        "
+        UPDATE dex_positions
+        SET state = 'withdrawn', reserves_1 = $2::NUMERIC, reserves_2 = $3::NUMERIC,
+            withdrawal_sequence = $4
+        WHERE position_id = $1
+        ",
+    )
+    .bind(position_id.0.to_vec())
+    .bind(numeric(reserves_1))
+    .bind(numeric(reserves_2))
+    .bind(pe.sequence as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    record_history(
+        dbtx,
+        event,
+        position_id,
+        "withdraw",
+        Some((reserves_1, reserves_2)),
+        None,
+    )
+    .await
+}
+
+async fn index_position_execution(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventPositionExecution::from_event(event.as_ref())?;
+    let position_id: position::Id = required(pe.position_id, "position_id")?;
+    let reserves_1: Amount = required(pe.reserves_1, "reserves_1")?;
+    let reserves_2: Amount = required(pe.reserves_2, "reserves_2")?;
+    let prev_reserves_1: Amount = required(pe.prev_reserves_1, "prev_reserves_1")?;
+    let prev_reserves_2: Amount = required(pe.prev_reserves_2, "prev_reserves_2")?;
+    let context: DirectedTradingPair = required(pe.context, "context")?;
+
+    sqlx::query(
+        "
+        UPDATE dex_positions
+        SET reserves_1 = $2::NUMERIC, reserves_2 = $3::NUMERIC
+        WHERE position_id = $1
+        ",
+    )
+    .bind(position_id.0.to_vec())
+    .bind(numeric(reserves_1))
+    .bind(numeric(reserves_2))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    record_history(
+        dbtx,
+        event,
+        position_id,
+        "execution",
+        Some((reserves_1, reserves_2)),
+        Some(((prev_reserves_1, prev_reserves_2), context)),
+    )
+    .await
+}
+
+/// Appends an entry to a position's lifecycle history.
+async fn record_history(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+    position_id: position::Id,
+    kind: &str,
+    reserves: Option<(Amount, Amount)>,
+    execution: Option<((Amount, Amount), DirectedTradingPair)>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "
+        INSERT INTO dex_position_history (
+            position_id, height, tx_hash, kind, reserves_1, reserves_2,
+            prev_reserves_1, prev_reserves_2, context_start, context_end
+        )
+        VALUES ($1, $2, $3, $4, $5::NUMERIC, $6::NUMERIC, $7::NUMERIC, $8::NUMERIC, $9, $10)
+        ",
+    )
+    .bind(position_id.0.to_vec())
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(kind)
+    .bind(reserves.map(|(r1, _)| numeric(r1)))
+    .bind(reserves.map(|(_, r2)| numeric(r2)))
+    .bind(execution.map(|((r1, _), _)| numeric(r1)))
+    .bind(execution.map(|((_, r2), _)| numeric(r2)))
+    .bind(execution.map(|(_, context)| asset_bytes(context.start)))
+    .bind(execution.map(|(_, context)| asset_bytes(context.end)))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_batch_swap(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventBatchSwap::from_event(event.as_ref())?;
+    let bsod: BatchSwapOutputData = required(pe.batch_swap_output_data, "batch_swap_output_data")?;
+
+    sqlx::query(
+        "
+        INSERT INTO dex_batch_swaps (
+            height, asset_1, asset_2, delta_1, delta_2, lambda_1, lambda_2,
+            unfilled_1, unfilled_2
+        )
+        VALUES (
+            $1, $2, $3, $4::NUMERIC, $5::NUMERIC, $6::NUMERIC, $7::NUMERIC,
+            $8::NUMERIC, $9::NUMERIC
+        )
+        ",
+    )
+    .bind(bsod.height as i64)
+    .bind(asset_bytes(bsod.trading_pair.asset_1()))
+    .bind(asset_bytes(bsod.trading_pair.asset_2()))
+    .bind(numeric(bsod.delta_1))
+    .bind(numeric(bsod.delta_2))
+    .bind(numeric(bsod.lambda_1))
+    .bind(numeric(bsod.lambda_2))
+    .bind(numeric(bsod.unfilled_1))
+    .bind(numeric(bsod.unfilled_2))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    for execution in [pe.swap_execution_1_for_2, pe.swap_execution_2_for_1]
+        .into_iter()
+        .flatten()
+    {
+        let execution: SwapExecution = execution.try_into()?;
+        record_candlestick(dbtx, bsod.height, &execution).await?;
+    }
+
+    Ok(())
+}
+
+/// Folds the fills of a batch swap execution into the candlestick for its directed pair.
+async fn record_candlestick(
+    dbtx: &mut PgTransaction<'_>,
+    height: u64,
+    execution: &SwapExecution,
+) -> anyhow::Result<()> {
+    // Each trace is one fill along a route, so its endpoints give the price of that fill.
+    let prices: Vec<f64> = execution
+        .traces
+        .iter()
+        .filter_map(|trace| {
+            let input = trace.first()?.amount.value();
+            let output = trace.last()?.amount.value();
+            if input == 0 {
+                return None;
+            }
+            Some(output as f64 / input as f64)
+        })
+        .collect();
+    let (Some(open), Some(close)) = (prices.first(), prices.last()) else {
+        return Ok(());
+    };
+    let high = prices.iter().copied().fold(f64::MIN, f64::max);
+    let low = prices.iter().copied().fold(f64::MAX, f64::min);
+
+    sqlx::query(
+        "
+        INSERT INTO dex_candlesticks (asset_start, asset_end, height, open, high, low, close, volume)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8::NUMERIC)
+        ON CONFLICT (asset_start, asset_end, height) DO UPDATE SET
+            high = GREATEST(dex_candlesticks.high, excluded.high),
+            low = LEAST(dex_candlesticks.low, excluded.low),
+            close = excluded.close,
+            volume = dex_candlesticks.volume + excluded.volume
+        ",
+    )
+    .bind(asset_bytes(execution.input.asset_id))
+    .bind(asset_bytes(execution.output.asset_id))
+    .bind(height as i64)
+    .bind(open)
+    .bind(high)
+    .bind(low)
+    .bind(close)
+    .bind(numeric(execution.input.amount))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_arb_execution(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventArbExecution::from_event(event.as_ref())?;
+    let execution: SwapExecution = required(pe.swap_execution, "swap_execution")?;
+
+    sqlx::query(
+        "
+        INSERT INTO dex_arb_executions (height, asset_id, input, output, num_traces)
+        VALUES ($1, $2, $3::NUMERIC, $4::NUMERIC, $5)
+        ",
+    )
+    .bind(pe.height as i64)
+    .bind(asset_bytes(execution.input.asset_id))
+    .bind(numeric(execution.input.amount))
+    .bind(numeric(execution.output.amount))
+    .bind(execution.traces.len() as i32)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+-- Amounts are stored as NUMERIC(39, 0), which can hold any u128.
+-- Asset and position IDs are stored as their raw 32-byte encodings.
+
+-- The current state of every liquidity position.
+CREATE TABLE IF NOT EXISTS dex_positions (
+    position_id BYTEA PRIMARY KEY,
+    asset_1 BYTEA NOT NULL,
+    asset_2 BYTEA NOT NULL,
+    -- The trading fee, in basis points.
+    fee INTEGER NOT NULL,
+    -- One of 'opened', 'closed' or 'withdrawn'.
+    state TEXT NOT NULL,
+    reserves_1 NUMERIC(39, 0) NOT NULL,
+    reserves_2 NUMERIC(39, 0) NOT NULL,
+    opening_height BIGINT NOT NULL,
+    opening_tx BYTEA,
+    closing_height BIGINT,
+    -- The sequence number of the latest withdrawal, if any.
+    withdrawal_sequence BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS dex_positions_pair_idx ON dex_positions (asset_1, asset_2);
+
+-- Every change to a liquidity position, in order.
+CREATE TABLE IF NOT EXISTS dex_position_history (
+    id SERIAL PRIMARY KEY,
+    position_id BYTEA NOT NULL,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    -- One of 'open', 'close', 'withdraw' or 'execution'.
+    kind TEXT NOT NULL,
+    reserves_1 NUMERIC(39, 0),
+    reserves_2 NUMERIC(39, 0),
+    -- Only set for executions.
+    prev_reserves_1 NUMERIC(39, 0),
+    prev_reserves_2 NUMERIC(39, 0),
+    context_start BYTEA,
+    context_end BYTEA
+);
+
+CREATE INDEX IF NOT EXISTS dex_position_history_position_idx ON dex_position_history (position_id, height);
+
+-- Every swap submitted to the chain.
+CREATE TABLE IF NOT EXISTS dex_swaps (
+    id SERIAL PRIMARY KEY,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    asset_1 BYTEA NOT NULL,
+    asset_2 BYTEA NOT NULL,
+    delta_1 NUMERIC(39, 0) NOT NULL,
+    delta_2 NUMERIC(39, 0) NOT NULL,
+    swap_commitment BYTEA NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dex_swaps_pair_idx ON dex_swaps (asset_1, asset_2, height);
+
+-- The output of each block's batch swap, per trading pair.
+CREATE TABLE IF NOT EXISTS dex_batch_swaps (
+    height BIGINT NOT NULL,
+    asset_1 BYTEA NOT NULL,
+    asset_2 BYTEA NOT NULL,
+    delta_1 NUMERIC(39, 0) NOT NULL,
+    delta_2 NUMERIC(39, 0) NOT NULL,
+    lambda_1 NUMERIC(39, 0) NOT NULL,
+    lambda_2 NUMERIC(39, 0) NOT NULL,
+    unfilled_1 NUMERIC(39, 0) NOT NULL,
+    unfilled_2 NUMERIC(39, 0) NOT NULL,
+    PRIMARY KEY (height, asset_1, asset_2)
+);
+
+-- Every arbitrage execution performed at the end of a block.
+CREATE TABLE IF NOT EXISTS dex_arb_executions (
+    id SERIAL PRIMARY KEY,
+    height BIGINT NOT NULL,
+    asset_id BYTEA NOT NULL,
+    input NUMERIC(39, 0) NOT NULL,
+    output NUMERIC(39, 0) NOT NULL,
+    num_traces INTEGER NOT NULL
+);
+
+-- Per-block price and volume of each directed pair, from batch swap executions.
+--
+-- Prices are the amount of `asset_end` received per unit of `asset_start`, in
+-- base units, and volume is the amount of `asset_start` swapped.
+CREATE TABLE IF NOT EXISTS dex_candlesticks (
+    asset_start BYTEA NOT NULL,
+    asset_end BYTEA NOT NULL,
+    height BIGINT NOT NULL,
+    open DOUBLE PRECISION NOT NULL,
+    high DOUBLE PRECISION NOT NULL,
+    low DOUBLE PRECISION NOT NULL,
+    close DOUBLE PRECISION NOT NULL,
+    volume NUMERIC(39, 0) NOT NULL,
+    PRIMARY KEY (asset_start, asset_end, height)
+);
@@ -0,0 +1,319 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::{
+    async_trait,
+    sqlx::{self, Executor as _},
+    AppView, ContextualizedEvent, PgTransaction,
+};
+use penumbra_governance::{
+    proposal_state::Outcome, DelegatorVote, Proposal, ProposalDepositClaim, ProposalKind,
+    ProposalSubmit, ProposalWithdraw, ValidatorVote,
+};
+use penumbra_proto::{core::component::governance::v1 as pb, event::ProtoEvent};
+
+use crate::parsing::{numeric, required};
+
+const EVENT_PROPOSAL_SUBMIT: &str = "penumbra.core.component.governance.v1.EventProposalSubmit";
+const EVENT_PROPOSAL_WITHDRAW: &str = "penumbra.core.component.governance.v1.EventProposalWithdraw";
+const EVENT_ENACT_PROPOSAL: &str = "penumbra.core.component.governance.v1.EventEnactProposal";
+const EVENT_PROPOSAL_FAILED: &str = "penumbra.core.component.governance.v1.EventProposalFailed";
+const EVENT_PROPOSAL_SLASHED: &str = "penumbra.core.component.governance.v1.EventProposalSlashed";
+const EVENT_PROPOSAL_DEPOSIT_CLAIM: &str =
+    "penumbra.core.component.governance.v1.EventProposalDepositClaim";
+const EVENT_VALIDATOR_VOTE: &str = "penumbra.core.component.governance.v1.EventValidatorVote";
+const EVENT_DELEGATOR_VOTE: &str = "penumbra.core.component.governance.v1.EventDelegatorVote";
+
+/// Materializes governance events into tables of proposals, their state
+/// transitions, validator votes and running delegator tallies.
+#[derive(Debug)]
+pub struct Component {}
+
+#[async_trait]
+impl AppView for Component {
+    fn name(&self) -> String {
+        "governance".to_string()
+    }
+
+    async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut()
+            .execute(include_str!("governance/governance.sql"))
+            .await?;
+        Ok(())
+    }
+
+    async fn reset(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut()
+            .execute(
+                "DROP TABLE IF EXISTS governance_proposals, governance_proposal_transitions, \
+                 governance_validator_votes, governance_delegator_votes, \
+                 governance_delegator_tallies, governance_deposit_claims",
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [
+            EVENT_PROPOSAL_SUBMIT,
+            EVENT_PROPOSAL_WITHDRAW,
+            EVENT_ENACT_PROPOSAL,
+            EVENT_PROPOSAL_FAILED,
+            EVENT_PROPOSAL_SLASHED,
+            EVENT_PROPOSAL_DEPOSIT_CLAIM,
+            EVENT_VALIDATOR_VOTE,
+            EVENT_DELEGATOR_VOTE,
+        ]
+        .contains(&type_str)
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+    ) -> Result<(), anyhow::Error> {
+        match event.event.kind.as_str() {
+            EVENT_PROPOSAL_SUBMIT => index_proposal_submit(dbtx, event).await,
+            EVENT_PROPOSAL_WITHDRAW => index_proposal_withdraw(dbtx, event).await,
+            EVENT_ENACT_PROPOSAL => {
+                let pe = pb::EventEnactProposal::from_event(event.as_ref())?;
+                index_proposal_outcome(dbtx, event, pe.proposal, "passed").await
+            }
+            EVENT_PROPOSAL_FAILED => {
+                let pe = pb::EventProposalFailed::from_event(event.as_ref())?;
+                index_proposal_outcome(dbtx, event, pe.proposal, "failed").await
+            }
+            EVENT_PROPOSAL_SLASHED => {
+                let pe = pb::EventProposalSlashed::from_event(event.as_ref())?;
+                index_proposal_outcome(dbtx, event, pe.proposal, "slashed").await
+            }
+            EVENT_PROPOSAL_DEPOSIT_CLAIM => index_deposit_claim(dbtx, event).await,
+            EVENT_VALIDATOR_VOTE => index_validator_vote(dbtx, event).await,
+            EVENT_DELEGATOR_VOTE => index_delegator_vote(dbtx, event).await,
+            other => Err(anyhow!("unexpected event type {other}")),
+        }
+        .with_context(|| format!("indexing {} event", event.event.kind))
+    }
+}
+
+/// The name of a proposal kind, matching its `FromStr` representation.
+fn kind_name(kind: ProposalKind) -> &'static str {
+    match kind {
+        ProposalKind::Signaling => "signaling",
+        ProposalKind::Emergency => "emergency",
+        ProposalKind::ParameterChange => "parameter_change",
+        ProposalKind::CommunityPoolSpend => "community_pool_spend",
+        ProposalKind::UpgradePlan => "upgrade_plan",
+        ProposalKind::FreezeIbcClient => "freeze_ibc_client",
+        ProposalKind::UnfreezeIbcClient => "unfreeze_ibc_client",
+    }
+}
+
+/// Moves a proposal into `state`, recording the transition.
+async fn transition(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+    proposal_id: u64,
+    state: &str,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE governance_proposals SET state = $2 WHERE proposal_id = $1")
+        .bind(proposal_id as i64)
+        .bind(state)
+        .execute(dbtx.as_mut())
+        .await?;
+
+    sqlx::query(
+        "
+        INSERT INTO governance_proposal_transitions (proposal_id, height, state)
+        VALUES ($1, $2, $3)
+        ",
+    )
+    .bind(proposal_id as i64)
+    .bind(event.block_height as i64)
+    .bind(state)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_proposal_submit(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventProposalSubmit::from_event(event.as_ref())?;
+    let submit: ProposalSubmit = required(pe.submit, "submit")?;
+    let proposal = &submit.proposal;
+
+    sqlx::query(
+        "
+        INSERT INTO governance_proposals (
+            proposal_id, title, description, kind, deposit_amount, submit_height,
+            submit_tx, state
+        )
+        VALUES ($1, $2, $3, $4, $5::NUMERIC, $6, $7, 'voting')
+        ",
+    )
+    .bind(proposal.id as i64)
+    .bind(&proposal.title)
+    .bind(&proposal.description)
+    .bind(kind_name(proposal.kind()))
+    .bind(numeric(submit.deposit_amount))
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "
+        INSERT INTO governance_proposal_transitions (proposal_id, height, state)
+        VALUES ($1, $2, 'voting')
+        ",
+    )
+    .bind(proposal.id as i64)
+    .bind(event.block_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_proposal_withdraw(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventProposalWithdraw::from_event(event.as_ref())?;
+    let withdraw: ProposalWithdraw = required(pe.withdraw, "withdraw")?;
+
+    sqlx::query("UPDATE governance_proposals SET withdrawal_reason = $2 WHERE proposal_id = $1")
+        .bind(withdraw.proposal as i64)
+        .bind(&withdraw.reason)
+        .execute(dbtx.as_mut())
+        .await?;
+
+    transition(dbtx, event, withdraw.proposal, "withdrawn").await
+}
+
+async fn index_proposal_outcome(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+    proposal: Option<pb::Proposal>,
+    state: &str,
+) -> anyhow::Result<()> {
+    let proposal: Proposal = required(proposal, "proposal")?;
+
+    sqlx::query("UPDATE governance_proposals SET outcome_height = $2 WHERE proposal_id = $1")
+        .bind(proposal.id as i64)
+        .bind(event.block_height as i64)
+        .execute(dbtx.as_mut())
+        .await?;
+
+    transition(dbtx, event, proposal.id, state).await
+}
+
+async fn index_deposit_claim(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventProposalDepositClaim::from_event(event.as_ref())?;
+    let claim: ProposalDepositClaim = required(pe.deposit_claim, "deposit_claim")?;
+
+    let outcome = match claim.outcome {
+        Outcome::Passed => "passed",
+        Outcome::Failed { .. } => "failed",
+        Outcome::Slashed { .. } => "slashed",
+    };
+
+    sqlx::query(
+        "
+        INSERT INTO governance_deposit_claims (proposal_id, height, tx_hash, deposit_amount, outcome)
+        VALUES ($1, $2, $3, $4::NUMERIC, $5)
+        ",
+    )
+    .bind(claim.proposal as i64)
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(numeric(claim.deposit_amount))
+    .bind(outcome)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    transition(dbtx, event, claim.proposal, "claimed").await
+}
+
+async fn index_validator_vote(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorVote::from_event(event.as_ref())?;
+    let vote: ValidatorVote = required(pe.vote, "vote")?;
+
+    sqlx::query(
+        "
+        INSERT INTO governance_validator_votes (
+            proposal_id, identity_key, height, tx_hash, vote, reason
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ",
+    )
+    .bind(vote.body.proposal as i64)
+    .bind(vote.body.identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(vote.body.vote.to_string())
+    .bind(&vote.body.reason.0)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_delegator_vote(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventDelegatorVote::from_event(event.as_ref())?;
+    let vote: DelegatorVote = required(pe.vote, "vote")?;
+    let body = &vote.body;
+
+    sqlx::query(
+        "
+        INSERT INTO governance_delegator_votes (
+            proposal_id, height, tx_hash, vote, unbonded_amount, delegation_asset_id
+        )
+        VALUES ($1, $2, $3, $4, $5::NUMERIC, $6)
+        ",
+    )
+    .bind(body.proposal as i64)
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(body.vote.to_string())
+    .bind(numeric(body.unbonded_amount))
+    .bind(body.value.asset_id.to_bytes().to_vec())
+    .execute(dbtx.as_mut())
+    .await?;
+
+    // Recompute the running tally from every vote cast so far, so that each
+    // height's row reflects all votes up to and including that height.
+    sqlx::query(
+        "
+        INSERT INTO governance_delegator_tallies (proposal_id, height, yes, no, abstain)
+        SELECT
+            $1,
+            $2,
+            COALESCE(SUM(unbonded_amount) FILTER (WHERE vote = 'yes'), 0),
+            COALESCE(SUM(unbonded_amount) FILTER (WHERE vote = 'no'), 0),
+            COALESCE(SUM(unbonded_amount) FILTER (WHERE vote = 'abstain'), 0)
+        FROM governance_delegator_votes
+        WHERE proposal_id = $1
+        ON CONFLICT (proposal_id, height) DO UPDATE SET
+            yes = excluded.yes,
+            no = excluded.no,
+            abstain = excluded.abstain
+        ",
+    )
+    .bind(body.proposal as i64)
+    .bind(event.block_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
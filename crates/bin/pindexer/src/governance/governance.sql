@@ -0,0 +1,78 @@
+-- Amounts are stored as NUMERIC(39, 0), which can hold any u128.
+
+-- The latest known state of every proposal.
+CREATE TABLE IF NOT EXISTS governance_proposals (
+    proposal_id BIGINT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    -- e.g. 'signaling' or 'parameter_change'.
+    kind TEXT NOT NULL,
+    deposit_amount NUMERIC(39, 0) NOT NULL,
+    submit_height BIGINT NOT NULL,
+    submit_tx BYTEA,
+    -- One of 'voting', 'withdrawn', 'passed', 'failed', 'slashed' or 'claimed'.
+    state TEXT NOT NULL,
+    withdrawal_reason TEXT,
+    -- The height at which voting concluded, if it has.
+    outcome_height BIGINT
+);
+
+-- Every state transition of every proposal, in order.
+CREATE TABLE IF NOT EXISTS governance_proposal_transitions (
+    id SERIAL PRIMARY KEY,
+    proposal_id BIGINT NOT NULL,
+    height BIGINT NOT NULL,
+    state TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS governance_proposal_transitions_idx ON governance_proposal_transitions (proposal_id, height);
+
+-- Every vote cast by a validator.
+CREATE TABLE IF NOT EXISTS governance_validator_votes (
+    id SERIAL PRIMARY KEY,
+    proposal_id BIGINT NOT NULL,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    -- One of 'yes', 'no' or 'abstain'.
+    vote TEXT NOT NULL,
+    reason TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS governance_validator_votes_idx ON governance_validator_votes (proposal_id, identity_key);
+
+-- Every vote cast by a delegator.
+CREATE TABLE IF NOT EXISTS governance_delegator_votes (
+    id SERIAL PRIMARY KEY,
+    proposal_id BIGINT NOT NULL,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    vote TEXT NOT NULL,
+    -- The voting power of the vote, in unbonded staking tokens.
+    unbonded_amount NUMERIC(39, 0) NOT NULL,
+    -- The delegation token voted with, identifying the validator delegated to.
+    delegation_asset_id BYTEA NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS governance_delegator_votes_idx ON governance_delegator_votes (proposal_id, height);
+
+-- The running totals of delegator votes on each proposal, as of each height at which one was cast.
+CREATE TABLE IF NOT EXISTS governance_delegator_tallies (
+    proposal_id BIGINT NOT NULL,
+    height BIGINT NOT NULL,
+    yes NUMERIC(39, 0) NOT NULL,
+    no NUMERIC(39, 0) NOT NULL,
+    abstain NUMERIC(39, 0) NOT NULL,
+    PRIMARY KEY (proposal_id, height)
+);
+
+-- Every proposal deposit claimed.
+CREATE TABLE IF NOT EXISTS governance_deposit_claims (
+    id SERIAL PRIMARY KEY,
+    proposal_id BIGINT NOT NULL,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    deposit_amount NUMERIC(39, 0) NOT NULL,
+    -- One of 'passed', 'failed' or 'slashed'.
+    outcome TEXT NOT NULL
+);
@@ -4,6 +4,9 @@ pub trait IndexerExt: Sized {
 
 impl IndexerExt for cometindex::Indexer {
     fn with_default_penumbra_app_views(self) -> Self {
-        self.with_index(crate::shielded_pool::fmd::ClueSet {})
+        self.with_legacy_index(crate::shielded_pool::fmd::ClueSet {})
+            .with_index(crate::dex::Component {})
+            .with_index(crate::stake::Component {})
+            .with_index(crate::governance::Component {})
     }
 }
@@ -1,4 +1,10 @@
 pub use cometindex::{AppView, Indexer};
 
 mod indexer_ext;
+mod parsing;
+pub use indexer_ext::IndexerExt;
+
+pub mod dex;
+pub mod governance;
 pub mod shielded_pool;
+pub mod stake;
@@ -0,0 +1,19 @@
+//! Helpers shared by the app views for decoding events and binding their contents.
+
+use anyhow::anyhow;
+use penumbra_num::Amount;
+
+/// Converts a required field of a proto event into its domain type.
+pub(crate) fn required<P, T>(field: Option<P>, name: &str) -> anyhow::Result<T>
+where
+    T: TryFrom<P, Error = anyhow::Error>,
+{
+    field
+        .ok_or_else(|| anyhow!("event missing {name}"))?
+        .try_into()
+}
+
+/// Formats an amount for binding to a `NUMERIC` column.
+pub(crate) fn numeric(amount: Amount) -> String {
+    amount.value().to_string()
+}
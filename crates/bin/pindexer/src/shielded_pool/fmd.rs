@@ -6,6 +6,10 @@ pub struct ClueSet {}
 
 #[async_trait]
 impl AppView for ClueSet {
+    fn name(&self) -> String {
+        "shielded_pool_fmd_clue_set".to_string()
+    }
+
     async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
         sqlx::query(
             // table name is module path + struct name
@@ -22,6 +26,13 @@ CREATE TABLE IF NOT EXISTS shielded_pool_fmd_clue_set (
         Ok(())
     }
 
+    async fn reset(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        sqlx::query("DROP TABLE IF EXISTS shielded_pool_fmd_clue_set")
+            .execute(dbtx.as_mut())
+            .await?;
+        Ok(())
+    }
+
     fn is_relevant(&self, type_str: &str) -> bool {
         type_str == "penumbra.core.component.shielded_pool.v1.EventBroadcastClue"
     }
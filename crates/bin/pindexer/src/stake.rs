@@ -0,0 +1,430 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::{
+    async_trait,
+    sqlx::{self, Executor as _},
+    AppView, ContextualizedEvent, PgTransaction,
+};
+use penumbra_num::Amount;
+use penumbra_proto::{
+    core::component::{sct::v1 as sct_pb, stake::v1 as pb},
+    event::ProtoEvent,
+};
+use penumbra_stake::{
+    rate::RateData,
+    validator::{BondingState, State, Validator},
+    IdentityKey, Penalty,
+};
+
+use crate::parsing::{numeric, required};
+
+const EVENT_VALIDATOR_DEFINITION_UPLOAD: &str =
+    "penumbra.core.component.stake.v1.EventValidatorDefinitionUpload";
+const EVENT_VALIDATOR_STATE_CHANGE: &str =
+    "penumbra.core.component.stake.v1.EventValidatorStateChange";
+const EVENT_VALIDATOR_BONDING_STATE_CHANGE: &str =
+    "penumbra.core.component.stake.v1.EventValidatorBondingStateChange";
+const EVENT_VALIDATOR_VOTING_POWER_CHANGE: &str =
+    "penumbra.core.component.stake.v1.EventValidatorVotingPowerChange";
+const EVENT_RATE_DATA_CHANGE: &str = "penumbra.core.component.stake.v1.EventRateDataChange";
+const EVENT_VALIDATOR_MISSED_BLOCK: &str =
+    "penumbra.core.component.stake.v1.EventValidatorMissedBlock";
+const EVENT_TOMBSTONE_VALIDATOR: &str = "penumbra.core.component.stake.v1.EventTombstoneValidator";
+const EVENT_SLASHING_PENALTY_APPLIED: &str =
+    "penumbra.core.component.stake.v1.EventSlashingPenaltyApplied";
+const EVENT_DELEGATE: &str = "penumbra.core.component.stake.v1.EventDelegate";
+const EVENT_UNDELEGATE: &str = "penumbra.core.component.stake.v1.EventUndelegate";
+const EVENT_REDELEGATE: &str = "penumbra.core.component.stake.v1.EventRedelegate";
+const EVENT_EPOCH_ROOT: &str = "penumbra.core.component.sct.v1.EventEpochRoot";
+
+/// Materializes staking events into validator history tables: status timelines,
+/// missed blocks, rate data per epoch and delegation flows.
+#[derive(Debug)]
+pub struct Component {}
+
+#[async_trait]
+impl AppView for Component {
+    fn name(&self) -> String {
+        "stake".to_string()
+    }
+
+    async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut()
+            .execute(include_str!("stake/stake.sql"))
+            .await?;
+        Ok(())
+    }
+
+    async fn reset(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        dbtx.as_mut()
+            .execute(
+                "DROP VIEW IF EXISTS stake_rate_data_by_epoch, stake_missed_blocks_per_window; \
+                 DROP TABLE IF EXISTS stake_validators, stake_validator_states, \
+                 stake_bonding_states, stake_voting_power, stake_epochs, stake_rate_data, \
+                 stake_missed_blocks, stake_tombstones, stake_slashing_penalties, \
+                 stake_delegation_flows",
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [
+            EVENT_VALIDATOR_DEFINITION_UPLOAD,
+            EVENT_VALIDATOR_STATE_CHANGE,
+            EVENT_VALIDATOR_BONDING_STATE_CHANGE,
+            EVENT_VALIDATOR_VOTING_POWER_CHANGE,
+            EVENT_RATE_DATA_CHANGE,
+            EVENT_VALIDATOR_MISSED_BLOCK,
+            EVENT_TOMBSTONE_VALIDATOR,
+            EVENT_SLASHING_PENALTY_APPLIED,
+            EVENT_DELEGATE,
+            EVENT_UNDELEGATE,
+            EVENT_REDELEGATE,
+            EVENT_EPOCH_ROOT,
+        ]
+        .contains(&type_str)
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+    ) -> Result<(), anyhow::Error> {
+        match event.event.kind.as_str() {
+            EVENT_VALIDATOR_DEFINITION_UPLOAD => index_definition_upload(dbtx, event).await,
+            EVENT_VALIDATOR_STATE_CHANGE => index_state_change(dbtx, event).await,
+            EVENT_VALIDATOR_BONDING_STATE_CHANGE => index_bonding_state_change(dbtx, event).await,
+            EVENT_VALIDATOR_VOTING_POWER_CHANGE => index_voting_power_change(dbtx, event).await,
+            EVENT_RATE_DATA_CHANGE => index_rate_data_change(dbtx, event).await,
+            EVENT_VALIDATOR_MISSED_BLOCK => index_missed_block(dbtx, event).await,
+            EVENT_TOMBSTONE_VALIDATOR => index_tombstone(dbtx, event).await,
+            EVENT_SLASHING_PENALTY_APPLIED => index_slashing_penalty(dbtx, event).await,
+            EVENT_DELEGATE => index_delegation_flow(dbtx, event, "delegate").await,
+            EVENT_UNDELEGATE => index_delegation_flow(dbtx, event, "undelegate").await,
+            EVENT_REDELEGATE => index_redelegation(dbtx, event).await,
+            EVENT_EPOCH_ROOT => index_epoch_root(dbtx, event).await,
+            other => Err(anyhow!("unexpected event type {other}")),
+        }
+        .with_context(|| format!("indexing {} event", event.event.kind))
+    }
+}
+
+async fn index_definition_upload(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorDefinitionUpload::from_event(event.as_ref())?;
+    let validator: Validator = required(pe.validator, "validator")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_validators (
+            identity_key, name, website, description, enabled, sequence_number,
+            definition_height
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (identity_key) DO UPDATE SET
+            name = excluded.name,
+            website = excluded.website,
+            description = excluded.description,
+            enabled = excluded.enabled,
+            sequence_number = excluded.sequence_number,
+            definition_height = excluded.definition_height
+        ",
+    )
+    .bind(validator.identity_key.to_string())
+    .bind(&validator.name)
+    .bind(&validator.website)
+    .bind(&validator.description)
+    .bind(validator.enabled)
+    .bind(validator.sequence_number as i64)
+    .bind(event.block_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_state_change(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorStateChange::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+    let state: State = required(pe.state, "state")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_validators (identity_key, state) VALUES ($1, $2)
+        ON CONFLICT (identity_key) DO UPDATE SET state = excluded.state
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(state.to_string())
+    .execute(dbtx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO stake_validator_states (identity_key, height, state) VALUES ($1, $2, $3)",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(state.to_string())
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_bonding_state_change(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorBondingStateChange::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+    let bonding_state: BondingState = required(pe.bonding_state, "bonding_state")?;
+
+    let (name, unbonds_at_height) = match bonding_state {
+        BondingState::Bonded => ("Bonded", None),
+        BondingState::Unbonded => ("Unbonded", None),
+        BondingState::Unbonding { unbonds_at_height } => {
+            ("Unbonding", Some(unbonds_at_height as i64))
+        }
+    };
+
+    sqlx::query(
+        "
+        INSERT INTO stake_validators (identity_key, bonding_state) VALUES ($1, $2)
+        ON CONFLICT (identity_key) DO UPDATE SET bonding_state = excluded.bonding_state
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(name)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_bonding_states (identity_key, height, bonding_state, unbonds_at_height)
+        VALUES ($1, $2, $3, $4)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(name)
+    .bind(unbonds_at_height)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_voting_power_change(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorVotingPowerChange::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+    let voting_power: Amount = required(pe.voting_power, "voting_power")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_validators (identity_key, voting_power) VALUES ($1, $2::NUMERIC)
+        ON CONFLICT (identity_key) DO UPDATE SET voting_power = excluded.voting_power
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(numeric(voting_power))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_voting_power (identity_key, height, voting_power)
+        VALUES ($1, $2, $3::NUMERIC)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(numeric(voting_power))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_rate_data_change(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventRateDataChange::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+    let rate_data: RateData = required(pe.rate_data, "rate_data")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_rate_data (identity_key, height, reward_rate, exchange_rate)
+        VALUES ($1, $2, $3::NUMERIC, $4::NUMERIC)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(numeric(rate_data.validator_reward_rate))
+    .bind(numeric(rate_data.validator_exchange_rate))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_missed_block(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventValidatorMissedBlock::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+
+    sqlx::query("INSERT INTO stake_missed_blocks (identity_key, height) VALUES ($1, $2)")
+        .bind(identity_key.to_string())
+        .bind(event.block_height as i64)
+        .execute(dbtx.as_mut())
+        .await?;
+
+    Ok(())
+}
+
+async fn index_tombstone(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventTombstoneValidator::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_validators (identity_key, tombstone_height) VALUES ($1, $2)
+        ON CONFLICT (identity_key) DO UPDATE SET tombstone_height = excluded.tombstone_height
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(pe.current_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_tombstones (identity_key, height, evidence_height, address, voting_power)
+        VALUES ($1, $2, $3, $4, $5)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(pe.current_height as i64)
+    .bind(pe.evidence_height as i64)
+    .bind(&pe.address)
+    .bind(pe.voting_power as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_slashing_penalty(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventSlashingPenaltyApplied::from_event(event.as_ref())?;
+    let identity_key: IdentityKey = required(pe.identity_key, "identity_key")?;
+    let penalty: Penalty = required(pe.new_penalty, "new_penalty")?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_slashing_penalties (identity_key, height, epoch_index, kept_rate)
+        VALUES ($1, $2, $3, $4)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(pe.epoch_index as i64)
+    .bind(f64::from(penalty.kept_rate()))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_delegation_flow(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+    kind: &str,
+) -> anyhow::Result<()> {
+    let (identity_key, amount) = if kind == "delegate" {
+        let pe = pb::EventDelegate::from_event(event.as_ref())?;
+        (pe.identity_key, pe.amount)
+    } else {
+        let pe = pb::EventUndelegate::from_event(event.as_ref())?;
+        (pe.identity_key, pe.amount)
+    };
+    let identity_key: IdentityKey = required(identity_key, "identity_key")?;
+    let amount: Amount = required(amount, "amount")?;
+
+    insert_delegation_flow(dbtx, event, identity_key, kind, amount).await
+}
+
+/// Records a redelegation as a flow out of the source validator's pool and a
+/// flow into the destination validator's pool.
+async fn index_redelegation(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = pb::EventRedelegate::from_event(event.as_ref())?;
+    let from: IdentityKey = required(pe.from_identity_key, "from_identity_key")?;
+    let to: IdentityKey = required(pe.to_identity_key, "to_identity_key")?;
+    let amount: Amount = required(pe.amount, "amount")?;
+
+    insert_delegation_flow(dbtx, event, from, "redelegate_out", amount).await?;
+    insert_delegation_flow(dbtx, event, to, "redelegate_in", amount).await
+}
+
+async fn insert_delegation_flow(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+    identity_key: IdentityKey,
+    kind: &str,
+    amount: Amount,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "
+        INSERT INTO stake_delegation_flows (identity_key, height, tx_hash, kind, amount)
+        VALUES ($1, $2, $3, $4, $5::NUMERIC)
+        ",
+    )
+    .bind(identity_key.to_string())
+    .bind(event.block_height as i64)
+    .bind(event.tx_hash.map(|h| h.to_vec()))
+    .bind(kind)
+    .bind(numeric(amount))
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+async fn index_epoch_root(
+    dbtx: &mut PgTransaction<'_>,
+    event: &ContextualizedEvent,
+) -> anyhow::Result<()> {
+    let pe = sct_pb::EventEpochRoot::from_event(event.as_ref())?;
+
+    sqlx::query(
+        "
+        INSERT INTO stake_epochs (epoch_index, end_height) VALUES ($1, $2)
+        ON CONFLICT (epoch_index) DO NOTHING
+        ",
+    )
+    .bind(pe.index as i64)
+    .bind(event.block_height as i64)
+    .execute(dbtx.as_mut())
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+-- Amounts are stored as NUMERIC(39, 0), which can hold any u128.
+-- Validators are identified by the bech32m encoding of their identity key.
+
+-- The latest known definition and status of every validator.
+--
+-- The definition columns are null for validators whose definition was not
+-- uploaded through a transaction, e.g. genesis validators.
+CREATE TABLE IF NOT EXISTS stake_validators (
+    identity_key TEXT PRIMARY KEY,
+    name TEXT,
+    website TEXT,
+    description TEXT,
+    enabled BOOLEAN,
+    sequence_number BIGINT,
+    definition_height BIGINT,
+    state TEXT,
+    bonding_state TEXT,
+    voting_power NUMERIC(39, 0),
+    tombstone_height BIGINT
+);
+
+-- Every change to a validator's state, e.g. 'Active' or 'Jailed'.
+CREATE TABLE IF NOT EXISTS stake_validator_states (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    state TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_validator_states_idx ON stake_validator_states (identity_key, height);
+
+-- Every change to a validator's bonding state.
+CREATE TABLE IF NOT EXISTS stake_bonding_states (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    -- One of 'Bonded', 'Unbonded' or 'Unbonding'.
+    bonding_state TEXT NOT NULL,
+    unbonds_at_height BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS stake_bonding_states_idx ON stake_bonding_states (identity_key, height);
+
+-- Every change to a validator's voting power.
+CREATE TABLE IF NOT EXISTS stake_voting_power (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    voting_power NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_voting_power_idx ON stake_voting_power (identity_key, height);
+
+-- The heights at which each epoch ended.
+CREATE TABLE IF NOT EXISTS stake_epochs (
+    epoch_index BIGINT PRIMARY KEY,
+    end_height BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_epochs_end_height_idx ON stake_epochs (end_height);
+
+-- Every change to a validator's rate data.
+--
+-- Rates are in basis points squared, i.e. scaled by 10^8.
+CREATE TABLE IF NOT EXISTS stake_rate_data (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    reward_rate NUMERIC(39, 0) NOT NULL,
+    exchange_rate NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_rate_data_idx ON stake_rate_data (identity_key, height);
+
+-- Rate data by the epoch it applies to: rates computed at the end of one epoch
+-- apply to the next.
+CREATE OR REPLACE VIEW stake_rate_data_by_epoch AS
+SELECT
+    stake_rate_data.identity_key,
+    stake_epochs.epoch_index + 1 AS epoch_index,
+    stake_rate_data.reward_rate,
+    stake_rate_data.exchange_rate
+FROM stake_rate_data
+JOIN stake_epochs ON stake_epochs.end_height = stake_rate_data.height;
+
+-- Every block a validator failed to sign.
+CREATE TABLE IF NOT EXISTS stake_missed_blocks (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_missed_blocks_idx ON stake_missed_blocks (identity_key, height);
+
+-- Missed blocks per validator, in windows of 1000 blocks.
+CREATE OR REPLACE VIEW stake_missed_blocks_per_window AS
+SELECT
+    identity_key,
+    (height / 1000) * 1000 AS window_start,
+    COUNT(*) AS missed_blocks
+FROM stake_missed_blocks
+GROUP BY identity_key, window_start;
+
+-- Every validator tombstoned for misbehavior.
+CREATE TABLE IF NOT EXISTS stake_tombstones (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    evidence_height BIGINT NOT NULL,
+    address BYTEA NOT NULL,
+    voting_power BIGINT NOT NULL
+);
+
+-- Every slashing penalty applied to a validator.
+CREATE TABLE IF NOT EXISTS stake_slashing_penalties (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    epoch_index BIGINT NOT NULL,
+    -- The fraction of stake kept after the (compounded) penalty.
+    kept_rate DOUBLE PRECISION NOT NULL
+);
+
+-- Every delegation and undelegation, in unbonded staking tokens.
+CREATE TABLE IF NOT EXISTS stake_delegation_flows (
+    id SERIAL PRIMARY KEY,
+    identity_key TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    tx_hash BYTEA,
+    -- One of 'delegate', 'undelegate', 'redelegate_out' or 'redelegate_in'.
+    kind TEXT NOT NULL,
+    amount NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stake_delegation_flows_idx ON stake_delegation_flows (identity_key, height);
@@ -81,7 +81,7 @@ pub use escaped_byte_slice::EscapedByteSlice;
 pub use jmt::{ics23_spec, RootHash};
 pub use read::StateRead;
 pub use snapshot::Snapshot;
-pub use storage::{Storage, TempStorage};
+pub use storage::{PruningMode, PruningStats, SnapshotError, Storage, TempStorage};
 pub use write::StateWrite;
 pub use write_batch::StagedWriteBatch;
 
@@ -26,8 +26,32 @@ pub fn register_metrics() {
         Unit::Seconds,
         "The duration of a nonverifiable_get_raw request"
     );
+    describe_counter!(
+        STORAGE_PRUNED_NODES_TOTAL,
+        Unit::Count,
+        "The number of JMT nodes deleted by pruning"
+    );
+    describe_counter!(
+        STORAGE_PRUNED_VALUES_TOTAL,
+        Unit::Count,
+        "The number of JMT values deleted by pruning"
+    );
+    describe_counter!(
+        STORAGE_PRUNED_BYTES_TOTAL,
+        Unit::Bytes,
+        "The number of bytes reclaimed by pruning"
+    );
+    describe_histogram!(
+        STORAGE_PRUNING_DURATION,
+        Unit::Seconds,
+        "The duration of a pruning pass"
+    );
 }
 
 pub const STORAGE_GET_RAW_DURATION: &str = "cnidarium_get_raw_duration_seconds";
 pub const STORAGE_NONCONSENSUS_GET_RAW_DURATION: &str =
     "cnidarium_nonverifiable_get_raw_duration_seconds";
+pub const STORAGE_PRUNED_NODES_TOTAL: &str = "cnidarium_pruned_jmt_nodes_total";
+pub const STORAGE_PRUNED_VALUES_TOTAL: &str = "cnidarium_pruned_jmt_values_total";
+pub const STORAGE_PRUNED_BYTES_TOTAL: &str = "cnidarium_pruned_bytes_total";
+pub const STORAGE_PRUNING_DURATION: &str = "cnidarium_pruning_duration_seconds";
@@ -1,4 +1,3 @@
-use std::iter;
 use std::{any::Any, sync::Arc};
 
 use anyhow::Result;
@@ -9,7 +8,7 @@ use tracing::Span;
 
 #[cfg(feature = "metrics")]
 use crate::metrics;
-use crate::store::multistore::{self, MultistoreCache};
+use crate::store::multistore::{self, MultistoreCache, PrefixMatch};
 use crate::{store, StateRead};
 
 mod rocks_wrapper;
@@ -176,6 +175,31 @@ impl Snapshot {
         self.prefix_root_hash("").await
     }
 
+    /// Returns a snapshot of every substore that can hold keys starting with `prefix`.
+    fn matching_substores(
+        &self,
+        prefix: &[u8],
+    ) -> Vec<(store::substore::SubstoreSnapshot, PrefixMatch)> {
+        self.0
+            .multistore_cache
+            .config
+            .match_prefix_all(prefix)
+            .into_iter()
+            .map(|matched| {
+                let version = self
+                    .substore_version(&matched.config)
+                    .expect("the substore exists and has been initialized");
+                let substore = store::substore::SubstoreSnapshot {
+                    config: matched.config.clone(),
+                    rocksdb_snapshot: self.0.snapshot.clone(),
+                    version,
+                    db: self.0.db.clone(),
+                };
+                (substore, matched)
+            })
+            .collect()
+    }
+
     pub(crate) fn substore_version(
         &self,
         prefix: &Arc<store::substore::SubstoreConfig>,
@@ -264,30 +288,12 @@ impl StateRead for Snapshot {
     }
 
     /// Returns a stream of all key-value pairs with the given prefix.
+    ///
+    /// The prefix may span several substores, in which case the entries of each
+    /// substore are interleaved in key order.
     fn prefix_raw(&self, prefix: &str) -> Self::PrefixRawStream {
         let span = Span::current();
-
-        let rocksdb_snapshot = self.0.snapshot.clone();
-        let db = self.0.db.clone();
-
-        let (prefix_truncated, config) = self.0.multistore_cache.config.match_prefix_str(prefix);
-        tracing::trace!(substore_key = prefix_truncated,  substore_prefix = config.prefix, prefix_supplied = ?prefix, "matched prefix, fetching substore");
-        let substore_prefix = config.prefix.clone();
-
-        let version = self
-            .substore_version(&config)
-            .expect("the substore exists and has been initialized");
-
-        let substore = store::substore::SubstoreSnapshot {
-            config,
-            rocksdb_snapshot,
-            version,
-            db,
-        };
-
-        let mut options = rocksdb::ReadOptions::default();
-        options.set_iterate_range(rocksdb::PrefixRange(prefix_truncated.as_bytes()));
-        let mode = rocksdb::IteratorMode::Start;
+        let substores = self.matching_substores(prefix.as_bytes());
         let (tx_prefix_item, rx_prefix_query) = mpsc::channel(10);
 
         // Since the JMT keys are hashed, we can't use a prefix iterator directly.
@@ -295,24 +301,15 @@ impl StateRead for Snapshot {
         // from the JMT column family.
         tokio::task::spawn_blocking(move || {
             span.in_scope(|| {
-                let cf_jmt_keys = substore.config.cf_jmt_keys(&substore.db);
                 let jmt_keys_iterator =
-                    substore
-                        .rocksdb_snapshot
-                        .iterator_cf_opt(cf_jmt_keys, options, mode);
+                    substore_entries(&substores, |s| s.config.cf_jmt_keys(&s.db), &[], None);
 
-                for tuple in jmt_keys_iterator {
+                for entry in jmt_keys_iterator {
                     // For each key that matches the prefix, fetch the value from the JMT column family.
-                    let (key_preimage, _) = tuple?;
-                    let substore_key = std::str::from_utf8(key_preimage.as_ref())
-                        .expect("saved jmt keys are utf-8 strings");
-                    let key_hash = jmt::KeyHash::with::<sha2::Sha256>(substore_key.as_bytes());
-
-                    let full_key = if substore_prefix.is_empty() {
-                        substore_key.to_string()
-                    } else {
-                        format!("{substore_prefix}/{substore_key}").to_string()
-                    };
+                    let (full_key, (substore, key_preimage, _)) = entry?;
+                    let full_key =
+                        String::from_utf8(full_key).expect("saved jmt keys are utf-8 strings");
+                    let key_hash = jmt::KeyHash::with::<sha2::Sha256>(&key_preimage);
 
                     let v = substore
                         .get_jmt(key_hash)?
@@ -332,48 +329,17 @@ impl StateRead for Snapshot {
     // be better overall.
     fn prefix_keys(&self, prefix: &str) -> Self::PrefixKeysStream {
         let span = Span::current();
-
-        let rocksdb_snapshot = self.0.snapshot.clone();
-        let db = self.0.db.clone();
-
-        let (prefix_truncated, config) = self.0.multistore_cache.config.match_prefix_str(prefix);
-
-        let version = self
-            .substore_version(&config)
-            .expect("the substore exists and has been initialized");
-
-        let substore = store::substore::SubstoreSnapshot {
-            config,
-            rocksdb_snapshot,
-            version,
-            db,
-        };
-
-        let mut options = rocksdb::ReadOptions::default();
-        options.set_iterate_range(rocksdb::PrefixRange(prefix_truncated.as_bytes()));
-        let mode = rocksdb::IteratorMode::Start;
+        let substores = self.matching_substores(prefix.as_bytes());
         let (tx_prefix_keys, rx_prefix_keys) = mpsc::channel(10);
 
         tokio::task::spawn_blocking(move || {
             span.in_scope(|| {
-                let cf_jmt_keys = substore.config.cf_jmt_keys(&substore.db);
-                let iter = substore
-                    .rocksdb_snapshot
-                    .iterator_cf_opt(cf_jmt_keys, options, mode);
-
-                let substore_prefix = &substore.config.prefix;
-
-                for key_and_keyhash in iter {
-                    let (raw_preimage, _) = key_and_keyhash?;
-                    let preimage = std::str::from_utf8(raw_preimage.as_ref())
-                        .expect("saved jmt keys are utf-8 strings");
-
-                    let full_key = if substore_prefix.is_empty() {
-                        preimage.to_string()
-                    } else {
-                        format!("{substore_prefix}/{preimage}").to_string()
-                    };
+                let iter = substore_entries(&substores, |s| s.config.cf_jmt_keys(&s.db), &[], None);
 
+                for entry in iter {
+                    let (full_key, _) = entry?;
+                    let full_key =
+                        String::from_utf8(full_key).expect("saved jmt keys are utf-8 strings");
                     tx_prefix_keys.blocking_send(Ok(full_key))?;
                 }
                 anyhow::Ok(())
@@ -386,53 +352,16 @@ impl StateRead for Snapshot {
     /// Returns a stream of all key-value pairs with the given prefix, from nonverifiable storage.
     fn nonverifiable_prefix_raw(&self, prefix: &[u8]) -> Self::NonconsensusPrefixRawStream {
         let span = Span::current();
-        let rocksdb_snapshot = self.0.snapshot.clone();
-        let db = self.0.db.clone();
-
-        let (truncated_prefix, config) = self.0.multistore_cache.config.match_prefix_bytes(prefix);
-        tracing::trace!(substore_key = ?truncated_prefix,  substore_prefix = config.prefix, prefix_supplied = ?prefix, "matched prefix, fetching substore");
-        let version = self
-            .substore_version(&config)
-            .expect("the substore exists and has been initialized");
-
-        let substore = store::substore::SubstoreSnapshot {
-            config,
-            rocksdb_snapshot,
-            version,
-            db,
-        };
-
-        let mut options = rocksdb::ReadOptions::default();
-        options.set_iterate_range(rocksdb::PrefixRange(truncated_prefix));
-        let mode = rocksdb::IteratorMode::Start;
-
+        let substores = self.matching_substores(prefix);
         let (tx_prefix_query, rx_prefix_query) = mpsc::channel(10);
 
         tokio::task::spawn_blocking(move || {
             span.in_scope(|| {
-                let cf_nonverifiable = substore.config.cf_nonverifiable(&substore.db);
                 let iter =
-                    substore
-                        .rocksdb_snapshot
-                        .iterator_cf_opt(cf_nonverifiable, options, mode);
-                let substore_prefix = substore.config.prefix.as_bytes().to_vec();
-                for i in iter {
-                    let (boxed_key, boxed_value) = i?;
-                    let key: Vec<u8> = boxed_key.into();
-                    let value: Vec<u8> = boxed_value.into();
-
-                    // Costly to do on every iteration, but this should be dwarfed by the
-                    // context switch to the tokio runtime.
-                    let mut full_key: Vec<u8> = vec![];
-                    if substore_prefix.is_empty() {
-                        full_key.extend(key);
-                    } else {
-                        full_key.extend(substore_prefix.clone());
-                        full_key.extend(iter::once(b'/'));
-                        full_key.extend(key);
-                    }
-
-                    tx_prefix_query.blocking_send(Ok((full_key, value)))?;
+                    substore_entries(&substores, |s| s.config.cf_nonverifiable(&s.db), &[], None);
+                for entry in iter {
+                    let (full_key, (_, _, value)) = entry?;
+                    tx_prefix_query.blocking_send(Ok((full_key, value.into())))?;
                 }
                 anyhow::Ok(())
             })
@@ -443,93 +372,35 @@ impl StateRead for Snapshot {
 
     /// Returns a stream of all key-value pairs with the given prefix, and range
     /// from nonverifiable storage.
-    /// **Important**: Only supports range queries over the main store.
+    ///
+    /// The keys that are returned are made of the prefix followed by a suffix that
+    /// falls within the range. Like prefix queries, range queries may span several
+    /// substores, e.g. when no prefix is supplied.
     fn nonverifiable_range_raw(
         &self,
         prefix: Option<&[u8]>,
         range: impl std::ops::RangeBounds<Vec<u8>>,
     ) -> anyhow::Result<Self::NonconsensusRangeRawStream> {
         let span = Span::current();
-        let rocksdb_snapshot = self.0.snapshot.clone();
-        let db = self.0.db.clone();
-
-        let (prefix, config) = self
-            .0
-            .multistore_cache
-            .config
-            .route_key_bytes(prefix.unwrap_or_default());
-
-        let version = self
-            .substore_version(&config)
-            .expect("the substore exists and has been initialized");
-
-        let substore = store::substore::SubstoreSnapshot {
-            config,
-            rocksdb_snapshot,
-            version,
-            db,
-        };
+        let prefix = prefix.unwrap_or_default();
+        let substores = self.matching_substores(prefix);
 
         let (_range, (start, end)) = crate::utils::convert_bounds(range)?;
-        let mut options = rocksdb::ReadOptions::default();
-
-        let (start, end) = (start.unwrap_or_default(), end.unwrap_or_default());
-        let end_is_empty = end.is_empty();
-
-        let mut prefix_start = Vec::with_capacity(prefix.len() + start.len());
-        let mut prefix_end = Vec::with_capacity(prefix.len() + end.len());
-
-        prefix_start.extend(prefix);
-        prefix_start.extend(start);
-        prefix_end.extend(prefix);
-        prefix_end.extend(end);
-
-        tracing::debug!(
-            ?prefix_start,
-            ?prefix_end,
-            ?prefix,
-            "nonverifiable_range_raw"
-        );
-
-        options.set_iterate_lower_bound(prefix_start);
-
-        // Our range queries implementation relies on forward iteration, which
-        // means that if the upper key is unbounded and a prefix has been set
-        // we cannot set the upper bound to the prefix. This is because the
-        // prefix is used as a lower bound for the iterator, and the upper bound
-        // is used to stop the iteration.
-        // If we set the upper bound to the prefix, we would get a range consisting of:
-        // ```
-        // "compactblock/001" to "compactblock/"
-        // ```
-        // which would not return anything.
-        if !end_is_empty {
-            options.set_iterate_upper_bound(prefix_end);
-        }
-
-        let mode = rocksdb::IteratorMode::Start;
-        let prefix = prefix.to_vec();
+        tracing::debug!(?prefix, ?start, ?end, "nonverifiable_range_raw");
 
         let (tx, rx) = mpsc::channel::<Result<(Vec<u8>, Vec<u8>)>>(10);
         tokio::task::spawn_blocking(move || {
             span.in_scope(|| {
-                let cf_nonverifiable = substore.config.cf_nonverifiable(&substore.db);
-                let iter =
-                    substore
-                        .rocksdb_snapshot
-                        .iterator_cf_opt(cf_nonverifiable, options, mode);
-
-                for i in iter {
-                    let (key, value) = i?;
-
-                    // This is a bit of a hack, but RocksDB doesn't let us express the "prefixed range-queries",
-                    // that we want to support. In particular, we want to be able to do a prefix query that starts
-                    // at a particular key, and does not have an upper bound. Since we can't create an iterator that
-                    // cover this range, we have to filter out the keys that don't match the prefix.
-                    if !prefix.is_empty() && !key.starts_with(&prefix) {
-                        break;
-                    }
-                    tx.blocking_send(Ok((key.into(), value.into())))?;
+                let iter = substore_entries(
+                    &substores,
+                    |s| s.config.cf_nonverifiable(&s.db),
+                    start.as_deref().unwrap_or_default(),
+                    end.as_deref(),
+                );
+
+                for entry in iter {
+                    let (full_key, (_, _, value)) = entry?;
+                    tx.blocking_send(Ok((full_key, value.into())))?;
                 }
                 Ok::<(), anyhow::Error>(())
             })
@@ -549,6 +420,47 @@ impl StateRead for Snapshot {
     }
 }
 
+/// An entry read from a substore: the substore, the truncated key and the value.
+type SubstoreEntry<'a> = (&'a store::substore::SubstoreSnapshot, Box<[u8]>, Box<[u8]>);
+
+/// Iterates over a column family of each of the matched `substores`, restricted to
+/// the keys made of the queried prefix followed by a suffix in `[start, end)`.
+///
+/// Entries are yielded in order of their full key, along with the substore they
+/// belong to.
+fn substore_entries<'a>(
+    substores: &'a [(store::substore::SubstoreSnapshot, PrefixMatch)],
+    column: fn(&store::substore::SubstoreSnapshot) -> &rocksdb::ColumnFamily,
+    start: &[u8],
+    end: Option<&[u8]>,
+) -> impl Iterator<Item = Result<(Vec<u8>, SubstoreEntry<'a>)>> + 'a {
+    let iters = substores
+        .iter()
+        .filter_map(|(substore, matched)| {
+            let (lower, upper) = matched.substore_bounds(start, end)?;
+            tracing::trace!(substore_prefix = ?substore.config.prefix, ?lower, ?upper, "iterating over substore");
+
+            let mut options = rocksdb::ReadOptions::default();
+            options.set_iterate_lower_bound(lower);
+            if let Some(upper) = upper {
+                options.set_iterate_upper_bound(upper);
+            }
+            let iter = substore.rocksdb_snapshot.iterator_cf_opt(
+                column(substore),
+                options,
+                rocksdb::IteratorMode::Start,
+            );
+
+            Some(iter.map(move |item| {
+                let (key, value) = item?;
+                anyhow::Ok((matched.full_key(&key), (substore, key, value)))
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    crate::utils::merge_sorted(iters)
+}
+
 impl std::fmt::Debug for Snapshot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Snapshot")
@@ -83,6 +83,15 @@ impl SnapshotCache {
     pub fn clear(&mut self) {
         self.cache.clear();
     }
+
+    /// Empties the cache, and inserts `snapshot` as its only entry.
+    ///
+    /// This bypasses the consecutive version check, and is used when the
+    /// underlying storage is replaced wholesale, e.g. after a state sync import.
+    pub fn reset(&mut self, snapshot: Snapshot) {
+        self.cache.clear();
+        self.cache.push_front(snapshot);
+    }
 }
 
 #[cfg(test)]
@@ -17,7 +17,10 @@ use crate::{
 };
 use crate::{snapshot_cache::SnapshotCache, StagedWriteBatch, StateDelta};
 
+mod pruning;
+mod state_sync;
 mod temp;
+pub use pruning::{PruningMode, PruningStats};
 pub use temp::TempStorage;
 
 /// A handle for a storage instance, backed by RocksDB.
@@ -43,12 +46,54 @@ struct Inner {
     /// A handle to the dispatcher task.
     /// This is used by `Storage::release` to wait for the task to terminate.
     jh_dispatcher: Option<tokio::task::JoinHandle<()>>,
+    /// A handle to the background pruning task, if pruning is enabled.
+    jh_pruner: Option<tokio::task::JoinHandle<()>>,
+    /// The policy determining which historical versions are kept on disk.
+    pruning: PruningMode,
     db: Arc<DB>,
 }
 
+/// An error returned when the [`Snapshot`] of a version cannot be provided.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// The version has been discarded by the storage's [`PruningMode`].
+    #[error("version {version} has been pruned (latest version is {latest})")]
+    Pruned {
+        version: jmt::Version,
+        latest: jmt::Version,
+    },
+    /// The version is retained on disk, but is too old to be in the snapshot cache.
+    #[error(
+        "version {version} is not available in the snapshot cache (latest version is {latest})"
+    )]
+    NotCached {
+        version: jmt::Version,
+        latest: jmt::Version,
+    },
+    /// The version has not been committed yet.
+    #[error("version {version} does not exist yet (latest version is {latest})")]
+    NotFound {
+        version: jmt::Version,
+        latest: jmt::Version,
+    },
+}
+
 impl Storage {
     /// Loads a storage instance from the given path, initializing it if necessary.
+    ///
+    /// The storage instance keeps every version of the chain state, see
+    /// [`Storage::load_with_pruning`] to discard historical versions.
     pub async fn load(path: PathBuf, default_prefixes: Vec<String>) -> Result<Self> {
+        Self::load_with_pruning(path, default_prefixes, PruningMode::Archive).await
+    }
+
+    /// Loads a storage instance from the given path, initializing it if necessary,
+    /// and discards historical versions in the background according to `pruning`.
+    pub async fn load_with_pruning(
+        path: PathBuf,
+        default_prefixes: Vec<String>,
+        pruning: PruningMode,
+    ) -> Result<Self> {
         let span = Span::current();
         let db_path = path.clone();
         // initializing main storage instance.
@@ -96,7 +141,7 @@ impl Storage {
         })
         .await?;
 
-        Storage::init(db_path, prefixes).await
+        Storage::init_with_pruning(db_path, prefixes, pruning).await
     }
 
     /// Initializes a new storage instance at the given path. Takes a list of default prefixes
@@ -108,6 +153,25 @@ impl Storage {
     /// 4. Initialize the substore cache with the latest version of each substore.
     /// 5. Spawn a dispatcher task that forwards new snapshots to subscribers.
     pub async fn init(path: PathBuf, prefixes: Vec<String>) -> Result<Self> {
+        Self::init_with_pruning(path, prefixes, PruningMode::Archive).await
+    }
+
+    /// Initializes a new storage instance at the given path, like [`Storage::init`],
+    /// and additionally spawns a pruning task if `pruning` discards any version.
+    pub async fn init_with_pruning(
+        path: PathBuf,
+        prefixes: Vec<String>,
+        pruning: PruningMode,
+    ) -> Result<Self> {
+        if let PruningMode::Pruned {
+            keep_recent,
+            interval,
+            ..
+        } = pruning
+        {
+            ensure!(keep_recent > 0, "pruning must retain at least one version");
+            ensure!(interval > 0, "the pruning interval must be non-zero");
+        }
         let span = Span::current();
 
         tokio::task
@@ -214,11 +278,42 @@ impl Storage {
                         tracing::info!("dispatcher task has terminated")
                     });
 
+                    let jh_pruner = match pruning {
+                        PruningMode::Archive => None,
+                        PruningMode::Pruned { interval, .. } => {
+                            let mut rx_snapshot = snapshot_rx.clone();
+                            let db = shared_db.clone();
+                            let config = multistore_config.clone();
+                            Some(tokio::spawn(async move {
+                                tracing::info!(?pruning, "pruning task has started");
+                                while rx_snapshot.changed().await.is_ok() {
+                                    let version = rx_snapshot.borrow_and_update().version();
+                                    if version % interval != 0 {
+                                        continue;
+                                    }
+                                    let (db, config) = (db.clone(), config.clone());
+                                    let result = tokio::task::spawn_blocking(move || {
+                                        pruning::prune(&db, &config, pruning, version)
+                                    })
+                                    .await;
+                                    match result {
+                                        Ok(Ok(stats)) => tracing::debug!(?version, ?stats, "pruned storage"),
+                                        Ok(Err(e)) => tracing::error!(?e, ?version, "failed to prune storage"),
+                                        Err(e) => tracing::error!(?e, ?version, "pruning task panicked"),
+                                    }
+                                }
+                                tracing::info!("pruning task has terminated")
+                            }))
+                        }
+                    };
+
                     Ok(Self(Arc::new(Inner {
                         // We don't need to wrap the task in a `CancelOnDrop<T>` because
                         // the task will stop when the sender is dropped. However, certain
                         // test scenarios require us to wait that all resources are released.
                         jh_dispatcher: Some(jh_dispatcher),
+                        jh_pruner,
+                        pruning,
                         dispatcher_tx: tx_dispatcher,
                         snapshot_rx,
                         changes_rx,
@@ -263,9 +358,52 @@ impl Storage {
     }
 
     /// Fetches the [`Snapshot`] corresponding to the supplied `jmt::Version` from
-    /// the [`SnapshotCache`]. Returns `None` if no match was found.
-    pub fn snapshot(&self, version: jmt::Version) -> Option<Snapshot> {
-        self.0.snapshots.read().get(version)
+    /// the [`SnapshotCache`].
+    ///
+    /// # Errors
+    /// Returns a [`SnapshotError`] describing why the version is unavailable, in
+    /// particular whether it was discarded by the [`PruningMode`].
+    pub fn snapshot(&self, version: jmt::Version) -> Result<Snapshot, SnapshotError> {
+        let latest = self.latest_version();
+        let committed = latest != u64::MAX && version <= latest;
+
+        // A cached snapshot of a discarded version may no longer be readable,
+        // so we check the pruning policy first.
+        if committed && !self.0.pruning.retains(version, latest) {
+            return Err(SnapshotError::Pruned { version, latest });
+        }
+
+        if let Some(snapshot) = self.0.snapshots.read().get(version) {
+            return Ok(snapshot);
+        }
+
+        if committed {
+            Err(SnapshotError::NotCached { version, latest })
+        } else {
+            Err(SnapshotError::NotFound { version, latest })
+        }
+    }
+
+    /// Returns the [`PruningMode`] of this storage instance.
+    pub fn pruning_mode(&self) -> PruningMode {
+        self.0.pruning
+    }
+
+    /// Runs a pruning pass immediately, discarding every historical version that
+    /// the [`PruningMode`] doesn't retain.
+    ///
+    /// Pruning normally happens in the background, so this is mostly useful for
+    /// offline maintenance of a database.
+    pub async fn prune(&self) -> Result<PruningStats> {
+        let db = self.0.db.clone();
+        let config = self.0.multistore_config.clone();
+        let mode = self.0.pruning;
+        let latest = self.latest_version();
+        let span = Span::current();
+        tokio::task::spawn_blocking(move || {
+            span.in_scope(|| pruning::prune(&db, &config, mode, latest))
+        })
+        .await?
     }
 
     /// Prepares a commit for the provided [`StateDelta`], returning a [`StagedWriteBatch`].
@@ -374,7 +512,13 @@ impl Storage {
 
             // Commit the substore and collect its root hash
             let (root_hash, substore_batch) = substore_storage
-                .commit(changeset, write_batch, new_version, perform_migration)
+                .commit(
+                    changeset,
+                    write_batch,
+                    new_version,
+                    version,
+                    perform_migration,
+                )
                 .await?;
             write_batch = substore_batch;
 
@@ -423,7 +567,13 @@ impl Storage {
         };
 
         let (global_root_hash, write_batch) = main_store_storage
-            .commit(main_store_changes, write_batch, version, perform_migration)
+            .commit(
+                main_store_changes,
+                write_batch,
+                version,
+                version,
+                perform_migration,
+            )
             .await?;
         tracing::debug!(
             ?global_root_hash,
@@ -607,5 +757,9 @@ impl Inner {
             jh.abort();
             let _ = jh.await;
         }
+        if let Some(jh) = self.jh_pruner.take() {
+            jh.abort();
+            let _ = jh.await;
+        }
     }
 }
@@ -0,0 +1,318 @@
+//! Pruning of historical versions of the JMT.
+//!
+//! Each substore keeps an index of the JMT nodes and values that stopped being
+//! part of the latest tree, keyed by the main store version at which that
+//! happened (see [`StaleIndexKey`]). Pruning walks that index up to the oldest
+//! version that the [`PruningMode`] retains, and deletes every node and value
+//! that isn't reachable from any retained version.
+//!
+//! Nonverifiable data is not versioned, and is never pruned. Versions committed
+//! before the stale index was introduced are not indexed, and are never pruned
+//! either.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use jmt::KeyHash;
+use rocksdb::{IteratorMode, ReadOptions, DB};
+
+#[cfg(feature = "metrics")]
+use crate::metrics;
+use crate::store::{
+    multistore::MultistoreConfig,
+    substore::{DbNodeKey, StaleEntry, StaleIndexKey, SubstoreConfig, VersionedKeyHash},
+};
+
+/// Specifies which historical versions of the chain state a [`Storage`](super::Storage)
+/// keeps on disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PruningMode {
+    /// Keep every version forever.
+    #[default]
+    Archive,
+    /// Discard historical versions that fall outside of the retention policy.
+    Pruned {
+        /// The number of most recent versions to keep, including the latest one.
+        /// Must be at least 1.
+        keep_recent: u64,
+        /// If set, also keep every version that is a multiple of this value.
+        keep_every: Option<u64>,
+        /// Run a pruning pass every `interval` committed versions.
+        interval: u64,
+    },
+}
+
+impl PruningMode {
+    /// Returns the oldest version of the "recent" retention window, given the latest version.
+    ///
+    /// Every version at or above the horizon is retained. Returns `None` when nothing is
+    /// ever discarded.
+    fn horizon(&self, latest: jmt::Version) -> Option<jmt::Version> {
+        match self {
+            PruningMode::Archive => None,
+            PruningMode::Pruned { keep_recent, .. } => {
+                // The pre-genesis version has no history to prune.
+                if latest == u64::MAX {
+                    return None;
+                }
+                Some((latest + 1).saturating_sub((*keep_recent).max(1)))
+            }
+        }
+    }
+
+    /// Returns whether the policy retains any version in the range `[start, end)`, given
+    /// the latest version.
+    pub fn retains_any(
+        &self,
+        start: jmt::Version,
+        end: jmt::Version,
+        latest: jmt::Version,
+    ) -> bool {
+        if start >= end {
+            return false;
+        }
+        let Some(horizon) = self.horizon(latest) else {
+            return true;
+        };
+        if end > horizon {
+            return true;
+        }
+        match self {
+            PruningMode::Pruned {
+                keep_every: Some(k),
+                ..
+            } if *k > 0 => {
+                // Find the first multiple of `k` at or after `start`, and check whether it
+                // falls within the range.
+                let first_multiple = start.div_ceil(*k).saturating_mul(*k);
+                first_multiple < end
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the policy retains the given `version`, given the latest version.
+    pub fn retains(&self, version: jmt::Version, latest: jmt::Version) -> bool {
+        self.retains_any(version, version.saturating_add(1), latest)
+    }
+}
+
+/// Statistics about a single pruning pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruningStats {
+    /// The number of JMT nodes deleted.
+    pub nodes: u64,
+    /// The number of JMT values deleted.
+    pub values: u64,
+    /// The total size of the deleted keys and values, in bytes.
+    pub bytes: u64,
+}
+
+impl std::ops::AddAssign for PruningStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.nodes += rhs.nodes;
+        self.values += rhs.values;
+        self.bytes += rhs.bytes;
+    }
+}
+
+/// Prunes every substore according to `mode`, given the `latest` committed version.
+///
+/// This method performs blocking I/O.
+pub(crate) fn prune(
+    db: &Arc<DB>,
+    config: &MultistoreConfig,
+    mode: PruningMode,
+    latest: jmt::Version,
+) -> Result<PruningStats> {
+    let Some(horizon) = mode.horizon(latest) else {
+        return Ok(PruningStats::default());
+    };
+
+    let start = std::time::Instant::now();
+    let mut stats = PruningStats::default();
+    for substore in std::iter::once(&config.main_store).chain(config.iter()) {
+        stats += prune_substore(db, substore, mode, horizon, latest)?;
+    }
+
+    tracing::debug!(?stats, ?horizon, elapsed = ?start.elapsed(), "pruned storage");
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(metrics::STORAGE_PRUNED_NODES_TOTAL).increment(stats.nodes);
+        metrics::counter!(metrics::STORAGE_PRUNED_VALUES_TOTAL).increment(stats.values);
+        metrics::counter!(metrics::STORAGE_PRUNED_BYTES_TOTAL).increment(stats.bytes);
+        metrics::histogram!(metrics::STORAGE_PRUNING_DURATION).record(start.elapsed());
+    }
+
+    Ok(stats)
+}
+
+fn prune_substore(
+    db: &Arc<DB>,
+    substore: &Arc<SubstoreConfig>,
+    mode: PruningMode,
+    horizon: jmt::Version,
+    latest: jmt::Version,
+) -> Result<PruningStats> {
+    let cf_jmt = substore.cf_jmt(db);
+    let cf_jmt_values = substore.cf_jmt_values(db);
+    let cf_jmt_stale = substore.cf_jmt_stale(db);
+
+    // We only consider entries that became stale at, or before, the horizon: the
+    // entries they refer to are not part of any version in the recent window.
+    let mut readopts = ReadOptions::default();
+    readopts.set_iterate_upper_bound(horizon.saturating_add(1).to_be_bytes().to_vec());
+
+    let mut stats = PruningStats::default();
+    let mut write_batch = rocksdb::WriteBatch::default();
+
+    for item in db.iterator_cf_opt(cf_jmt_stale, readopts, IteratorMode::Start) {
+        let (raw_key, _) = item?;
+        let StaleIndexKey {
+            stale_since,
+            version,
+            entry,
+        } = StaleIndexKey::decode(&raw_key)?;
+
+        match entry {
+            // Nodes created by the commit itself were never part of a committed tree
+            // (this can happen during migrations), and must not be touched.
+            StaleEntry::Node(node_key) if node_key.version() < version => {
+                // The node was live from its creation until it became stale.
+                let retained = main_version(db, substore, node_key.version())?
+                    .map(|created| mode.retains_any(created, stale_since, latest))
+                    .unwrap_or(true);
+                if !retained {
+                    let db_node_key = DbNodeKey::encode_from_node_key(&node_key)?;
+                    if let Some(node) = db.get_cf(cf_jmt, &db_node_key)? {
+                        stats.nodes += 1;
+                        stats.bytes += (db_node_key.len() + node.len()) as u64;
+                        write_batch.delete_cf(cf_jmt, db_node_key);
+                    }
+                }
+            }
+            StaleEntry::Node(_) => {}
+            StaleEntry::Value(key_hash) => {
+                // The value written right before the superseding write was live until then.
+                if let Some((previous, value_len)) =
+                    previous_value(db, substore, key_hash, version)?
+                {
+                    let retained = main_version(db, substore, previous)?
+                        .map(|created| mode.retains_any(created, stale_since, latest))
+                        .unwrap_or(true);
+                    if !retained {
+                        let key = VersionedKeyHash::encode_from_keyhash(&key_hash, &previous);
+                        stats.values += 1;
+                        stats.bytes += (key.len() + value_len) as u64;
+                        write_batch.delete_cf(cf_jmt_values, key);
+                    }
+                }
+            }
+        }
+
+        // Whether or not the entry was deleted, it will never become prunable later:
+        // every version that may still need it is retained forever.
+        stats.bytes += raw_key.len() as u64;
+        write_batch.delete_cf(cf_jmt_stale, raw_key);
+    }
+
+    db.write(write_batch)?;
+    Ok(stats)
+}
+
+/// Returns the main store version at which the given version of `substore` was committed.
+///
+/// Returns `None` if the version predates the version index, in which case callers should
+/// assume the version is retained.
+fn main_version(
+    db: &Arc<DB>,
+    substore: &Arc<SubstoreConfig>,
+    version: jmt::Version,
+) -> Result<Option<jmt::Version>> {
+    // The main store versions are, by definition, main store versions.
+    if substore.prefix.is_empty() {
+        return Ok(Some(version));
+    }
+
+    let cf_jmt_versions = substore.cf_jmt_versions(db);
+    let Some(raw) = db.get_cf(cf_jmt_versions, version.to_be_bytes())? else {
+        return Ok(None);
+    };
+    let raw: [u8; 8] = raw
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("invalid version index entry"))?;
+    Ok(Some(u64::from_be_bytes(raw)))
+}
+
+/// Returns the latest version of `substore`, strictly before `before`, at which a
+/// value was written for `key_hash`, along with the size of the stored value.
+fn previous_value(
+    db: &Arc<DB>,
+    substore: &Arc<SubstoreConfig>,
+    key_hash: KeyHash,
+    before: jmt::Version,
+) -> Result<Option<(jmt::Version, usize)>> {
+    let cf_jmt_values = substore.cf_jmt_values(db);
+
+    let mut readopts = ReadOptions::default();
+    readopts.set_iterate_lower_bound(VersionedKeyHash::encode_from_keyhash(&key_hash, &0));
+    // The upper bound is excluded from the iteration results.
+    readopts.set_iterate_upper_bound(VersionedKeyHash::encode_from_keyhash(&key_hash, &before));
+    let mut iter = db.iterator_cf_opt(cf_jmt_values, readopts, IteratorMode::End);
+
+    let Some((raw_key, value)) = iter.next().transpose()? else {
+        return Ok(None);
+    };
+    let versioned = VersionedKeyHash::decode(raw_key.to_vec())?;
+    Ok(Some((versioned.version, value.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PruningMode;
+
+    #[test]
+    fn archive_retains_everything() {
+        let mode = PruningMode::Archive;
+        assert!(mode.retains(0, 1_000));
+        assert!(mode.retains_any(10, 11, 1_000));
+    }
+
+    #[test]
+    fn pruned_retains_recent_and_periodic_versions() {
+        let mode = PruningMode::Pruned {
+            keep_recent: 10,
+            keep_every: Some(100),
+            interval: 1,
+        };
+        let latest = 1_000;
+        // The recent window is [991, 1000].
+        assert!(mode.retains(1_000, latest));
+        assert!(mode.retains(991, latest));
+        assert!(!mode.retains(990, latest));
+        // Periodic versions are kept forever.
+        assert!(mode.retains(900, latest));
+        assert!(mode.retains(0, latest));
+        assert!(!mode.retains(899, latest));
+        // Ranges are end-exclusive.
+        assert!(!mode.retains_any(901, 991, latest));
+        assert!(mode.retains_any(850, 901, latest));
+        assert!(!mode.retains_any(850, 900, latest));
+        assert!(mode.retains_any(985, 992, latest));
+    }
+
+    #[test]
+    fn pruned_without_periodic_versions() {
+        let mode = PruningMode::Pruned {
+            keep_recent: 1,
+            keep_every: None,
+            interval: 1,
+        };
+        assert!(mode.retains(5, 5));
+        assert!(!mode.retains(4, 5));
+        assert!(!mode.retains(0, 5));
+        // Nothing is ever discarded before genesis.
+        assert!(mode.retains(0, u64::MAX));
+    }
+}
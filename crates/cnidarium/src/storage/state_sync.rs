@@ -0,0 +1,165 @@
+//! Chunked export and import of a storage version, used to implement state sync.
+//!
+//! An export is a point-in-time dump of every column family owned by the
+//! multistore (JMT nodes, JMT values, key indices and nonverifiable data, for
+//! the main store and each substore), read through the RocksDB snapshot that
+//! backs a [`Snapshot`]. The entries are split into chunks of bounded size,
+//! which can be replayed in order into a fresh [`Storage`] instance to recover
+//! the exact same state, including its root hash.
+
+use std::sync::Arc;
+
+use anyhow::{bail, ensure, Context, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use rocksdb::IteratorMode;
+
+use crate::{
+    cache::Cache,
+    store::multistore::{MultistoreCache, MultistoreConfig},
+    Snapshot,
+};
+
+use super::Storage;
+
+/// A single column family entry, as recorded in an exported chunk.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct ChunkEntry {
+    column: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
+}
+
+/// Rough per-entry bookkeeping overhead, used when sizing chunks.
+const ENTRY_OVERHEAD: usize = 12;
+
+impl Snapshot {
+    /// Exports the full contents of this snapshot as a sequence of chunks,
+    /// each of which is passed to `sink` in order.
+    ///
+    /// Chunks are closed as soon as they reach `max_chunk_size` bytes, so a
+    /// single oversized entry may produce a chunk larger than the limit.
+    ///
+    /// This method performs blocking I/O and should be called from a blocking
+    /// context, e.g. via [`tokio::task::spawn_blocking`].
+    pub fn export_chunks<F>(&self, max_chunk_size: usize, mut sink: F) -> Result<()>
+    where
+        F: FnMut(Vec<u8>) -> Result<()>,
+    {
+        ensure!(max_chunk_size > 0, "chunk size must be non-zero");
+        let config = &self.0.multistore_cache.config;
+        let db = &self.0.db;
+
+        let mut entries = Vec::new();
+        let mut chunk_size = 0usize;
+
+        for column in columns(config) {
+            let cf = db
+                .cf_handle(column)
+                .with_context(|| format!("column family {column} not found"))?;
+            for item in self.0.snapshot.iterator_cf(cf, IteratorMode::Start) {
+                let (key, value) = item?;
+                chunk_size += key.len() + value.len() + column.len() + ENTRY_OVERHEAD;
+                entries.push(ChunkEntry {
+                    column: column.clone(),
+                    key: key.to_vec(),
+                    value: value.to_vec(),
+                });
+
+                if chunk_size >= max_chunk_size {
+                    sink(borsh::to_vec(&std::mem::take(&mut entries))?)?;
+                    chunk_size = 0;
+                }
+            }
+        }
+
+        if !entries.is_empty() {
+            sink(borsh::to_vec(&entries)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Storage {
+    /// Writes a chunk produced by [`Snapshot::export_chunks`] to the database.
+    ///
+    /// Chunks may only be imported into an uninitialized storage instance. Once
+    /// every chunk has been imported, [`Storage::finish_import`] must be called
+    /// to make the restored version visible.
+    pub fn import_chunk(&self, chunk: &[u8]) -> Result<()> {
+        ensure!(
+            self.latest_version() == u64::MAX,
+            "cannot import state into a storage instance that is already initialized (version={})",
+            self.latest_version()
+        );
+
+        let entries: Vec<ChunkEntry> =
+            BorshDeserialize::try_from_slice(chunk).context("malformed state sync chunk")?;
+        let known_columns: Vec<&String> = columns(&self.0.multistore_config).collect();
+
+        let mut write_batch = rocksdb::WriteBatch::default();
+        for ChunkEntry { column, key, value } in entries {
+            if !known_columns.contains(&&column) {
+                bail!("state sync chunk references unknown column family {column}");
+            }
+            let cf = self
+                .0
+                .db
+                .cf_handle(&column)
+                .with_context(|| format!("column family {column} not found"))?;
+            write_batch.put_cf(cf, key, value);
+        }
+
+        self.0.db.write(write_batch)?;
+        Ok(())
+    }
+
+    /// Completes an import started with [`Storage::import_chunk`].
+    ///
+    /// This reads back the latest version of each substore from the database,
+    /// resets the snapshot cache to the restored version, and notifies
+    /// subscribers. Returns the [`Snapshot`] of the restored version, so that
+    /// callers can check its root hash against a trusted value.
+    pub fn finish_import(&self) -> Result<Snapshot> {
+        ensure!(
+            self.latest_version() == u64::MAX,
+            "cannot finish an import into a storage instance that is already initialized"
+        );
+
+        let config = self.0.multistore_config.clone();
+        let db = self.0.db.clone();
+
+        let mut multistore_cache = MultistoreCache::from_config(config.clone());
+        for substore_config in config.iter() {
+            let version = substore_config
+                .latest_version_from_db(&db)?
+                .unwrap_or(u64::MAX);
+            multistore_cache.set_version(substore_config.clone(), version);
+        }
+
+        let Some(version) = config.main_store.latest_version_from_db(&db)? else {
+            bail!("no main store data was imported")
+        };
+        multistore_cache.set_version(config.main_store.clone(), version);
+        tracing::info!(?version, "finished importing state");
+
+        let snapshot = Snapshot::new(db, version, multistore_cache);
+        self.0.snapshots.write().reset(snapshot.clone());
+
+        // Send fails if the channel is closed (i.e., if there are no receivers);
+        // in this case, we should ignore the error, we have no one to notify.
+        let _ = self
+            .0
+            .dispatcher_tx
+            .send((snapshot.clone(), (version, Arc::new(Cache::default()))));
+
+        Ok(snapshot)
+    }
+}
+
+/// Returns the name of every column family owned by the multistore.
+fn columns(config: &MultistoreConfig) -> impl Iterator<Item = &String> {
+    std::iter::once(&config.main_store)
+        .chain(config.iter())
+        .flat_map(|substore| substore.columns())
+}
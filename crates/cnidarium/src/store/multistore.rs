@@ -15,6 +15,11 @@ impl MultistoreConfig {
     }
 
     /// Returns the substore matching the key's prefix, return `None` otherwise.
+    ///
+    /// A key matches a substore if it is equal to the substore prefix, or if it
+    /// starts with the substore prefix followed by a delimiter. This means that
+    /// `prefix_akey` does not match a `prefix_a` substore, and that keys of a
+    /// `prefix_ab` substore are not claimed by a `prefix_a` substore.
     pub fn find_substore(&self, key: &[u8]) -> Option<Arc<SubstoreConfig>> {
         if key.is_empty() {
             return Some(self.main_store.clone());
@@ -23,7 +28,10 @@ impl MultistoreConfig {
         // Note: This is a linear search, but the number of substores is small.
         self.substores
             .iter()
-            .find(|s| key.starts_with(s.prefix.as_bytes()))
+            .find(|s| match key.strip_prefix(s.prefix.as_bytes()) {
+                Some(rest) => rest.is_empty() || rest.starts_with(b"/"),
+                None => false,
+            })
             .cloned()
     }
 
@@ -144,7 +152,8 @@ impl MultistoreConfig {
     /// `prefix_a/key` -> `key` in `substore_a`
     /// `prefix_a` -> "" in `substore_a`
     /// `prefix_a/` -> "" in `substore_a`
-    /// `nonexistent_prefix` -> "" in `main_store`
+    /// `prefix_akey` -> `prefix_akey` in `main_store`
+    /// `nonexistent_prefix` -> `nonexistent_prefix` in `main_store`
     pub fn match_prefix_str<'a>(&self, prefix: &'a str) -> (&'a str, Arc<SubstoreConfig>) {
         let config = self
             .find_substore(prefix.as_bytes())
@@ -172,7 +181,8 @@ impl MultistoreConfig {
     /// `prefix_a/key` -> `key` in `substore_a`
     /// `prefix_a` -> "" in `substore_a`
     /// `prefix_a/` -> "" in `substore_a`
-    /// `nonexistent_prefix` -> "" in `main_store`
+    /// `prefix_akey` -> `prefix_akey` in `main_store`
+    /// `nonexistent_prefix` -> `nonexistent_prefix` in `main_store`
     pub fn match_prefix_bytes<'a>(&self, prefix: &'a [u8]) -> (&'a [u8], Arc<SubstoreConfig>) {
         let config = self
             .find_substore(prefix)
@@ -187,6 +197,132 @@ impl MultistoreConfig {
             .unwrap_or(truncated_prefix);
         (truncated_prefix, config)
     }
+
+    /// Returns every substore that can hold keys starting with `prefix`.
+    ///
+    /// This method is used to implement prefix iteration and range queries across
+    /// substores.
+    ///
+    /// If the prefix designates a substore, as in [`MultistoreConfig::match_prefix_bytes`],
+    /// only that substore is returned. Otherwise, the prefix may span several substores
+    /// e.g. an empty prefix matches every key in the multistore. In that case, the main
+    /// store is returned along with each substore whose namespace starts with the prefix.
+    ///
+    /// # Examples
+    /// With substores `prefix_a` and `prefix_b`:
+    /// `prefix_a/key` -> `key` in `substore_a`
+    /// `prefix_a` -> "" in `substore_a`
+    /// `prefix_` -> `prefix_` in `main_store`, "" in `substore_a` and `substore_b`
+    /// `` -> "" in `main_store`, `substore_a` and `substore_b`
+    pub fn match_prefix_all(&self, prefix: &[u8]) -> Vec<PrefixMatch> {
+        if !prefix.is_empty() && self.find_substore(prefix).is_some() {
+            let (truncated_prefix, config) = self.match_prefix_bytes(prefix);
+            return vec![PrefixMatch {
+                config,
+                truncated_prefix: truncated_prefix.to_vec(),
+                remainder: vec![],
+            }];
+        }
+
+        let main_store = PrefixMatch {
+            config: self.main_store.clone(),
+            truncated_prefix: prefix.to_vec(),
+            remainder: vec![],
+        };
+
+        let substores = self.substores.iter().filter_map(|config| {
+            let namespace = format!("{}/", config.prefix).into_bytes();
+            let remainder = namespace.strip_prefix(prefix)?.to_vec();
+            Some(PrefixMatch {
+                config: config.clone(),
+                truncated_prefix: vec![],
+                remainder,
+            })
+        });
+
+        std::iter::once(main_store).chain(substores).collect()
+    }
+}
+
+/// A substore that can hold keys matching a prefix query, see [`MultistoreConfig::match_prefix_all`].
+#[derive(Debug, Clone)]
+pub struct PrefixMatch {
+    /// The substore holding the matching keys.
+    pub config: Arc<SubstoreConfig>,
+    /// The prefix to query within the substore.
+    pub truncated_prefix: Vec<u8>,
+    /// The part of the substore namespace, including the delimiter, that extends
+    /// past the queried prefix e.g. `fix_a/` for a `prefix_a` substore queried with
+    /// `pre`. It is empty if the prefix designates the substore.
+    pub remainder: Vec<u8>,
+}
+
+impl PrefixMatch {
+    /// Returns the full key corresponding to a key of the matched substore.
+    pub fn full_key(&self, substore_key: &[u8]) -> Vec<u8> {
+        if self.config.prefix.is_empty() {
+            return substore_key.to_vec();
+        }
+
+        let mut full_key = Vec::with_capacity(self.config.prefix.len() + 1 + substore_key.len());
+        full_key.extend_from_slice(self.config.prefix.as_bytes());
+        full_key.push(b'/');
+        full_key.extend_from_slice(substore_key);
+        full_key
+    }
+
+    /// Returns the lower (inclusive) and upper (exclusive) bounds of the substore keys
+    /// whose full keys are made of the queried prefix followed by a suffix in the range
+    /// `[start, end)`, or `None` if there are no such keys.
+    ///
+    /// An unbounded `end` is mapped to the first key past the queried prefix, if any.
+    pub fn substore_bounds(
+        &self,
+        start: &[u8],
+        end: Option<&[u8]>,
+    ) -> Option<(Vec<u8>, Option<Vec<u8>>)> {
+        // Suffixes of the queried prefix that belong to this substore are made of the
+        // remainder of its namespace, followed by a truncated key.
+        let remainder = self.remainder.as_slice();
+        let start = match start.strip_prefix(remainder) {
+            Some(start) => start,
+            // The range starts before the substore namespace.
+            None if start < remainder => &[],
+            // The range starts after the substore namespace.
+            None => return None,
+        };
+        let end = match end {
+            Some(end) => match end.strip_prefix(remainder) {
+                Some([]) => return None,
+                Some(end) => Some(end),
+                // The range ends before the substore namespace.
+                None if end < remainder => return None,
+                // The range ends after the substore namespace.
+                None => None,
+            },
+            None => None,
+        };
+
+        let lower = [self.truncated_prefix.as_slice(), start].concat();
+        let upper = match end {
+            Some(end) => Some([self.truncated_prefix.as_slice(), end].concat()),
+            None => prefix_upper_bound(&self.truncated_prefix),
+        };
+        Some((lower, upper))
+    }
+}
+
+/// Returns the smallest key that is greater than every key starting with `prefix`,
+/// or `None` if there is no such key.
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut bound = prefix.to_vec();
+    while let Some(last) = bound.pop() {
+        if last < u8::MAX {
+            bound.push(last + 1);
+            return Some(bound);
+        }
+    }
+    None
 }
 
 impl Default for MultistoreConfig {
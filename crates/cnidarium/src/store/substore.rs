@@ -47,6 +47,16 @@ pub struct SubstoreConfig {
     /// part of consensus.
     /// maps: arbitrary keys to arbitrary values.
     cf_nonverifiable: String,
+    /// name: "substore-{prefix}-jmt-stale"
+    /// role: index of JMT nodes and values that are no longer part of the latest tree,
+    /// used to prune old versions.
+    /// maps: `StaleIndexKey` to an empty value.
+    cf_jmt_stale: String,
+    /// name: "substore-{prefix}-jmt-versions"
+    /// role: records the main store version at which each substore version was committed,
+    /// so that pruning can reason about substore versions in terms of main store versions.
+    /// maps: BE(substore version) to BE(main store version).
+    cf_jmt_versions: String,
 }
 
 impl SubstoreConfig {
@@ -58,6 +68,8 @@ impl SubstoreConfig {
             cf_jmt_values: format!("substore-{}-jmt-values", prefix),
             cf_jmt_keys_by_keyhash: format!("substore-{}-jmt-keys-by-keyhash", prefix),
             cf_nonverifiable: format!("substore-{}-nonverifiable", prefix),
+            cf_jmt_stale: format!("substore-{}-jmt-stale", prefix),
+            cf_jmt_versions: format!("substore-{}-jmt-versions", prefix),
             prefix_with_delimiter: format!("{}/", prefix),
             prefix,
         }
@@ -71,6 +83,8 @@ impl SubstoreConfig {
             .chain(std::iter::once(&self.cf_jmt_values))
             .chain(std::iter::once(&self.cf_jmt_keys_by_keyhash))
             .chain(std::iter::once(&self.cf_nonverifiable))
+            .chain(std::iter::once(&self.cf_jmt_stale))
+            .chain(std::iter::once(&self.cf_jmt_versions))
     }
 
     pub fn cf_jmt<'s>(&self, db_handle: &'s Arc<rocksdb::DB>) -> &'s ColumnFamily {
@@ -123,6 +137,26 @@ impl SubstoreConfig {
         })
     }
 
+    pub fn cf_jmt_stale<'s>(&self, db_handle: &'s Arc<rocksdb::DB>) -> &'s ColumnFamily {
+        let column = self.cf_jmt_stale.as_str();
+        db_handle.cf_handle(column).unwrap_or_else(|| {
+            panic!(
+                "jmt-stale column family not found for prefix: {}, substore: {}",
+                column, self.prefix
+            )
+        })
+    }
+
+    pub fn cf_jmt_versions<'s>(&self, db_handle: &'s Arc<rocksdb::DB>) -> &'s ColumnFamily {
+        let column = self.cf_jmt_versions.as_str();
+        db_handle.cf_handle(column).unwrap_or_else(|| {
+            panic!(
+                "jmt-versions column family not found for prefix: {}, substore: {}",
+                column, self.prefix
+            )
+        })
+    }
+
     pub fn latest_version_from_db(
         &self,
         db_handle: &Arc<rocksdb::DB>,
@@ -371,6 +405,7 @@ impl SubstoreStorage {
         cache: Cache,
         mut write_batch: rocksdb::WriteBatch,
         write_version: jmt::Version,
+        main_version: jmt::Version,
         perform_migration: bool,
     ) -> Result<(RootHash, rocksdb::WriteBatch)> {
         let span = Span::current();
@@ -389,6 +424,8 @@ impl SubstoreStorage {
                         let cf_jmt_keys_by_keyhash = self.substore_snapshot.config.cf_jmt_keys_by_keyhash(&self.substore_snapshot.db);
                         let cf_jmt = self.substore_snapshot.config.cf_jmt(&self.substore_snapshot.db);
                         let cf_jmt_values = self.substore_snapshot.config.cf_jmt_values(&self.substore_snapshot.db);
+                        let cf_jmt_stale = self.substore_snapshot.config.cf_jmt_stale(&self.substore_snapshot.db);
+                        let cf_jmt_versions = self.substore_snapshot.config.cf_jmt_versions(&self.substore_snapshot.db);
 
                         /* Keyhash and pre-image indices */
                         for (keyhash, key_preimage, value) in unwritten_changes.iter() {
@@ -405,6 +442,12 @@ impl SubstoreStorage {
                             };
                         }
 
+                        /* Stale value index: any older value of a written key is superseded as of `main_version` */
+                        for (keyhash, _, _) in unwritten_changes.iter() {
+                            let key = StaleIndexKey { stale_since: main_version, version: write_version, entry: StaleEntry::Value(*keyhash) };
+                            write_batch.put_cf(cf_jmt_stale, key.encode()?, b"");
+                        }
+
                         // We only track the keyhash and possible values; at the time of writing,
                         // `rustfmt` panics on inlining the closure, so we use a helper function to skip the key.
                         let skip_key = |(keyhash, _key, some_value)| (keyhash, some_value);
@@ -431,6 +474,13 @@ impl SubstoreStorage {
                             write_batch.put_cf(cf_jmt_values, key_bytes, value_bytes);
                         }
 
+                        /* Stale node index and version mapping, used for pruning */
+                        for stale_node in batch.stale_node_index_batch.iter() {
+                            let key = StaleIndexKey { stale_since: main_version, version: write_version, entry: StaleEntry::Node(stale_node.node_key.clone()) };
+                            write_batch.put_cf(cf_jmt_stale, key.encode()?, b"");
+                        }
+                        write_batch.put_cf(cf_jmt_versions, write_version.to_be_bytes(), main_version.to_be_bytes());
+
                         tracing::trace!(?root_hash, "accumulated node changes in the write batch");
 
 
@@ -544,3 +594,72 @@ impl VersionedKeyHash {
         }
     }
 }
+
+/// A key in the stale index of a substore, recording that a JMT node or value
+/// stopped being part of the latest tree as of a given commit.
+///
+/// Keys are prefixed by the big-endian encoding of the main store version of
+/// that commit, so that the index can be scanned in the order in which entries
+/// became stale.
+#[derive(Clone, Debug)]
+pub struct StaleIndexKey {
+    /// The main store version of the commit that made the entry stale.
+    pub stale_since: jmt::Version,
+    /// The substore version of the commit that made the entry stale.
+    pub version: jmt::Version,
+    /// The entry that became stale.
+    pub entry: StaleEntry,
+}
+
+/// An entry of the JMT that is no longer part of the latest tree.
+#[derive(Clone, Debug)]
+pub enum StaleEntry {
+    /// A JMT node, replaced by a newer node.
+    Node(NodeKey),
+    /// Every value of the key hash written before the commit, superseded by a new write.
+    Value(KeyHash),
+}
+
+impl StaleIndexKey {
+    const NODE_TAG: u8 = 0;
+    const VALUE_TAG: u8 = 1;
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut bytes = self.stale_since.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.version.to_be_bytes());
+        match &self.entry {
+            StaleEntry::Node(node_key) => {
+                bytes.push(Self::NODE_TAG);
+                bytes.extend(DbNodeKey::encode_from_node_key(node_key)?);
+            }
+            StaleEntry::Value(key_hash) => {
+                bytes.push(Self::VALUE_TAG);
+                bytes.extend_from_slice(&key_hash.0);
+            }
+        }
+        Ok(bytes)
+    }
+
+    pub fn decode(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let bytes = bytes.as_ref();
+        if bytes.len() < 17 {
+            anyhow::bail!("byte slice is too short")
+        }
+        let stale_since = u64::from_be_bytes(bytes[0..8].try_into().expect("slice is 8 bytes"));
+        let version = u64::from_be_bytes(bytes[8..16].try_into().expect("slice is 8 bytes"));
+        let rest = &bytes[17..];
+        let entry = match bytes[16] {
+            Self::NODE_TAG => StaleEntry::Node(DbNodeKey::decode(rest)?.into_inner()),
+            Self::VALUE_TAG => StaleEntry::Value(KeyHash(
+                rest.try_into()
+                    .map_err(|_| anyhow::anyhow!("invalid key hash length"))?,
+            )),
+            tag => anyhow::bail!("unknown stale index tag {tag}"),
+        };
+        Ok(StaleIndexKey {
+            stale_since,
+            version,
+            entry,
+        })
+    }
+}
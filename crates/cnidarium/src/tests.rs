@@ -1466,3 +1466,38 @@ async fn range_query_storage() -> anyhow::Result<()> {
     std::mem::drop(range);
     Ok(())
 }
+
+#[test]
+/// Substores with overlapping prefixes, like `prefix_a` and `prefix_ab`, must each be
+/// routed their own keys, so a key only belongs to a substore if it is the substore
+/// prefix, or if the prefix is followed by a delimiter.
+fn find_substore_with_overlapping_prefixes() {
+    use crate::store::{multistore::MultistoreConfig, substore::SubstoreConfig};
+    use std::sync::Arc;
+
+    let config = MultistoreConfig {
+        main_store: Arc::new(SubstoreConfig::new("")),
+        substores: vec![
+            Arc::new(SubstoreConfig::new("prefix_a")),
+            Arc::new(SubstoreConfig::new("prefix_ab")),
+        ],
+    };
+    let prefix_of = |key: &str| {
+        config
+            .find_substore(key.as_bytes())
+            .map(|substore| substore.prefix.clone())
+    };
+
+    assert_eq!(prefix_of("prefix_a/key").as_deref(), Some("prefix_a"));
+    assert_eq!(prefix_of("prefix_a").as_deref(), Some("prefix_a"));
+    // Matching on the prefix alone would route these to `prefix_a`, which comes
+    // first, and `route_key_str` would then send `prefix_ab/key` to the main store.
+    assert_eq!(prefix_of("prefix_ab/key").as_deref(), Some("prefix_ab"));
+    assert_eq!(prefix_of("prefix_ab").as_deref(), Some("prefix_ab"));
+    assert_eq!(prefix_of("prefix_akey"), None);
+
+    let (key, substore) = config.route_key_str("prefix_ab/key");
+    assert_eq!((key, substore.prefix.as_str()), ("key", "prefix_ab"));
+    let (key, substore) = config.route_key_str("prefix_akey");
+    assert_eq!((key, substore.prefix.as_str()), ("prefix_akey", ""));
+}
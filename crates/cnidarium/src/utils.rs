@@ -32,3 +32,41 @@ pub(crate) fn convert_bounds(
 
     Ok((range, (start, end)))
 }
+
+/// Merges iterators that each yield key-value pairs in ascending key order into
+/// a single iterator, that yields every pair in ascending key order.
+///
+/// Errors are yielded as soon as they are encountered.
+pub(crate) fn merge_sorted<V, I>(
+    iters: Vec<I>,
+) -> impl Iterator<Item = anyhow::Result<(Vec<u8>, V)>>
+where
+    I: Iterator<Item = anyhow::Result<(Vec<u8>, V)>>,
+{
+    let mut iters: Vec<_> = iters.into_iter().map(Iterator::fuse).collect();
+    let mut heads: Vec<Option<anyhow::Result<(Vec<u8>, V)>>> = iters.iter().map(|_| None).collect();
+
+    std::iter::from_fn(move || {
+        for (head, iter) in heads.iter_mut().zip(iters.iter_mut()) {
+            if head.is_none() {
+                *head = iter.next();
+            }
+        }
+
+        let next = heads
+            .iter()
+            .position(|head| matches!(head, Some(Err(_))))
+            .or_else(|| {
+                heads
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, head)| match head {
+                        Some(Ok((key, _))) => Some((i, key)),
+                        _ => None,
+                    })
+                    .min_by(|(_, a), (_, b)| a.cmp(b))
+                    .map(|(i, _)| i)
+            })?;
+        heads[next].take()
+    })
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use cnidarium::{PruningMode, SnapshotError, StateDelta, StateRead, StateWrite, Storage};
+use tempfile;
+use tokio;
+
+#[tokio::test]
+/// Check that pruning deletes historical data without affecting the latest state,
+/// and that requesting a discarded version returns a `Pruned` error.
+async fn test_pruning_keeps_latest_state() -> Result<()> {
+    let _ = tracing_subscriber::fmt::try_init();
+    let tmpdir = tempfile::tempdir()?;
+    let substore_prefixes = vec!["ibc".to_string()];
+    let pruning = PruningMode::Pruned {
+        keep_recent: 3,
+        keep_every: Some(10),
+        // We run pruning passes manually in this test.
+        interval: u64::MAX,
+    };
+    let storage = Storage::load_with_pruning(
+        tmpdir.path().to_path_buf(),
+        substore_prefixes.clone(),
+        pruning,
+    )
+    .await?;
+
+    // Repeatedly overwrite the same keys, so that every commit makes the
+    // previous version of the tree stale.
+    for i in 0..25u64 {
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        delta.put_raw("key".to_string(), i.to_be_bytes().to_vec());
+        delta.put_raw("ibc/key".to_string(), i.to_be_bytes().to_vec());
+        if i % 2 == 0 {
+            delta.put_raw(format!("unique_{i}"), vec![i as u8]);
+        }
+        storage.commit(delta).await?;
+    }
+
+    let latest = storage.latest_snapshot();
+    let root_hash = latest.root_hash().await?;
+    assert_eq!(latest.version(), 24);
+
+    let stats = storage.prune().await?;
+    assert!(stats.nodes > 0, "pruning should delete stale nodes");
+    assert!(stats.values > 0, "pruning should delete stale values");
+    assert!(stats.bytes > 0);
+
+    // A second pass has nothing left to do.
+    let stats = storage.prune().await?;
+    assert_eq!(stats.nodes, 0);
+    assert_eq!(stats.values, 0);
+
+    // The latest state is unaffected.
+    let latest = storage.latest_snapshot();
+    assert_eq!(latest.root_hash().await?, root_hash);
+    assert_eq!(
+        latest.get_raw("key").await?,
+        Some(24u64.to_be_bytes().to_vec())
+    );
+    assert_eq!(
+        latest.get_raw("ibc/key").await?,
+        Some(24u64.to_be_bytes().to_vec())
+    );
+    for i in (0..25u64).step_by(2) {
+        assert_eq!(
+            latest.get_raw(&format!("unique_{i}")).await?,
+            Some(vec![i as u8])
+        );
+    }
+
+    // So are the versions in the recent window, and the periodic versions.
+    for version in [20, 22, 23] {
+        let snapshot = storage
+            .snapshot(version)
+            .expect("retained versions are available");
+        assert_eq!(
+            snapshot.get_raw("key").await?,
+            Some(version.to_be_bytes().to_vec())
+        );
+        assert_eq!(
+            snapshot.get_raw("ibc/key").await?,
+            Some(version.to_be_bytes().to_vec())
+        );
+    }
+
+    // Discarded versions are reported as such, even if they are still cached.
+    assert!(matches!(
+        storage.snapshot(21),
+        Err(SnapshotError::Pruned { version: 21, .. })
+    ));
+    assert!(matches!(
+        storage.snapshot(5),
+        Err(SnapshotError::Pruned { version: 5, .. })
+    ));
+    // Periodic versions are retained, but may be too old to be cached.
+    assert!(matches!(
+        storage.snapshot(10),
+        Err(SnapshotError::NotCached { version: 10, .. })
+    ));
+    assert!(matches!(
+        storage.snapshot(25),
+        Err(SnapshotError::NotFound { version: 25, .. })
+    ));
+
+    // The storage keeps working after pruning, including after a restart.
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.put_raw("key".to_string(), b"after".to_vec());
+    storage.commit(delta).await?;
+    storage.release().await;
+
+    let storage =
+        Storage::load_with_pruning(tmpdir.path().to_path_buf(), substore_prefixes, pruning)
+            .await?;
+    let latest = storage.latest_snapshot();
+    assert_eq!(latest.version(), 25);
+    assert_eq!(latest.get_raw("key").await?, Some(b"after".to_vec()));
+    assert_eq!(
+        latest.get_raw("ibc/key").await?,
+        Some(24u64.to_be_bytes().to_vec())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+/// Archive storage never discards any version.
+async fn test_archive_mode_never_prunes() -> Result<()> {
+    let tmpdir = tempfile::tempdir()?;
+    let storage = Storage::load(tmpdir.path().to_path_buf(), vec![]).await?;
+
+    for i in 0..20u64 {
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        delta.put_raw("key".to_string(), i.to_be_bytes().to_vec());
+        storage.commit(delta).await?;
+    }
+
+    assert_eq!(storage.pruning_mode(), PruningMode::Archive);
+    let stats = storage.prune().await?;
+    assert_eq!(stats.nodes, 0);
+    assert_eq!(stats.values, 0);
+    assert!(matches!(
+        storage.snapshot(0),
+        Err(SnapshotError::NotCached { version: 0, .. })
+    ));
+
+    Ok(())
+}
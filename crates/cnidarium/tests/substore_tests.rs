@@ -567,9 +567,8 @@ async fn test_substore_nv_prefix() -> anyhow::Result<()> {
 }
 
 #[tokio::test]
-/// Test that range queries over the main store work as expected.
-/// TODO(erwan): the range query implementation is broken for substores. Ignore this test for now.
-#[ignore]
+/// Test that range queries work as expected over the main store, over substores,
+/// and across substores, both on a snapshot and through a dirty `StateDelta`.
 async fn test_substore_nv_range_queries_main_store() -> anyhow::Result<()> {
     let _ = tracing_subscriber::fmt::try_init();
     let tmpdir = tempfile::tempdir()?;
@@ -584,20 +583,19 @@ async fn test_substore_nv_range_queries_main_store() -> anyhow::Result<()> {
     let mut all_kv = vec![];
     let mut kv_a = vec![];
     let mut kv_c = vec![];
-    let mut kv_d = vec![];
     let mut kv_main = vec![];
     for i in 0..100 {
-        let key_a_i = format!("a/key_{}", i);
+        let key_a_i = format!("a/key_{i:03}");
         let value_a_i = format!("value_{}a", i).as_bytes().to_vec();
-        delta.put_raw(key_a_i.clone(), value_a_i.clone());
+        delta.nonverifiable_put_raw(key_a_i.as_bytes().to_vec(), value_a_i.clone());
         all_kv.push((key_a_i.clone(), value_a_i.clone()));
         kv_a.push((key_a_i, value_a_i));
     }
 
     for i in 0..100 {
-        let key_c_i = format!("c/key_{}", i);
+        let key_c_i = format!("c/key_{i:03}");
         let value_c_i = format!("value_{}c", i).as_bytes().to_vec();
-        delta.put_raw(key_c_i.clone(), value_c_i.clone());
+        delta.nonverifiable_put_raw(key_c_i.as_bytes().to_vec(), value_c_i.clone());
         all_kv.push((key_c_i.clone(), value_c_i.clone()));
         kv_c.push((key_c_i, value_c_i));
     }
@@ -605,59 +603,238 @@ async fn test_substore_nv_range_queries_main_store() -> anyhow::Result<()> {
     for i in 0..100 {
         let key_i = format!("compactblock/{i:020}");
         let value_i = format!("value_{}", i).as_bytes().to_vec();
-        delta.put_raw(key_i.clone(), value_i.clone());
+        delta.nonverifiable_put_raw(key_i.as_bytes().to_vec(), value_i.clone());
         all_kv.push((key_i.clone(), value_i.clone()));
         kv_main.push((key_i, value_i));
     }
 
     for i in 0..100 {
-        let key_d_i = format!("d/key_{}", i);
-        let value_d_i = format!("value_{}c", i).as_bytes().to_vec();
-        delta.put_raw(key_d_i.clone(), value_d_i.clone());
+        let key_d_i = format!("d/key_{i:03}");
+        let value_d_i = format!("value_{}d", i).as_bytes().to_vec();
+        delta.nonverifiable_put_raw(key_d_i.as_bytes().to_vec(), value_d_i.clone());
         all_kv.push((key_d_i.clone(), value_d_i.clone()));
-        kv_d.push((key_d_i, value_d_i));
     }
 
     let _ = storage.commit(delta).await?;
+    all_kv.sort();
 
     let snapshot = storage.latest_snapshot();
 
     // First, check that we can iterate over a range of compact blocks in the main store.
     // We define a range that spans all compact blocks between 12 and 34.
-    let mut counter = 0;
-    let start_index = 12;
-    let end_index = 34;
-    let mut index = start_index;
-
-    let start_key = format!("compactblock/{:020}", start_index)
-        .as_bytes()
-        .to_vec();
-    let end_key = format!("compactblock/{:020}", end_index)
-        .as_bytes()
-        .to_vec();
-    let mut range = snapshot.nonverifiable_range_raw(None, start_key.clone()..end_key.clone())?;
-    while let Some(res) = range.next().await {
-        let (raw_key, _) = res?;
-        let key = String::from_utf8(raw_key)?;
-        if counter > kv_a.len() {
-            tracing::debug!(?key, ?start_key, ?end_key, "unexpected key");
-            panic!("prefix_keys query returned too many entries")
+    let start_key = format!("compactblock/{:020}", 12).as_bytes().to_vec();
+    let end_key = format!("compactblock/{:020}", 34).as_bytes().to_vec();
+    let range = snapshot.nonverifiable_range_raw(None, start_key..end_key)?;
+    assert_eq!(
+        collect_range(range).await?,
+        kv_main[12..34].to_vec(),
+        "should have iterated over all entries (compact block range)"
+    );
+
+    // The same range, expressed relative to a prefix, with an unbounded end.
+    let range = snapshot.nonverifiable_range_raw(
+        Some("compactblock/".as_bytes()),
+        format!("{:020}", 90).as_bytes().to_vec()..,
+    )?;
+    assert_eq!(
+        collect_range(range).await?,
+        kv_main[90..].to_vec(),
+        "should have iterated over all entries (prefixed compact block range)"
+    );
+
+    // A range over a substore returns full keys.
+    let range = snapshot.nonverifiable_range_raw(
+        Some("a/".as_bytes()),
+        b"key_010".to_vec()..b"key_020".to_vec(),
+    )?;
+    assert_eq!(
+        collect_range(range).await?,
+        kv_a[10..20].to_vec(),
+        "should have iterated over all entries (substore range)"
+    );
+
+    // A range with no prefix, whose bounds fall in two different substores.
+    let range =
+        snapshot.nonverifiable_range_raw(None, b"a/key_090".to_vec()..b"c/key_010".to_vec())?;
+    let expected: Vec<_> = kv_a[90..]
+        .iter()
+        .chain(kv_c[..10].iter())
+        .cloned()
+        .collect();
+    assert_eq!(
+        collect_range(range).await?,
+        expected,
+        "should have iterated over all entries (cross-substore range)"
+    );
+
+    // An unbounded range with no prefix covers the entire multistore, in key order.
+    let range = snapshot.nonverifiable_range_raw(None, ..)?;
+    assert_eq!(
+        collect_range(range).await?,
+        all_kv,
+        "should have iterated over all entries (full range)"
+    );
+
+    // A prefix at the root can span a substore and the main store.
+    let prefix = snapshot.nonverifiable_prefix_raw("c".as_bytes());
+    let expected: Vec<_> = kv_c.iter().chain(kv_main.iter()).cloned().collect();
+    assert_eq!(
+        collect_range(prefix).await?,
+        expected,
+        "should have iterated over all entries (cross-substore prefix)"
+    );
+
+    // Finally, check that a dirty `StateDelta` interleaves its writes and deletions
+    // with the entries of the underlying substores.
+    let mut delta = StateDelta::new(snapshot);
+    delta.nonverifiable_delete(b"a/key_095".to_vec());
+    delta.nonverifiable_put_raw(b"b/key_000".to_vec(), b"value_0b".to_vec());
+    delta.nonverifiable_put_raw(b"c/key_005".to_vec(), b"new_value".to_vec());
+
+    let range =
+        delta.nonverifiable_range_raw(None, b"a/key_090".to_vec()..b"c/key_010".to_vec())?;
+    let mut expected: Vec<_> = kv_a[90..]
+        .iter()
+        .filter(|(k, _)| k != "a/key_095")
+        .cloned()
+        .collect();
+    expected.push(("b/key_000".to_string(), b"value_0b".to_vec()));
+    expected.extend(kv_c[..10].iter().cloned());
+    expected[15].1 = b"new_value".to_vec();
+    assert_eq!(expected[15].0, "c/key_005");
+    assert_eq!(
+        collect_range(range).await?,
+        expected,
+        "should have iterated over all entries (dirty cross-substore range)"
+    );
+
+    let mut expected = all_kv.clone();
+    expected.retain(|(k, _)| k != "a/key_095");
+    expected.push(("b/key_000".to_string(), b"value_0b".to_vec()));
+    expected.sort();
+    for (k, v) in expected.iter_mut() {
+        if k == "c/key_005" {
+            *v = b"new_value".to_vec();
         }
+    }
+    let prefix = delta.nonverifiable_prefix_raw(b"");
+    assert_eq!(
+        collect_range(prefix).await?,
+        expected,
+        "should have iterated over all entries (dirty full prefix)"
+    );
 
-        let expected_key = kv_main[index].0.clone();
-        assert_eq!(key, expected_key, "key {} should match", counter);
-        counter += 1;
-        index += 1;
+    Ok(())
+}
+
+#[tokio::test]
+/// Test that verifiable prefix queries at the root span the main store and substores.
+async fn test_substore_cross_prefix_queries() -> anyhow::Result<()> {
+    let _ = tracing_subscriber::fmt::try_init();
+    let tmpdir = tempfile::tempdir()?;
+    let db_path = tmpdir.into_path();
+    let substore_prefixes = vec!["prefix_a", "prefix_ab", "other"]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect();
+    let storage = Storage::load(db_path, substore_prefixes).await?;
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+
+    let keys = vec![
+        "prefix_a/key_1",
+        "prefix_a/key_2",
+        "prefix_ab/key_1",
+        "prefix_akey_1",
+        "prefix_b",
+        "other/key_1",
+    ];
+    for key in keys.iter() {
+        delta.put_raw(key.to_string(), key.as_bytes().to_vec());
     }
+    let _ = storage.commit(delta).await?;
+    let snapshot = storage.latest_snapshot();
+
+    // A key without a delimiter does not belong to a substore that it shares a prefix with.
     assert_eq!(
-        counter,
-        end_index - start_index,
-        "should have iterated over all entries (compact block range)"
+        snapshot.get_raw("prefix_akey_1").await?,
+        Some(b"prefix_akey_1".to_vec())
+    );
+    assert_eq!(
+        snapshot.get_raw("prefix_ab/key_1").await?,
+        Some(b"prefix_ab/key_1".to_vec())
+    );
+
+    // A prefix that designates a substore only returns keys from that substore.
+    let keys: Vec<String> = snapshot
+        .prefix_keys("prefix_a")
+        .collect::<Result<_, _>>()
+        .await?;
+    assert_eq!(keys, vec!["prefix_a/key_1", "prefix_a/key_2"]);
+    let keys: Vec<String> = snapshot
+        .prefix_keys("prefix_ab/")
+        .collect::<Result<_, _>>()
+        .await?;
+    assert_eq!(keys, vec!["prefix_ab/key_1"]);
+
+    // A prefix at the root returns keys from the main store, including the substore
+    // root hashes, and from every substore it spans, in key order.
+    let keys: Vec<String> = snapshot
+        .prefix_keys("prefix_")
+        .collect::<Result<_, _>>()
+        .await?;
+    assert_eq!(
+        keys,
+        vec![
+            "prefix_a",
+            "prefix_a/key_1",
+            "prefix_a/key_2",
+            "prefix_ab",
+            "prefix_ab/key_1",
+            "prefix_akey_1",
+            "prefix_b",
+        ]
     );
 
+    // The same query through a dirty `StateDelta` sees its writes and deletions.
+    let mut delta = StateDelta::new(snapshot);
+    delta.delete("prefix_a/key_2".to_string());
+    delta.put_raw("prefix_ab/key_0".to_string(), b"prefix_ab/key_0".to_vec());
+    let entries: Vec<(String, Vec<u8>)> = delta
+        .prefix_raw("prefix_")
+        .collect::<Result<_, _>>()
+        .await?;
+    let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(
+        keys,
+        vec![
+            "prefix_a",
+            "prefix_a/key_1",
+            "prefix_ab",
+            "prefix_ab/key_0",
+            "prefix_ab/key_1",
+            "prefix_akey_1",
+            "prefix_b",
+        ]
+    );
+    for (k, v) in entries.iter().filter(|(k, _)| k.contains("key")) {
+        assert_eq!(v, k.as_bytes(), "value for key {k} should match");
+    }
+
     Ok(())
 }
 
+/// Collects a nonverifiable stream into a vector of utf-8 keys and values.
+async fn collect_range(
+    mut stream: impl tokio_stream::Stream<Item = anyhow::Result<(Vec<u8>, Vec<u8>)>> + Unpin,
+) -> anyhow::Result<Vec<(String, Vec<u8>)>> {
+    let mut entries = vec![];
+    while let Some(res) = stream.next().await {
+        let (raw_key, value) = res?;
+        entries.push((String::from_utf8(raw_key)?, value));
+    }
+    Ok(entries)
+}
+
 #[tokio::test]
 /// Minimal reproduction of the prefix range cache bug.
 ///
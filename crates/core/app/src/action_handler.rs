@@ -7,6 +7,8 @@ use cnidarium::{StateRead, StateWrite};
 mod actions;
 mod transaction;
 
+pub use transaction::CheckTxError;
+
 /// Stub: to be replaced with impls of cnidarium_component::ActionHandler
 ///
 /// This trait should move to that crate, but the orphan rules make it tricky to
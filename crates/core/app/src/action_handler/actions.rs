@@ -25,6 +25,8 @@ impl AppActionHandler for Action {
             Action::DelegatorVote(action) => action.check_stateless(context).await,
             Action::Delegate(action) => action.check_stateless(()).await,
             Action::Undelegate(action) => action.check_stateless(()).await,
+            Action::Redelegate(action) => action.check_stateless(()).await,
+            Action::RedelegateClaim(action) => action.check_stateless(()).await,
             Action::UndelegateClaim(action) => action.check_stateless(()).await,
             Action::ValidatorDefinition(action) => action.check_stateless(()).await,
             Action::ValidatorVote(action) => action.check_stateless(()).await,
@@ -57,6 +59,8 @@ impl AppActionHandler for Action {
         match self {
             Action::Delegate(action) => action.check_historical(state).await,
             Action::Undelegate(action) => action.check_historical(state).await,
+            Action::Redelegate(action) => action.check_historical(state).await,
+            Action::RedelegateClaim(action) => action.check_historical(state).await,
             Action::UndelegateClaim(action) => action.check_historical(state).await,
             Action::ValidatorDefinition(action) => action.check_historical(state).await,
             Action::DelegatorVote(action) => action.check_historical(state).await,
@@ -92,6 +96,8 @@ impl AppActionHandler for Action {
         match self {
             Action::Delegate(action) => action.check_and_execute(state).await,
             Action::Undelegate(action) => action.check_and_execute(state).await,
+            Action::Redelegate(action) => action.check_and_execute(state).await,
+            Action::RedelegateClaim(action) => action.check_and_execute(state).await,
             Action::UndelegateClaim(action) => action.check_and_execute(state).await,
             Action::ValidatorDefinition(action) => action.check_and_execute(state).await,
             Action::DelegatorVote(action) => action.check_and_execute(state).await,
@@ -22,7 +22,7 @@ use penumbra_keys::keys::{FullViewingKey, NullifierKey};
 use penumbra_proto::{DomainType, StateWriteProto as _};
 use penumbra_sct::component::clock::EpochRead;
 use penumbra_sct::component::tree::SctRead;
-use penumbra_shielded_pool::component::AssetRegistry;
+use penumbra_shielded_pool::component::{AssetRegistry, SupplyWrite};
 use penumbra_transaction::{AuthorizationData, Transaction, TransactionPlan, WitnessData};
 
 use crate::app::StateReadExt;
@@ -86,7 +86,7 @@ impl AppActionHandler for ProposalSubmit {
                         | UndelegateClaim(_) => {
                             anyhow::bail!("invalid action in Community Pool spend proposal (would require proving)")
                         }
-                        Delegate(_) | Undelegate(_) => {
+                        Delegate(_) | Undelegate(_) | Redelegate(_) | RedelegateClaim(_) => {
                             anyhow::bail!("invalid action in Community Pool spend proposal (can't claim outputs of undelegation)")
                         }
                         ProposalSubmit(_) | ProposalWithdraw(_) | ProposalDepositClaim(_) => {
@@ -265,6 +265,9 @@ impl AppActionHandler for ProposalSubmit {
             .register_denom(&VotingReceiptToken::new(proposal_id).denom())
             .await;
 
+        // The deposit is burned in exchange for the voting proposal NFT
+        state.record_supply_change(self.balance()).await?;
+
         // Set the proposal state to voting (votes start immediately)
         state.put_proposal_state(proposal_id, ProposalState::Voting);
 
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
 use penumbra_fee::component::FeePay as _;
@@ -12,9 +12,12 @@ use tracing::{instrument, Instrument};
 
 use super::AppActionHandler;
 
+mod error;
 mod stateful;
 mod stateless;
 
+pub use self::error::CheckTxError;
+
 use self::stateful::{
     claimed_anchor_is_valid, fmd_parameters_valid, tx_parameters_historical_check,
 };
@@ -62,7 +65,7 @@ impl AppActionHandler for Transaction {
         }
         // Now check if any component action failed verification.
         while let Some(check) = action_checks.join_next().await {
-            check??;
+            check?.context(CheckTxError::InvalidAction)?;
         }
 
         Ok(())
@@ -94,7 +97,7 @@ impl AppActionHandler for Transaction {
         }
         // Now check if any component action failed verification.
         while let Some(check) = action_checks.join_next().await {
-            check??;
+            check?.context(CheckTxError::InvalidActionState)?;
         }
 
         Ok(())
@@ -114,14 +117,18 @@ impl AppActionHandler for Transaction {
         // before doing the rest of execution.
         let gas_used = self.gas_cost();
         let fee = self.transaction_body.transaction_parameters.fee;
-        state.pay_fee(gas_used, fee).await?;
+        state
+            .pay_fee(gas_used, fee)
+            .await
+            .context(CheckTxError::FeeRejected)?;
 
         for (i, action) in self.actions().enumerate() {
             let span = action.create_span(i);
             action
                 .check_and_execute(&mut state)
                 .instrument(span)
-                .await?;
+                .await
+                .context(CheckTxError::ExecutionFailed)?;
         }
 
         // Delete the note source, in case someone else tries to read it.
@@ -0,0 +1,266 @@
+use penumbra_fee::component::FeePaymentError;
+use penumbra_sct::component::tree::NullifierSpent;
+
+/// The reason a transaction was rejected by `CheckTx`.
+///
+/// These are attached as context to the errors produced by the transaction
+/// checks, so that the mempool can recover them from the error chain with
+/// [`CheckTxError::from_error`] and report a distinct ABCI code and codespace
+/// for each class of failure. Clients can map a code returned by the node back
+/// to a variant with [`CheckTxError::from_code`].
+///
+/// Some failures are instead reported by the components with their own error
+/// types, which [`CheckTxError::from_error`] maps to a more precise rejection.
+///
+/// The numeric codes are part of the node's external interface and must not
+/// be reassigned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum CheckTxError {
+    /// The check failed for a reason not covered by another variant.
+    #[error("transaction rejected")]
+    Other,
+    /// The transaction bytes could not be decoded.
+    #[error("transaction could not be decoded")]
+    Decode,
+    /// The binding signature did not verify.
+    #[error("binding signature failed to verify")]
+    InvalidBindingSignature,
+    /// The number of FMD clues does not match the number of outputs.
+    #[error("number of FMD clues does not match number of outputs")]
+    ClueCountMismatch,
+    /// A memo was present without outputs, or missing with outputs.
+    #[error("memo must be present if and only if the transaction has outputs")]
+    InvalidMemo,
+    /// One of the transaction's actions failed its stateless checks.
+    #[error("action failed stateless checks")]
+    InvalidAction,
+    /// The transaction contains Community Pool spends or outputs.
+    #[error("Community Pool spends and outputs are not permitted in user-submitted transactions")]
+    CommunityPoolNotPermitted,
+    /// The transaction's chain ID does not match the chain.
+    #[error("transaction chain ID does not match the chain")]
+    WrongChainId,
+    /// The transaction's expiry height has passed.
+    #[error("transaction has expired")]
+    Expired,
+    /// The transaction's anchor is not a known state commitment tree root.
+    #[error("transaction anchor is not a known state commitment tree root")]
+    UnknownAnchor,
+    /// An FMD clue uses a precision the chain no longer accepts.
+    #[error("FMD clue precision is not accepted by the chain")]
+    InvalidCluePrecision,
+    /// One of the transaction's actions failed its historical checks.
+    #[error("action failed historical checks")]
+    InvalidActionState,
+    /// The fee could not be paid, because it was too low or in an unrecognized token.
+    #[error("transaction fee was not accepted")]
+    FeeRejected,
+    /// The fee is lower than the base fee for the transaction's gas.
+    #[error("transaction fee is too low")]
+    FeeTooLow,
+    /// The fee is paid in a token the chain doesn't accept fees in.
+    #[error("transaction fee is paid in a token the chain does not accept")]
+    WrongFeeToken,
+    /// One of the transaction's actions failed during execution.
+    #[error("action failed during execution")]
+    ExecutionFailed,
+    /// The transaction spends a note which was already spent.
+    #[error("transaction spends a nullifier which was already spent")]
+    SpentNullifier,
+}
+
+impl CheckTxError {
+    const ALL: [CheckTxError; 17] = [
+        CheckTxError::Other,
+        CheckTxError::Decode,
+        CheckTxError::InvalidBindingSignature,
+        CheckTxError::ClueCountMismatch,
+        CheckTxError::InvalidMemo,
+        CheckTxError::InvalidAction,
+        CheckTxError::CommunityPoolNotPermitted,
+        CheckTxError::WrongChainId,
+        CheckTxError::Expired,
+        CheckTxError::UnknownAnchor,
+        CheckTxError::InvalidCluePrecision,
+        CheckTxError::InvalidActionState,
+        CheckTxError::FeeRejected,
+        CheckTxError::FeeTooLow,
+        CheckTxError::WrongFeeToken,
+        CheckTxError::ExecutionFailed,
+        CheckTxError::SpentNullifier,
+    ];
+
+    /// The ABCI response code for this rejection.
+    ///
+    /// Codes are unique across codespaces, so the code alone identifies the
+    /// variant; code `0` is reserved for success.
+    pub fn code(&self) -> u32 {
+        match self {
+            CheckTxError::Other => 1,
+            CheckTxError::Decode => 2,
+            CheckTxError::InvalidBindingSignature => 10,
+            CheckTxError::ClueCountMismatch => 11,
+            CheckTxError::InvalidMemo => 12,
+            CheckTxError::InvalidAction => 13,
+            CheckTxError::CommunityPoolNotPermitted => 14,
+            CheckTxError::WrongChainId => 20,
+            CheckTxError::Expired => 21,
+            CheckTxError::UnknownAnchor => 22,
+            CheckTxError::InvalidCluePrecision => 23,
+            CheckTxError::InvalidActionState => 24,
+            CheckTxError::FeeRejected => 30,
+            CheckTxError::FeeTooLow => 31,
+            CheckTxError::WrongFeeToken => 32,
+            CheckTxError::ExecutionFailed => 40,
+            CheckTxError::SpentNullifier => 41,
+        }
+    }
+
+    /// The ABCI codespace for this rejection, grouping codes by the stage of
+    /// checking that produced them.
+    pub fn codespace(&self) -> &'static str {
+        match self {
+            CheckTxError::Other | CheckTxError::Decode => "tx",
+            CheckTxError::InvalidBindingSignature
+            | CheckTxError::ClueCountMismatch
+            | CheckTxError::InvalidMemo
+            | CheckTxError::InvalidAction
+            | CheckTxError::CommunityPoolNotPermitted => "stateless",
+            CheckTxError::WrongChainId
+            | CheckTxError::Expired
+            | CheckTxError::UnknownAnchor
+            | CheckTxError::InvalidCluePrecision
+            | CheckTxError::InvalidActionState => "historical",
+            CheckTxError::FeeRejected | CheckTxError::FeeTooLow | CheckTxError::WrongFeeToken => {
+                "fee"
+            }
+            CheckTxError::ExecutionFailed | CheckTxError::SpentNullifier => "execution",
+        }
+    }
+
+    /// A suggestion for how the submitter could fix the transaction, if any.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            CheckTxError::Other
+            | CheckTxError::InvalidAction
+            | CheckTxError::InvalidActionState
+            | CheckTxError::ExecutionFailed => None,
+            CheckTxError::Decode
+            | CheckTxError::InvalidBindingSignature
+            | CheckTxError::ClueCountMismatch
+            | CheckTxError::InvalidMemo
+            | CheckTxError::CommunityPoolNotPermitted => {
+                Some("the transaction is malformed; rebuild it with an up-to-date client")
+            }
+            CheckTxError::WrongChainId => {
+                Some("the client is configured for a different chain; check the node URL")
+            }
+            CheckTxError::Expired => Some("rebuild the transaction with a later expiry height"),
+            CheckTxError::UnknownAnchor => {
+                Some("the client's view of the chain may be out of date; resync and retry")
+            }
+            CheckTxError::InvalidCluePrecision => {
+                Some("the chain's FMD parameters changed; resync and rebuild the transaction")
+            }
+            CheckTxError::FeeRejected => {
+                Some("retry with a higher fee tier, or pay the fee in the staking token")
+            }
+            CheckTxError::FeeTooLow => Some("retry with a higher fee tier"),
+            CheckTxError::WrongFeeToken => Some("pay the fee in the staking token"),
+            CheckTxError::SpentNullifier => {
+                Some("some of the notes were already spent; resync and rebuild the transaction")
+            }
+        }
+    }
+
+    /// Looks up the rejection corresponding to a non-zero ABCI code.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.code() == code)
+    }
+
+    /// Extracts the rejection attached to `error`, falling back to
+    /// [`CheckTxError::Other`] if none was attached.
+    ///
+    /// Errors reported by the fee payment and the nullifier checks take
+    /// precedence over the stage they were attached to.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        // `anyhow` can downcast through layers of context, so this finds the
+        // rejection wherever it was attached in the chain.
+        if error.downcast_ref::<NullifierSpent>().is_some() {
+            return CheckTxError::SpentNullifier;
+        }
+        match error.downcast_ref::<FeePaymentError>() {
+            Some(FeePaymentError::TooLow { .. }) => return CheckTxError::FeeTooLow,
+            Some(FeePaymentError::UnrecognizedToken(_)) => return CheckTxError::WrongFeeToken,
+            None => {}
+        }
+        error
+            .downcast_ref::<CheckTxError>()
+            .copied()
+            .unwrap_or(CheckTxError::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use anyhow::Context as _;
+    use decaf377::Fq;
+    use penumbra_asset::STAKING_TOKEN_ASSET_ID;
+    use penumbra_fee::component::FeePaymentError;
+    use penumbra_sct::{component::tree::NullifierSpent, Nullifier};
+
+    use super::CheckTxError;
+
+    #[test]
+    fn codes_are_unique_and_round_trip() {
+        let codes: BTreeSet<u32> = CheckTxError::ALL.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), CheckTxError::ALL.len());
+        assert!(!codes.contains(&0));
+        for e in CheckTxError::ALL {
+            assert_eq!(CheckTxError::from_code(e.code()), Some(e));
+        }
+        assert_eq!(CheckTxError::from_code(0), None);
+    }
+
+    #[test]
+    fn from_error_finds_attached_context() {
+        let error = Err::<(), _>(anyhow::anyhow!("fee too low"))
+            .context(CheckTxError::FeeRejected)
+            .context("executing transaction")
+            .context("failed to deliver transaction")
+            .unwrap_err();
+        assert_eq!(CheckTxError::from_error(&error), CheckTxError::FeeRejected);
+
+        let error = anyhow::anyhow!("something else");
+        assert_eq!(CheckTxError::from_error(&error), CheckTxError::Other);
+    }
+
+    #[test]
+    fn from_error_refines_component_errors() {
+        let error = Err::<(), _>(anyhow::Error::from(FeePaymentError::TooLow {
+            supplied: 1u64.into(),
+            base: 2u64.into(),
+        }))
+        .context(CheckTxError::FeeRejected)
+        .unwrap_err();
+        assert_eq!(CheckTxError::from_error(&error), CheckTxError::FeeTooLow);
+
+        let error = Err::<(), _>(anyhow::Error::from(FeePaymentError::UnrecognizedToken(
+            *STAKING_TOKEN_ASSET_ID,
+        )))
+        .context(CheckTxError::FeeRejected)
+        .unwrap_err();
+        assert_eq!(CheckTxError::from_error(&error), CheckTxError::WrongFeeToken);
+
+        let error = Err::<(), _>(anyhow::Error::from(NullifierSpent {
+            nullifier: Nullifier(Fq::from(1u64)),
+            spent_in: [0; 32],
+        }))
+        .context(CheckTxError::ExecutionFailed)
+        .context("failed to deliver transaction")
+        .unwrap_err();
+        assert_eq!(CheckTxError::from_error(&error), CheckTxError::SpentNullifier);
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{ensure, Result};
+use anyhow::{anyhow, Context as _, Result};
 use cnidarium::StateRead;
 use penumbra_sct::component::clock::EpochRead;
 use penumbra_sct::component::tree::VerificationExt;
@@ -6,6 +6,7 @@ use penumbra_shielded_pool::component::StateReadExt as _;
 use penumbra_shielded_pool::fmd;
 use penumbra_transaction::{Transaction, TransactionParameters};
 
+use super::CheckTxError;
 use crate::app::StateReadExt;
 
 pub async fn tx_parameters_historical_check<S: StateRead>(
@@ -36,12 +37,14 @@ pub async fn chain_id_is_correct<S: StateRead>(state: S, tx_chain_id: String) ->
     let chain_id = state.get_chain_id().await?;
 
     // The chain ID in the transaction must exactly match the current chain ID.
-    ensure!(
-        tx_chain_id == chain_id,
-        "transaction chain ID '{}' must match the current chain ID '{}'",
-        tx_chain_id,
-        chain_id
-    );
+    if tx_chain_id != chain_id {
+        return Err(anyhow!(
+            "transaction chain ID '{}' must match the current chain ID '{}'",
+            tx_chain_id,
+            chain_id
+        )
+        .context(CheckTxError::WrongChainId));
+    }
     Ok(())
 }
 
@@ -54,12 +57,14 @@ pub async fn expiry_height_is_valid<S: StateRead>(state: S, expiry_height: u64)
     }
 
     // Otherwise, the expiry height must be greater than or equal to the current block height.
-    ensure!(
-        expiry_height >= current_height,
-        "transaction expiry height '{}' must be greater than or equal to the current block height '{}'",
-        expiry_height,
-        current_height
-    );
+    if expiry_height < current_height {
+        return Err(anyhow!(
+            "transaction expiry height '{}' must be greater than or equal to the current block height '{}'",
+            expiry_height,
+            current_height
+        )
+        .context(CheckTxError::Expired));
+    }
 
     Ok(())
 }
@@ -127,7 +132,8 @@ pub fn fmd_precision_within_grace_period(
                 %within_grace_period,
                 "invalid clue precision"
             );
-            anyhow::bail!("consensus rule violated: invalid clue precision");
+            return Err(anyhow!("consensus rule violated: invalid clue precision")
+                .context(CheckTxError::InvalidCluePrecision));
         }
     }
     Ok(())
@@ -137,5 +143,8 @@ pub async fn claimed_anchor_is_valid<S: StateRead>(
     state: S,
     transaction: &Transaction,
 ) -> Result<()> {
-    state.check_claimed_anchor(transaction.anchor).await
+    state
+        .check_claimed_anchor(transaction.anchor)
+        .await
+        .context(CheckTxError::UnknownAnchor)
 }
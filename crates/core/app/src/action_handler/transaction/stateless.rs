@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use penumbra_transaction::Transaction;
 use penumbra_txhash::AuthorizingData;
 
+use super::CheckTxError;
+
 #[tracing::instrument(skip(tx))]
 pub(super) fn valid_binding_signature(tx: &Transaction) -> Result<()> {
     let auth_hash = tx.auth_hash();
@@ -11,7 +13,7 @@ pub(super) fn valid_binding_signature(tx: &Transaction) -> Result<()> {
     // Check binding signature.
     tx.binding_verification_key()
         .verify(auth_hash.as_bytes(), tx.binding_sig())
-        .context("binding signature failed to verify")
+        .context(CheckTxError::InvalidBindingSignature)
 }
 
 pub fn num_clues_equal_to_num_outputs(tx: &Transaction) -> anyhow::Result<()> {
@@ -25,7 +27,8 @@ pub fn num_clues_equal_to_num_outputs(tx: &Transaction) -> anyhow::Result<()> {
     {
         Err(anyhow::anyhow!(
             "consensus rule violated: must have equal number of outputs and FMD clues"
-        ))
+        )
+        .context(CheckTxError::ClueCountMismatch))
     } else {
         Ok(())
     }
@@ -35,16 +38,18 @@ pub fn num_clues_equal_to_num_outputs(tx: &Transaction) -> anyhow::Result<()> {
 pub fn check_memo_exists_if_outputs_absent_if_not(tx: &Transaction) -> anyhow::Result<()> {
     let num_outputs = tx.outputs().count();
     if num_outputs > 0 && tx.transaction_body().memo.is_none() {
-        Err(anyhow::anyhow!(
-            "consensus rule violated: must have memo if outputs present"
-        ))
+        Err(
+            anyhow::anyhow!("consensus rule violated: must have memo if outputs present")
+                .context(CheckTxError::InvalidMemo),
+        )
     } else if num_outputs > 0 && tx.transaction_body().memo.is_some() {
         Ok(())
     } else if num_outputs == 0 && tx.transaction_body().memo.is_none() {
         Ok(())
     } else {
-        Err(anyhow::anyhow!(
-            "consensus rule violated: cannot have memo if no outputs present"
-        ))
+        Err(
+            anyhow::anyhow!("consensus rule violated: cannot have memo if no outputs present")
+                .context(CheckTxError::InvalidMemo),
+        )
     }
 }
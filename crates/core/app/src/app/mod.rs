@@ -2,7 +2,7 @@ use std::process;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use cnidarium::{ArcStateDeltaExt, Snapshot, StateDelta, StateRead, StateWrite, Storage};
 use cnidarium_component::Component;
@@ -40,12 +40,13 @@ use tendermint::validator::Update;
 use tokio::time::sleep;
 use tracing::{instrument, Instrument};
 
-use crate::action_handler::AppActionHandler;
+use crate::action_handler::{AppActionHandler, CheckTxError};
 use crate::genesis::AppState;
 use crate::params::change::ParameterChangeExt as _;
 use crate::params::AppParameters;
 use crate::{CommunityPoolStateReadExt, PenumbraHost};
 
+mod priority;
 pub mod state_key;
 
 /// The inter-block state being written to by the application.
@@ -198,28 +199,39 @@ impl App {
         // Tracking the size of the proposal
         let mut proposal_size_bytes = 0u64;
 
-        for tx in proposal.txs {
-            let transaction_size = tx.len() as u64;
+        // This should never happen, unless Comet is misbehaving because of a bug
+        // or a misconfiguration. We handle it gracefully, to prioritize forward progress.
+        let candidate_txs = proposal
+            .txs
+            .into_iter()
+            .filter(|tx| tx.len() <= MAX_TRANSACTION_SIZE_BYTES)
+            .collect();
+
+        // When the block is saturated, we want to include the transactions that pay the
+        // most per unit of gas, rather than the ones that were received first.
+        let fee_params = self
+            .state
+            .get_fee_params()
+            .await
+            .expect("fee parameters must be present in state");
+        let candidate_txs = priority::order_by_fee_priority(candidate_txs, &fee_params);
+
+        for (tx_bytes, tx) in candidate_txs {
+            let transaction_size = tx_bytes.len() as u64;
 
             // We compute the total proposal size if we were to include this transaction.
             let total_with_tx = proposal_size_bytes.saturating_add(transaction_size);
 
-            // This should never happen, unless Comet is misbehaving because of a bug
-            // or a misconfiguration. We handle it gracefully, to prioritize forward progress.
-            if transaction_size > MAX_TRANSACTION_SIZE_BYTES as u64 {
-                continue;
-            }
-
             // First, we filter proposals to fit within the block limit.
             if total_with_tx >= max_proposal_size_bytes {
                 break;
             }
 
             // Then, we make sure to only include successful transactions.
-            match self.deliver_tx_bytes(&tx).await {
+            match self.deliver_tx(tx).await {
                 Ok(_) => {
                     proposal_size_bytes = total_with_tx;
-                    included_txs.push(tx)
+                    included_txs.push(tx_bytes)
                 }
                 Err(_) => continue,
             }
@@ -385,7 +397,7 @@ impl App {
 
     /// Wrapper function for [`Self::deliver_tx`]  that decodes from bytes.
     pub async fn deliver_tx_bytes(&mut self, tx_bytes: &[u8]) -> Result<Vec<abci::Event>> {
-        let tx = Arc::new(Transaction::decode(tx_bytes).context("decoding transaction")?);
+        let tx = Arc::new(Transaction::decode(tx_bytes).context(CheckTxError::Decode)?);
         self.deliver_tx(tx)
             .await
             .context("failed to deliver transaction")
@@ -395,14 +407,18 @@ impl App {
         // Ensure that any normally-delivered transaction (originating from a user) does not contain
         // any Community Pool spends or outputs; the only place those are permitted is transactions originating
         // from the chain itself:
-        anyhow::ensure!(
-            tx.community_pool_spends().peekable().peek().is_none(),
-            "Community Pool spends are not permitted in user-submitted transactions"
-        );
-        anyhow::ensure!(
-            tx.community_pool_outputs().peekable().peek().is_none(),
-            "Community Pool outputs are not permitted in user-submitted transactions"
-        );
+        if tx.community_pool_spends().peekable().peek().is_some() {
+            return Err(anyhow!(
+                "Community Pool spends are not permitted in user-submitted transactions"
+            )
+            .context(CheckTxError::CommunityPoolNotPermitted));
+        }
+        if tx.community_pool_outputs().peekable().peek().is_some() {
+            return Err(anyhow!(
+                "Community Pool outputs are not permitted in user-submitted transactions"
+            )
+            .context(CheckTxError::CommunityPoolNotPermitted));
+        }
 
         // Now that we've ensured that there are not any Community Pool spends or outputs, we can deliver the transaction:
         self.deliver_tx_allowing_community_pool_spends(tx).await
@@ -0,0 +1,59 @@
+//! Prioritization of candidate transactions in block proposals.
+
+use std::{cmp::Reverse, sync::Arc};
+
+use bytes::Bytes;
+use penumbra_fee::FeeParameters;
+use penumbra_proto::DomainType;
+use penumbra_transaction::{gas::GasCost as _, Transaction};
+
+/// Returns the fee paid by `tx` as a multiple of its cost at the chain's gas prices, in
+/// thousandths, e.g., `2_000` for a transaction paying twice its base fee.
+///
+/// Each resource the transaction consumes is weighted by its price, so a transaction
+/// heavy on a cheap resource isn't outbid by one paying less for scarcer resources.
+/// If the chain doesn't price gas at all, every resource is weighted equally instead.
+///
+/// Fees paid in alternative fee tokens are converted using the chain's fixed gas prices,
+/// see [`FeeParameters::staking_token_equivalent`]. Fees that can't be valued have zero
+/// priority.
+pub(crate) fn fee_priority(tx: &Transaction, fee_params: &FeeParameters) -> u128 {
+    let gas = tx.gas_cost();
+    let fee = &tx.transaction_body.transaction_parameters.fee;
+    let Some(fee) = fee_params.staking_token_equivalent(fee, &gas) else {
+        return 0;
+    };
+    // The cost is in thousandths of a base unit, so this scales the ratio by a thousand.
+    let cost = match fee_params.fixed_gas_prices.cost(&gas) {
+        0 => u128::from(gas.total()).saturating_mul(1_000),
+        cost => cost,
+    };
+    fee.value().saturating_mul(1_000_000) / cost.max(1)
+}
+
+/// Decodes the candidate transactions of a proposal, and orders them by decreasing
+/// [`fee_priority`].
+///
+/// Ties are broken by the order in which the candidates were supplied, i.e., the order
+/// in which they entered the mempool. Candidates that can't be decoded are dropped,
+/// since they could never be included in a block anyways.
+pub(crate) fn order_by_fee_priority(
+    txs: Vec<Bytes>,
+    fee_params: &FeeParameters,
+) -> Vec<(Bytes, Arc<Transaction>)> {
+    let mut candidates: Vec<_> = txs
+        .into_iter()
+        .filter_map(|tx_bytes| {
+            let tx = Transaction::decode(tx_bytes.as_ref()).ok()?;
+            let priority = fee_priority(&tx, fee_params);
+            Some((priority, tx_bytes, Arc::new(tx)))
+        })
+        .collect();
+
+    // The sort is stable, so candidates with the same priority keep their relative order.
+    candidates.sort_by_key(|(priority, _, _)| Reverse(*priority));
+    candidates
+        .into_iter()
+        .map(|(_, tx_bytes, tx)| (tx_bytes, tx))
+        .collect()
+}
@@ -13,8 +13,10 @@ mod community_pool_ext;
 mod penumbra_host_chain;
 
 pub use crate::{
-    action_handler::AppActionHandler, app::StateWriteExt,
-    community_pool_ext::CommunityPoolStateReadExt, metrics::register_metrics,
+    action_handler::{AppActionHandler, CheckTxError},
+    app::StateWriteExt,
+    community_pool_ext::CommunityPoolStateReadExt,
+    metrics::register_metrics,
     penumbra_host_chain::PenumbraHost,
 };
 
@@ -22,7 +24,7 @@ use once_cell::sync::Lazy;
 
 /// Representation of the Penumbra application version. Notably, this is distinct
 /// from the crate version(s). This number should only ever be incremented.
-pub const APP_VERSION: u64 = 7;
+pub const APP_VERSION: u64 = 8;
 
 pub static SUBSTORE_PREFIXES: Lazy<Vec<String>> = Lazy::new(|| {
     vec![
@@ -68,6 +68,7 @@ impl AppParameters {
             distributions_params:
                 DistributionsParameters {
                     staking_issuance_per_block: _,
+                    liquidity_provider_issuance_per_block: _,
                 },
             fee_params:
                 FeeParameters {
@@ -88,6 +89,7 @@ impl AppParameters {
                     ibc_enabled: _,
                     inbound_ics20_transfers_enabled: _,
                     outbound_ics20_transfers_enabled: _,
+                    ics20_rate_limits: _,
                 },
             sct_params: SctParameters { epoch_duration },
             shielded_pool_params: ShieldedPoolParameters { fmd_meta_params: _ },
@@ -163,6 +165,7 @@ impl AppParameters {
             distributions_params:
                 DistributionsParameters {
                     staking_issuance_per_block: _,
+                    liquidity_provider_issuance_per_block: _,
                 },
             fee_params:
                 FeeParameters {
@@ -183,6 +186,7 @@ impl AppParameters {
                     ibc_enabled,
                     inbound_ics20_transfers_enabled,
                     outbound_ics20_transfers_enabled,
+                    ics20_rate_limits,
                 },
             sct_params: SctParameters { epoch_duration },
             shielded_pool_params: ShieldedPoolParameters { fmd_meta_params: _ },
@@ -250,6 +254,14 @@ impl AppParameters {
                     || *ibc_enabled,
                 "IBC must be enabled if either inbound or outbound ICS20 transfers are enabled",
             ),
+            (
+                ics20_rate_limits.iter().enumerate().all(|(i, limit)| {
+                    ics20_rate_limits[..i].iter().all(|other| {
+                        other.channel_id != limit.channel_id || other.asset_id() != limit.asset_id()
+                    })
+                }),
+                "each channel and asset may have at most one ICS20 rate limit",
+            ),
             (
                 *proposal_voting_blocks >= 1,
                 "proposal voting blocks must be at least 1",
@@ -2,8 +2,11 @@
 
 use {
     self::{
-        consensus::Consensus, events::EventIndexLayer, info::Info, mempool::Mempool,
-        snapshot::Snapshot,
+        consensus::Consensus,
+        events::EventIndexLayer,
+        info::Info,
+        mempool::Mempool,
+        snapshot::{Snapshot, SnapshotStore},
     },
     cnidarium::Storage,
     penumbra_tower_trace::trace::request_span,
@@ -21,8 +24,11 @@ pub mod snapshot;
 mod events;
 
 /// Returns a newly instantiated ABCI [`Server`], backed by the provided [`Storage`].
+///
+/// State sync snapshots are served from, and restored through, the provided [`SnapshotStore`].
 pub fn new(
     storage: Storage,
+    snapshot_store: SnapshotStore,
 ) -> Server<
     // These bounds ensure that the server can be bound to a TCP port, or a Unix socket.
     impl tower_service::Service<
@@ -60,7 +66,7 @@ pub fn new(
             Mempool::new(storage.clone(), queue).run()
         }));
     let info = Info::new(storage.clone());
-    let snapshot = Snapshot {};
+    let snapshot = Snapshot::new(storage.clone(), snapshot_store);
 
     tower_abci::v037::Server::builder()
         .consensus(consensus)
@@ -79,8 +85,9 @@ mod bounds_test {
     #[allow(dead_code, unreachable_code, unused_variables)]
     async fn servers_can_listen() {
         let storage: cnidarium::Storage = todo!();
+        let snapshot_store: super::SnapshotStore = todo!();
         let addr: std::net::SocketAddr = todo!();
-        let server = super::new(storage).listen_tcp(addr);
+        let server = super::new(storage, snapshot_store).listen_tcp(addr);
         drop(server);
     }
 }
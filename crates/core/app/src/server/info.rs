@@ -112,7 +112,7 @@ impl Info {
                 let snapshot = self
                     .storage
                     .snapshot(height)
-                    .ok_or_else(|| anyhow::anyhow!("no snapshot of height {height}"))?;
+                    .with_context(|| format!("no snapshot of height {height}"))?;
 
                 Ok((snapshot, height))
             }
@@ -120,7 +120,7 @@ impl Info {
                 let snapshot = self
                     .storage
                     .snapshot(height)
-                    .ok_or_else(|| anyhow::anyhow!("no snapshot of height {height}"))?;
+                    .with_context(|| format!("no snapshot of height {height}"))?;
 
                 Ok((snapshot, height))
             }
@@ -10,7 +10,7 @@ use tokio::sync::mpsc;
 use tower_actor::Message;
 use tracing::Instrument;
 
-use crate::{app::App, metrics};
+use crate::{app::App, metrics, CheckTxError};
 
 /// A mempool service that applies transaction checks against an isolated application fork.
 pub struct Mempool {
@@ -51,12 +51,15 @@ impl Mempool {
             }
             Err(e) => {
                 let elapsed = start.elapsed();
-                tracing::info!(?e, ?elapsed, "tx rejected");
-                metrics::counter!(metrics::MEMPOOL_CHECKTX_TOTAL, "kind" => kind_str, "code" => "1").increment(1);
+                let rejection = CheckTxError::from_error(&e);
+                let code = rejection.code();
+                tracing::info!(?e, ?elapsed, ?rejection, "tx rejected");
+                metrics::counter!(metrics::MEMPOOL_CHECKTX_TOTAL, "kind" => kind_str, "code" => code.to_string()).increment(1);
                 Ok(Response::CheckTx(CheckTxRsp {
-                    code: 1.into(),
+                    code: code.into(),
                     // Use the alternate format specifier to include the chain of error causes.
                     log: format!("{e:#}"),
+                    codespace: rejection.codespace().to_string(),
                     ..Default::default()
                 }))
             }
@@ -1,15 +1,192 @@
+//! State sync support, allowing new full nodes to bootstrap from a recent
+//! snapshot of the chain state rather than replaying from genesis.
+//!
+//! Snapshots are produced in the background by [`run_snapshot_worker`], which
+//! exports a [`cnidarium::Snapshot`] into hash-verified chunks every so many
+//! blocks, and served to peers by the [`Snapshot`] ABCI service. The same
+//! service restores a snapshot offered by CometBFT into an empty [`Storage`].
 use std::{
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
+use anyhow::{anyhow, ensure};
+use cnidarium::Storage;
 use futures::FutureExt;
-use tendermint::v0_37::abci::{SnapshotRequest, SnapshotResponse};
+use penumbra_ibc::component::HostInterface as _;
+use tendermint::{
+    abci::types,
+    v0_37::abci::{
+        request,
+        response::{self, ApplySnapshotChunkResult, OfferSnapshot},
+        SnapshotRequest, SnapshotResponse,
+    },
+};
+use tokio::sync::Mutex;
 use tower_abci::BoxError;
 
+use crate::PenumbraHost;
+
+mod store;
+
+pub use store::{SnapshotMetadata, SnapshotStore};
+
+/// The format identifier for snapshots produced by this version of the software.
+///
+/// This must be incremented whenever the chunk encoding changes.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// The maximum size of an individual snapshot chunk, in bytes.
+pub const SNAPSHOT_CHUNK_SIZE: usize = 10 * 1024 * 1024;
+
+/// An ABCI service that lists, serves and restores state sync snapshots.
 #[derive(Clone, Debug)]
-pub struct Snapshot {}
+pub struct Snapshot {
+    storage: Storage,
+    store: SnapshotStore,
+    /// The snapshot currently being restored, if any.
+    restore: Arc<Mutex<Option<SnapshotMetadata>>>,
+}
+
+impl Snapshot {
+    pub fn new(storage: Storage, store: SnapshotStore) -> Self {
+        Self {
+            storage,
+            store,
+            restore: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn list_snapshots(&self) -> anyhow::Result<response::ListSnapshots> {
+        let snapshots = self
+            .store
+            .list()?
+            .into_iter()
+            .map(|metadata| {
+                Ok(types::Snapshot {
+                    height: metadata.height.try_into()?,
+                    format: SNAPSHOT_FORMAT,
+                    chunks: metadata.chunks(),
+                    hash: metadata.hash().into(),
+                    metadata: metadata.encode()?.into(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(response::ListSnapshots { snapshots })
+    }
+
+    fn load_snapshot_chunk(
+        &self,
+        req: request::LoadSnapshotChunk,
+    ) -> anyhow::Result<response::LoadSnapshotChunk> {
+        ensure!(
+            req.format == SNAPSHOT_FORMAT,
+            "unsupported snapshot format {}",
+            req.format
+        );
+        let chunk = self.store.load_chunk(req.height.value(), req.chunk)?;
+        Ok(response::LoadSnapshotChunk {
+            chunk: chunk.into(),
+        })
+    }
+
+    async fn offer_snapshot(&self, req: request::OfferSnapshot) -> OfferSnapshot {
+        let request::OfferSnapshot { snapshot, app_hash } = req;
+
+        if snapshot.format != SNAPSHOT_FORMAT {
+            tracing::info!(format = snapshot.format, "rejecting snapshot format");
+            return OfferSnapshot::RejectFormat;
+        }
+
+        if self.storage.latest_version() != u64::MAX {
+            tracing::warn!("rejecting snapshot offer, local state is not empty");
+            return OfferSnapshot::Abort;
+        }
+
+        let metadata = match SnapshotMetadata::decode(&snapshot.metadata) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                tracing::info!(?e, "rejecting snapshot with malformed metadata");
+                return OfferSnapshot::Reject;
+            }
+        };
+
+        // The advertised metadata must be consistent with the snapshot itself, and with
+        // the light-client verified app hash, since that's what we check the restored
+        // state against once all the chunks are applied.
+        if metadata.height != snapshot.height.value()
+            || metadata.chunks() != snapshot.chunks
+            || metadata.hash() != snapshot.hash.as_ref()
+            || metadata.app_hash != hex::encode(app_hash.as_bytes())
+        {
+            tracing::info!(?metadata, "rejecting inconsistent snapshot");
+            return OfferSnapshot::Reject;
+        }
+
+        tracing::info!(
+            height = metadata.height,
+            chunks = metadata.chunks(),
+            "accepting snapshot"
+        );
+        *self.restore.lock().await = Some(metadata);
+        OfferSnapshot::Accept
+    }
+
+    async fn apply_snapshot_chunk(
+        &self,
+        req: request::ApplySnapshotChunk,
+    ) -> anyhow::Result<response::ApplySnapshotChunk> {
+        let request::ApplySnapshotChunk {
+            index,
+            chunk,
+            sender,
+        } = req;
+
+        let mut restore = self.restore.lock().await;
+        let metadata = restore
+            .as_ref()
+            .ok_or_else(|| anyhow!("received a snapshot chunk without an accepted snapshot"))?;
+
+        if !metadata.verify_chunk(index, &chunk) {
+            tracing::info!(index, %sender, "chunk failed verification, refetching");
+            return Ok(response::ApplySnapshotChunk {
+                result: ApplySnapshotChunkResult::Retry,
+                refetch_chunks: vec![index],
+                reject_senders: vec![sender],
+            });
+        }
+
+        let storage = self.storage.clone();
+        tokio::task::spawn_blocking(move || storage.import_chunk(&chunk)).await??;
+        tracing::debug!(index, "applied snapshot chunk");
+
+        if index + 1 == metadata.chunks() {
+            let restored = self.storage.finish_import()?;
+            let root_hash = restored.root_hash().await?;
+            ensure!(
+                hex::encode(root_hash.0) == metadata.app_hash,
+                "restored state has app hash {}, expected {}",
+                hex::encode(root_hash.0),
+                metadata.app_hash
+            );
+            tracing::info!(
+                height = metadata.height,
+                version = restored.version(),
+                "finished restoring snapshot"
+            );
+            *restore = None;
+        }
+
+        Ok(response::ApplySnapshotChunk {
+            result: ApplySnapshotChunkResult::Accept,
+            refetch_chunks: vec![],
+            reject_senders: vec![],
+        })
+    }
+}
 
 impl tower_service::Service<SnapshotRequest> for Snapshot {
     type Response = SnapshotResponse;
@@ -22,17 +199,112 @@ impl tower_service::Service<SnapshotRequest> for Snapshot {
     }
 
     fn call(&mut self, req: SnapshotRequest) -> Self::Future {
-        // No-op, we don't implement snapshot support
         use SnapshotRequest as Request;
         use SnapshotResponse as Response;
+        let this = self.clone();
         async move {
             Ok(match req {
-                Request::ListSnapshots => Response::ListSnapshots(Default::default()),
-                Request::OfferSnapshot(_) => Response::OfferSnapshot(Default::default()),
-                Request::LoadSnapshotChunk(_) => Response::LoadSnapshotChunk(Default::default()),
-                Request::ApplySnapshotChunk(_) => Response::ApplySnapshotChunk(Default::default()),
+                Request::ListSnapshots => {
+                    Response::ListSnapshots(this.list_snapshots().unwrap_or_else(|e| {
+                        tracing::error!(?e, "failed to list snapshots");
+                        Default::default()
+                    }))
+                }
+                Request::OfferSnapshot(req) => {
+                    Response::OfferSnapshot(this.offer_snapshot(req).await)
+                }
+                Request::LoadSnapshotChunk(req) => {
+                    Response::LoadSnapshotChunk(this.load_snapshot_chunk(req).unwrap_or_else(|e| {
+                        tracing::warn!(?e, "failed to load snapshot chunk");
+                        Default::default()
+                    }))
+                }
+                Request::ApplySnapshotChunk(req) => {
+                    Response::ApplySnapshotChunk(
+                        this.apply_snapshot_chunk(req).await.unwrap_or_else(|e| {
+                            // A failure to write a chunk, or a restored state that doesn't match
+                            // the app hash, leaves the local state unusable: abort the state sync.
+                            tracing::error!(
+                                ?e,
+                                "failed to apply snapshot chunk, aborting state sync"
+                            );
+                            response::ApplySnapshotChunk {
+                                result: ApplySnapshotChunkResult::Abort,
+                                refetch_chunks: vec![],
+                                reject_senders: vec![],
+                            }
+                        }),
+                    )
+                }
             })
         }
         .boxed()
     }
 }
+
+/// Periodically exports the latest committed state to `store`.
+///
+/// A snapshot is taken after every block whose height is a multiple of `interval`,
+/// and only the `keep_recent` most recent snapshots are retained on disk. Failures to
+/// take a snapshot are logged, and the worker carries on with the next one.
+pub async fn run_snapshot_worker(
+    storage: Storage,
+    store: SnapshotStore,
+    interval: u64,
+    keep_recent: usize,
+) -> anyhow::Result<()> {
+    ensure!(interval > 0, "snapshot interval must be non-zero");
+    tracing::info!(interval, keep_recent, "snapshot worker started");
+    let mut rx = storage.subscribe();
+
+    while rx.changed().await.is_ok() {
+        let snapshot = rx.borrow_and_update().clone();
+        let height = match PenumbraHost::get_block_height(snapshot.clone()).await {
+            Ok(height) => height,
+            // We're in pregenesis, there is nothing to snapshot yet.
+            Err(_) => continue,
+        };
+
+        if height == 0 || height % interval != 0 {
+            continue;
+        }
+
+        // Failing to take a snapshot shouldn't take down the node, nor stop it
+        // from taking the next ones.
+        match take_snapshot(snapshot, store.clone(), height, keep_recent).await {
+            Ok(metadata) => {
+                tracing::info!(height, chunks = metadata.chunks(), "snapshot complete")
+            }
+            Err(e) => tracing::error!(?e, height, "failed to take snapshot"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Exports `snapshot`, taken at `height`, to `store`, then prunes all but the
+/// `keep_recent` most recent snapshots.
+async fn take_snapshot(
+    snapshot: cnidarium::Snapshot,
+    store: SnapshotStore,
+    height: u64,
+    keep_recent: usize,
+) -> anyhow::Result<SnapshotMetadata> {
+    let app_hash = snapshot.root_hash().await?;
+    let version = snapshot.version();
+    let span = tracing::info_span!("snapshot", height, version);
+
+    // Exporting the state can take a while; do it off the async runtime,
+    // so that it doesn't hold up other tasks.
+    tokio::task::spawn_blocking(move || {
+        span.in_scope(|| {
+            tracing::info!("taking snapshot");
+            let mut writer = store.writer(height)?;
+            snapshot.export_chunks(SNAPSHOT_CHUNK_SIZE, |chunk| writer.write_chunk(&chunk))?;
+            let metadata = writer.finish(height, version, &app_hash.0)?;
+            store.prune(keep_recent)?;
+            anyhow::Ok(metadata)
+        })
+    })
+    .await?
+}
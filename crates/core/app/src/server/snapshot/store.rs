@@ -0,0 +1,191 @@
+//! On-disk storage for state sync snapshots.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The name of the file recording a snapshot's [`SnapshotMetadata`].
+const METADATA_FILE: &str = "metadata.json";
+
+/// Describes a state sync snapshot, independently of where its chunks are stored.
+///
+/// The JSON encoding of this type is what we advertise as the ABCI snapshot metadata,
+/// so that a restoring node can verify each chunk before applying it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotMetadata {
+    /// The block height at which the snapshot was taken.
+    pub height: u64,
+    /// The `cnidarium` version of the exported state.
+    pub version: u64,
+    /// The hex-encoded app hash of the exported state.
+    pub app_hash: String,
+    /// The hex-encoded SHA-256 hashes of each chunk, in order.
+    pub chunk_hashes: Vec<String>,
+}
+
+impl SnapshotMetadata {
+    /// Returns the number of chunks in the snapshot.
+    pub fn chunks(&self) -> u32 {
+        self.chunk_hashes.len() as u32
+    }
+
+    /// Returns a hash committing to the contents of every chunk of the snapshot.
+    pub fn hash(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        for chunk_hash in &self.chunk_hashes {
+            hasher.update(chunk_hash.as_bytes());
+        }
+        hasher.finalize().to_vec()
+    }
+
+    /// Checks that `chunk` is the expected content for the chunk at `index`.
+    pub fn verify_chunk(&self, index: u32, chunk: &[u8]) -> bool {
+        self.chunk_hashes
+            .get(index as usize)
+            .map(|expected| *expected == chunk_hash(chunk))
+            .unwrap_or(false)
+    }
+
+    /// Encodes the metadata into the bytes advertised over ABCI.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Decodes the metadata from the bytes advertised over ABCI.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).context("malformed snapshot metadata")
+    }
+}
+
+/// Returns the hex-encoded SHA-256 hash of a chunk.
+pub fn chunk_hash(chunk: &[u8]) -> String {
+    hex::encode(Sha256::digest(chunk))
+}
+
+/// A directory of state sync snapshots, one subdirectory per height.
+///
+/// Snapshots are written to a temporary directory and renamed into place once
+/// complete, so a snapshot that is listed is always fully written.
+#[derive(Clone, Debug)]
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Opens the snapshot store rooted at `root`, creating it if necessary.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root)
+            .with_context(|| format!("failed to create snapshot directory {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    fn snapshot_dir(&self, height: u64) -> PathBuf {
+        self.root.join(height.to_string())
+    }
+
+    fn chunk_path(dir: &Path, index: u32) -> PathBuf {
+        dir.join(format!("chunk-{index:06}"))
+    }
+
+    /// Returns the metadata of every complete snapshot in the store, ordered by height.
+    pub fn list(&self) -> Result<Vec<SnapshotMetadata>> {
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            // Skip in-progress snapshots, and anything that isn't ours.
+            let is_height = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.parse::<u64>().is_ok())
+                .unwrap_or(false);
+            if !is_height {
+                continue;
+            }
+            match fs::read(path.join(METADATA_FILE)) {
+                Ok(bytes) => snapshots.push(SnapshotMetadata::decode(&bytes)?),
+                Err(e) => tracing::warn!(?path, ?e, "skipping unreadable snapshot"),
+            }
+        }
+        snapshots.sort_by_key(|metadata| metadata.height);
+        Ok(snapshots)
+    }
+
+    /// Returns the metadata of the snapshot at `height`, if it exists.
+    pub fn metadata(&self, height: u64) -> Result<Option<SnapshotMetadata>> {
+        let path = self.snapshot_dir(height).join(METADATA_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(SnapshotMetadata::decode(&fs::read(path)?)?))
+    }
+
+    /// Loads the chunk at `index` of the snapshot at `height`.
+    pub fn load_chunk(&self, height: u64, index: u32) -> Result<Vec<u8>> {
+        let path = Self::chunk_path(&self.snapshot_dir(height), index);
+        fs::read(&path).with_context(|| format!("failed to read chunk {}", path.display()))
+    }
+
+    /// Starts writing a new snapshot at `height`.
+    pub fn writer(&self, height: u64) -> Result<SnapshotWriter> {
+        let tmp_dir = self.root.join(format!("{height}.tmp"));
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+        fs::create_dir_all(&tmp_dir)?;
+        Ok(SnapshotWriter {
+            tmp_dir,
+            final_dir: self.snapshot_dir(height),
+            chunk_hashes: Vec::new(),
+        })
+    }
+
+    /// Deletes all but the `keep_recent` most recent snapshots.
+    pub fn prune(&self, keep_recent: usize) -> Result<()> {
+        let snapshots = self.list()?;
+        let excess = snapshots.len().saturating_sub(keep_recent);
+        for metadata in snapshots.into_iter().take(excess) {
+            tracing::info!(height = metadata.height, "pruning snapshot");
+            fs::remove_dir_all(self.snapshot_dir(metadata.height))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the chunks of a single snapshot to a [`SnapshotStore`].
+pub struct SnapshotWriter {
+    tmp_dir: PathBuf,
+    final_dir: PathBuf,
+    chunk_hashes: Vec<String>,
+}
+
+impl SnapshotWriter {
+    /// Appends a chunk to the snapshot.
+    pub fn write_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        let index = self.chunk_hashes.len() as u32;
+        fs::write(SnapshotStore::chunk_path(&self.tmp_dir, index), chunk)?;
+        self.chunk_hashes.push(chunk_hash(chunk));
+        Ok(())
+    }
+
+    /// Writes the snapshot metadata and moves the snapshot into place.
+    pub fn finish(self, height: u64, version: u64, app_hash: &[u8]) -> Result<SnapshotMetadata> {
+        let metadata = SnapshotMetadata {
+            height,
+            version,
+            app_hash: hex::encode(app_hash),
+            chunk_hashes: self.chunk_hashes,
+        };
+        fs::write(self.tmp_dir.join(METADATA_FILE), metadata.encode()?)?;
+        if self.final_dir.exists() {
+            fs::remove_dir_all(&self.final_dir)?;
+        }
+        fs::rename(&self.tmp_dir, &self.final_dir)?;
+        Ok(metadata)
+    }
+}
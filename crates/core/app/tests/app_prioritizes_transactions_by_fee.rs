@@ -0,0 +1,148 @@
+use {
+    self::common::BuilderExt,
+    anyhow::{anyhow, Context},
+    cnidarium::TempStorage,
+    penumbra_app::{
+        genesis::{self, AppState},
+        server::consensus::Consensus,
+    },
+    penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID},
+    penumbra_fee::Fee,
+    penumbra_keys::test_keys,
+    penumbra_mock_client::MockClient,
+    penumbra_mock_consensus::TestNode,
+    penumbra_num::Amount,
+    penumbra_proto::DomainType,
+    penumbra_sct::component::tree::SctRead as _,
+    penumbra_shielded_pool::{Note, OutputPlan, SpendPlan},
+    penumbra_transaction::{
+        memo::MemoPlaintext, plan::MemoPlan, Transaction, TransactionParameters, TransactionPlan,
+    },
+    rand_core::OsRng,
+    std::ops::Deref,
+    tap::{Tap, TapFallible},
+    tracing::info,
+};
+
+mod common;
+
+/// Builds a transaction that sends `note` back to the test wallet, paying `fee` in the
+/// staking token.
+async fn self_send(client: &MockClient, note: Note, fee: u64) -> anyhow::Result<Transaction> {
+    let fee = Fee::from_staking_token_amount(Amount::from(fee));
+    let plan = TransactionPlan {
+        actions: vec![
+            SpendPlan::new(
+                &mut OsRng,
+                note.clone(),
+                client
+                    .position(note.commit())
+                    .ok_or_else(|| anyhow!("input note commitment was unknown to mock client"))?,
+            )
+            .into(),
+            OutputPlan::new(
+                &mut OsRng,
+                Value {
+                    amount: note.amount() - fee.amount(),
+                    asset_id: note.asset_id(),
+                },
+                test_keys::ADDRESS_0.deref().clone(),
+            )
+            .into(),
+        ],
+        memo: Some(MemoPlan::new(
+            &mut OsRng,
+            MemoPlaintext::blank_memo(test_keys::ADDRESS_0.deref().clone()),
+        )),
+        detection_data: None, // We'll set this automatically below
+        transaction_parameters: TransactionParameters {
+            chain_id: TestNode::<()>::CHAIN_ID.to_string(),
+            fee,
+            ..Default::default()
+        },
+    }
+    .with_populated_detection_data(OsRng, Default::default());
+
+    client.witness_auth_build(&plan).await
+}
+
+#[tokio::test]
+/// When more transactions are submitted than fit in a block, the proposer should include
+/// the ones paying the highest fee per unit of gas, regardless of the order they were
+/// received in.
+async fn app_prioritizes_transactions_by_fee() -> anyhow::Result<()> {
+    // Install a test logger, acquire some temporary storage, and start the test node.
+    let guard = common::set_tracing_subscriber();
+    let storage = TempStorage::new().await?;
+    let mut test_node = {
+        let app_state = AppState::Content(
+            genesis::Content::default().with_chain_id(TestNode::<()>::CHAIN_ID.to_string()),
+        );
+        let consensus = Consensus::new(storage.as_ref().clone());
+        TestNode::builder()
+            .single_validator()
+            .with_penumbra_auto_app_state(app_state)?
+            .init_chain(consensus)
+            .await
+            .tap_ok(|e| tracing::info!(hash = %e.last_app_hash_hex(), "finished init chain"))?
+    };
+
+    // Sync the mock client, using the test wallet's spend key, to the latest snapshot.
+    let client = MockClient::new(test_keys::SPEND_KEY.clone())
+        .with_sync_to_storage(&storage)
+        .await?
+        .tap(|c| info!(client.notes = %c.notes.len(), "mock client synced to test storage"));
+
+    // Build three transactions of similar size, each paying a different fee.
+    let mut notes = client
+        .notes
+        .values()
+        .filter(|n| n.asset_id() == *STAKING_TOKEN_ASSET_ID && n.amount() > 1_000_000u64.into())
+        .cloned();
+    let mut next_note = || notes.next().context("test wallet should have enough notes");
+    let low_fee = self_send(&client, next_note()?, 10).await?;
+    let mid_fee = self_send(&client, next_note()?, 1_000).await?;
+    let high_fee = self_send(&client, next_note()?, 100_000).await?;
+
+    // Submit the candidates in increasing fee order, with a payload limit that only
+    // leaves room for two of them.
+    let candidates: Vec<_> = [&low_fee, &mid_fee, &high_fee]
+        .into_iter()
+        .map(|tx| tx.encode_to_vec())
+        .collect();
+    let max_tx_bytes = candidates[1].len() + candidates[2].len() + 1;
+    let proposal = test_node
+        .prepare_proposal(
+            candidates.iter().cloned().map(Into::into).collect(),
+            max_tx_bytes.try_into()?,
+        )
+        .await?;
+
+    // The highest paying transactions were selected, highest fee first.
+    let proposed: Vec<Vec<u8>> = proposal.txs.into_iter().map(Into::into).collect();
+    assert_eq!(
+        proposed,
+        vec![candidates[2].clone(), candidates[1].clone()],
+        "proposal should contain the high and mid fee transactions, in that order"
+    );
+
+    // The proposed block executes cleanly.
+    let pre_tx_snapshot = storage.latest_snapshot();
+    test_node.block().with_data(proposed).execute().await?;
+    let post_tx_snapshot = storage.latest_snapshot();
+    for tx in [&high_fee, &mid_fee] {
+        for nf in tx.spent_nullifiers() {
+            assert!(pre_tx_snapshot.spend_info(nf).await?.is_none());
+            assert!(post_tx_snapshot.spend_info(nf).await?.is_some());
+        }
+    }
+    for nf in low_fee.spent_nullifiers() {
+        assert!(post_tx_snapshot.spend_info(nf).await?.is_none());
+    }
+
+    // Free our temporary storage.
+    drop(storage);
+    drop(guard);
+
+    Ok(())
+}
@@ -378,6 +378,32 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
                 )
             }) as for<'r> fn(&'r str) -> _,
         )
+        .add_asset(
+            // Note: this regex must be in sync with RedelegationToken::try_from
+            // and VALIDATOR_IDENTITY_BECH32_PREFIX in the penumbra-stake crate
+            // TODO: this doesn't restrict the length of the bech32 encoding
+            "^uredelegating_(?P<data>start_at_(?P<start>[0-9]+)_(?P<from>penumbravalid1[a-zA-HJ-NP-Z0-9]+)_to_(?P<to>penumbravalid1[a-zA-HJ-NP-Z0-9]+))$",
+            &[
+                "^redelegating_(?P<data>start_at_(?P<start>[0-9]+)_(?P<from>penumbravalid1[a-zA-HJ-NP-Z0-9]+)_to_(?P<to>penumbravalid1[a-zA-HJ-NP-Z0-9]+))$",
+                "^mredelegating_(?P<data>start_at_(?P<start>[0-9]+)_(?P<from>penumbravalid1[a-zA-HJ-NP-Z0-9]+)_to_(?P<to>penumbravalid1[a-zA-HJ-NP-Z0-9]+))$",
+            ],
+            (|data: &str| {
+                assert!(!data.is_empty());
+                denom_metadata::Inner::new(
+                    format!("uredelegating_{data}"),
+                    vec![
+                        denom_metadata::BareDenomUnit {
+                            exponent: 6,
+                            denom: format!("redelegating_{data}"),
+                        },
+                        denom_metadata::BareDenomUnit {
+                            exponent: 3,
+                            denom: format!("mredelegating_{data}"),
+                        },
+                    ],
+                )
+            }) as for<'r> fn(&'r str) -> _,
+        )
         .add_asset(
             // Note: this regex must be in sync with LpNft::try_from
             // and the bech32 prefix for LP IDs defined in the proto crate.
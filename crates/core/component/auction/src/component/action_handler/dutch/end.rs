@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
 use penumbra_proto::StateWriteProto;
+use penumbra_shielded_pool::component::SupplyWrite;
 use tracing::instrument;
 
 use crate::auction::dutch::ActionDutchAuctionEnd;
@@ -50,6 +51,9 @@ impl ActionHandler for ActionDutchAuctionEnd {
             auction_state,
         ));
 
+        // The opened auction NFT is exchanged for a closed auction NFT.
+        state.record_supply_change(self.balance()).await?;
+
         Ok(())
     }
 }
@@ -1,12 +1,15 @@
 use crate::auction::dutch::actions::schedule::MAX_AUCTION_AMOUNT_RESERVES;
 use crate::auction::dutch::DutchAuctionDescription;
+use crate::auction::AuctionNft;
 use crate::component::AuctionStoreRead;
 use anyhow::{ensure, Result};
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
+use penumbra_asset::Value;
 use penumbra_num::Amount;
 use penumbra_sct::component::clock::EpochRead;
+use penumbra_shielded_pool::component::SupplyWrite;
 
 use crate::auction::dutch::ActionDutchAuctionSchedule;
 use crate::component::DutchAuctionManager;
@@ -127,6 +130,14 @@ impl ActionHandler for ActionDutchAuctionSchedule {
         );
 
         state.schedule_auction(schedule.description.clone()).await?;
+
+        // The input moves into the auction, but the opened auction NFT is new.
+        state
+            .increase_token_supply(Value {
+                amount: 1u128.into(),
+                asset_id: AuctionNft::new(id, 0u64).asset_id(),
+            })
+            .await?;
         Ok(())
     }
 }
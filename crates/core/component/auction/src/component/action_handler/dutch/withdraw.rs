@@ -1,4 +1,5 @@
 use crate::auction::dutch::ActionDutchAuctionWithdraw;
+use crate::auction::AuctionNft;
 use crate::component::AuctionStoreRead;
 use crate::component::DutchAuctionManager;
 use anyhow::{bail, ensure, Context, Result};
@@ -7,6 +8,8 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
 use decaf377::Fr;
+use penumbra_asset::Value;
+use penumbra_shielded_pool::component::SupplyWrite;
 
 #[async_trait]
 impl ActionHandler for ActionDutchAuctionWithdraw {
@@ -59,6 +62,21 @@ impl ActionHandler for ActionDutchAuctionWithdraw {
             "the reported reserve commitment is incorrect"
         );
 
+        // The reserves leave the auction, and the auction NFT is replaced
+        // by one for the next sequence number.
+        state
+            .decrease_token_supply(Value {
+                amount: 1u128.into(),
+                asset_id: AuctionNft::new(auction_id, self.seq - 1).asset_id(),
+            })
+            .await?;
+        state
+            .increase_token_supply(Value {
+                amount: 1u128.into(),
+                asset_id: AuctionNft::new(auction_id, self.seq).asset_id(),
+            })
+            .await?;
+
         Ok(())
     }
 }
@@ -77,6 +77,8 @@ pub(crate) trait DutchAuctionManager: StateWrite {
             .context("failed to schedule auction")?;
         // Set the triggger
         self.set_trigger_for_dutch_id(auction_id, next_trigger);
+        // Index the auction by its trading pair
+        self.index_dutch_auction_by_pair(&dutch_auction.description);
         // Write position to state
         self.write_dutch_auction_state(dutch_auction);
         // Emit an event
@@ -359,6 +361,8 @@ pub(crate) trait DutchAuctionManager: StateWrite {
         if let Some(height) = next_trigger {
             self.unset_trigger_for_dutch_id(auction_id, height.into())
         }
+        // The auction is no longer live, so we remove it from the pair index.
+        self.unindex_dutch_auction_by_pair(&auction_to_close.description);
         let closed_auction = DutchAuction {
             description: auction_to_close.description,
             state: DutchAuctionState {
@@ -416,6 +420,8 @@ pub(crate) trait DutchAuctionManager: StateWrite {
         auction.state.next_trigger = None;
         auction.state.input_reserves = Amount::zero();
         auction.state.output_reserves = Amount::zero();
+        // Auctions are normally unindexed when they close, but this is a no-op in that case.
+        self.unindex_dutch_auction_by_pair(&auction.description);
         self.record_proto(event::dutch_auction_withdrawn(
             auction.description.id(),
             auction.state.clone(),
@@ -468,6 +474,27 @@ pub(crate) trait DutchAuctionData: StateRead {
             })
             .boxed()
     }
+
+    /// Stream the ids of the open Dutch auctions selling `pair.start` for `pair.end`,
+    /// or of all open Dutch auctions if no pair is specified.
+    ///
+    /// Auctions are removed from the index once they are closed or withdrawn.
+    async fn stream_dutch_ids_by_pair(
+        &self,
+        pair: Option<DirectedTradingPair>,
+    ) -> Pin<Box<dyn futures::Stream<Item = Result<AuctionId>> + Send + 'static>> {
+        use penumbra_proto::StateReadProto;
+        let prefix_key = match pair {
+            Some(pair) => state_key::dutch::by_pair::by_pair(&pair),
+            None => state_key::dutch::by_pair::prefix().to_string(),
+        }
+        .as_bytes()
+        .to_vec();
+
+        self.nonverifiable_prefix::<AuctionId>(&prefix_key)
+            .map(|res| res.map(|(_, auction_id)| auction_id))
+            .boxed()
+    }
 }
 
 impl<T: StateRead + ?Sized> DutchAuctionData for T {}
@@ -590,6 +617,28 @@ trait Inner: StateWrite {
         self.nonverifiable_put(trigger_path, auction_id);
     }
 
+    /// Index a Dutch auction by the directed trading pair it sells along.
+    #[instrument(skip(self))]
+    fn index_dutch_auction_by_pair(&mut self, description: &DutchAuctionDescription) {
+        let pair = DirectedTradingPair::new(description.input.asset_id, description.output_id);
+        let index_path = state_key::dutch::by_pair::auction_for_pair(&pair, description.id());
+        tracing::trace!(state_key = ?index_path, "indexing dutch auction by pair");
+        let index_path = index_path.as_bytes().to_vec();
+
+        self.nonverifiable_put(index_path, description.id());
+    }
+
+    /// Remove a Dutch auction from the index of auctions by trading pair.
+    #[instrument(skip(self))]
+    fn unindex_dutch_auction_by_pair(&mut self, description: &DutchAuctionDescription) {
+        let pair = DirectedTradingPair::new(description.input.asset_id, description.output_id);
+        let index_path = state_key::dutch::by_pair::auction_for_pair(&pair, description.id());
+        tracing::trace!(state_key = ?index_path, "unindexing dutch auction by pair");
+        let index_path = index_path.as_bytes().to_vec();
+
+        self.nonverifiable_delete(index_path);
+    }
+
     /// Delete a trigger for a Dutch auction.
     #[instrument(skip(self))]
     fn unset_trigger_for_dutch_id(&mut self, auction_id: AuctionId, trigger_height: u64) {
@@ -6,6 +6,9 @@ pub mod metrics;
 pub mod rpc;
 mod trigger_data;
 
+#[cfg(test)]
+mod tests;
+
 pub use auction::Auction;
 pub(crate) use auction::AuctionCircuitBreaker;
 pub use auction::{StateReadExt, StateWriteExt};
@@ -1,27 +1,26 @@
-#![allow(unused)] // TODO: remove this when filling in the RPCs
-
-use penumbra_dex::{component::PositionRead, lp::position};
+use penumbra_dex::{component::PositionRead, lp::position, DirectedTradingPair};
 use penumbra_proto::{
     core::component::auction::v1 as pb,
     core::component::auction::v1::{
         query_service_server::QueryService, AuctionStateByIdRequest, AuctionStateByIdResponse,
-        AuctionStateByIdsRequest, AuctionStateByIdsResponse, DutchAuctionState,
+        AuctionStateByIdsRequest, AuctionStateByIdsResponse, DutchAuctionsRequest,
+        DutchAuctionsResponse,
     },
+    core::component::dex::v1 as pb_dex,
     DomainType,
 };
 
 use async_stream::try_stream;
 use futures::{StreamExt, TryStreamExt};
-use penumbra_proto::Message;
 use prost::Name;
 use std::pin::Pin;
 use tonic::Status;
 use tracing::instrument;
 
-use crate::auction::dutch::DutchAuction;
+use crate::auction::{dutch::DutchAuction, AuctionId};
 
-use super::{action_handler::dutch, AuctionStoreRead};
-use cnidarium::Storage;
+use super::{dutch_auction::DutchAuctionData, AuctionStoreRead};
+use cnidarium::{StateRead, Storage};
 
 pub struct Server {
     storage: Storage,
@@ -65,16 +64,9 @@ impl QueryService for Server {
             return Err(tonic::Status::unimplemented("unrecognized auction type"));
         };
 
-        let positions = match maybe_lp {
-            Some(id) => state
-                .position_by_id(&id)
-                .await
-                .map_err(|_| tonic::Status::internal("error fetching position state"))?
-                .into_iter()
-                .map(Into::into)
-                .collect(),
-            None => Vec::new(),
-        };
+        let positions = auction_positions(&state, maybe_lp)
+            .await
+            .map_err(|_| tonic::Status::internal("error fetching position state"))?;
 
         Ok(tonic::Response::new(AuctionStateByIdResponse {
             auction: Some(raw_auction),
@@ -91,6 +83,112 @@ impl QueryService for Server {
         &self,
         request: tonic::Request<AuctionStateByIdsRequest>,
     ) -> Result<tonic::Response<Self::AuctionStateByIdsStream>, Status> {
-        todo!()
+        let state = self.storage.latest_snapshot();
+
+        let auction_ids: Vec<AuctionId> = request
+            .into_inner()
+            .id
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e: anyhow::Error| {
+                tonic::Status::invalid_argument(format!("error converting auction id: {e}"))
+            })?;
+
+        let s = try_stream! {
+            for auction_id in auction_ids {
+                // Only known ids are returned, so unknown ones are skipped.
+                let Some(auction) = state.get_dutch_auction_by_id(auction_id).await? else {
+                    continue;
+                };
+                let positions = auction_positions(&state, auction.state.current_position).await?;
+
+                yield AuctionStateByIdsResponse {
+                    id: Some(auction_id.into()),
+                    auction: Some(auction.state.into()),
+                    positions,
+                };
+            }
+        };
+
+        Ok(tonic::Response::new(
+            s.map_err(|e: anyhow::Error| {
+                tonic::Status::unavailable(format!("error getting auction state from storage: {e}"))
+            })
+            .boxed(),
+        ))
+    }
+
+    type DutchAuctionsStream =
+        Pin<Box<dyn futures::Stream<Item = Result<DutchAuctionsResponse, tonic::Status>> + Send>>;
+
+    #[instrument(skip(self, request))]
+    async fn dutch_auctions(
+        &self,
+        request: tonic::Request<DutchAuctionsRequest>,
+    ) -> Result<tonic::Response<Self::DutchAuctionsStream>, Status> {
+        let state = self.storage.latest_snapshot();
+        let DutchAuctionsRequest {
+            pair,
+            start_height,
+            end_height,
+        } = request.into_inner();
+
+        let pair: Option<DirectedTradingPair> = pair
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid trading pair"))?;
+
+        let s = try_stream! {
+            let mut auction_ids = state.stream_dutch_ids_by_pair(pair).await;
+            while let Some(auction_id) = auction_ids.next().await {
+                let auction_id = auction_id?;
+                let Some(auction) = state.get_dutch_auction_by_id(auction_id).await? else {
+                    continue;
+                };
+
+                // Skip auctions that don't overlap the requested height range.
+                if start_height != 0 && auction.description.end_height < start_height {
+                    continue;
+                }
+                if end_height != 0 && auction.description.start_height > end_height {
+                    continue;
+                }
+
+                let positions = auction_positions(&state, auction.state.current_position).await?;
+
+                yield DutchAuctionsResponse {
+                    id: Some(auction_id.into()),
+                    auction: Some(auction.into()),
+                    positions,
+                };
+            }
+        };
+
+        Ok(tonic::Response::new(
+            s.map_err(|e: anyhow::Error| {
+                tonic::Status::unavailable(format!(
+                    "error getting dutch auctions from storage: {e}"
+                ))
+            })
+            .boxed(),
+        ))
     }
 }
+
+/// Fetch the state of the DEX position currently controlled by an auction, if any.
+async fn auction_positions<S: StateRead + ?Sized>(
+    state: &S,
+    current_position: Option<position::Id>,
+) -> anyhow::Result<Vec<pb_dex::Position>> {
+    let Some(id) = current_position else {
+        return Ok(Vec::new());
+    };
+
+    Ok(state
+        .position_by_id(&id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
@@ -0,0 +1,167 @@
+use cnidarium::{StateDelta, TempStorage};
+use futures::{StreamExt, TryStreamExt};
+use penumbra_asset::{asset, Value};
+use penumbra_dex::DirectedTradingPair;
+use penumbra_proto::core::component::auction::v1::{
+    query_service_server::QueryService, DutchAuctionsRequest,
+};
+use penumbra_sct::component::clock::EpochManager as _;
+
+use crate::auction::{dutch::DutchAuctionDescription, AuctionId};
+use crate::component::dutch_auction::DutchAuctionData;
+use crate::component::{rpc::Server, DutchAuctionManager};
+
+fn gm_for_gn(nonce: u8) -> DutchAuctionDescription {
+    let gm = asset::Cache::with_known_assets().get_unit("gm").unwrap();
+    let gn = asset::Cache::with_known_assets().get_unit("gn").unwrap();
+
+    DutchAuctionDescription {
+        input: Value {
+            amount: 100_000u64.into(),
+            asset_id: gm.id(),
+        },
+        output_id: gn.id(),
+        max_output: 200_000u64.into(),
+        min_output: 50_000u64.into(),
+        start_height: 10,
+        end_height: 20,
+        step_count: 5,
+        nonce: [nonce; 32],
+    }
+}
+
+fn pair_of(description: &DutchAuctionDescription) -> DirectedTradingPair {
+    DirectedTradingPair::new(description.input.asset_id, description.output_id)
+}
+
+async fn ids_by_pair(
+    state: &StateDelta<cnidarium::Snapshot>,
+    pair: Option<DirectedTradingPair>,
+) -> Vec<AuctionId> {
+    state
+        .stream_dutch_ids_by_pair(pair)
+        .await
+        .try_collect()
+        .await
+        .expect("can read the pair index")
+}
+
+async fn listed_by_rpc(storage: &TempStorage, request: DutchAuctionsRequest) -> Vec<AuctionId> {
+    let server = Server::new(storage.as_ref().clone());
+    server
+        .dutch_auctions(tonic::Request::new(request))
+        .await
+        .expect("can query dutch auctions")
+        .into_inner()
+        .map(|response| {
+            response
+                .expect("no errors streaming auctions")
+                .id
+                .expect("auction id is set")
+                .try_into()
+                .expect("auction id is valid")
+        })
+        .collect()
+        .await
+}
+
+#[tokio::test]
+/// Checks that a Dutch auction is indexed by its directed pair when it's scheduled,
+/// and unindexed when it's ended.
+async fn dutch_auction_is_unindexed_on_close() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    state.put_block_height(0);
+
+    let description = gm_for_gn(1);
+    let pair = pair_of(&description);
+    let auction_id = description.id();
+
+    state.schedule_auction(description).await?;
+    assert_eq!(ids_by_pair(&state, Some(pair)).await, vec![auction_id]);
+    assert_eq!(ids_by_pair(&state, None).await, vec![auction_id]);
+    assert!(ids_by_pair(&state, Some(pair.flip())).await.is_empty());
+
+    state.end_auction_by_id(auction_id).await?;
+    assert!(ids_by_pair(&state, Some(pair)).await.is_empty());
+    assert!(ids_by_pair(&state, None).await.is_empty());
+
+    // Withdrawing a closed auction leaves the index untouched.
+    state.withdraw_auction_by_id(auction_id).await?;
+    assert!(ids_by_pair(&state, None).await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+/// Checks that a Dutch auction withdrawn without being ended first is unindexed.
+async fn dutch_auction_is_unindexed_on_withdraw() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    state.put_block_height(0);
+
+    let description = gm_for_gn(2);
+    let pair = pair_of(&description);
+    let auction_id = description.id();
+
+    state.schedule_auction(description).await?;
+    assert_eq!(ids_by_pair(&state, Some(pair)).await, vec![auction_id]);
+
+    state.withdraw_auction_by_id(auction_id).await?;
+    assert!(ids_by_pair(&state, Some(pair)).await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+/// Checks that the `DutchAuctions` query only returns auctions while they're active,
+/// filtered by pair and height range.
+async fn dutch_auctions_query_returns_active_auctions() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    state.put_block_height(0);
+
+    let description = gm_for_gn(3);
+    let pair = pair_of(&description);
+    let auction_id = description.id();
+    state.schedule_auction(description).await?;
+    storage.commit(state).await?;
+
+    let all = DutchAuctionsRequest::default();
+    let by_pair = DutchAuctionsRequest {
+        pair: Some(pair.into()),
+        ..Default::default()
+    };
+    let by_flipped_pair = DutchAuctionsRequest {
+        pair: Some(pair.flip().into()),
+        ..Default::default()
+    };
+    let overlapping = DutchAuctionsRequest {
+        start_height: 15,
+        end_height: 30,
+        ..Default::default()
+    };
+    let after_end = DutchAuctionsRequest {
+        start_height: 21,
+        ..Default::default()
+    };
+    let before_start = DutchAuctionsRequest {
+        end_height: 9,
+        ..Default::default()
+    };
+
+    assert_eq!(listed_by_rpc(&storage, all.clone()).await, vec![auction_id]);
+    assert_eq!(listed_by_rpc(&storage, by_pair).await, vec![auction_id]);
+    assert_eq!(listed_by_rpc(&storage, overlapping).await, vec![auction_id]);
+    assert!(listed_by_rpc(&storage, by_flipped_pair).await.is_empty());
+    assert!(listed_by_rpc(&storage, after_end).await.is_empty());
+    assert!(listed_by_rpc(&storage, before_start).await.is_empty());
+
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    state.end_auction_by_id(auction_id).await?;
+    storage.commit(state).await?;
+
+    assert!(listed_by_rpc(&storage, all).await.is_empty());
+
+    Ok(())
+}
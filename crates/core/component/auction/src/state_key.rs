@@ -50,6 +50,23 @@ pub mod dutch {
             format!("{}{auction_id}", by_height(trigger_height))
         }
     }
+
+    pub mod by_pair {
+        use crate::auction::id::AuctionId;
+        use penumbra_dex::DirectedTradingPair;
+
+        pub fn prefix() -> &'static str {
+            "auction/dutch/by_pair/"
+        }
+
+        pub fn by_pair(pair: &DirectedTradingPair) -> String {
+            format!("{}{}/{}/", prefix(), pair.start, pair.end)
+        }
+
+        pub fn auction_for_pair(pair: &DirectedTradingPair, auction_id: AuctionId) -> String {
+            format!("{}{auction_id}", by_pair(pair))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +39,36 @@ pub struct BatchSwapOutputData {
     pub trading_pair: TradingPair,
     /// The position prefix where this batch swap occurred. The commitment index must be 0.
     pub sct_position_prefix: Position,
+    /// The highest limit price of the 1=>2 swaps that were filled, in asset 2 per unit of asset 1.
+    ///
+    /// 1=>2 swaps with a higher limit price were refunded.
+    pub limit_price_1_for_2: U128x128,
+    /// The highest limit price of the 2=>1 swaps that were filled, in asset 1 per unit of asset 2.
+    ///
+    /// 2=>1 swaps with a higher limit price were refunded.
+    pub limit_price_2_for_1: U128x128,
+}
+
+/// Computes the limit price of a swap with inputs `(delta_1_i, delta_2_i)` and
+/// minimum output `min_output`, i.e., the minimum output per unit of input.
+///
+/// Swaps without a minimum output have a limit price of zero. Swaps with a
+/// minimum output only have one nonzero input; a zero input is treated as one,
+/// so that the limit price is always well defined.
+pub fn limit_price((delta_1_i, delta_2_i): (Amount, Amount), min_output: Amount) -> U128x128 {
+    let input = if delta_2_i == Amount::zero() {
+        delta_1_i
+    } else {
+        delta_2_i
+    };
+    let input = if input == Amount::zero() {
+        Amount::from(1u64)
+    } else {
+        input
+    };
+
+    (U128x128::from(min_output) / U128x128::from(input))
+        .expect("dividing by an amount of at least one cannot overflow")
 }
 
 impl BatchSwapOutputData {
@@ -81,6 +111,36 @@ impl BatchSwapOutputData {
                 .expect("rounded amount is integral"),
         )
     }
+
+    /// Returns whether a swap with inputs `(delta_1_i, delta_2_i)` and minimum
+    /// output `min_output` was filled by the batch.
+    ///
+    /// Swaps without a minimum output are always filled.
+    pub fn is_filled(&self, (delta_1_i, delta_2_i): (Amount, Amount), min_output: Amount) -> bool {
+        let cutoff = if delta_2_i == Amount::zero() {
+            self.limit_price_1_for_2
+        } else {
+            self.limit_price_2_for_1
+        };
+        limit_price((delta_1_i, delta_2_i), min_output) <= cutoff
+    }
+
+    /// Given a user's inputs `(delta_1_i, delta_2_i)` and minimum output
+    /// `min_output`, compute the outputs of their swap `(lambda_1_i, lambda_2_i)`.
+    ///
+    /// This is their pro rata share of the batch output if the swap was filled,
+    /// or a refund of their inputs if it wasn't.
+    pub fn swap_outputs(
+        &self,
+        (delta_1_i, delta_2_i): (Amount, Amount),
+        min_output: Amount,
+    ) -> (Amount, Amount) {
+        if self.is_filled((delta_1_i, delta_2_i), min_output) {
+            self.pro_rata_outputs((delta_1_i, delta_2_i))
+        } else {
+            (delta_1_i, delta_2_i)
+        }
+    }
 }
 
 impl ToConstraintField<Fq> for BatchSwapOutputData {
@@ -132,6 +192,16 @@ impl ToConstraintField<Fq> for BatchSwapOutputData {
                 .to_field_elements()
                 .expect("Position types are Bls12-377 field members"),
         );
+        public_inputs.extend(
+            self.limit_price_1_for_2
+                .to_field_elements()
+                .expect("U128x128 types are Bls12-377 field members"),
+        );
+        public_inputs.extend(
+            self.limit_price_2_for_1
+                .to_field_elements()
+                .expect("U128x128 types are Bls12-377 field members"),
+        );
         Some(public_inputs)
     }
 }
@@ -146,6 +216,8 @@ pub struct BatchSwapOutputDataVar {
     pub trading_pair: TradingPairVar,
     pub epoch: FqVar,
     pub block_within_epoch: FqVar,
+    pub limit_price_1_for_2: U128x128Var,
+    pub limit_price_2_for_1: U128x128Var,
 }
 
 impl AllocVar<BatchSwapOutputData, Fq> for BatchSwapOutputDataVar {
@@ -186,6 +258,10 @@ impl AllocVar<BatchSwapOutputData, Fq> for BatchSwapOutputDataVar {
             mode,
         )?;
         bit_constrain(block_within_epoch.clone(), 16)?;
+        let limit_price_1_for_2 =
+            U128x128Var::new_variable(cs.clone(), || Ok(output_data.limit_price_1_for_2), mode)?;
+        let limit_price_2_for_1 =
+            U128x128Var::new_variable(cs, || Ok(output_data.limit_price_2_for_1), mode)?;
 
         Ok(Self {
             delta_1,
@@ -197,6 +273,8 @@ impl AllocVar<BatchSwapOutputData, Fq> for BatchSwapOutputDataVar {
             trading_pair,
             epoch,
             block_within_epoch,
+            limit_price_1_for_2,
+            limit_price_2_for_1,
         })
     }
 }
@@ -218,6 +296,8 @@ impl From<BatchSwapOutputData> for pb::BatchSwapOutputData {
             height: s.height,
             trading_pair: Some(s.trading_pair.into()),
             sct_position_prefix: s.sct_position_prefix.into(),
+            limit_price_1_for_2: limit_price_to_bytes(s.limit_price_1_for_2),
+            limit_price_2_for_1: limit_price_to_bytes(s.limit_price_2_for_1),
             // Deprecated fields we explicitly fill with defaults.
             // We could instead use a `..Default::default()` here, but that would silently
             // work if we were to add fields to the domain type.
@@ -226,6 +306,26 @@ impl From<BatchSwapOutputData> for pb::BatchSwapOutputData {
     }
 }
 
+/// Encodes a limit price, using empty bytes for zero.
+fn limit_price_to_bytes(price: U128x128) -> Vec<u8> {
+    if price == U128x128::default() {
+        Vec::new()
+    } else {
+        price.to_bytes().to_vec()
+    }
+}
+
+/// Decodes a limit price, treating empty bytes as zero.
+fn limit_price_from_bytes(bytes: &[u8]) -> Result<U128x128> {
+    if bytes.is_empty() {
+        return Ok(U128x128::default());
+    }
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("limit price must be 32 bytes"))?;
+    Ok(U128x128::from_bytes(bytes))
+}
+
 impl BatchSwapOutputDataVar {
     pub fn pro_rata_outputs(
         &self,
@@ -274,6 +374,52 @@ impl BatchSwapOutputDataVar {
 
         Ok((lambda_1_i_rounded.into(), lambda_2_i_rounded.into()))
     }
+
+    /// Computes the outputs of a swap with inputs `(delta_1_i, delta_2_i)` and
+    /// minimum output `min_output`: its pro rata share of the batch output if it
+    /// was filled, or a refund of its inputs otherwise.
+    ///
+    /// This mirrors [`BatchSwapOutputData::swap_outputs`].
+    pub fn swap_outputs(
+        &self,
+        delta_1_i: AmountVar,
+        delta_2_i: AmountVar,
+        min_output: AmountVar,
+        cs: ConstraintSystemRef<Fq>,
+    ) -> Result<(AmountVar, AmountVar), SynthesisError> {
+        let zero = U128x128Var::zero();
+        let one = U128x128Var::new_constant(cs.clone(), U128x128::from(1u64))?;
+
+        let delta_1_i_fixpoint = U128x128Var::from_amount_var(delta_1_i.clone())?;
+        let delta_2_i_fixpoint = U128x128Var::from_amount_var(delta_2_i.clone())?;
+        let min_output_fixpoint = U128x128Var::from_amount_var(min_output)?;
+
+        // The swap's input is delta_2_i if nonzero, and delta_1_i otherwise,
+        // with a zero input treated as one.
+        let delta_2_i_is_zero = delta_2_i_fixpoint.is_eq(&zero)?;
+        let input = U128x128Var::conditionally_select(
+            &delta_2_i_is_zero,
+            &delta_1_i_fixpoint,
+            &delta_2_i_fixpoint,
+        )?;
+        let input_is_zero = input.is_eq(&zero)?;
+        let divisor = U128x128Var::conditionally_select(&input_is_zero, &one, &input)?;
+        let limit_price = min_output_fixpoint.checked_div(&divisor, cs.clone())?;
+
+        let cutoff = U128x128Var::conditionally_select(
+            &delta_2_i_is_zero,
+            &self.limit_price_1_for_2,
+            &self.limit_price_2_for_1,
+        )?;
+        let is_filled = limit_price.is_gt(&cutoff)?.not();
+
+        let (lambda_1_i, lambda_2_i) =
+            self.pro_rata_outputs(delta_1_i.clone(), delta_2_i.clone(), cs)?;
+        let lambda_1_i = AmountVar::conditionally_select(&is_filled, &lambda_1_i, &delta_1_i)?;
+        let lambda_2_i = AmountVar::conditionally_select(&is_filled, &lambda_2_i, &delta_2_i)?;
+
+        Ok((lambda_1_i, lambda_2_i))
+    }
 }
 
 impl From<BatchSwapOutputData> for pb::BatchSwapOutputDataResponse {
@@ -326,6 +472,8 @@ impl TryFrom<pb::BatchSwapOutputData> for BatchSwapOutputData {
                 .ok_or_else(|| anyhow!("Missing trading_pair"))?
                 .try_into()?,
             sct_position_prefix,
+            limit_price_1_for_2: limit_price_from_bytes(&s.limit_price_1_for_2)?,
+            limit_price_2_for_1: limit_price_from_bytes(&s.limit_price_2_for_1)?,
         })
     }
 }
@@ -343,7 +491,7 @@ impl TryFrom<pb::BatchSwapOutputDataResponse> for BatchSwapOutputData {
 #[cfg(test)]
 mod tests {
     use ark_groth16::{r1cs_to_qap::LibsnarkReduction, Groth16};
-    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
     use ark_snark::SNARK;
     use decaf377::Bls12_377;
     use penumbra_asset::asset;
@@ -443,6 +591,8 @@ mod tests {
                     height: 0,
                     trading_pair,
                     sct_position_prefix: 0u64.into(),
+                    limit_price_1_for_2: U128x128::default(),
+                    limit_price_2_for_1: U128x128::default(),
                 },
             }
         }
@@ -464,6 +614,8 @@ mod tests {
             height: 0u64,
             trading_pair,
             sct_position_prefix: 0u64.into(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         };
 
         // Now suppose our user's contribution is:
@@ -496,4 +648,76 @@ mod tests {
 
         assert!(proof_result);
     }
+
+    #[test]
+    fn swaps_above_the_limit_price_are_refunded() {
+        let gm = asset::Cache::with_known_assets().get_unit("gm").unwrap();
+        let gn = asset::Cache::with_known_assets().get_unit("gn").unwrap();
+        let trading_pair = TradingPair::new(gm.id(), gn.id());
+        // 1=>2 swaps with a limit price up to 2 were filled, 2=>1 swaps with a
+        // minimum output were all refunded.
+        let bsod = BatchSwapOutputData {
+            delta_1: Amount::from(100u64),
+            delta_2: Amount::from(0u64),
+            lambda_1: Amount::from(0u64),
+            lambda_2: Amount::from(250u64),
+            unfilled_1: Amount::from(0u64),
+            unfilled_2: Amount::from(0u64),
+            height: 0u64,
+            trading_pair,
+            sct_position_prefix: 0u64.into(),
+            limit_price_1_for_2: U128x128::from(2u64),
+            limit_price_2_for_1: U128x128::default(),
+        };
+
+        let input = (Amount::from(40u64), Amount::from(0u64));
+        assert_eq!(
+            bsod.swap_outputs(input, Amount::zero()),
+            (Amount::from(0u64), Amount::from(100u64))
+        );
+        assert_eq!(
+            bsod.swap_outputs(input, Amount::from(80u64)),
+            (Amount::from(0u64), Amount::from(100u64))
+        );
+        assert_eq!(bsod.swap_outputs(input, Amount::from(81u64)), input);
+
+        let input = (Amount::from(0u64), Amount::from(40u64));
+        assert_eq!(
+            bsod.swap_outputs(input, Amount::zero()),
+            (0u64.into(), 0u64.into())
+        );
+        assert_eq!(bsod.swap_outputs(input, Amount::from(1u64)), input);
+
+        // The in-circuit computation agrees with the out-of-circuit one.
+        for (delta_1_i, delta_2_i, min_output) in [
+            (40u64, 0u64, 0u64),
+            (40, 0, 80),
+            (40, 0, 81),
+            (0, 40, 1),
+            (0, 0, 5),
+        ] {
+            let cs = ConstraintSystem::new_ref();
+            let delta_1_i = Amount::from(delta_1_i);
+            let delta_2_i = Amount::from(delta_2_i);
+            let min_output = Amount::from(min_output);
+            let (lambda_1_i, lambda_2_i) = bsod.swap_outputs((delta_1_i, delta_2_i), min_output);
+
+            let bsod_var = BatchSwapOutputDataVar::new_witness(cs.clone(), || Ok(bsod)).unwrap();
+            let (lambda_1_i_var, lambda_2_i_var) = bsod_var
+                .swap_outputs(
+                    AmountVar::new_witness(cs.clone(), || Ok(delta_1_i)).unwrap(),
+                    AmountVar::new_witness(cs.clone(), || Ok(delta_2_i)).unwrap(),
+                    AmountVar::new_witness(cs.clone(), || Ok(min_output)).unwrap(),
+                    cs.clone(),
+                )
+                .unwrap();
+            lambda_1_i_var
+                .enforce_equal(&AmountVar::new_witness(cs.clone(), || Ok(lambda_1_i)).unwrap())
+                .unwrap();
+            lambda_2_i_var
+                .enforce_equal(&AmountVar::new_witness(cs.clone(), || Ok(lambda_2_i)).unwrap())
+                .unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
 }
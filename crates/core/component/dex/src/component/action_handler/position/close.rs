@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
 use penumbra_proto::StateWriteProto as _;
+use penumbra_shielded_pool::component::SupplyWrite;
 
 use crate::{component::PositionManager, event, lp::action::PositionClose};
 
@@ -27,6 +28,10 @@ impl ActionHandler for PositionClose {
         // queue position close you will...
         state.record_proto(event::queue_position_close(self));
 
+        // The opened position NFT is exchanged for a closed position NFT now,
+        // regardless of when the position itself is closed.
+        state.record_supply_change(self.balance()).await?;
+
         Ok(())
     }
 }
@@ -2,10 +2,12 @@ use anyhow::{ensure, Result};
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
+use penumbra_asset::Value;
+use penumbra_shielded_pool::component::SupplyWrite;
 
 use crate::{
     component::{PositionManager, StateReadExt},
-    lp::{action::PositionOpen, position},
+    lp::{action::PositionOpen, position, LpNft},
 };
 
 #[async_trait]
@@ -37,6 +39,14 @@ impl ActionHandler for PositionOpen {
         );
 
         state.open_position(self.position.clone()).await?;
+
+        // The reserves move into the DEX, but the opened position NFT is new.
+        state
+            .increase_token_supply(Value {
+                amount: 1u64.into(),
+                asset_id: LpNft::new(self.position.id(), position::State::Opened).asset_id(),
+            })
+            .await?;
         Ok(())
     }
 }
@@ -4,8 +4,13 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
 use decaf377::Fr;
+use penumbra_asset::Value;
+use penumbra_shielded_pool::component::SupplyWrite;
 
-use crate::{component::PositionManager, lp::action::PositionWithdraw};
+use crate::{
+    component::PositionManager,
+    lp::{action::PositionWithdraw, position, LpNft},
+};
 
 #[async_trait]
 /// Debits a closed position NFT and credits a withdrawn position NFT and the final reserves.
@@ -34,9 +39,8 @@ impl ActionHandler for PositionWithdraw {
         // shielding a value, this commitment is used for compression, giving a
         // single commitment rather than a list of token amounts.
         //
-        // Note: since this is forming a commitment only to the reserves,
-        // we are implicitly setting the reward amount to 0. However, we can
-        // add support for rewards in the future without client changes.
+        // Note: liquidity rewards are added to the reserves of a position when it
+        // is closed, so the commitment to the reserves already covers them.
         let expected_reserves_commitment = actual_reserves.commit(Fr::zero());
 
         if self.reserves_commitment != expected_reserves_commitment {
@@ -47,6 +51,31 @@ impl ActionHandler for PositionWithdraw {
             );
         }
 
+        // The reserves leave the DEX, but the position NFT is burned and
+        // replaced by one for the next withdrawal state.
+        let prev_state = if self.sequence == 0 {
+            position::State::Closed
+        } else {
+            position::State::Withdrawn {
+                sequence: self.sequence - 1,
+            }
+        };
+        let new_state = position::State::Withdrawn {
+            sequence: self.sequence,
+        };
+        state
+            .decrease_token_supply(Value {
+                amount: 1u64.into(),
+                asset_id: LpNft::new(self.position_id, prev_state).asset_id(),
+            })
+            .await?;
+        state
+            .increase_token_supply(Value {
+                amount: 1u64.into(),
+                asset_id: LpNft::new(self.position_id, new_state).asset_id(),
+            })
+            .await?;
+
         Ok(())
     }
 }
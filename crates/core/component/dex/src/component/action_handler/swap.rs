@@ -4,14 +4,17 @@ use anyhow::{ensure, Result};
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use cnidarium_component::ActionHandler;
+use penumbra_num::Amount;
 use penumbra_proof_params::SWAP_PROOF_VERIFICATION_KEY;
 use penumbra_proto::StateWriteProto;
 use penumbra_sct::component::source::SourceContext;
 
 use crate::{
+    component::flow::LimitedSwap,
     component::{InternalDexWrite, StateReadExt, SwapDataWrite, SwapManager},
     event,
     swap::{proof::SwapProofPublic, Swap},
+    DirectedTradingPair,
 };
 
 #[async_trait]
@@ -23,12 +26,22 @@ impl ActionHandler for Swap {
             anyhow::bail!("Trading pair must be distinct");
         }
 
+        // A minimum output is denominated in the asset being swapped into, so
+        // limited swaps must have exactly one input.
+        if self.body.min_output != Amount::zero() {
+            ensure!(
+                (self.body.delta_1_i == Amount::zero()) != (self.body.delta_2_i == Amount::zero()),
+                "Swaps with a minimum output must have exactly one nonzero input"
+            );
+        }
+
         self.proof.verify(
             &SWAP_PROOF_VERIFICATION_KEY,
             SwapProofPublic {
                 balance_commitment: self.balance_commitment_inner(),
                 swap_commitment: self.body.payload.commitment,
                 fee_commitment: self.body.fee_commitment,
+                min_output: self.body.min_output,
             },
         )?;
 
@@ -52,6 +65,30 @@ impl ActionHandler for Swap {
             .accumulate_swap_flow(&swap.body.trading_pair, flow.into())
             .await?;
 
+        // Swaps with a minimum output are also tracked individually, so that
+        // they can be refunded if the batch does not meet their minimum output.
+        if swap.body.min_output != Amount::zero() {
+            let pair = &swap.body.trading_pair;
+            let (pair, input) = if swap.body.delta_1_i != Amount::zero() {
+                (
+                    DirectedTradingPair::new(pair.asset_1(), pair.asset_2()),
+                    swap.body.delta_1_i,
+                )
+            } else {
+                (
+                    DirectedTradingPair::new(pair.asset_2(), pair.asset_1()),
+                    swap.body.delta_2_i,
+                )
+            };
+            state.record_limited_swap(
+                pair,
+                LimitedSwap {
+                    input,
+                    min_output: swap.body.min_output,
+                },
+            );
+        }
+
         // Record the swap commitment in the state.
         let source = state.get_current_source().expect("source is set");
         state
@@ -7,6 +7,7 @@ use cnidarium::{StateDelta, StateWrite};
 use penumbra_asset::{asset, Value};
 use penumbra_proto::StateWriteProto as _;
 use penumbra_sct::component::clock::EpochRead;
+use penumbra_shielded_pool::component::SupplyWrite as _;
 use tracing::instrument;
 
 use crate::{
@@ -133,6 +134,14 @@ pub trait Arbitrage: StateWrite + Sized {
             })
             .await?;
 
+        // The surplus isn't paid out to anyone, so it is burned.
+        self_mut
+            .decrease_token_supply(Value {
+                amount: arb_profit,
+                asset_id: arb_token,
+            })
+            .await?;
+
         // Emit an ABCI event detailing the arb execution.
         self_mut.record_proto(event::arb_execution(height, se));
         return Ok(Some(Value {
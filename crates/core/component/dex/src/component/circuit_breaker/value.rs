@@ -84,7 +84,7 @@ mod tests {
     use cnidarium::{ArcStateDeltaExt as _, StateDelta, TempStorage};
     use cnidarium_component::ActionHandler as _;
     use penumbra_asset::asset;
-    use penumbra_num::Amount;
+    use penumbra_num::{fixpoint::U128x128, Amount};
     use penumbra_proto::StateWriteProto as _;
     use penumbra_sct::component::clock::EpochManager as _;
     use penumbra_sct::component::source::SourceContext as _;
@@ -187,6 +187,8 @@ mod tests {
                     height: 1,
                     trading_pair: pair_1.into_directed_trading_pair().into(),
                     sct_position_prefix: Default::default(),
+                    limit_price_1_for_2: U128x128::default(),
+                    limit_price_2_for_1: U128x128::default(),
                 },
                 None,
                 None,
@@ -1,16 +1,18 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
 use cnidarium_component::Component;
 use penumbra_asset::asset;
 use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+use penumbra_distributions::component::StateReadExt as _;
 use penumbra_fee::component::StateWriteExt as _;
 use penumbra_fee::Fee;
 use penumbra_num::Amount;
 use penumbra_proto::{StateReadProto, StateWriteProto};
+use penumbra_shielded_pool::component::{Ics20SwapWrite as _, NoteManager as _, SupplyWrite as _};
 use tendermint::v0_37::abci;
 use tracing::instrument;
 
@@ -21,8 +23,10 @@ use crate::{
 };
 
 use super::eviction_manager::EvictionManager;
+use super::position_manager::lp_rewards::LpRewards as _;
 use super::{
     chandelier::Chandelier,
+    flow::LimitedSwap,
     router::{HandleBatchSwaps, RoutingParams},
     Arbitrage, PositionManager, PositionRead as _, ValueCircuitBreaker,
 };
@@ -56,7 +60,7 @@ impl Component for Dex {
         end_block: &abci::request::EndBlock,
     ) {
         // F.0. Add all non-native fee payments as swap flows.
-        let base_fees_and_tips = {
+        let (base_fees_and_tips, ics20_swaps) = {
             let state_ref =
                 Arc::get_mut(state).expect("should have unique ref at start of Dex::end_block");
 
@@ -95,8 +99,40 @@ impl Component for Dex {
                     .expect("should be able to credit DEX VCB");
             }
 
-            // Hold on to the list of base fees and tips so we can claim outputs correctly.
-            base_fees_and_tips
+            // Likewise add the swaps requested by the memos of inbound ICS-20 transfers.
+            let ics20_swaps = state_ref.take_pending_ics20_swaps();
+            for swap in ics20_swaps.iter() {
+                let pair = TradingPair::new(swap.input.asset_id, swap.output_asset_id);
+                let flow = if pair.asset_1() == swap.input.asset_id {
+                    (swap.input.amount, Amount::zero())
+                } else {
+                    (Amount::zero(), swap.input.amount)
+                };
+                tracing::debug!(
+                    ?swap,
+                    ?flow,
+                    "inserting chain-submitted swap for ics20 transfer"
+                );
+
+                state_ref
+                    .accumulate_swap_flow(&pair, flow.into())
+                    .await
+                    .expect("should be able to credit DEX VCB");
+
+                // Swaps with a minimum output are refunded if the batch doesn't meet it.
+                if swap.min_output != Amount::zero() {
+                    state_ref.record_limited_swap(
+                        DirectedTradingPair::new(swap.input.asset_id, swap.output_asset_id),
+                        LimitedSwap {
+                            input: swap.input.amount,
+                            min_output: swap.min_output,
+                        },
+                    );
+                }
+            }
+
+            // Hold on to the list of base fees and tips and ICS-20 swaps so we can claim outputs correctly.
+            (base_fees_and_tips, ics20_swaps)
         };
 
         // 1. Add all newly opened positions to the DEX.
@@ -148,6 +184,14 @@ impl Component for Dex {
                     // to claim and we just accumulate the fee we took back into the fee component.
                     state_ref.raw_accumulate_base_fee(Fee::from_staking_token_amount(*base_fee));
                     state_ref.raw_accumulate_tip(Fee::from_staking_token_amount(*tip));
+                    // Fees are not paid out to anyone, so they are burned.
+                    state_ref
+                        .decrease_token_supply(Value {
+                            amount: *base_fee + *tip,
+                            asset_id: *STAKING_TOKEN_ASSET_ID,
+                        })
+                        .await
+                        .expect("should be able to burn fees");
                     continue;
                 }
                 let pair = TradingPair::new(*asset_id, *STAKING_TOKEN_ASSET_ID);
@@ -173,18 +217,80 @@ impl Component for Dex {
                 );
 
                 // Obtain the base fee and tip amounts in the native token, discarding any unfilled amounts.
-                let (swapped_base, swapped_tip) = if pair.asset_1() == *asset_id {
-                    // If `asset_id` is `R_1` we want to pull the other leg of the pair.
-                    (base_output.1, tip_output.1)
-                } else {
-                    // and vice-versa. `R_1` contains native tokens.
-                    (base_output.0, tip_output.0)
-                };
+                let ((swapped_base, swapped_tip), (unfilled_base, unfilled_tip)) =
+                    if pair.asset_1() == *asset_id {
+                        // If `asset_id` is `R_1` we want to pull the other leg of the pair.
+                        ((base_output.1, tip_output.1), (base_output.0, tip_output.0))
+                    } else {
+                        // and vice-versa. `R_1` contains native tokens.
+                        ((base_output.0, tip_output.0), (base_output.1, tip_output.1))
+                    };
 
                 // Finally, accumulate the swapped base fee and tip back into the fee component.
                 // (We already took all the fees out).
                 state_ref.raw_accumulate_base_fee(Fee::from_staking_token_amount(swapped_base));
                 state_ref.raw_accumulate_tip(Fee::from_staking_token_amount(swapped_tip));
+
+                // Both the swapped fees and the discarded unfilled amounts are burned.
+                state_ref
+                    .decrease_token_supply(Value {
+                        amount: swapped_base + swapped_tip,
+                        asset_id: *STAKING_TOKEN_ASSET_ID,
+                    })
+                    .await
+                    .expect("should be able to burn fees");
+                state_ref
+                    .decrease_token_supply(Value {
+                        amount: unfilled_base + unfilled_tip,
+                        asset_id: *asset_id,
+                    })
+                    .await
+                    .expect("should be able to burn fees");
+            }
+        }
+
+        // F.2. Pay out the outputs of the ICS-20 swaps, along with any unfilled input, to their
+        // claim addresses. The VCB has already been debited through the BSOD.
+        {
+            let state_ref =
+                Arc::get_mut(state).expect("should have unique ref after finishing batch swaps");
+            for swap in ics20_swaps.iter() {
+                let pair = TradingPair::new(swap.input.asset_id, swap.output_asset_id);
+                let bsod = bsods
+                    .get(&pair)
+                    .expect("bsod should be present for chain-submitted swap");
+
+                let input = if pair.asset_1() == swap.input.asset_id {
+                    (swap.input.amount, 0u64.into())
+                } else {
+                    (0u64.into(), swap.input.amount)
+                };
+                let output = bsod.swap_outputs(input, swap.min_output);
+                tracing::debug!(
+                    ?swap,
+                    ?output,
+                    "claiming chain-submitted swap for ics20 transfer"
+                );
+
+                let outputs = [
+                    Value {
+                        amount: output.0,
+                        asset_id: pair.asset_1(),
+                    },
+                    Value {
+                        amount: output.1,
+                        asset_id: pair.asset_2(),
+                    },
+                ];
+                for value in outputs {
+                    if value.amount == Amount::zero() {
+                        continue;
+                    }
+                    state_ref
+                        .mint_note(value, &swap.claim_address, swap.source.clone())
+                        .await
+                        .expect("should be able to mint ics20 swap outputs");
+                }
             }
         }
 
@@ -250,9 +356,14 @@ impl Component for Dex {
             .expect("finalizing block candlesticks should not fail");
     }
 
-    #[instrument(name = "dex", skip(_state))]
-    async fn end_epoch<S: StateWrite + 'static>(mut _state: &mut Arc<S>) -> Result<()> {
-        Ok(())
+    #[instrument(name = "dex", skip(state))]
+    async fn end_epoch<S: StateWrite + 'static>(state: &mut Arc<S>) -> Result<()> {
+        let state = Arc::get_mut(state).context("state should be unique")?;
+        let Some(lp_issuance) = state.get_lp_token_issuance_for_epoch() else {
+            tracing::error!("the liquidity provider issuance for the epoch has not been found in object storage, skipping reward distribution");
+            return Ok(());
+        };
+        state.distribute_lp_rewards(lp_issuance).await
     }
 }
 
@@ -1,6 +1,8 @@
 use std::ops::{Deref, DerefMut};
 
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
+
+use crate::batch_swap_output_data::limit_price;
 
 // Tuple represents:
 // ((amount of asset 1 being exchanged for asset 2),
@@ -27,3 +29,18 @@ impl From<(Amount, Amount)> for SwapFlow {
         Self(tuple)
     }
 }
+
+/// A swap in one direction of a batch that is only filled if it receives at
+/// least `min_output`. Its input is also part of the batch's [`SwapFlow`].
+#[derive(Debug, Clone)]
+pub struct LimitedSwap {
+    pub input: Amount,
+    pub min_output: Amount,
+}
+
+impl LimitedSwap {
+    /// The minimum output of the swap per unit of input.
+    pub fn limit_price(&self) -> U128x128 {
+        limit_price((self.input, Amount::zero()), self.min_output)
+    }
+}
@@ -18,7 +18,7 @@ use crate::component::{
     dex::StateReadExt as _,
     position_manager::{
         base_liquidity_index::AssetByLiquidityIndex, inventory_index::PositionByInventoryIndex,
-        price_index::PositionByPriceIndex,
+        lp_rewards::LpRewards, price_index::PositionByPriceIndex,
     },
 };
 use crate::lp::Reserves;
@@ -38,6 +38,7 @@ const DYNAMIC_ASSET_LIMIT: usize = 10;
 mod base_liquidity_index;
 pub(crate) mod counter;
 pub(crate) mod inventory_index;
+pub(crate) mod lp_rewards;
 pub(crate) mod price_index;
 
 #[async_trait]
@@ -350,6 +351,9 @@ pub trait PositionManager: StateWrite + PositionRead {
         // event and not worry about duplicates.
         self.record_proto(event::position_execution(&prev_state, &new_state, context));
 
+        // Track the execution towards the position's share of this epoch's liquidity rewards.
+        self.record_lp_reward_weight(&prev_state, &new_state).await?;
+
         // Handle "close-on-fill": automatically flip the position state to "closed" if
         // either of the reserves are zero.
         if new_state.close_on_fill {
@@ -478,6 +482,15 @@ trait Inner: StateWrite {
         // Assert `update_position` state transitions invariants:
         Self::guard_invalid_transitions(&prev_state, &new_state, &id)?;
 
+        // Closing positions collect the liquidity rewards they accrued while open,
+        // so that they are paid out on withdrawal.
+        let new_state = match (prev_state.as_ref().map(|p| p.state), new_state.state) {
+            (Some(position::State::Opened), position::State::Closed) => {
+                self.collect_lp_rewards(id, new_state).await?
+            }
+            _ => new_state,
+        };
+
         // Update the DEX engine indices:
         self.update_position_by_inventory_index(&id, &prev_state, &new_state)?;
         self.update_asset_by_base_liquidity_index(&id, &prev_state, &new_state)
@@ -0,0 +1,176 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use cnidarium::{StateRead, StateWrite};
+use futures::TryStreamExt;
+use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+use penumbra_num::{fixpoint::U128x128, Amount};
+use penumbra_proto::{StateReadProto, StateWriteProto};
+use penumbra_shielded_pool::component::SupplyWrite;
+use tracing::instrument;
+
+use crate::component::ValueCircuitBreaker;
+use crate::lp::position::{self, Position};
+use crate::state_key::lp_rewards;
+
+use super::{Inner, PositionRead};
+
+#[async_trait]
+pub(crate) trait LpRewardsRead: StateRead {
+    /// Returns the liquidity rewards accrued by an open position, which are
+    /// added to its reserves when the position is closed.
+    async fn unclaimed_lp_rewards(&self, id: &position::Id) -> Result<Amount> {
+        Ok(self
+            .get(&lp_rewards::unclaimed(id))
+            .await?
+            .unwrap_or_default())
+    }
+}
+
+impl<T: StateRead + ?Sized> LpRewardsRead for T {}
+
+/// Tracks the activity of liquidity positions over an epoch, and distributes
+/// the liquidity provider issuance among them at the end of the epoch.
+///
+/// # Weighting
+/// A position's share of the epoch's issuance is proportional to the fees it
+/// earned on flows of the staking token, i.e. the staking token volume it
+/// executed multiplied by its fee. Positions whose pair does not include the
+/// staking token have no common denomination to compare against and earn no
+/// rewards.
+///
+/// # Payout
+/// Rewards for closed positions are added to their staking token reserves right
+/// away, rewards for open positions are held until they close. Either way, they
+/// are paid out when the position is withdrawn.
+#[async_trait]
+pub(crate) trait LpRewards: StateWrite {
+    /// Record the reward weight earned by a position during an execution.
+    async fn record_lp_reward_weight(
+        &mut self,
+        prev_state: &Position,
+        new_state: &Position,
+    ) -> Result<()> {
+        let (Some(before), Some(after)) = (
+            prev_state.reserves_for(*STAKING_TOKEN_ASSET_ID),
+            new_state.reserves_for(*STAKING_TOKEN_ASSET_ID),
+        ) else {
+            return Ok(());
+        };
+
+        let volume = if after > before {
+            after - before
+        } else {
+            before - after
+        };
+        let weight = volume
+            .value()
+            .saturating_mul(new_state.phi.component.fee as u128);
+        if weight == 0 {
+            return Ok(());
+        }
+
+        let key = lp_rewards::weight(&new_state.id());
+        let prev_weight: Amount = self.get(&key).await?.unwrap_or_default();
+        self.put(key, prev_weight.saturating_add(&weight.into()));
+        Ok(())
+    }
+
+    /// Split `issuance` among the positions that executed during the epoch,
+    /// according to their accumulated weight, and reset the weights.
+    #[instrument(skip(self))]
+    async fn distribute_lp_rewards(&mut self, issuance: Amount) -> Result<()> {
+        let weights: Vec<(String, Amount)> = self
+            .prefix::<Amount>(lp_rewards::weight_prefix())
+            .try_collect()
+            .await?;
+
+        let total_weight = weights.iter().fold(Amount::zero(), |acc, (_, weight)| {
+            acc.saturating_add(weight)
+        });
+
+        for (key, weight) in weights {
+            self.delete(key.clone());
+
+            if issuance == Amount::zero() || total_weight == Amount::zero() {
+                continue;
+            }
+
+            let id: position::Id = key
+                .strip_prefix(lp_rewards::weight_prefix())
+                .ok_or_else(|| anyhow::anyhow!("malformed lp reward weight key {key}"))?
+                .parse()?;
+            let reward = U128x128::ratio(weight, total_weight)?.apply_to_amount(&issuance)?;
+            if reward == Amount::zero() {
+                continue;
+            }
+
+            self.credit_lp_reward(id, reward).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Credit a newly issued reward to a position.
+    async fn credit_lp_reward(&mut self, id: position::Id, reward: Amount) -> Result<()> {
+        let prev_state = self
+            .position_by_id(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("rewarded unknown position {}", id))?;
+
+        match prev_state.state {
+            position::State::Opened => {
+                let unclaimed = self.unclaimed_lp_rewards(&id).await?;
+                self.put(lp_rewards::unclaimed(&id), unclaimed + reward);
+            }
+            position::State::Closed => {
+                let new_state = add_staking_token_reserves(prev_state.clone(), reward);
+                self.update_position(&id, Some(prev_state), new_state)
+                    .await?;
+            }
+            position::State::Withdrawn { .. } => {
+                tracing::debug!(
+                    ?id,
+                    ?reward,
+                    "position already withdrawn, forfeiting reward"
+                );
+                return Ok(());
+            }
+        }
+
+        tracing::debug!(?id, ?reward, "credited liquidity reward");
+        let reward = Value {
+            amount: reward,
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        };
+        self.increase_token_supply(reward).await?;
+        self.dex_vcb_credit(reward).await
+    }
+
+    /// Move the rewards accrued by a closing position into its reserves,
+    /// so that they are paid out on withdrawal.
+    async fn collect_lp_rewards(
+        &mut self,
+        id: &position::Id,
+        new_state: Position,
+    ) -> Result<Position> {
+        let unclaimed = self.unclaimed_lp_rewards(id).await?;
+        if unclaimed == Amount::zero() {
+            return Ok(new_state);
+        }
+
+        self.delete(lp_rewards::unclaimed(id));
+        Ok(add_staking_token_reserves(new_state, unclaimed))
+    }
+}
+
+impl<T: StateWrite + ?Sized> LpRewards for T {}
+
+/// Adds `amount` to the staking token side of a position's reserves.
+fn add_staking_token_reserves(mut position: Position, amount: Amount) -> Position {
+    if position.phi.pair.asset_1() == *STAKING_TOKEN_ASSET_ID {
+        position.reserves.r1 += amount;
+    } else {
+        position.reserves.r2 += amount;
+    }
+    position
+}
@@ -2,21 +2,22 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use cnidarium::StateWrite;
+use cnidarium::{StateDelta, StateWrite};
 use penumbra_asset::{asset, Value};
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
 use penumbra_sct::component::clock::EpochRead;
 use tracing::instrument;
 
 use crate::{
     component::{
         chandelier::Chandelier,
-        flow::SwapFlow,
+        flow::{LimitedSwap, SwapFlow},
         router::{FillRoute, PathSearch, RoutingParams},
-        ExecutionCircuitBreaker, InternalDexWrite, PositionManager,
+        ExecutionCircuitBreaker, InternalDexWrite, PositionManager, SwapDataRead,
+        ValueCircuitBreaker,
     },
     lp::position::MAX_RESERVE_AMOUNT,
-    BatchSwapOutputData, SwapExecution, TradingPair,
+    BatchSwapOutputData, DirectedTradingPair, SwapExecution, TradingPair,
 };
 
 use super::fill_route::FillError;
@@ -40,6 +41,18 @@ pub trait HandleBatchSwaps: StateWrite + Sized {
         let (delta_1, delta_2) = (batch_data.0, batch_data.1);
         tracing::debug!(?delta_1, ?delta_2, ?trading_pair, "decrypted batch swaps");
 
+        // Exclude the swaps whose minimum output can't be met from the batch, refunding
+        // their input. This settles the 1=>2 direction first, so that the 2=>1 swaps are
+        // checked against the state they will actually execute against.
+        let (delta_1, limit_price_1_for_2) = self
+            .select_limited_swaps(
+                DirectedTradingPair::new(trading_pair.asset_1(), trading_pair.asset_2()),
+                delta_1,
+                params.clone(),
+                execution_budget,
+            )
+            .await?;
+
         // We initialize a circuit breaker for this batch swap. This will limit the number of frontier
         // executions up to the specified `execution_budget` parameter.
         let execution_circuit_breaker = ExecutionCircuitBreaker::new(execution_budget);
@@ -54,6 +67,15 @@ pub trait HandleBatchSwaps: StateWrite + Sized {
             )
             .await?;
 
+        let (delta_2, limit_price_2_for_1) = self
+            .select_limited_swaps(
+                DirectedTradingPair::new(trading_pair.asset_2(), trading_pair.asset_1()),
+                delta_2,
+                params.clone(),
+                execution_budget,
+            )
+            .await?;
+
         let swap_execution_2_for_1 = self
             .route_and_fill(
                 trading_pair.asset_2(),
@@ -97,6 +119,8 @@ pub trait HandleBatchSwaps: StateWrite + Sized {
                 0,
             )
                 .into(),
+            limit_price_1_for_2,
+            limit_price_2_for_1,
         };
 
         tracing::debug!(
@@ -129,6 +153,124 @@ pub trait HandleBatchSwaps: StateWrite + Sized {
 
         Ok(output_data)
     }
+
+    /// Determines which of the swaps with a minimum output in the `pair` direction
+    /// of the batch can be filled, refunding the input of the others.
+    ///
+    /// The limited swaps are filled in order of their limit price, picking the highest
+    /// cutoff such that executing all unlimited swaps along with the limited swaps at
+    /// or below the cutoff meets all of their minimum outputs. This is checked by
+    /// executing against a fork of the state, with the same budget as the batch.
+    ///
+    /// Returns the input to execute for the batch, and the limit price cutoff.
+    #[instrument(skip(self, params, execution_budget))]
+    async fn select_limited_swaps(
+        self: &mut Arc<Self>,
+        pair: DirectedTradingPair,
+        total_input: Amount,
+        params: RoutingParams,
+        execution_budget: u32,
+    ) -> Result<(Amount, U128x128)>
+    where
+        Self: 'static,
+    {
+        let mut limited_swaps: Vec<LimitedSwap> = self.limited_swaps(&pair).into_iter().collect();
+        if limited_swaps.is_empty() {
+            return Ok((total_input, U128x128::default()));
+        }
+        limited_swaps.sort_by_key(LimitedSwap::limit_price);
+
+        let limited_input = limited_swaps
+            .iter()
+            .fold(Amount::zero(), |acc, swap| acc + swap.input);
+        let unlimited_input = total_input
+            .checked_sub(&limited_input)
+            .context("limited swap inputs should be part of the swap flow")?;
+
+        // The distinct limit prices, in increasing order. Each is a candidate cutoff.
+        let mut cutoffs: Vec<U128x128> =
+            limited_swaps.iter().map(LimitedSwap::limit_price).collect();
+        cutoffs.dedup();
+
+        // Binary search for the number of cutoffs that can be filled. Filling fewer
+        // limited swaps can only improve the price received by the others, so if
+        // the swaps up to a cutoff can be filled, so can those up to a lower one.
+        let (mut lo, mut hi) = (0, cutoffs.len());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            let cutoff = cutoffs[mid - 1];
+            let included: Vec<&LimitedSwap> = limited_swaps
+                .iter()
+                .filter(|swap| swap.limit_price() <= cutoff)
+                .collect();
+            let input = included
+                .iter()
+                .fold(unlimited_input, |acc, swap| acc + swap.input);
+
+            // Execute the batch in this direction against a fork of the state.
+            let mut fork = Arc::new(StateDelta::new(self.clone()));
+            let execution = fork
+                .route_and_fill(
+                    pair.start,
+                    pair.end,
+                    input,
+                    params.clone(),
+                    ExecutionCircuitBreaker::new(execution_budget),
+                )
+                .await?;
+            let (filled, output) = execution
+                .map(|execution| (execution.input.amount, execution.output.amount))
+                .unwrap_or_default();
+
+            // Compute the outputs of the included swaps exactly as the BSOD would,
+            // treating this direction as the 1=>2 side of the batch.
+            let bsod = BatchSwapOutputData {
+                delta_1: input,
+                delta_2: Amount::zero(),
+                lambda_1: Amount::zero(),
+                lambda_2: output,
+                unfilled_1: input - filled,
+                unfilled_2: Amount::zero(),
+                height: 0,
+                trading_pair: pair.into(),
+                sct_position_prefix: Default::default(),
+                limit_price_1_for_2: U128x128::default(),
+                limit_price_2_for_1: U128x128::default(),
+            };
+            let fillable = included.iter().all(|swap| {
+                let (_, output) = bsod.pro_rata_outputs((swap.input, Amount::zero()));
+                output >= swap.min_output
+            });
+
+            if fillable {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        let cutoff = match lo {
+            0 => U128x128::default(),
+            n => cutoffs[n - 1],
+        };
+        let refunded = limited_swaps
+            .iter()
+            .filter(|swap| swap.limit_price() > cutoff)
+            .fold(Amount::zero(), |acc, swap| acc + swap.input);
+        tracing::debug!(?pair, ?cutoff, ?refunded, "selected limited swaps to fill");
+
+        // The refunded inputs are paid out by the swap claims rather than through the
+        // BSOD, so debit them from the DEX now, having credited all inflows.
+        Arc::get_mut(self)
+            .expect("expected state to have no other refs")
+            .dex_vcb_debit(Value {
+                amount: refunded,
+                asset_id: pair.start,
+            })
+            .await?;
+
+        Ok((total_input - refunded, cutoff))
+    }
 }
 
 impl<T: PositionManager> HandleBatchSwaps for T {}
@@ -9,7 +9,10 @@ use crate::component::circuit_breaker::value::ValueCircuitBreaker;
 use crate::BatchSwapOutputData;
 use crate::SwapExecution;
 use crate::{
-    component::flow::SwapFlow, state_key, swap::SwapPayload, DirectedTradingPair, TradingPair,
+    component::flow::{LimitedSwap, SwapFlow},
+    state_key,
+    swap::SwapPayload,
+    DirectedTradingPair, TradingPair,
 };
 use anyhow::Result;
 use penumbra_proto::StateWriteProto;
@@ -52,6 +55,17 @@ pub trait SwapDataRead: StateRead {
             .unwrap_or_default()
     }
 
+    /// Get the swaps with a minimum output for the given direction accumulated in this block so far.
+    fn limited_swaps(&self, pair: &DirectedTradingPair) -> im::Vector<LimitedSwap> {
+        self.object_get::<im::OrdMap<DirectedTradingPair, im::Vector<LimitedSwap>>>(
+            state_key::limited_swaps(),
+        )
+        .unwrap_or_default()
+        .get(pair)
+        .cloned()
+        .unwrap_or_default()
+    }
+
     fn pending_batch_swap_outputs(&self) -> im::OrdMap<TradingPair, BatchSwapOutputData> {
         self.object_get(state_key::pending_outputs())
             .unwrap_or_default()
@@ -96,6 +110,16 @@ pub(crate) trait SwapDataWrite: StateWrite {
         Ok(())
     }
 
+    /// Record a swap with a minimum output, whose input has already been
+    /// accumulated into the swap flows.
+    fn record_limited_swap(&mut self, pair: DirectedTradingPair, swap: LimitedSwap) {
+        let mut limited_swaps: im::OrdMap<DirectedTradingPair, im::Vector<LimitedSwap>> = self
+            .object_get(state_key::limited_swaps())
+            .unwrap_or_default();
+        limited_swaps.entry(pair).or_default().push_back(swap);
+        self.object_put(state_key::limited_swaps(), limited_swaps);
+    }
+
     fn put_swap_execution_at_height(
         &mut self,
         height: u64,
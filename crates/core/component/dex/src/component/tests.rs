@@ -5,9 +5,10 @@ use async_trait::async_trait;
 use cnidarium::{ArcStateDeltaExt, StateDelta, TempStorage};
 use futures::StreamExt;
 use penumbra_asset::{asset, Value};
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
 use rand_core::OsRng;
 
+use crate::component::flow::{LimitedSwap, SwapFlow};
 use crate::component::{SwapDataRead, SwapDataWrite};
 use crate::lp::action::PositionOpen;
 use crate::lp::{position, SellOrder};
@@ -523,6 +524,60 @@ async fn empty_order_fails() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+/// Checks that liquidity rewards are allocated to positions that traded the staking
+/// token, and added to their reserves when they close.
+async fn lp_rewards_paid_on_close() -> anyhow::Result<()> {
+    use crate::component::position_manager::lp_rewards::{LpRewards, LpRewardsRead};
+
+    let storage = TempStorage::new().await?.apply_minimal_genesis().await?;
+    let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
+    let mut state_tx = state.try_begin_transaction().unwrap();
+
+    let gm = asset::Cache::with_known_assets().get_unit("gm").unwrap();
+
+    let position_1 = SellOrder::parse_str("100gm@1penumbra/10bps")?.into_position(OsRng);
+    let position_2 = SellOrder::parse_str("100gm@1gn/10bps")?.into_position(OsRng);
+    let position_1_id = position_1.id();
+    let position_2_id = position_2.id();
+
+    state_tx.open_position(position_1.clone()).await.unwrap();
+    state_tx.open_position(position_2.clone()).await.unwrap();
+
+    // Trade against both positions, only one of which involves the staking token.
+    let route = [gm.id()];
+    FillRoute::fill_route(&mut state_tx, "50penumbra".parse()?, &route, None).await?;
+    FillRoute::fill_route(&mut state_tx, "50gn".parse()?, &route, None).await?;
+
+    let reward = Amount::from(1_000_000u64);
+    state_tx.distribute_lp_rewards(reward).await?;
+
+    assert_eq!(state_tx.unclaimed_lp_rewards(&position_1_id).await?, reward);
+    assert_eq!(
+        state_tx.unclaimed_lp_rewards(&position_2_id).await?,
+        Amount::zero()
+    );
+
+    // Closing the position moves the reward into its reserves.
+    let position_1_pre_close = state_tx.position_by_id(&position_1_id).await?.unwrap();
+    state_tx.close_position_by_id(&position_1_id).await?;
+    let position_1_post_close = state_tx.position_by_id(&position_1_id).await?.unwrap();
+
+    assert_eq!(position_1_post_close.state, position::State::Closed);
+    assert_eq!(
+        position_1_post_close.reserves_for(*penumbra_asset::STAKING_TOKEN_ASSET_ID),
+        position_1_pre_close
+            .reserves_for(*penumbra_asset::STAKING_TOKEN_ASSET_ID)
+            .map(|r| r + reward)
+    );
+    assert_eq!(
+        state_tx.unclaimed_lp_rewards(&position_1_id).await?,
+        Amount::zero()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 /// Test that positions are created and returned as expected.
 async fn position_create_and_retrieve() -> anyhow::Result<()> {
@@ -759,6 +814,8 @@ async fn swap_execution_tests() -> anyhow::Result<()> {
             height: 0,
             trading_pair,
             sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         }
     );
 
@@ -791,6 +848,82 @@ async fn swap_execution_tests() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+/// Test that swaps whose minimum output can't be met are excluded from the batch.
+async fn limited_swaps_are_refunded() -> anyhow::Result<()> {
+    let _ = tracing_subscriber::fmt::try_init();
+    let storage = TempStorage::new().await?.apply_minimal_genesis().await?;
+    let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
+    let mut state_tx = state.try_begin_transaction().unwrap();
+
+    let penumbra = asset::Cache::with_known_assets()
+        .get_unit("penumbra")
+        .unwrap();
+    let gn = asset::Cache::with_known_assets().get_unit("gn").unwrap();
+
+    // Sell 10 gn at 1 penumbra each.
+    state_tx
+        .open_position(create_sell(
+            DirectedUnitPair::new(gn.clone(), penumbra.clone()),
+            10u64.into(),
+            1u64.into(),
+        ))
+        .await
+        .unwrap();
+    state_tx.apply();
+
+    let trading_pair = DirectedUnitPair::new(penumbra.clone(), gn.clone())
+        .into_directed_trading_pair()
+        .into();
+    assert!(trading_pair.asset_1() == penumbra.id());
+
+    // Three swaps of 4 penumbra each: one unlimited, one asking for at least 3 gn,
+    // and one asking for at least 4 gn. There isn't enough liquidity to give the
+    // last one its minimum output once the other two are filled.
+    let swap_flow: SwapFlow = (penumbra.value(12u32.into()).amount, Amount::zero()).into();
+    let state_mut = Arc::get_mut(&mut state).unwrap();
+    state_mut
+        .accumulate_swap_flow(&trading_pair, swap_flow.clone())
+        .await
+        .unwrap();
+    for min_output in [3u32, 4] {
+        state_mut.record_limited_swap(
+            DirectedTradingPair::new(penumbra.id(), gn.id()),
+            LimitedSwap {
+                input: penumbra.value(4u32.into()).amount,
+                min_output: gn.value(min_output.into()).amount,
+            },
+        );
+    }
+    let routing_params = state.routing_params().await.unwrap();
+    state
+        .handle_batch_swaps(trading_pair, swap_flow, 0, routing_params, 64)
+        .await
+        .expect("unable to process batch swaps");
+
+    let output_data = state.output_data(0, trading_pair).await?.unwrap();
+    assert_eq!(output_data.delta_1, penumbra.value(8u32.into()).amount);
+    assert_eq!(output_data.lambda_2, gn.value(8u32.into()).amount);
+    assert_eq!(
+        output_data.limit_price_1_for_2,
+        U128x128::ratio(3u64, 4u64).unwrap()
+    );
+
+    let input = (penumbra.value(4u32.into()).amount, Amount::zero());
+    let four_gn = (Amount::zero(), gn.value(4u32.into()).amount);
+    assert_eq!(output_data.swap_outputs(input, Amount::zero()), four_gn);
+    assert_eq!(
+        output_data.swap_outputs(input, gn.value(3u32.into()).amount),
+        four_gn
+    );
+    assert_eq!(
+        output_data.swap_outputs(input, gn.value(4u32.into()).amount),
+        input
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 /// Test that a basic cycle arb is detected and filled.
 async fn basic_cycle_arb() -> anyhow::Result<()> {
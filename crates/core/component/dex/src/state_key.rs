@@ -28,6 +28,24 @@ pub fn all_positions() -> &'static str {
     "dex/position/"
 }
 
+pub mod lp_rewards {
+    use crate::lp::position;
+
+    pub fn weight_prefix() -> &'static str {
+        "dex/lp_rewards/weight/"
+    }
+
+    /// The reward weight accumulated by a position during the current epoch.
+    pub fn weight(id: &position::Id) -> String {
+        format!("{}{id}", weight_prefix())
+    }
+
+    /// The rewards accrued by an open position, awaiting its closure.
+    pub fn unclaimed(id: &position::Id) -> String {
+        format!("dex/lp_rewards/unclaimed/{id}")
+    }
+}
+
 pub mod candlesticks {
 
     pub mod object {
@@ -101,6 +119,10 @@ pub fn swap_flows() -> &'static str {
     "dex/swap_flows"
 }
 
+pub fn limited_swaps() -> &'static str {
+    "dex/limited_swaps"
+}
+
 pub fn pending_position_closures() -> &'static str {
     "dex/pending_position_closures"
 }
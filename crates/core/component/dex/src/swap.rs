@@ -21,6 +21,10 @@ pub use view::SwapView;
 pub const SWAP_CIPHERTEXT_BYTES: usize = 272;
 // Swap plaintext byte length.
 pub const SWAP_LEN_BYTES: usize = 256;
+// Swap ciphertext byte length, for swaps with a minimum output.
+pub const LIMITED_SWAP_CIPHERTEXT_BYTES: usize = 288;
+// Swap plaintext byte length, for swaps with a minimum output.
+pub const LIMITED_SWAP_LEN_BYTES: usize = 272;
 
 pub static DOMAIN_SEPARATOR: Lazy<Fq> =
     Lazy::new(|| Fq::from_le_bytes_mod_order(blake2b_simd::blake2b(b"penumbra.swap").as_bytes()));
@@ -87,6 +87,8 @@ pub struct Body {
     pub delta_2_i: Amount,
     pub fee_commitment: balance::Commitment,
     pub payload: SwapPayload,
+    /// The minimum output of the swap, or zero if the swap is unlimited.
+    pub min_output: Amount,
 }
 
 impl EffectingData for Body {
@@ -107,6 +109,8 @@ impl From<Body> for pb::SwapBody {
             delta_2_i: Some(s.delta_2_i.into()),
             fee_commitment: Some(s.fee_commitment.into()),
             payload: Some(s.payload.into()),
+            // Omitted when unset, so that the effect hash of unlimited swaps is unchanged.
+            min_output: (s.min_output != Amount::zero()).then(|| s.min_output.into()),
         }
     }
 }
@@ -137,6 +141,11 @@ impl TryFrom<pb::SwapBody> for Body {
                 .payload
                 .ok_or_else(|| anyhow::anyhow!("missing payload"))?
                 .try_into()?,
+            min_output: s
+                .min_output
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
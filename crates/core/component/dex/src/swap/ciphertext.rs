@@ -3,10 +3,14 @@ use anyhow::Result;
 use penumbra_keys::{keys::OutgoingViewingKey, PayloadKey};
 use penumbra_shielded_pool::note;
 
-use super::{SwapPlaintext, SWAP_CIPHERTEXT_BYTES, SWAP_LEN_BYTES};
+use super::{SwapPlaintext, LIMITED_SWAP_CIPHERTEXT_BYTES, SWAP_CIPHERTEXT_BYTES};
 
+/// An encrypted swap plaintext.
+///
+/// This is [`SWAP_CIPHERTEXT_BYTES`] long, or [`LIMITED_SWAP_CIPHERTEXT_BYTES`]
+/// long for swaps with a minimum output.
 #[derive(Debug, Clone)]
-pub struct SwapCiphertext(pub [u8; SWAP_CIPHERTEXT_BYTES]);
+pub struct SwapCiphertext(Vec<u8>);
 
 impl SwapCiphertext {
     pub fn decrypt(
@@ -23,28 +27,37 @@ impl SwapCiphertext {
         payload_key: &PayloadKey,
         commitment: note::StateCommitment,
     ) -> Result<SwapPlaintext> {
-        let swap_ciphertext = self.0;
         let decryption_result = payload_key
-            .decrypt_swap(swap_ciphertext.to_vec(), commitment)
+            .decrypt_swap(self.0.clone(), commitment)
             .map_err(|_| anyhow::anyhow!("unable to decrypt swap ciphertext"))?;
 
         // TODO: encapsulate plaintext encoding by making this a
         // pub(super) parse_decryption method on SwapPlaintext
         // and removing the TryFrom impls
-        let plaintext: [u8; SWAP_LEN_BYTES] = decryption_result
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("swap decryption result did not fit in plaintext len"))?;
-
-        plaintext.try_into().map_err(|_| {
+        decryption_result.as_slice().try_into().map_err(|_| {
             anyhow::anyhow!("unable to convert swap plaintext bytes into SwapPlaintext")
         })
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; SWAP_CIPHERTEXT_BYTES]> for SwapCiphertext {
+    fn from(bytes: [u8; SWAP_CIPHERTEXT_BYTES]) -> SwapCiphertext {
+        SwapCiphertext(bytes.to_vec())
+    }
 }
 
-impl TryFrom<[u8; SWAP_CIPHERTEXT_BYTES]> for SwapCiphertext {
+impl TryFrom<Vec<u8>> for SwapCiphertext {
     type Error = anyhow::Error;
 
-    fn try_from(bytes: [u8; SWAP_CIPHERTEXT_BYTES]) -> Result<SwapCiphertext, Self::Error> {
+    fn try_from(bytes: Vec<u8>) -> Result<SwapCiphertext, Self::Error> {
+        anyhow::ensure!(
+            bytes.len() == SWAP_CIPHERTEXT_BYTES || bytes.len() == LIMITED_SWAP_CIPHERTEXT_BYTES,
+            "incorrect length for swap ciphertext"
+        );
         Ok(SwapCiphertext(bytes))
     }
 }
@@ -53,6 +66,6 @@ impl TryFrom<&[u8]> for SwapCiphertext {
     type Error = anyhow::Error;
 
     fn try_from(slice: &[u8]) -> Result<SwapCiphertext, Self::Error> {
-        Ok(SwapCiphertext(slice[..].try_into()?))
+        slice.to_vec().try_into()
     }
 }
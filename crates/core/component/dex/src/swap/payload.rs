@@ -52,7 +52,7 @@ impl From<SwapPayload> for pb::SwapPayload {
     fn from(msg: SwapPayload) -> Self {
         pb::SwapPayload {
             commitment: Some(msg.commitment.into()),
-            encrypted_swap: msg.encrypted_swap.0.to_vec(),
+            encrypted_swap: msg.encrypted_swap.as_bytes().to_vec(),
         }
     }
 }
@@ -65,11 +65,10 @@ impl TryFrom<pb::SwapPayload> for SwapPayload {
             .commitment
             .ok_or_else(|| anyhow!("missing commitment"))?
             .try_into()?;
-        let encrypted_swap = SwapCiphertext(
-            msg.encrypted_swap
-                .try_into()
-                .map_err(|_| anyhow!("expected correct length swap ciphertext"))?,
-        );
+        let encrypted_swap: SwapCiphertext = msg
+            .encrypted_swap
+            .try_into()
+            .map_err(|_| anyhow!("expected correct length swap ciphertext"))?;
         Ok(Self {
             commitment,
             encrypted_swap,
@@ -10,7 +10,7 @@ use penumbra_proto::{
     core::keys::v1 as pb_keys, penumbra::core::component::dex::v1 as pb, DomainType,
 };
 use penumbra_tct::StateCommitment;
-use poseidon377::{hash_1, hash_4, hash_7};
+use poseidon377::{hash_1, hash_4, hash_5, hash_7};
 use rand_core::{CryptoRng, RngCore};
 
 use decaf377_ka as ka;
@@ -22,7 +22,7 @@ use penumbra_tct::r1cs::StateCommitmentVar;
 
 use crate::{BatchSwapOutputData, TradingPair, TradingPairVar};
 
-use super::{SwapCiphertext, SwapPayload, DOMAIN_SEPARATOR, SWAP_CIPHERTEXT_BYTES, SWAP_LEN_BYTES};
+use super::{SwapPayload, DOMAIN_SEPARATOR, LIMITED_SWAP_LEN_BYTES, SWAP_LEN_BYTES};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SwapPlaintext {
@@ -38,6 +38,9 @@ pub struct SwapPlaintext {
     pub claim_address: Address,
     // Swap rseed
     pub rseed: Rseed,
+    // Minimum output of the swap, in the asset not being swapped in. If the
+    // batch would pay out less, the input is refunded instead. Zero if unlimited.
+    pub min_output: Amount,
 }
 
 pub static OUTPUT_1_BLINDING_DOMAIN_SEPARATOR: Lazy<Fq> = Lazy::new(|| {
@@ -66,7 +69,7 @@ impl SwapPlaintext {
         let (output_1_rseed, output_2_rseed) = self.output_rseeds();
 
         let (lambda_1_i, lambda_2_i) =
-            batch_data.pro_rata_outputs((self.delta_1_i, self.delta_2_i));
+            batch_data.swap_outputs((self.delta_1_i, self.delta_2_i), self.min_output);
 
         let output_1_note = Note::from_parts(
             self.claim_address.clone(),
@@ -94,7 +97,32 @@ impl SwapPlaintext {
     // Constructs the unique asset ID for a swap as a poseidon hash of the input data for the swap.
     //
     // https://protocol.penumbra.zone/main/zswap/swap.html#swap-actions
+    //
+    // Swaps without a minimum output commit to their inputs exactly as they did before
+    // minimum outputs were introduced, so that their commitments are unchanged.
     pub fn swap_commitment(&self) -> StateCommitment {
+        let inputs = if self.min_output == Amount::zero() {
+            hash_4(
+                &DOMAIN_SEPARATOR,
+                (
+                    self.trading_pair.asset_1().0,
+                    self.trading_pair.asset_2().0,
+                    self.delta_1_i.into(),
+                    self.delta_2_i.into(),
+                ),
+            )
+        } else {
+            hash_5(
+                &DOMAIN_SEPARATOR,
+                (
+                    self.trading_pair.asset_1().0,
+                    self.trading_pair.asset_2().0,
+                    self.delta_1_i.into(),
+                    self.delta_2_i.into(),
+                    self.min_output.into(),
+                ),
+            )
+        };
         let inner = hash_7(
             &DOMAIN_SEPARATOR,
             (
@@ -106,15 +134,7 @@ impl SwapPlaintext {
                     .vartime_compress_to_field(),
                 *self.claim_address.transmission_key_s(),
                 Fq::from_le_bytes_mod_order(&self.claim_address.clue_key().0[..]),
-                hash_4(
-                    &DOMAIN_SEPARATOR,
-                    (
-                        self.trading_pair.asset_1().0,
-                        self.trading_pair.asset_2().0,
-                        self.delta_1_i.into(),
-                        self.delta_2_i.into(),
-                    ),
-                ),
+                inputs,
             ),
         );
 
@@ -132,15 +152,13 @@ impl SwapPlaintext {
     pub fn encrypt(&self, ovk: &OutgoingViewingKey) -> SwapPayload {
         let commitment = self.swap_commitment();
         let key = PayloadKey::derive_swap(ovk, commitment);
-        let swap_plaintext: [u8; SWAP_LEN_BYTES] = self.into();
-        let encryption_result = key.encrypt_swap(swap_plaintext.to_vec());
-
-        let ciphertext: [u8; SWAP_CIPHERTEXT_BYTES] = encryption_result
-            .try_into()
-            .expect("swap encryption result fits in ciphertext len");
+        let swap_plaintext: Vec<u8> = self.into();
+        let encryption_result = key.encrypt_swap(swap_plaintext);
 
         SwapPayload {
-            encrypted_swap: SwapCiphertext(ciphertext),
+            encrypted_swap: encryption_result
+                .try_into()
+                .expect("swap encryption result fits in ciphertext len"),
             commitment,
         }
     }
@@ -176,8 +194,18 @@ impl SwapPlaintext {
             claim_fee,
             claim_address,
             rseed,
+            min_output: Amount::zero(),
         }
     }
+
+    /// Sets the minimum output of the swap.
+    ///
+    /// The minimum output is denominated in the asset the swap is trading into,
+    /// so it can only be set on swaps with a single nonzero input.
+    pub fn with_min_output(mut self, min_output: Amount) -> SwapPlaintext {
+        self.min_output = min_output;
+        self
+    }
 }
 
 pub struct SwapPlaintextVar {
@@ -187,6 +215,7 @@ pub struct SwapPlaintextVar {
     pub delta_2_i: AmountVar,
     pub claim_address: AddressVar,
     pub rseed: FqVar,
+    pub min_output: AmountVar,
 }
 
 impl SwapPlaintextVar {
@@ -224,6 +253,20 @@ impl SwapPlaintextVar {
                 self.delta_2_i.amount.clone(),
             ),
         )?;
+        let inner_hash5 = poseidon377::r1cs::hash_5(
+            cs.clone(),
+            &domain_sep,
+            (
+                self.trading_pair.asset_1.asset_id.clone(),
+                self.trading_pair.asset_2.asset_id.clone(),
+                self.delta_1_i.amount.clone(),
+                self.delta_2_i.amount.clone(),
+                self.min_output.amount.clone(),
+            ),
+        )?;
+        // Unlimited swaps commit to their inputs as they did before minimum outputs existed.
+        let unlimited = self.min_output.amount.is_eq(&FqVar::zero())?;
+        let inputs = FqVar::conditionally_select(&unlimited, &inner_hash4, &inner_hash5)?;
 
         let inner = poseidon377::r1cs::hash_7(
             cs,
@@ -235,7 +278,7 @@ impl SwapPlaintextVar {
                 compressed_g_d,
                 self.claim_address.transmission_key().compress_to_field()?,
                 self.claim_address.clue_key(),
-                inner_hash4,
+                inputs,
             ),
         )?;
 
@@ -269,7 +312,7 @@ impl AllocVar<SwapPlaintext, Fq> for SwapPlaintextVar {
         let claim_address =
             AddressVar::new_variable(cs.clone(), || Ok(swap_plaintext.claim_address), mode)?;
         let rseed = FqVar::new_variable(
-            cs,
+            cs.clone(),
             || {
                 Ok(Fq::from_le_bytes_mod_order(
                     &swap_plaintext.rseed.to_bytes()[..],
@@ -277,6 +320,7 @@ impl AllocVar<SwapPlaintext, Fq> for SwapPlaintextVar {
             },
             mode,
         )?;
+        let min_output = AmountVar::new_variable(cs, || Ok(swap_plaintext.min_output), mode)?;
         Ok(Self {
             claim_fee,
             delta_1_i,
@@ -284,6 +328,7 @@ impl AllocVar<SwapPlaintext, Fq> for SwapPlaintextVar {
             delta_2_i,
             claim_address,
             rseed,
+            min_output,
         })
     }
 }
@@ -318,6 +363,11 @@ impl TryFrom<pb::SwapPlaintext> for SwapPlaintext {
                 .ok_or_else(|| anyhow::anyhow!("missing trading pair in SwapPlaintext"))?
                 .try_into()?,
             rseed: Rseed(plaintext.rseed.as_slice().try_into()?),
+            min_output: plaintext
+                .min_output
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -331,13 +381,17 @@ impl From<SwapPlaintext> for pb::SwapPlaintext {
             claim_address: Some(plaintext.claim_address.into()),
             trading_pair: Some(plaintext.trading_pair.into()),
             rseed: plaintext.rseed.to_bytes().to_vec(),
+            min_output: (plaintext.min_output != Amount::zero())
+                .then(|| plaintext.min_output.into()),
         }
     }
 }
 
-impl From<&SwapPlaintext> for [u8; SWAP_LEN_BYTES] {
-    fn from(swap: &SwapPlaintext) -> [u8; SWAP_LEN_BYTES] {
-        let mut bytes = [0u8; SWAP_LEN_BYTES];
+impl From<&SwapPlaintext> for Vec<u8> {
+    /// Encodes the swap plaintext, appending the minimum output only if it is
+    /// set, so that unlimited swaps keep the original encoding.
+    fn from(swap: &SwapPlaintext) -> Vec<u8> {
+        let mut bytes = vec![0u8; SWAP_LEN_BYTES];
         bytes[0..64].copy_from_slice(&swap.trading_pair.to_bytes());
         bytes[64..80].copy_from_slice(&swap.delta_1_i.to_le_bytes());
         bytes[80..96].copy_from_slice(&swap.delta_2_i.to_le_bytes());
@@ -346,12 +400,15 @@ impl From<&SwapPlaintext> for [u8; SWAP_LEN_BYTES] {
         let pb_address = pb_keys::Address::from(swap.claim_address.clone());
         bytes[144..224].copy_from_slice(&pb_address.inner);
         bytes[224..256].copy_from_slice(&swap.rseed.to_bytes());
+        if swap.min_output != Amount::zero() {
+            bytes.extend_from_slice(&swap.min_output.to_le_bytes());
+        }
         bytes
     }
 }
 
-impl From<SwapPlaintext> for [u8; SWAP_LEN_BYTES] {
-    fn from(swap: SwapPlaintext) -> [u8; SWAP_LEN_BYTES] {
+impl From<SwapPlaintext> for Vec<u8> {
+    fn from(swap: SwapPlaintext) -> Vec<u8> {
         (&swap).into()
     }
 }
@@ -360,7 +417,7 @@ impl TryFrom<&[u8]> for SwapPlaintext {
     type Error = Error;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-        if bytes.len() != SWAP_LEN_BYTES {
+        if bytes.len() != SWAP_LEN_BYTES && bytes.len() != LIMITED_SWAP_LEN_BYTES {
             anyhow::bail!("incorrect length for serialized swap plaintext");
         }
 
@@ -389,6 +446,23 @@ impl TryFrom<&[u8]> for SwapPlaintext {
         let rseed: [u8; 32] = bytes[224..256]
             .try_into()
             .map_err(|_| anyhow!("error fetching rseed bytes"))?;
+        let min_output = match bytes.get(SWAP_LEN_BYTES..LIMITED_SWAP_LEN_BYTES) {
+            Some(min_output_bytes) => {
+                let min_output = Amount::from_le_bytes(
+                    min_output_bytes
+                        .try_into()
+                        .map_err(|_| anyhow!("error fetching min output bytes"))?,
+                );
+                // A zero minimum output must use the shorter encoding, so that each
+                // swap has exactly one encoding.
+                anyhow::ensure!(
+                    min_output != Amount::zero(),
+                    "swap plaintext has an explicit zero minimum output"
+                );
+                min_output
+            }
+            None => Amount::zero(),
+        };
 
         Ok(SwapPlaintext {
             trading_pair: tp_bytes
@@ -402,6 +476,7 @@ impl TryFrom<&[u8]> for SwapPlaintext {
             }),
             claim_address: pb_address.try_into()?,
             rseed: Rseed(rseed),
+            min_output,
         })
     }
 }
@@ -420,6 +495,7 @@ mod tests {
     use rand_core::OsRng;
 
     use super::*;
+    use crate::swap::SwapCiphertext;
     use penumbra_asset::{asset, Value};
     use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
 
@@ -466,4 +542,51 @@ mod tests {
 
         assert_eq!(plaintext, swap);
     }
+
+    #[test]
+    /// Check that only swaps with a minimum output use the extended encoding.
+    fn limited_swap_encoding() {
+        let mut rng = OsRng;
+
+        let seed_phrase = SeedPhrase::generate(rng);
+        let sk = SpendKey::from_seed_phrase_bip44(seed_phrase, &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let ovk = fvk.outgoing();
+        let (dest, _dtk_d) = fvk.incoming().payment_address(0u32.into());
+        let trading_pair = TradingPair::new(
+            asset::Cache::with_known_assets()
+                .get_unit("upenumbra")
+                .unwrap()
+                .id(),
+            asset::Cache::with_known_assets()
+                .get_unit("nala")
+                .unwrap()
+                .id(),
+        );
+
+        let swap = SwapPlaintext::new(
+            &mut rng,
+            trading_pair,
+            100000u64.into(),
+            0u64.into(),
+            Fee(Value {
+                amount: 0u64.into(),
+                asset_id: trading_pair.asset_1(),
+            }),
+            dest,
+        );
+        let limited_swap = swap.clone().with_min_output(5000u64.into());
+
+        let bytes: Vec<u8> = (&swap).into();
+        assert_eq!(bytes.len(), SWAP_LEN_BYTES);
+        let limited_bytes: Vec<u8> = (&limited_swap).into();
+        assert_eq!(limited_bytes.len(), LIMITED_SWAP_LEN_BYTES);
+        assert_eq!(bytes[..], limited_bytes[..SWAP_LEN_BYTES]);
+        assert_ne!(swap.swap_commitment(), limited_swap.swap_commitment());
+
+        let ciphertext = limited_swap.encrypt(ovk).encrypted_swap;
+        let plaintext = SwapCiphertext::decrypt(&ciphertext, ovk, limited_swap.swap_commitment())
+            .expect("can decrypt limited swap");
+        assert_eq!(plaintext, limited_swap);
+    }
 }
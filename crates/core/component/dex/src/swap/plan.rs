@@ -52,6 +52,7 @@ impl SwapPlan {
             delta_2_i: self.swap_plaintext.delta_2_i,
             fee_commitment: self.fee_commitment(),
             payload: self.swap_plaintext.encrypt(fvk.outgoing()),
+            min_output: self.swap_plaintext.min_output,
         }
     }
 
@@ -69,6 +70,7 @@ impl SwapPlan {
                 balance_commitment,
                 swap_commitment: self.swap_plaintext.swap_commitment(),
                 fee_commitment: self.fee_commitment(),
+                min_output: self.swap_plaintext.min_output,
             },
             SwapProofPrivate {
                 fee_blinding: self.fee_blinding,
@@ -22,6 +22,7 @@ use penumbra_asset::{
     Value,
 };
 use penumbra_keys::{keys::Diversifier, Address};
+use penumbra_num::{Amount, AmountVar};
 use penumbra_shielded_pool::Rseed;
 
 use crate::{
@@ -40,6 +41,8 @@ pub struct SwapProofPublic {
     pub swap_commitment: tct::StateCommitment,
     /// A commitment to the fee that was paid.
     pub fee_commitment: balance::Commitment,
+    /// The minimum output of the swap, or zero if the swap is unlimited.
+    pub min_output: Amount,
 }
 
 /// The private inputs to a [`SwapProof`].
@@ -66,6 +69,10 @@ fn check_satisfaction(public: &SwapProofPublic, private: &SwapProofPrivate) -> R
         anyhow::bail!("fee commitment did not match public input");
     }
 
+    if private.swap_plaintext.min_output != public.min_output {
+        anyhow::bail!("min output did not match public input");
+    }
+
     let balance_1 = -Balance::from(private.swap_plaintext.delta_1_value());
     let balance_2 = -Balance::from(private.swap_plaintext.delta_2_value());
     let transparent_blinding = Fr::from(0u64);
@@ -116,7 +123,8 @@ impl ConstraintSynthesizer<Fq> for SwapCircuit {
         let claimed_swap_commitment =
             StateCommitmentVar::new_input(cs.clone(), || Ok(self.public.swap_commitment))?;
         let claimed_fee_commitment =
-            BalanceCommitmentVar::new_input(cs, || Ok(self.public.fee_commitment))?;
+            BalanceCommitmentVar::new_input(cs.clone(), || Ok(self.public.fee_commitment))?;
+        let claimed_min_output = AmountVar::new_input(cs, || Ok(self.public.min_output))?;
 
         // Swap commitment integrity check
         let swap_commitment = swap_plaintext_var.commit()?;
@@ -127,6 +135,9 @@ impl ConstraintSynthesizer<Fq> for SwapCircuit {
         let fee_commitment = fee_balance.commit(fee_blinding_var)?;
         claimed_fee_commitment.enforce_equal(&fee_commitment)?;
 
+        // Min output integrity check
+        claimed_min_output.enforce_equal(&swap_plaintext_var.min_output)?;
+
         // Reconstruct swap action balance commitment
         let transparent_blinding_var = UInt8::constant_vec(&[0u8; 32]);
         let balance_1 = BalanceVar::from_negative_value_var(swap_plaintext_var.delta_1_value());
@@ -175,6 +186,7 @@ impl DummyWitness for SwapCircuit {
             }),
             claim_address: address,
             rseed: Rseed([1u8; 32]),
+            min_output: Amount::zero(),
         };
 
         Self {
@@ -186,6 +198,7 @@ impl DummyWitness for SwapCircuit {
                 swap_commitment: swap_plaintext.swap_commitment(),
                 fee_commitment: balance::Commitment(decaf377::Element::GENERATOR),
                 balance_commitment: balance::Commitment(decaf377::Element::GENERATOR),
+                min_output: Amount::zero(),
             },
         }
     }
@@ -219,6 +232,7 @@ impl SwapProof {
     /// * balance commitment,
     /// * swap commitment,
     /// * fee commimtment,
+    /// * min output,
     ///
     // Commented out, but this may be useful when debugging proof verification failures,
     // to check that the proof data and verification keys are consistent.
@@ -254,6 +268,12 @@ impl SwapProof {
                 .to_field_elements()
                 .context("fee_commitment should be a Bls12-377 field member")?,
         );
+        public_inputs.extend(
+            public
+                .min_output
+                .to_field_elements()
+                .context("min_output should be a Bls12-377 field member")?,
+        );
 
         tracing::trace!(?public_inputs);
         let start = std::time::Instant::now();
@@ -295,7 +315,6 @@ mod tests {
     use super::*;
     use penumbra_asset::{Balance, Value};
     use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
-    use penumbra_num::Amount;
     use proptest::prelude::*;
 
     fn fr_strategy() -> BoxedStrategy<Fr> {
@@ -328,6 +347,7 @@ mod tests {
                 claim_fee: fee,
                 claim_address,
                 rseed,
+                min_output: Amount::zero(),
             };
             let fee_commitment = swap_plaintext.claim_fee.commit(fee_blinding);
             let swap_commitment = swap_plaintext.swap_commitment();
@@ -350,7 +370,7 @@ mod tests {
             balance -= value_fee;
             let balance_commitment = balance.commit(fee_blinding);
 
-            let public = SwapProofPublic { balance_commitment, swap_commitment, fee_commitment };
+            let public = SwapProofPublic { balance_commitment, swap_commitment, fee_commitment, min_output: Amount::zero() };
             let private = SwapProofPrivate { fee_blinding, swap_plaintext };
 
             (public, private)
@@ -365,6 +385,35 @@ mod tests {
         }
     }
 
+    prop_compose! {
+        // This strategy generates a valid swap statement with a minimum output.
+        fn arb_valid_limited_swap_statement()(statement in arb_valid_swap_statement(), min_output in 1u64..) -> (SwapProofPublic, SwapProofPrivate) {
+            let (mut public, mut private) = statement;
+            private.swap_plaintext.min_output = min_output.into();
+            public.swap_commitment = private.swap_plaintext.swap_commitment();
+            public.min_output = min_output.into();
+
+            (public, private)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn limited_swap_proof_happy_path((public, private) in arb_valid_limited_swap_statement()) {
+            assert!(check_satisfaction(&public, &private).is_ok());
+            assert!(check_circuit_satisfaction(public, private).is_ok());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn swap_proof_invalid_min_output((mut public, private) in arb_valid_limited_swap_statement()) {
+            public.min_output = Amount::zero();
+            assert!(check_satisfaction(&public, &private).is_err());
+            assert!(check_circuit_satisfaction(public, private).is_err());
+        }
+    }
+
     prop_compose! {
         // This strategy generates a swap statement with an invalid fee blinding factor.
         fn arb_invalid_swap_statement_fee_commitment()(fee_blinding in fr_strategy(), invalid_fee_blinding in fr_strategy(), address_index in any::<u32>(), value1_amount in any::<u64>(), seed_phrase_randomness in any::<[u8; 32]>(), rseed_randomness in any::<[u8; 32]>()) -> (SwapProofPublic, SwapProofPrivate) {
@@ -390,6 +439,7 @@ mod tests {
                 claim_fee: fee,
                 claim_address,
                 rseed,
+                min_output: Amount::zero(),
             };
             let swap_commitment = swap_plaintext.swap_commitment();
 
@@ -413,7 +463,7 @@ mod tests {
 
             let invalid_fee_commitment = swap_plaintext.claim_fee.commit(invalid_fee_blinding);
 
-            let public = SwapProofPublic { balance_commitment, swap_commitment, fee_commitment: invalid_fee_commitment };
+            let public = SwapProofPublic { balance_commitment, swap_commitment, fee_commitment: invalid_fee_commitment, min_output: Amount::zero() };
             let private = SwapProofPrivate { fee_blinding, swap_plaintext };
 
             (public, private)
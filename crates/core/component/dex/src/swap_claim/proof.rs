@@ -22,7 +22,7 @@ use penumbra_keys::keys::{
     AuthorizationKeyVar, Bip44Path, IncomingViewingKeyVar, NullifierKey, NullifierKeyVar,
     SeedPhrase, SpendKey,
 };
-use penumbra_num::{Amount, AmountVar};
+use penumbra_num::{fixpoint::U128x128, Amount, AmountVar};
 use penumbra_sct::{Nullifier, NullifierVar};
 use penumbra_shielded_pool::{
     note::{self, NoteVar},
@@ -135,10 +135,13 @@ fn check_satisfaction(
         anyhow::bail!("trading pair did not match public input");
     }
 
-    let (lambda_1, lambda_2) = public.output_data.pro_rata_outputs((
-        private.swap_plaintext.delta_1_i,
-        private.swap_plaintext.delta_2_i,
-    ));
+    let (lambda_1, lambda_2) = public.output_data.swap_outputs(
+        (
+            private.swap_plaintext.delta_1_i,
+            private.swap_plaintext.delta_2_i,
+        ),
+        private.swap_plaintext.min_output,
+    );
     if lambda_1 != private.lambda_1 {
         anyhow::bail!("lambda_1 did not match public input");
     }
@@ -265,10 +268,12 @@ impl ConstraintSynthesizer<Fq> for SwapClaimCircuit {
             .trading_pair
             .enforce_equal(&swap_plaintext_var.trading_pair)?;
 
-        // Output amounts integrity
-        let (computed_lambda_1_i, computed_lambda_2_i) = output_data_var.pro_rata_outputs(
+        // Output amounts integrity. Swaps that were not filled because of their
+        // minimum output are refunded their inputs.
+        let (computed_lambda_1_i, computed_lambda_2_i) = output_data_var.swap_outputs(
             swap_plaintext_var.delta_1_i,
             swap_plaintext_var.delta_2_i,
+            swap_plaintext_var.min_output,
             cs,
         )?;
         computed_lambda_1_i.enforce_equal(&lambda_1_i_var)?;
@@ -337,6 +342,7 @@ impl DummyWitness for SwapClaimCircuit {
             }),
             claim_address: address,
             rseed: Rseed([1u8; 32]),
+            min_output: Amount::zero(),
         };
         let mut sct = tct::Tree::new();
         let swap_commitment = swap_plaintext.swap_commitment();
@@ -358,6 +364,8 @@ impl DummyWitness for SwapClaimCircuit {
             height: 0,
             trading_pair: swap_plaintext.trading_pair,
             sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         };
         let note_blinding_1 = Fq::from(1u64);
         let note_blinding_2 = Fq::from(1u64);
@@ -619,6 +627,7 @@ mod tests {
             claim_fee: fee,
             claim_address,
             rseed,
+            min_output: Amount::zero(),
         };
         let fee = swap_plaintext.clone().claim_fee;
         let mut sct = tct::Tree::new();
@@ -641,6 +650,8 @@ mod tests {
             height: height.into(),
             trading_pair: swap_plaintext.trading_pair,
             sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         };
         let (lambda_1, lambda_2) = output_data.pro_rata_outputs((delta_1_i, delta_2_i));
 
@@ -724,6 +735,102 @@ mod tests {
         }
     }
 
+    prop_compose! {
+        // This strategy generates a swap claim statement for a swap whose minimum output
+        // was not met by the batch, so its input is refunded.
+        fn arb_valid_swapclaim_statement_refunded()(seed_phrase_randomness in any::<[u8; 32]>(), rseed_randomness in any::<[u8; 32]>(), value1_amount in 2..200u64, test_bsod in unfilled_bsod_strategy()) -> (SwapClaimProofPublic, SwapClaimProofPrivate) {
+            let seed_phrase = SeedPhrase::from_randomness(&seed_phrase_randomness);
+        let sk_recipient = SpendKey::from_seed_phrase_bip44(seed_phrase, &Bip44Path::new(0));
+        let fvk_recipient = sk_recipient.full_viewing_key();
+        let ivk_recipient = fvk_recipient.incoming();
+        let (claim_address, _dtk_d) = ivk_recipient.payment_address(0u32.into());
+        let nk = *sk_recipient.nullifier_key();
+        let ak = *fvk_recipient.spend_verification_key();
+
+        let gm = asset::Cache::with_known_assets().get_unit("gm").unwrap();
+        let gn = asset::Cache::with_known_assets().get_unit("gn").unwrap();
+        let trading_pair = TradingPair::new(gm.id(), gn.id());
+
+        let delta_1_i = Amount::from(value1_amount);
+        let delta_2_i = Amount::from(0u64);
+        let fee = Fee::default();
+
+        let rseed = Rseed(rseed_randomness);
+        let swap_plaintext = SwapPlaintext {
+            trading_pair,
+            delta_1_i,
+            delta_2_i,
+            claim_fee: fee,
+            claim_address,
+            rseed,
+            // The swap asks for 3 units of output per unit of input.
+            min_output: Amount::from(value1_amount * 3),
+        };
+        let claim_fee = swap_plaintext.clone().claim_fee;
+        let mut sct = tct::Tree::new();
+        let swap_commitment = swap_plaintext.swap_commitment();
+        sct.insert(tct::Witness::Keep, swap_commitment).unwrap();
+        let anchor = sct.root();
+        let state_commitment_proof = sct.witness(swap_commitment).unwrap();
+        let position = state_commitment_proof.position();
+        let nullifier = Nullifier::derive(&nk, position, &swap_commitment);
+        let epoch_duration = 20;
+        let height = epoch_duration * position.epoch() + position.block();
+
+        // The batch only filled 1=>2 swaps with a limit price of up to 2.
+        let output_data = BatchSwapOutputData {
+            delta_1: test_bsod.delta_1,
+            delta_2: test_bsod.delta_2,
+            lambda_1: test_bsod.lambda_1,
+            lambda_2: test_bsod.lambda_2,
+            unfilled_1: test_bsod.unfilled_1,
+            unfilled_2: test_bsod.unfilled_2,
+            height: height.into(),
+            trading_pair: swap_plaintext.trading_pair,
+            sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::from(2u64),
+            limit_price_2_for_1: U128x128::default(),
+        };
+        let (lambda_1, lambda_2) = (delta_1_i, delta_2_i);
+
+        let (output_rseed_1, output_rseed_2) = swap_plaintext.output_rseeds();
+        let note_blinding_1 = output_rseed_1.derive_note_blinding();
+        let note_blinding_2 = output_rseed_2.derive_note_blinding();
+        let (output_1_note, output_2_note) = swap_plaintext.output_notes(&output_data);
+        let note_commitment_1 = output_1_note.commit();
+        let note_commitment_2 = output_2_note.commit();
+
+        let public = SwapClaimProofPublic {
+            anchor,
+            nullifier,
+            claim_fee,
+            output_data,
+            note_commitment_1,
+            note_commitment_2,
+        };
+        let private = SwapClaimProofPrivate {
+            swap_plaintext,
+            state_commitment_proof,
+            ak,
+            nk,
+            lambda_1,
+            lambda_2,
+            note_blinding_1,
+            note_blinding_2,
+        };
+
+        (public, private)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn swap_claim_proof_happy_path_refunded((public, private) in arb_valid_swapclaim_statement_refunded()) {
+            assert!(check_satisfaction(&public, &private).is_ok());
+            assert!(check_circuit_satisfaction(public, private).is_ok());
+        }
+    }
+
     prop_compose! {
         // This strategy is invalid because the fee is not equal to the claim fee.
         fn arb_invalid_swapclaim_statement_fee()(seed_phrase_randomness in any::<[u8; 32]>(), rseed_randomness in any::<[u8; 32]>(), value1_amount in 2..200u64, fee_amount in any::<u64>(), test_bsod in unfilled_bsod_strategy()) -> (SwapClaimProofPublic, SwapClaimProofPrivate) {
@@ -751,6 +858,7 @@ mod tests {
             claim_fee: fee,
             claim_address,
             rseed,
+            min_output: Amount::zero(),
         };
         let incorrect_fee = Fee::from_staking_token_amount(Amount::from(fee_amount));
         let mut sct = tct::Tree::new();
@@ -772,7 +880,9 @@ mod tests {
             unfilled_2: test_bsod.unfilled_2,
             height: height.into(),
             trading_pair: swap_plaintext.trading_pair,
-            sct_position_prefix: Default::default()
+            sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         };
         let (lambda_1, lambda_2) = output_data.pro_rata_outputs((delta_1_i, delta_2_i));
 
@@ -842,6 +952,7 @@ mod tests {
             claim_fee: fee,
             claim_address,
             rseed,
+            min_output: Amount::zero(),
         };
         let incorrect_fee = Fee::from_staking_token_amount(Amount::from(fee_amount));
         let mut sct = tct::Tree::new();
@@ -872,7 +983,9 @@ mod tests {
             unfilled_2: test_bsod.unfilled_2,
             height: height.into(),
             trading_pair: swap_plaintext.trading_pair,
-            sct_position_prefix: Default::default()
+            sct_position_prefix: Default::default(),
+            limit_price_1_for_2: U128x128::default(),
+            limit_price_2_for_1: U128x128::default(),
         };
         let (lambda_1, lambda_2) = output_data.pro_rata_outputs((delta_1_i, delta_2_i));
 
@@ -50,14 +50,16 @@ impl Component for Distributions {
         let state = Arc::get_mut(state).context("state should be unique")?;
         let new_issuance = state.compute_new_issuance().await?;
         tracing::debug!(?new_issuance, "computed new issuance for epoch");
-        Ok(state.distribute(new_issuance).await)
+        let new_lp_issuance = state.compute_new_lp_issuance().await?;
+        tracing::debug!(?new_lp_issuance, "computed new lp issuance for epoch");
+        Ok(state.distribute(new_issuance, new_lp_issuance).await)
     }
 }
 
 #[async_trait]
 trait DistributionManager: StateWriteExt {
-    /// Compute the total new issuance of staking tokens for this epoch.
-    async fn compute_new_issuance(&self) -> Result<Amount> {
+    /// Compute the number of blocks elapsed in the current epoch.
+    async fn blocks_in_epoch(&self) -> Result<u64> {
         use penumbra_sct::component::clock::EpochRead;
 
         let current_block_height = self.get_block_height().await?;
@@ -66,6 +68,13 @@ trait DistributionManager: StateWriteExt {
             .checked_sub(current_epoch.start_height)
             .unwrap_or_else(|| panic!("epoch start height is less than or equal to current block height (epoch_start={}, current_height={}", current_epoch.start_height, current_block_height));
 
+        Ok(num_blocks)
+    }
+
+    /// Compute the total new issuance of staking tokens for this epoch.
+    async fn compute_new_issuance(&self) -> Result<Amount> {
+        let num_blocks = self.blocks_in_epoch().await?;
+
         // TODO(erwan): Will make the distribution chain param an `Amount`
         // in a subsequent PR. Want to avoid conflicts with other in-flight changes.
         let staking_issuance_per_block = self
@@ -88,9 +97,32 @@ trait DistributionManager: StateWriteExt {
         Ok(Amount::from(new_issuance_for_epoch))
     }
 
+    /// Compute the total new issuance of staking tokens for liquidity providers this epoch.
+    async fn compute_new_lp_issuance(&self) -> Result<Amount> {
+        let num_blocks = self.blocks_in_epoch().await?;
+
+        let lp_issuance_per_block = self
+            .get_distributions_params()
+            .await?
+            .liquidity_provider_issuance_per_block as u128;
+
+        tracing::debug!(
+            number_of_blocks_in_epoch = num_blocks,
+            lp_issuance_per_block,
+            "calculating lp issuance per epoch"
+        );
+
+        let new_lp_issuance_for_epoch = lp_issuance_per_block
+            .checked_mul(num_blocks as u128) /* Safe to cast a `u64` to `u128` */
+            .expect("infaillible unless issuance is pathological");
+
+        Ok(Amount::from(new_lp_issuance_for_epoch))
+    }
+
     /// Update the object store with the new issuance of staking tokens for this epoch.
-    async fn distribute(&mut self, new_issuance: Amount) {
-        self.set_staking_token_issuance_for_epoch(new_issuance)
+    async fn distribute(&mut self, new_issuance: Amount, new_lp_issuance: Amount) {
+        self.set_staking_token_issuance_for_epoch(new_issuance);
+        self.set_lp_token_issuance_for_epoch(new_lp_issuance);
     }
 }
 
@@ -3,6 +3,11 @@ pub fn staking_token_issuance_for_epoch() -> &'static str {
     "distributions/staking_token_issuance_for_epoch"
 }
 
+// The amount of staking tokens issued to liquidity providers for this epoch.
+pub fn lp_token_issuance_for_epoch() -> &'static str {
+    "distributions/lp_token_issuance_for_epoch"
+}
+
 pub fn distributions_parameters() -> &'static str {
     "distributions/parameters"
 }
@@ -18,6 +18,11 @@ pub trait StateReadExt: StateRead {
     fn get_staking_token_issuance_for_epoch(&self) -> Option<Amount> {
         self.object_get(&state_key::staking_token_issuance_for_epoch())
     }
+
+    /// Gets the amount of staking tokens to distribute to liquidity providers for this epoch.
+    fn get_lp_token_issuance_for_epoch(&self) -> Option<Amount> {
+        self.object_get(&state_key::lp_token_issuance_for_epoch())
+    }
 }
 
 impl<T: StateRead + ?Sized> StateReadExt for T {}
@@ -29,6 +34,11 @@ pub trait StateWriteExt: StateWrite + StateReadExt {
         self.object_put(state_key::staking_token_issuance_for_epoch(), issuance);
     }
 
+    /// Set the total amount of staking tokens issued to liquidity providers for this epoch.
+    fn set_lp_token_issuance_for_epoch(&mut self, issuance: Amount) {
+        self.object_put(state_key::lp_token_issuance_for_epoch(), issuance);
+    }
+
     /// Set the Distributions parameters in the JMT.
     fn put_distributions_params(&mut self, params: DistributionsParameters) {
         self.put(state_key::distributions_parameters().into(), params)
@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 )]
 pub struct DistributionsParameters {
     pub staking_issuance_per_block: u64,
+    /// The amount of staking token issued per block to DEX liquidity providers.
+    pub liquidity_provider_issuance_per_block: u64,
 }
 
 impl DomainType for DistributionsParameters {
@@ -21,6 +23,7 @@ impl TryFrom<pb::DistributionsParameters> for DistributionsParameters {
     fn try_from(msg: pb::DistributionsParameters) -> anyhow::Result<Self> {
         Ok(DistributionsParameters {
             staking_issuance_per_block: msg.staking_issuance_per_block,
+            liquidity_provider_issuance_per_block: msg.liquidity_provider_issuance_per_block,
         })
     }
 }
@@ -29,6 +32,7 @@ impl From<DistributionsParameters> for pb::DistributionsParameters {
     fn from(params: DistributionsParameters) -> Self {
         pb::DistributionsParameters {
             staking_issuance_per_block: params.staking_issuance_per_block,
+            liquidity_provider_issuance_per_block: params.liquidity_provider_issuance_per_block,
         }
     }
 }
@@ -37,6 +41,7 @@ impl Default for DistributionsParameters {
     fn default() -> Self {
         Self {
             staking_issuance_per_block: 1_000_000,
+            liquidity_provider_issuance_per_block: 0,
         }
     }
 }
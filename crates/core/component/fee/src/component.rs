@@ -13,7 +13,7 @@ use penumbra_proto::state::StateWriteProto as _;
 use tendermint::abci;
 use tracing::instrument;
 
-pub use fee_pay::FeePay;
+pub use fee_pay::{FeePay, FeePaymentError};
 pub use view::{StateReadExt, StateWriteExt};
 
 // Fee component
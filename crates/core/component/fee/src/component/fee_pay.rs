@@ -1,7 +1,8 @@
 use anyhow::{ensure, Result};
 use async_trait::async_trait;
 use cnidarium::StateWrite;
-use penumbra_asset::Value;
+use penumbra_asset::{asset, Value};
+use penumbra_num::Amount;
 use penumbra_proto::core::component::fee::v1 as pb;
 use penumbra_proto::state::StateWriteProto as _;
 
@@ -28,9 +29,7 @@ pub trait FeePay: StateWrite {
             alt_gas_prices
                 .into_iter()
                 .find(|prices| prices.asset_id == fee.asset_id())
-                .ok_or_else(|| {
-                    anyhow::anyhow!("fee token {} not recognized by the chain", fee.asset_id())
-                })?
+                .ok_or(FeePaymentError::UnrecognizedToken(fee.asset_id()))?
         };
 
         // Double check that the gas price assets match.
@@ -45,12 +44,13 @@ pub trait FeePay: StateWrite {
         let base_fee = current_gas_prices.fee(&gas_used);
 
         // The provided fee must be at least the base fee.
-        ensure!(
-            fee.amount() >= base_fee.amount(),
-            "fee must be greater than or equal to the transaction base price (supplied: {}, base: {})",
-            fee.amount(),
-            base_fee.amount(),
-        );
+        if fee.amount() < base_fee.amount() {
+            return Err(FeePaymentError::TooLow {
+                supplied: fee.amount(),
+                base: base_fee.amount(),
+            }
+            .into());
+        }
 
         // Otherwise, the fee less the base fee is the proposer tip.
         let tip = Fee(Value {
@@ -75,3 +75,16 @@ pub trait FeePay: StateWrite {
 }
 
 impl<S: StateWrite + ?Sized> FeePay for S {}
+
+/// The reason a fee payment was rejected.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum FeePaymentError {
+    /// The fee is paid in a token the chain doesn't accept fees in.
+    #[error("fee token {0} not recognized by the chain")]
+    UnrecognizedToken(asset::Id),
+    /// The fee is lower than the base fee for the gas used.
+    #[error(
+        "fee must be greater than or equal to the transaction base price (supplied: {supplied}, base: {base})"
+    )]
+    TooLow { supplied: Amount, base: Amount },
+}
@@ -60,6 +60,14 @@ impl Gas {
             execution: 0,
         }
     }
+
+    /// Returns the total amount of gas consumed, across all resources.
+    pub fn total(&self) -> u64 {
+        self.block_space
+            .saturating_add(self.compact_block_space)
+            .saturating_add(self.verification)
+            .saturating_add(self.execution)
+    }
 }
 
 impl Add for Gas {
@@ -119,6 +127,18 @@ impl GasPrices {
         Self::default()
     }
 
+    /// Returns the cost of a given gas vector at these prices, in thousandths of a
+    /// base unit of the fee token.
+    ///
+    /// Unlike [`GasPrices::fee`], the cost of each resource isn't rounded down to a
+    /// whole base unit, so that small differences in gas usage are still accounted for.
+    pub fn cost(&self, gas: &Gas) -> u128 {
+        u128::from(self.block_space_price) * u128::from(gas.block_space)
+            + u128::from(self.compact_block_space_price) * u128::from(gas.compact_block_space)
+            + u128::from(self.verification_price) * u128::from(gas.verification)
+            + u128::from(self.execution_price) * u128::from(gas.execution)
+    }
+
     /// Use these gas prices to calculate the fee for a given gas vector.
     pub fn fee(&self, gas: &Gas) -> Fee {
         let amount = Amount::from(
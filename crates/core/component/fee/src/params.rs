@@ -1,9 +1,11 @@
+use penumbra_asset::{asset, STAKING_TOKEN_ASSET_ID};
+use penumbra_num::Amount;
 use penumbra_proto::penumbra::core::component::fee::v1 as pb;
 
 use penumbra_proto::DomainType;
 use serde::{Deserialize, Serialize};
 
-use crate::GasPrices;
+use crate::{Fee, Gas, GasPrices};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(try_from = "pb::FeeParameters", into = "pb::FeeParameters")]
@@ -12,6 +14,45 @@ pub struct FeeParameters {
     pub fixed_alt_gas_prices: Vec<GasPrices>,
 }
 
+impl FeeParameters {
+    /// Returns the gas prices for fees paid in `asset_id`, or `None` if the chain
+    /// does not accept that asset as a fee token.
+    pub fn gas_prices_for(&self, asset_id: asset::Id) -> Option<GasPrices> {
+        if asset_id == *STAKING_TOKEN_ASSET_ID {
+            return Some(self.fixed_gas_prices);
+        }
+        // This does a linear scan, but we expect a small number of alt gas prices.
+        self.fixed_alt_gas_prices
+            .iter()
+            .find(|prices| prices.asset_id == asset_id)
+            .copied()
+    }
+
+    /// Returns the value of `fee`, paid by a transaction consuming `gas`, in terms of
+    /// the staking token.
+    ///
+    /// Fees paid in an alternative fee token are converted using the ratio between the
+    /// base fees for `gas` in the staking token and in that token, so that paying some
+    /// multiple of the base fee is worth the same regardless of the fee token.
+    ///
+    /// Returns `None` if the fee token is not accepted by the chain, or if its base fee
+    /// for `gas` is zero, in which case the fee can't be valued.
+    pub fn staking_token_equivalent(&self, fee: &Fee, gas: &Gas) -> Option<Amount> {
+        let prices = self.gas_prices_for(fee.asset_id())?;
+        if prices.asset_id == *STAKING_TOKEN_ASSET_ID {
+            return Some(fee.amount());
+        }
+
+        let alt_base_fee = prices.fee(gas).amount().value();
+        if alt_base_fee == 0 {
+            return None;
+        }
+        let base_fee = self.fixed_gas_prices.fee(gas).amount().value();
+        let equivalent = fee.amount().value().saturating_mul(base_fee) / alt_base_fee;
+        Some(equivalent.into())
+    }
+}
+
 impl DomainType for FeeParameters {
     type Proto = pb::FeeParameters;
 }
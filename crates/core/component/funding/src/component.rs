@@ -59,7 +59,7 @@ impl Component for Funding {
         use penumbra_community_pool::StateWriteExt as _;
         use penumbra_distributions::component::StateReadExt as _;
         use penumbra_sct::CommitmentSource;
-        use penumbra_shielded_pool::component::NoteManager;
+        use penumbra_shielded_pool::component::{NoteManager, SupplyWrite};
         use penumbra_stake::funding_stream::Recipient;
         use penumbra_stake::StateReadExt as _;
 
@@ -148,6 +148,13 @@ impl Component for Funding {
                             .await;
                     }
                 }
+
+                state
+                    .increase_token_supply(Value {
+                        amount: reward_amount_for_stream.into(),
+                        asset_id: *STAKING_TOKEN_ASSET_ID,
+                    })
+                    .await?;
             }
         }
 
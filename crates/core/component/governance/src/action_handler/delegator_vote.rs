@@ -3,12 +3,14 @@ use ark_ff::Zero;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use decaf377::Fr;
+use penumbra_asset::Value;
 use penumbra_proof_params::DELEGATOR_VOTE_PROOF_VERIFICATION_KEY;
 use penumbra_proto::StateWriteProto as _;
+use penumbra_shielded_pool::component::SupplyWrite;
 use penumbra_txhash::TransactionContext;
 
 use crate::{
-    event, DelegatorVote, DelegatorVoteBody, DelegatorVoteProofPublic,
+    event, DelegatorVote, DelegatorVoteBody, DelegatorVoteProofPublic, VotingReceiptToken,
     {component::StateWriteExt, StateReadExt},
 };
 use cnidarium_component::ActionHandler;
@@ -91,6 +93,13 @@ impl ActionHandler for DelegatorVote {
             .cast_delegator_vote(*proposal, identity_key, *vote, nullifier, *unbonded_amount)
             .await?;
 
+        state
+            .increase_token_supply(Value {
+                amount: *unbonded_amount,
+                asset_id: VotingReceiptToken::new(*proposal).id(),
+            })
+            .await?;
+
         state.record_proto(event::delegator_vote(self));
 
         Ok(())
@@ -2,7 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use penumbra_proto::StateWriteProto as _;
-use penumbra_shielded_pool::component::AssetRegistry;
+use penumbra_shielded_pool::component::{AssetRegistry, SupplyWrite};
 
 use crate::action_handler::ActionHandler;
 use crate::component::{StateReadExt as _, StateWriteExt as _};
@@ -65,6 +65,10 @@ impl ActionHandler for ProposalDepositClaim {
             // Set the proposal state to claimed
             state.put_proposal_state(*proposal, ProposalState::Claimed { outcome });
 
+            // The proposal NFT is exchanged for a claimed one, and the deposit
+            // is minted back unless the proposal was slashed.
+            state.record_supply_change(self.balance()).await?;
+
             state.record_proto(event::proposal_deposit_claim(self));
         } else {
             anyhow::bail!("proposal {} is not in finished state", proposal);
@@ -2,7 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
 use penumbra_proto::StateWriteProto as _;
-use penumbra_shielded_pool::component::AssetRegistry;
+use penumbra_shielded_pool::component::{AssetRegistry, SupplyWrite};
 
 use crate::{
     action_handler::ActionHandler,
@@ -47,6 +47,9 @@ impl ActionHandler for ProposalWithdraw {
             .register_denom(&ProposalNft::unbonding_deposit(*proposal).denom())
             .await;
 
+        // The voting proposal NFT is exchanged for an unbonding deposit NFT.
+        state.record_supply_change(self.balance()).await?;
+
         state.record_proto(event::proposal_withdraw(self));
 
         tracing::debug!(proposal = %proposal, "withdrew proposal");
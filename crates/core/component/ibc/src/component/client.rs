@@ -577,7 +577,12 @@ mod tests {
         async fn timeout_packet_execute<S: StateWrite>(_state: S, _msg: &MsgTimeout) -> Result<()> {
             Ok(())
         }
-        async fn acknowledge_packet_execute<S: StateWrite>(_state: S, _msg: &MsgAcknowledgement) {}
+        async fn acknowledge_packet_execute<S: StateWrite>(
+            _state: S,
+            _msg: &MsgAcknowledgement,
+        ) -> Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -625,6 +630,7 @@ mod tests {
             ibc_enabled: true,
             inbound_ics20_transfers_enabled: true,
             outbound_ics20_transfers_enabled: true,
+            ics20_rate_limits: vec![],
         });
         state_tx.put_epoch_by_height(
             1,
@@ -715,6 +721,7 @@ mod tests {
             ibc_enabled: false,
             inbound_ics20_transfers_enabled: true,
             outbound_ics20_transfers_enabled: true,
+            ics20_rate_limits: vec![],
         });
 
         let msg_create_client_stargaze_raw = BASE64_STANDARD
@@ -1,3 +1,8 @@
+use std::str::FromStr;
+
+use ibc_types::core::channel::ChannelId;
+use penumbra_asset::asset;
+use penumbra_num::Amount;
 use penumbra_proto::core::component::ibc::v1 as pb;
 use penumbra_proto::DomainType;
 use serde::{Deserialize, Serialize};
@@ -11,6 +16,21 @@ pub struct IBCParameters {
     pub inbound_ics20_transfers_enabled: bool,
     /// Whether outbound ICS-20 transfers are enabled
     pub outbound_ics20_transfers_enabled: bool,
+    /// Limits on the value of ICS-20 transfers over individual channels.
+    pub ics20_rate_limits: Vec<Ics20RateLimit>,
+}
+
+impl IBCParameters {
+    /// Returns the rate limit for transfers of `asset_id` over `channel_id`, if any.
+    pub fn ics20_rate_limit(
+        &self,
+        channel_id: &ChannelId,
+        asset_id: &asset::Id,
+    ) -> Option<&Ics20RateLimit> {
+        self.ics20_rate_limits
+            .iter()
+            .find(|limit| &limit.channel_id == channel_id && &limit.asset_id() == asset_id)
+    }
 }
 
 impl DomainType for IBCParameters {
@@ -25,6 +45,11 @@ impl TryFrom<pb::IbcParameters> for IBCParameters {
             ibc_enabled: msg.ibc_enabled,
             inbound_ics20_transfers_enabled: msg.inbound_ics20_transfers_enabled,
             outbound_ics20_transfers_enabled: msg.outbound_ics20_transfers_enabled,
+            ics20_rate_limits: msg
+                .ics20_rate_limits
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
         })
     }
 }
@@ -35,6 +60,11 @@ impl From<IBCParameters> for pb::IbcParameters {
             ibc_enabled: params.ibc_enabled,
             inbound_ics20_transfers_enabled: params.inbound_ics20_transfers_enabled,
             outbound_ics20_transfers_enabled: params.outbound_ics20_transfers_enabled,
+            ics20_rate_limits: params
+                .ics20_rate_limits
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 }
@@ -45,6 +75,71 @@ impl Default for IBCParameters {
             ibc_enabled: true,
             inbound_ics20_transfers_enabled: true,
             outbound_ics20_transfers_enabled: true,
+            ics20_rate_limits: Vec::new(),
+        }
+    }
+}
+
+/// A limit on the amount of an asset that can be transferred over an ICS-20
+/// channel within a rolling window of blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "pb::Ics20RateLimit", into = "pb::Ics20RateLimit")]
+pub struct Ics20RateLimit {
+    /// The Penumbra-side identifier of the channel.
+    pub channel_id: ChannelId,
+    /// The denomination of the limited asset, as it is known on Penumbra.
+    pub denom: String,
+    /// The maximum amount that can be received within the window, if limited.
+    pub max_inbound: Option<Amount>,
+    /// The maximum amount that can be sent within the window, if limited.
+    pub max_outbound: Option<Amount>,
+    /// The length of the rolling window, in blocks.
+    pub window_blocks: u64,
+}
+
+impl Ics20RateLimit {
+    /// The asset ID of the limited denomination.
+    pub fn asset_id(&self) -> asset::Id {
+        asset::REGISTRY
+            .parse_denom(&self.denom)
+            .expect("denom was validated on construction")
+            .id()
+    }
+}
+
+impl DomainType for Ics20RateLimit {
+    type Proto = pb::Ics20RateLimit;
+}
+
+impl TryFrom<pb::Ics20RateLimit> for Ics20RateLimit {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: pb::Ics20RateLimit) -> anyhow::Result<Self> {
+        if msg.window_blocks == 0 {
+            anyhow::bail!("ics20 rate limit window must be at least one block");
+        }
+        if asset::REGISTRY.parse_denom(&msg.denom).is_none() {
+            anyhow::bail!("invalid ics20 rate limit denom {}", msg.denom);
+        }
+
+        Ok(Ics20RateLimit {
+            channel_id: ChannelId::from_str(&msg.channel_id)?,
+            denom: msg.denom,
+            max_inbound: msg.max_inbound.map(TryInto::try_into).transpose()?,
+            max_outbound: msg.max_outbound.map(TryInto::try_into).transpose()?,
+            window_blocks: msg.window_blocks,
+        })
+    }
+}
+
+impl From<Ics20RateLimit> for pb::Ics20RateLimit {
+    fn from(limit: Ics20RateLimit) -> Self {
+        pb::Ics20RateLimit {
+            channel_id: limit.channel_id.to_string(),
+            denom: limit.denom,
+            max_inbound: limit.max_inbound.map(Into::into),
+            max_outbound: limit.max_outbound.map(Into::into),
+            window_blocks: limit.window_blocks,
         }
     }
 }
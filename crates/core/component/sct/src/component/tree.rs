@@ -229,13 +229,23 @@ pub trait VerificationExt: StateRead {
             ))
             .await?
         {
-            anyhow::bail!(
-                "nullifier {} was already spent in {:?}",
+            return Err(NullifierSpent {
                 nullifier,
-                hex::encode(info.id),
-            );
+                spent_in: info.id,
+            }
+            .into());
         }
         Ok(())
     }
 }
+
+/// The error returned when a transaction spends a nullifier that was already spent.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("nullifier {nullifier} was already spent in {:?}", hex::encode(.spent_in))]
+pub struct NullifierSpent {
+    /// The nullifier being spent again.
+    pub nullifier: Nullifier,
+    /// The ID of the transaction which first spent the nullifier.
+    pub spent_in: [u8; 32],
+}
 impl<T: StateRead + ?Sized> VerificationExt for T {}
@@ -3,16 +3,22 @@
 mod action_handler;
 mod assets;
 mod fmd;
+mod ics20_swap;
 mod metrics;
 mod note_manager;
+mod rate_limit;
 mod shielded_pool;
+mod supply;
 mod transfer;
 
 pub use self::metrics::register_metrics;
 pub use assets::{AssetRegistry, AssetRegistryRead};
 pub use fmd::ClueManager;
+pub use ics20_swap::{Ics20Swap, Ics20SwapRead, Ics20SwapWrite};
 pub use note_manager::NoteManager;
+pub use rate_limit::{Ics20Flow, Ics20RateLimitRead, Ics20RateLimitWrite};
 pub use shielded_pool::{ShieldedPool, StateReadExt, StateWriteExt};
+pub use supply::{SupplyRead, SupplyWrite};
 pub use transfer::Ics20Transfer;
 
 pub mod rpc;
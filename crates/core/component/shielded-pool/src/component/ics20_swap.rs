@@ -0,0 +1,54 @@
+use cnidarium::{StateRead, StateWrite};
+use penumbra_asset::{asset, Value};
+use penumbra_keys::Address;
+use penumbra_num::Amount;
+use penumbra_sct::CommitmentSource;
+
+use crate::state_key;
+
+/// A swap requested by the memo of an inbound ICS-20 transfer.
+///
+/// The shielded pool can't execute swaps itself, so it queues them for the
+/// DEX, which adds them to the block's batch swaps and mints their outputs to
+/// the claim address once the batches have executed.
+#[derive(Clone, Debug)]
+pub struct Ics20Swap {
+    /// The transferred funds to swap.
+    pub input: Value,
+    /// The asset to swap into.
+    pub output_asset_id: asset::Id,
+    /// The minimum amount of the output asset to accept, or zero for none.
+    pub min_output: Amount,
+    /// The address to mint the outputs of the swap to.
+    pub claim_address: Address,
+    /// The source to record for the minted outputs.
+    pub source: CommitmentSource,
+}
+
+pub trait Ics20SwapRead: StateRead {
+    /// Returns the swaps requested by inbound ICS-20 transfers in this block so far.
+    fn pending_ics20_swaps(&self) -> im::Vector<Ics20Swap> {
+        self.object_get(state_key::pending_ics20_swaps())
+            .unwrap_or_default()
+    }
+}
+
+impl<T: StateRead + ?Sized> Ics20SwapRead for T {}
+
+pub trait Ics20SwapWrite: StateWrite {
+    /// Queue a swap to be executed by the DEX at the end of the block.
+    fn queue_ics20_swap(&mut self, swap: Ics20Swap) {
+        let mut swaps = self.pending_ics20_swaps();
+        swaps.push_back(swap);
+        self.object_put(state_key::pending_ics20_swaps(), swaps);
+    }
+
+    /// Removes and returns the swaps queued in this block.
+    fn take_pending_ics20_swaps(&mut self) -> im::Vector<Ics20Swap> {
+        let swaps = self.pending_ics20_swaps();
+        self.object_delete(state_key::pending_ics20_swaps());
+        swaps
+    }
+}
+
+impl<T: StateWrite + ?Sized> Ics20SwapWrite for T {}
@@ -0,0 +1,297 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use cnidarium::{StateRead, StateWrite};
+use futures::{StreamExt, TryStreamExt};
+use ibc_types::core::channel::ChannelId;
+use penumbra_asset::{asset, Value};
+use penumbra_ibc::{params::Ics20RateLimit, StateReadExt as _};
+use penumbra_num::Amount;
+use penumbra_proto::{StateReadProto, StateWriteProto};
+use penumbra_sct::component::clock::EpochRead;
+use tracing::instrument;
+
+use crate::state_key;
+
+/// The direction of an ICS-20 transfer, relative to Penumbra.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ics20Flow {
+    Inbound,
+    Outbound,
+}
+
+impl Ics20Flow {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Ics20Flow::Inbound => "inbound",
+            Ics20Flow::Outbound => "outbound",
+        }
+    }
+
+    fn max_amount(&self, limit: &Ics20RateLimit) -> Option<Amount> {
+        match self {
+            Ics20Flow::Inbound => limit.max_inbound,
+            Ics20Flow::Outbound => limit.max_outbound,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Ics20RateLimitRead: StateRead {
+    /// Returns the amount of `asset_id` that can still be transferred over
+    /// `channel_id` in the current window, or `None` if the flow is not limited.
+    async fn ics20_quota(
+        &self,
+        flow: Ics20Flow,
+        channel_id: &ChannelId,
+        asset_id: &asset::Id,
+    ) -> Result<Option<Amount>> {
+        let params = self.get_ibc_params().await?;
+        let Some(limit) = params.ics20_rate_limit(channel_id, asset_id) else {
+            return Ok(None);
+        };
+        let Some(max_amount) = flow.max_amount(limit) else {
+            return Ok(None);
+        };
+
+        let height = self.get_block_height().await?;
+        let used = self
+            .ics20_flow_since(
+                flow,
+                channel_id,
+                asset_id,
+                height.saturating_sub(limit.window_blocks),
+            )
+            .await?;
+        Ok(Some(max_amount.saturating_sub(&used)))
+    }
+
+    /// Returns the amount of `asset_id` transferred over `channel_id` in blocks
+    /// after `cutoff_height`.
+    async fn ics20_flow_since(
+        &self,
+        flow: Ics20Flow,
+        channel_id: &ChannelId,
+        asset_id: &asset::Id,
+        cutoff_height: u64,
+    ) -> Result<Amount> {
+        let flows = ics20_flows(self, flow, channel_id, asset_id).await?;
+        Ok(flows
+            .into_iter()
+            .filter(|(height, _)| *height > cutoff_height)
+            .fold(Amount::zero(), |total, (_, amount)| {
+                total.saturating_add(&amount)
+            }))
+    }
+
+    /// Checks that transferring `value` over `channel_id` would stay within the
+    /// channel's rate limit for that asset.
+    async fn check_ics20_rate_limit(
+        &self,
+        flow: Ics20Flow,
+        channel_id: &ChannelId,
+        value: Value,
+    ) -> Result<()> {
+        if let Some(quota) = self.ics20_quota(flow, channel_id, &value.asset_id).await? {
+            anyhow::ensure!(
+                value.amount <= quota,
+                "{} ics20 transfer of {} exceeds the remaining rate limit of {} on {}",
+                flow.as_str(),
+                value.amount,
+                quota,
+                channel_id,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<T: StateRead + ?Sized> Ics20RateLimitRead for T {}
+
+/// Accounts ICS-20 transfers against the rate limits set in the IBC parameters.
+///
+/// Flows are only recorded for rate limited channels and assets, bucketed by
+/// block height, and buckets that fall out of the window are pruned as new
+/// flows are recorded.
+#[async_trait]
+pub trait Ics20RateLimitWrite: StateWrite {
+    /// Record that `value` was transferred over `channel_id` in the current block.
+    #[instrument(skip(self))]
+    async fn record_ics20_flow(
+        &mut self,
+        flow: Ics20Flow,
+        channel_id: &ChannelId,
+        value: Value,
+    ) -> Result<()> {
+        let params = self.get_ibc_params().await?;
+        let Some(limit) = params.ics20_rate_limit(channel_id, &value.asset_id) else {
+            return Ok(());
+        };
+        if flow.max_amount(limit).is_none() {
+            return Ok(());
+        }
+
+        let height = self.get_block_height().await?;
+        let cutoff_height = height.saturating_sub(limit.window_blocks);
+        let direction = flow.as_str();
+        let mut current = Amount::zero();
+        for (flow_height, amount) in ics20_flows(self, flow, channel_id, &value.asset_id).await? {
+            if flow_height <= cutoff_height {
+                self.delete(state_key::ics20_flow::by_height(
+                    direction,
+                    channel_id,
+                    &value.asset_id,
+                    flow_height,
+                ));
+            } else if flow_height == height {
+                current = amount;
+            }
+        }
+
+        let new_amount = current
+            .checked_add(&value.amount)
+            .context("overflow recording ics20 flow")?;
+        self.put(
+            state_key::ics20_flow::by_height(direction, channel_id, &value.asset_id, height),
+            new_amount,
+        );
+        Ok(())
+    }
+}
+
+impl<T: StateWrite + ?Sized> Ics20RateLimitWrite for T {}
+
+/// Returns the recorded flows of `asset_id` over `channel_id`, by block height.
+async fn ics20_flows<S: StateRead + ?Sized>(
+    state: &S,
+    flow: Ics20Flow,
+    channel_id: &ChannelId,
+    asset_id: &asset::Id,
+) -> Result<Vec<(u64, Amount)>> {
+    let prefix = state_key::ics20_flow::prefix(flow.as_str(), channel_id, asset_id);
+    state
+        .prefix::<Amount>(&prefix)
+        .map(|entry| {
+            let (key, amount) = entry?;
+            let height = key
+                .strip_prefix(&prefix)
+                .context("malformed ics20 flow key")?
+                .parse::<u64>()?;
+            Ok((height, amount))
+        })
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use cnidarium::{ArcStateDeltaExt, StateDelta};
+    use penumbra_ibc::{params::IBCParameters, StateWriteExt as _};
+    use penumbra_sct::component::clock::EpochManager as _;
+
+    use super::*;
+
+    const WINDOW: u64 = 10;
+
+    fn channel() -> ChannelId {
+        ChannelId::new(0)
+    }
+
+    fn value(amount: u64) -> Value {
+        Value {
+            amount: amount.into(),
+            asset_id: *penumbra_asset::STAKING_TOKEN_ASSET_ID,
+        }
+    }
+
+    fn params() -> IBCParameters {
+        IBCParameters {
+            ics20_rate_limits: vec![Ics20RateLimit {
+                channel_id: channel(),
+                denom: "upenumbra".to_string(),
+                max_inbound: None,
+                max_outbound: Some(100u64.into()),
+                window_blocks: WINDOW,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    /// Outbound transfers are rejected once the window's quota is used up.
+    async fn outbound_flows_share_the_window_quota() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(params());
+        state_tx.put_block_height(1);
+
+        state_tx
+            .check_ics20_rate_limit(Ics20Flow::Outbound, &channel(), value(60))
+            .await?;
+        state_tx
+            .record_ics20_flow(Ics20Flow::Outbound, &channel(), value(60))
+            .await?;
+
+        state_tx.put_block_height(2);
+        state_tx
+            .check_ics20_rate_limit(Ics20Flow::Outbound, &channel(), value(50))
+            .await
+            .expect_err("should not exceed the remaining quota");
+        state_tx
+            .check_ics20_rate_limit(Ics20Flow::Outbound, &channel(), value(40))
+            .await?;
+        state_tx
+            .record_ics20_flow(Ics20Flow::Outbound, &channel(), value(40))
+            .await?;
+
+        let quota = state_tx
+            .ics20_quota(Ics20Flow::Outbound, &channel(), &value(0).asset_id)
+            .await?;
+        assert_eq!(quota, Some(Amount::zero()));
+
+        // Inbound transfers of the asset are not limited.
+        let quota = state_tx
+            .ics20_quota(Ics20Flow::Inbound, &channel(), &value(0).asset_id)
+            .await?;
+        assert_eq!(quota, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// The quota is restored as recorded flows fall out of the window.
+    async fn quota_recovers_after_the_window() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(params());
+        state_tx.put_block_height(1);
+        state_tx
+            .record_ics20_flow(Ics20Flow::Outbound, &channel(), value(100))
+            .await?;
+
+        state_tx.put_block_height(WINDOW);
+        state_tx
+            .check_ics20_rate_limit(Ics20Flow::Outbound, &channel(), value(1))
+            .await
+            .expect_err("the first flow is still within the window");
+
+        state_tx.put_block_height(WINDOW + 1);
+        state_tx
+            .check_ics20_rate_limit(Ics20Flow::Outbound, &channel(), value(100))
+            .await?;
+        state_tx
+            .record_ics20_flow(Ics20Flow::Outbound, &channel(), value(100))
+            .await?;
+
+        // Recording the new flow pruned the expired one.
+        let flows = ics20_flows(
+            &state_tx,
+            Ics20Flow::Outbound,
+            &channel(),
+            &value(0).asset_id,
+        )
+        .await?;
+        assert_eq!(flows, vec![(WINDOW + 1, 100u64.into())]);
+        Ok(())
+    }
+}
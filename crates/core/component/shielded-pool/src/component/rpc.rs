@@ -1,16 +1,20 @@
-use std::pin::Pin;
+use std::{collections::BTreeSet, pin::Pin, str::FromStr};
 
 use cnidarium::Storage;
+use futures::StreamExt;
+use ibc_types::core::channel::ChannelId;
 use penumbra_asset::asset::{self};
+use penumbra_ibc::StateReadExt as _;
 use penumbra_proto::core::component::shielded_pool::v1::{
     query_service_server::QueryService, AssetMetadataByIdRequest, AssetMetadataByIdResponse,
-    AssetMetadataByIdsRequest, AssetMetadataByIdsResponse,
+    AssetMetadataByIdsRequest, AssetMetadataByIdsResponse, AssetSupplyRequest, AssetSupplyResponse,
+    Ics20QuotaRequest, Ics20QuotaResponse,
 };
 
 use tonic::Status;
 use tracing::instrument;
 
-use super::AssetRegistryRead;
+use super::{AssetRegistryRead, Ics20Flow, Ics20RateLimitRead, SupplyRead};
 
 mod bank_query;
 mod transfer_query;
@@ -64,10 +68,102 @@ impl QueryService for Server {
         Ok(tonic::Response::new(rsp))
     }
 
+    #[instrument(skip(self, request))]
     async fn asset_metadata_by_ids(
         &self,
-        _request: tonic::Request<AssetMetadataByIdsRequest>,
+        request: tonic::Request<AssetMetadataByIdsRequest>,
     ) -> Result<tonic::Response<Self::AssetMetadataByIdsStream>, tonic::Status> {
-        unimplemented!("asset_metadata_by_ids not yet implemented")
+        let state = self.storage.latest_snapshot();
+
+        let ids = request
+            .into_inner()
+            .asset_id
+            .into_iter()
+            .map(asset::Id::try_from)
+            .collect::<Result<BTreeSet<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("could not parse asset_id: {e}")))?;
+
+        // Unknown asset ids are skipped, rather than failing the whole request.
+        let stream = futures::stream::iter(ids)
+            .filter_map(move |id| {
+                let state = state.clone();
+                async move {
+                    let denom_metadata = state.denom_metadata_by_asset(&id).await;
+                    if denom_metadata.is_none() {
+                        tracing::debug!(?id, "unknown asset id");
+                    }
+                    denom_metadata
+                }
+            })
+            .map(|denom_metadata| {
+                Ok(AssetMetadataByIdsResponse {
+                    denom_metadata: Some(denom_metadata.into()),
+                })
+            });
+
+        Ok(tonic::Response::new(stream.boxed()))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn asset_supply(
+        &self,
+        request: tonic::Request<AssetSupplyRequest>,
+    ) -> Result<tonic::Response<AssetSupplyResponse>, Status> {
+        let state = self.storage.latest_snapshot();
+
+        let request = request.into_inner();
+        let id: asset::Id = request
+            .asset_id
+            .ok_or_else(|| Status::invalid_argument("missing asset_id"))?
+            .try_into()
+            .map_err(|e| Status::invalid_argument(format!("could not parse asset_id: {e}")))?;
+
+        let supply = state
+            .token_supply(&id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(AssetSupplyResponse {
+            supply: Some(supply.into()),
+        }))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn ics20_quota(
+        &self,
+        request: tonic::Request<Ics20QuotaRequest>,
+    ) -> Result<tonic::Response<Ics20QuotaResponse>, Status> {
+        let state = self.storage.latest_snapshot();
+
+        let request = request.into_inner();
+        let channel_id = ChannelId::from_str(&request.channel_id)
+            .map_err(|e| Status::invalid_argument(format!("could not parse channel_id: {e}")))?;
+        let id: asset::Id = request
+            .asset_id
+            .ok_or_else(|| Status::invalid_argument("missing asset_id"))?
+            .try_into()
+            .map_err(|e| Status::invalid_argument(format!("could not parse asset_id: {e}")))?;
+
+        let window_blocks = state
+            .get_ibc_params()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ics20_rate_limit(&channel_id, &id)
+            .map(|limit| limit.window_blocks)
+            .unwrap_or_default();
+        let inbound_remaining = state
+            .ics20_quota(Ics20Flow::Inbound, &channel_id, &id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let outbound_remaining = state
+            .ics20_quota(Ics20Flow::Outbound, &channel_id, &id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(Ics20QuotaResponse {
+            inbound_remaining: inbound_remaining.map(Into::into),
+            outbound_remaining: outbound_remaining.map(Into::into),
+            window_blocks,
+        }))
     }
 }
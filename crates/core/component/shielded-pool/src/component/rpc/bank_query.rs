@@ -2,7 +2,8 @@ use std::collections::BTreeMap;
 
 use anyhow::Context;
 use async_trait::async_trait;
-use futures::StreamExt;
+use cnidarium::StateRead;
+use futures::TryStreamExt as _;
 use ibc_proto::cosmos::bank::v1beta1::{
     query_server::Query as BankQuery, QueryAllBalancesRequest, QueryAllBalancesResponse,
     QueryBalanceRequest, QueryBalanceResponse, QueryParamsRequest, QueryParamsResponse,
@@ -15,113 +16,84 @@ use ibc_proto::cosmos::bank::v1beta1::{
     QuerySpendableBalanceByDenomResponse, QuerySpendableBalancesRequest,
     QuerySpendableBalancesResponse, QuerySupplyOfRequest, QuerySupplyOfResponse,
 };
+use ibc_proto::cosmos::base::{query::v1beta1::PageResponse, v1beta1::Coin};
 use penumbra_asset::asset::{self, Metadata};
 use penumbra_ibc::component::state_key as ibc_state_key;
 use penumbra_num::Amount;
 use penumbra_proto::StateReadProto as _;
 use tracing::instrument;
 
-use crate::component::AssetRegistryRead as _;
+use crate::component::{AssetRegistryRead as _, SupplyRead as _};
 use crate::state_key;
 
 use super::Server;
 
+/// The page size used when a total supply request doesn't specify a limit.
+const DEFAULT_PAGE_LIMIT: usize = 100;
+
 #[async_trait]
 impl BankQuery for Server {
-    /// Returns the total supply for all IBC assets.
-    /// Internally-minted assets (Penumbra tokens, LP tokens, delegation tokens, etc.)
-    /// are also included but the supplies are hardcoded at 0 for now.
+    /// Returns the total supply of every asset minted on the chain, ordered by asset ID.
+    ///
+    /// Inbound ICS-20 vouchers without a tracked supply fall back to their value
+    /// balance, which is what this query reported for them before supply tracking.
     ///
-    /// TODO: Implement a way to fetch the total supply for these assets.
-    /// TODO: implement pagination
-    #[instrument(skip(self, _request))]
+    /// Pagination is supported through either a `key` (the `next_key` of a
+    /// previous response) or an `offset`.
+    #[instrument(skip(self, request))]
     async fn total_supply(
         &self,
-        _request: tonic::Request<QueryTotalSupplyRequest>,
+        request: tonic::Request<QueryTotalSupplyRequest>,
     ) -> Result<tonic::Response<QueryTotalSupplyResponse>, tonic::Status> {
         let snapshot = self.storage.latest_snapshot();
+        let pagination = request.into_inner().pagination.unwrap_or_default();
 
-        // Find every non-IBC known asset
-        let s = snapshot.prefix(state_key::denom_metadata_by_asset::prefix());
-        let mut total_supply = s
-            .filter_map(move |i: anyhow::Result<(String, Metadata)>| async move {
-                if i.is_err() {
-                    return Some(Err(i.context("bad denom in state").err().unwrap()));
-                }
-                let (_key, denom_metadata) = i.expect("should not be an error");
-
-                // Return a hardcoded 0 supply for now
-                Some(Ok((denom_metadata, Amount::from(0u32))))
-            })
-            .collect::<Vec<_>>()
+        let mut supplies = snapshot
+            .token_supplies()
             .await
-            .into_iter()
-            .collect::<anyhow::Result<Vec<_>>>()
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
-            .into_iter()
-            .collect::<BTreeMap<_, _>>();
-
-        let s = snapshot.prefix(ibc_state_key::ics20_value_balance::prefix());
-        let ibc_amounts = s
-            .filter_map(move |i: anyhow::Result<(String, Amount)>| async move {
-                if i.is_err() {
-                    return Some(Err(i.context("bad amount in state").err().unwrap()));
-                }
-                let (key, amount) = i.expect("should not be an error");
-
-                // Extract the asset ID from the key
-                let asset_id = key.split('/').last();
-                if asset_id.is_none() {
-                    return Some(Err(asset_id
-                        .context("bad IBC ics20 value balance key in state")
-                        .err()
-                        .unwrap()));
-                }
-                let asset_id = asset_id.expect("should not be an error");
-
-                // Parse the asset ID
-                let asset_id = asset_id.parse::<asset::Id>();
-                if asset_id.is_err() {
-                    return Some(Err(asset_id
-                        .context("invalid IBC ics20 value balance asset ID in state")
-                        .err()
-                        .unwrap()));
-                }
-                let asset_id = asset_id.expect("should not be an error");
-
-                Some(Ok((asset_id, amount)))
-            })
-            .collect::<Vec<_>>()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let mut ics20_supplies = ics20_supplies(&snapshot)
             .await
-            .into_iter()
-            .collect::<anyhow::Result<Vec<_>>>()
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        for (asset_id, _) in &supplies {
+            ics20_supplies.remove(asset_id);
+        }
+        if !ics20_supplies.is_empty() {
+            supplies.extend(ics20_supplies);
+            supplies.sort_by_cached_key(|(asset_id, _)| asset_id.to_string());
+        }
+
+        let start = if !pagination.key.is_empty() {
+            let key = String::from_utf8(pagination.key)
+                .map_err(|_| tonic::Status::invalid_argument("invalid pagination key"))?;
+            supplies.partition_point(|(asset_id, _)| asset_id.to_string() < key)
+        } else {
+            usize::try_from(pagination.offset).unwrap_or(usize::MAX)
+        };
+        let limit = match pagination.limit {
+            0 => DEFAULT_PAGE_LIMIT,
+            limit => usize::try_from(limit).unwrap_or(usize::MAX),
+        };
+        let end = start.saturating_add(limit).min(supplies.len());
 
-        // Fetch the denoms associated with the IBC asset IDs
-        for (asset_id, amount) in ibc_amounts {
-            let denom_metadata = snapshot.denom_metadata_by_asset(&asset_id).await;
-            if denom_metadata.is_none() {
-                // This is likely an NFT asset that is intentionally
-                // not registered, so it is fine to exclude from the output.
-                continue;
-            }
-            let denom_metadata = denom_metadata.expect("should not be an error");
-
-            total_supply.insert(denom_metadata, amount);
+        let mut supply = Vec::new();
+        for (asset_id, amount) in supplies.get(start..end).unwrap_or_default() {
+            supply.push(coin(&snapshot, asset_id, *amount).await);
         }
 
+        let next_key = supplies
+            .get(end)
+            .map(|(asset_id, _)| asset_id.to_string().into_bytes())
+            .unwrap_or_default();
+        let total = if pagination.count_total {
+            supplies.len() as u64
+        } else {
+            0
+        };
+
         Ok(tonic::Response::new(QueryTotalSupplyResponse {
-            // Pagination disabled for now
-            pagination: None,
-            supply: total_supply
-                .into_iter()
-                .map(
-                    |(denom_metadata, amount)| ibc_proto::cosmos::base::v1beta1::Coin {
-                        denom: denom_metadata.to_string(),
-                        amount: amount.to_string(),
-                    },
-                )
-                .collect::<Vec<ibc_proto::cosmos::base::v1beta1::Coin>>(),
+            supply,
+            pagination: Some(PageResponse { next_key, total }),
         }))
     }
 
@@ -169,11 +141,41 @@ impl BankQuery for Server {
         ))
     }
 
+    /// Returns the total supply of a single asset, given either its denom or its asset ID.
+    ///
+    /// Like [`Self::total_supply`], this falls back to the ICS-20 value balance for
+    /// inbound vouchers without a tracked supply.
+    #[instrument(skip(self, request))]
     async fn supply_of(
         &self,
-        _: tonic::Request<QuerySupplyOfRequest>,
+        request: tonic::Request<QuerySupplyOfRequest>,
     ) -> std::result::Result<tonic::Response<QuerySupplyOfResponse>, tonic::Status> {
-        Err(tonic::Status::unimplemented("not implemented"))
+        let snapshot = self.storage.latest_snapshot();
+        let denom = request.into_inner().denom;
+
+        let asset_id = match denom.parse::<asset::Id>() {
+            Ok(asset_id) => asset_id,
+            Err(_) => Metadata::try_from(denom.as_str())
+                .map_err(|e| tonic::Status::invalid_argument(format!("invalid denom: {e}")))?
+                .id(),
+        };
+
+        let tracked: Option<Amount> = snapshot
+            .get(&state_key::token_supply::by_asset_id(&asset_id))
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let amount = match tracked {
+            Some(amount) => amount,
+            None => ics20_supplies(&snapshot)
+                .await
+                .map_err(|e| tonic::Status::internal(e.to_string()))?
+                .remove(&asset_id)
+                .unwrap_or_default(),
+        };
+
+        Ok(tonic::Response::new(QuerySupplyOfResponse {
+            amount: Some(coin(&snapshot, &asset_id, amount).await),
+        }))
     }
 
     async fn denom_metadata(
@@ -204,3 +206,54 @@ impl BankQuery for Server {
         Err(tonic::Status::unimplemented("not implemented"))
     }
 }
+
+/// Returns the ICS-20 value balance of every voucher for an inbound transfer, summed
+/// across channels.
+///
+/// A channel's value balance for a Penumbra-native asset is the amount held in escrow
+/// for outbound transfers, which isn't part of the asset's supply, so only the assets
+/// that a channel is not the source of are counted.
+async fn ics20_supplies<S: StateRead>(state: &S) -> anyhow::Result<BTreeMap<asset::Id, Amount>> {
+    let value_balances: Vec<(String, Amount)> = state
+        .prefix(ibc_state_key::ics20_value_balance::prefix())
+        .try_collect()
+        .await?;
+
+    let mut supplies = BTreeMap::new();
+    for (key, amount) in value_balances {
+        // Keys are of the form `{prefix}{channel_id}/{asset_id}`.
+        let (channel_id, asset_id) = key
+            .strip_prefix(ibc_state_key::ics20_value_balance::prefix())
+            .and_then(|rest| rest.split_once('/'))
+            .context("bad IBC ics20 value balance key in state")?;
+        let asset_id = asset_id
+            .parse::<asset::Id>()
+            .context("invalid IBC ics20 value balance asset ID in state")?;
+        let is_voucher = match state.denom_metadata_by_asset(&asset_id).await {
+            Some(denom) => denom
+                .base_denom()
+                .denom
+                .starts_with(&format!("transfer/{channel_id}/")),
+            None => false,
+        };
+        if is_voucher {
+            *supplies.entry(asset_id).or_insert_with(Amount::zero) += amount;
+        }
+    }
+
+    Ok(supplies)
+}
+
+/// Formats a supply as a [`Coin`], using the asset's denom if it is registered
+/// and its asset ID otherwise (e.g. for position and auction NFTs).
+async fn coin<S: StateRead>(state: &S, asset_id: &asset::Id, amount: Amount) -> Coin {
+    let denom = match state.denom_metadata_by_asset(asset_id).await {
+        Some(denom_metadata) => denom_metadata.to_string(),
+        None => asset_id.to_string(),
+    };
+
+    Coin {
+        denom,
+        amount: amount.to_string(),
+    }
+}
@@ -15,7 +15,7 @@ use penumbra_sct::CommitmentSource;
 use tendermint::v0_37::abci;
 use tracing::instrument;
 
-use super::{AssetRegistry, NoteManager};
+use super::{AssetRegistry, NoteManager, SupplyWrite};
 
 pub struct ShieldedPool {}
 
@@ -52,6 +52,10 @@ impl Component for ShieldedPool {
                         )
                         .await
                         .expect("able to mint note for genesis allocation");
+                    state
+                        .increase_token_supply(allocation.value())
+                        .await
+                        .expect("able to record supply of genesis allocation");
                 }
             }
         }
@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use cnidarium::{StateRead, StateWrite};
+use futures::{StreamExt, TryStreamExt};
+use penumbra_asset::{asset, Balance, Value};
+use penumbra_num::Amount;
+use penumbra_proto::{StateReadProto, StateWriteProto};
+use tracing::instrument;
+
+use crate::state_key;
+
+#[async_trait]
+pub trait SupplyRead: StateRead {
+    /// Returns the total amount of `asset_id` in existence on the chain.
+    ///
+    /// This includes value held by the protocol itself, e.g. in liquidity
+    /// positions, auctions, or IBC escrow accounts.
+    async fn token_supply(&self, asset_id: &asset::Id) -> Result<Amount> {
+        Ok(self
+            .get(&state_key::token_supply::by_asset_id(asset_id))
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Returns the supply of every asset that has ever been minted, ordered by asset ID.
+    async fn token_supplies(&self) -> Result<Vec<(asset::Id, Amount)>> {
+        self.prefix::<Amount>(state_key::token_supply::prefix())
+            .map(|entry| {
+                let (key, amount) = entry?;
+                let asset_id = key
+                    .strip_prefix(state_key::token_supply::prefix())
+                    .context("malformed token supply key")?
+                    .parse::<asset::Id>()?;
+                Ok((asset_id, amount))
+            })
+            .try_collect()
+            .await
+    }
+}
+
+impl<T: StateRead + ?Sized> SupplyRead for T {}
+
+/// Tracks the supply of each asset as the chain mints and burns value.
+///
+/// Components should call these methods whenever value enters or leaves
+/// existence, rather than when it merely changes hands (e.g. moving into a
+/// liquidity position or an IBC escrow account).
+#[async_trait]
+pub trait SupplyWrite: StateWrite {
+    /// Record that `value` was created.
+    #[instrument(skip(self))]
+    async fn increase_token_supply(&mut self, value: Value) -> Result<()> {
+        if value.amount == Amount::zero() {
+            return Ok(());
+        }
+        let supply = self.token_supply(&value.asset_id).await?;
+        let new_supply = supply
+            .checked_add(&value.amount)
+            .context("overflow increasing token supply")?;
+        tracing::debug!(?supply, ?new_supply, "increasing token supply");
+        self.put(
+            state_key::token_supply::by_asset_id(&value.asset_id),
+            new_supply,
+        );
+        Ok(())
+    }
+
+    /// Record that `value` was destroyed.
+    ///
+    /// Chains that started without supply tracking have no record of the value
+    /// minted before the upgrade, so the supply saturates at zero rather than
+    /// failing the burn.
+    #[instrument(skip(self))]
+    async fn decrease_token_supply(&mut self, value: Value) -> Result<()> {
+        if value.amount == Amount::zero() {
+            return Ok(());
+        }
+        let supply = self.token_supply(&value.asset_id).await?;
+        let new_supply = supply.saturating_sub(&value.amount);
+        tracing::debug!(?supply, ?new_supply, "decreasing token supply");
+        self.put(
+            state_key::token_supply::by_asset_id(&value.asset_id),
+            new_supply,
+        );
+        Ok(())
+    }
+
+    /// Record the contribution of an action whose value balance consists
+    /// entirely of minted and burned value, e.g. exchanging one NFT for another.
+    ///
+    /// Value provided by the action is created, and value required by it is destroyed.
+    async fn record_supply_change(&mut self, balance: Balance) -> Result<()> {
+        let minted: Vec<Value> = balance.provided().collect();
+        let burned: Vec<Value> = balance.required().collect();
+        for value in minted {
+            self.increase_token_supply(value).await?;
+        }
+        for value in burned {
+            self.decrease_token_supply(value).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: StateWrite + ?Sized> SupplyWrite for T {}
+
+#[cfg(test)]
+mod tests {
+    use cnidarium::StateDelta;
+    use penumbra_asset::STAKING_TOKEN_ASSET_ID;
+
+    use super::*;
+
+    fn staking(amount: u64) -> Value {
+        Value {
+            amount: amount.into(),
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        }
+    }
+
+    #[tokio::test]
+    async fn supply_follows_mints_and_burns() -> Result<()> {
+        let mut state = StateDelta::new(());
+
+        state.increase_token_supply(staking(100)).await?;
+        state.decrease_token_supply(staking(30)).await?;
+        assert_eq!(
+            state.token_supply(&STAKING_TOKEN_ASSET_ID).await?,
+            70u64.into()
+        );
+
+        // Burning more than was ever recorded saturates at zero.
+        state.decrease_token_supply(staking(100)).await?;
+        assert_eq!(
+            state.token_supply(&STAKING_TOKEN_ASSET_ID).await?,
+            Amount::zero()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn balance_changes_mint_provided_and_burn_required_value() -> Result<()> {
+        let mut state = StateDelta::new(());
+        let nft = Value {
+            amount: 1u64.into(),
+            asset_id: asset::Metadata::try_from("test_nft")?.id(),
+        };
+
+        state.increase_token_supply(staking(100)).await?;
+        state
+            .record_supply_change(Balance::from(nft) - Balance::from(staking(40)))
+            .await?;
+
+        assert_eq!(
+            state.token_supply(&STAKING_TOKEN_ASSET_ID).await?,
+            60u64.into()
+        );
+        assert_eq!(state.token_supply(&nft.asset_id).await?, 1u64.into());
+        assert_eq!(state.token_supplies().await?.len(), 2);
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
 use std::str::FromStr;
 
 use crate::{
-    component::{AssetRegistry, NoteManager},
+    component::{
+        AssetRegistry, Ics20Flow, Ics20RateLimitRead, Ics20RateLimitWrite, Ics20Swap,
+        Ics20SwapWrite, NoteManager, SupplyWrite,
+    },
+    ics20_memo::{ForwardMemo, Ics20Memo},
+    state_key::ics20_forward,
     Ics20Withdrawal,
 };
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use cnidarium::{StateRead, StateWrite};
+use cnidarium::{StateDelta, StateRead, StateWrite};
 use ibc_types::{
     core::channel::{
         channel::Order as ChannelOrder,
@@ -15,8 +20,9 @@ use ibc_types::{
             MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket,
             MsgTimeout,
         },
-        ChannelId, PortId, Version,
+        ChannelId, Packet, PortId, Version,
     },
+    core::client::Height,
     transfer::acknowledgement::TokenTransferAcknowledgement,
 };
 use penumbra_asset::{asset, asset::Metadata, Value};
@@ -25,14 +31,14 @@ use penumbra_num::Amount;
 use penumbra_proto::{
     penumbra::core::component::ibc::v1::FungibleTokenPacketData, StateReadProto, StateWriteProto,
 };
-use penumbra_sct::CommitmentSource;
+use penumbra_sct::{component::clock::EpochRead as _, CommitmentSource};
 
 use penumbra_ibc::component::{
     app_handler::{AppHandler, AppHandlerCheck, AppHandlerExecute},
     packet::{
         IBCPacket, SendPacketRead as _, SendPacketWrite as _, Unchecked, WriteAcknowledgement as _,
     },
-    state_key,
+    state_key, ChannelStateReadExt as _, ClientStateReadExt as _, ConnectionStateReadExt as _,
 };
 
 // returns a bool indicating if the provided denom was issued locally or if it was bridged in.
@@ -62,6 +68,10 @@ fn is_source(
     }
 }
 
+/// How far past the counterparty's latest known height a forwarded transfer times out, roughly a
+/// day of blocks on a chain with five second blocks.
+const FORWARD_TIMEOUT_BLOCKS: u64 = 17_280;
+
 #[derive(Clone)]
 pub struct Ics20Transfer {}
 
@@ -74,6 +84,13 @@ pub trait Ics20TransferReadExt: StateRead {
         // send packet
         self.send_packet_check(packet).await?;
 
+        self.check_ics20_rate_limit(
+            Ics20Flow::Outbound,
+            &withdrawal.source_channel,
+            withdrawal.value(),
+        )
+        .await?;
+
         Ok(())
     }
 }
@@ -86,64 +103,20 @@ pub trait Ics20TransferWriteExt: StateWrite {
         // create packet, assume it's already checked since the component caller contract calls `check` before `execute`
         let checked_packet = IBCPacket::<Unchecked>::from(withdrawal.clone()).assume_checked();
 
-        let prefix = format!("transfer/{}/", &withdrawal.source_channel);
-        if !withdrawal.denom.starts_with(&prefix) {
-            // we are the source. add the value balance to the escrow channel.
-            let existing_value_balance: Amount = self
-                .get(&state_key::ics20_value_balance::by_asset_id(
-                    &withdrawal.source_channel,
-                    &withdrawal.denom.id(),
-                ))
-                .await
-                .expect("able to retrieve value balance in ics20 withdrawal! (execute)")
-                .unwrap_or_else(Amount::zero);
-
-            let new_value_balance = existing_value_balance
-                .checked_add(&withdrawal.amount)
-                .ok_or_else(|| {
-                    anyhow::anyhow!("overflow adding value balance in ics20 withdrawal")
-                })?;
-            self.put(
-                state_key::ics20_value_balance::by_asset_id(
-                    &withdrawal.source_channel,
-                    &withdrawal.denom.id(),
-                ),
-                new_value_balance,
-            );
-        } else {
-            // receiver is the source, burn utxos
-
-            // double check the value balance here.
-            //
-            // for assets not originating from Penumbra, never transfer out more tokens than were
-            // transferred in. (Our counterparties should be checking this anyways, since if we
-            // were Byzantine we could lie to them).
-            let value_balance: Amount = self
-                .get(&state_key::ics20_value_balance::by_asset_id(
-                    &withdrawal.source_channel,
-                    &withdrawal.denom.id(),
-                ))
-                .await?
-                .unwrap_or_else(Amount::zero);
-
-            if value_balance < withdrawal.amount {
-                anyhow::bail!("insufficient balance to withdraw tokens");
-            }
-
-            let new_value_balance =
-                value_balance
-                    .checked_sub(&withdrawal.amount)
-                    .ok_or_else(|| {
-                        anyhow::anyhow!("underflow subtracting value balance in ics20 withdrawal")
-                    })?;
-            self.put(
-                state_key::ics20_value_balance::by_asset_id(
-                    &withdrawal.source_channel,
-                    &withdrawal.denom.id(),
-                ),
-                new_value_balance,
-            );
-        }
+        send_tokens(
+            self,
+            &withdrawal.source_channel,
+            &withdrawal.denom,
+            withdrawal.amount,
+        )
+        .await?;
+
+        self.record_ics20_flow(
+            Ics20Flow::Outbound,
+            &withdrawal.source_channel,
+            withdrawal.value(),
+        )
+        .await?;
 
         self.send_packet_execute(checked_packet).await;
 
@@ -153,6 +126,390 @@ pub trait Ics20TransferWriteExt: StateWrite {
 
 impl<T: StateWrite + ?Sized> Ics20TransferWriteExt for T {}
 
+// escrows or burns the tokens sent in an outbound transfer over `source_channel`, depending on
+// whether we are the source of the denom.
+async fn send_tokens<S: StateWrite + ?Sized>(
+    state: &mut S,
+    source_channel: &ChannelId,
+    denom: &asset::Metadata,
+    amount: Amount,
+) -> Result<()> {
+    let value_balance_key =
+        state_key::ics20_value_balance::by_asset_id(source_channel, &denom.id());
+    let value_balance: Amount = state
+        .get(&value_balance_key)
+        .await?
+        .unwrap_or_else(Amount::zero);
+
+    let prefix = format!("transfer/{}/", source_channel);
+    if !denom.starts_with(&prefix) {
+        // we are the source. add the value balance to the escrow channel.
+        let new_value_balance = value_balance
+            .checked_add(&amount)
+            .ok_or_else(|| anyhow::anyhow!("overflow adding value balance in ics20 withdrawal"))?;
+        state.put(value_balance_key, new_value_balance);
+    } else {
+        // receiver is the source, burn utxos
+
+        // double check the value balance here.
+        //
+        // for assets not originating from Penumbra, never transfer out more tokens than were
+        // transferred in. (Our counterparties should be checking this anyways, since if we
+        // were Byzantine we could lie to them).
+        if value_balance < amount {
+            anyhow::bail!("insufficient balance to withdraw tokens");
+        }
+
+        let new_value_balance = value_balance.checked_sub(&amount).ok_or_else(|| {
+            anyhow::anyhow!("underflow subtracting value balance in ics20 withdrawal")
+        })?;
+        state.put(value_balance_key, new_value_balance);
+        state
+            .decrease_token_supply(Value {
+                amount,
+                asset_id: denom.id(),
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+// reverses `send_tokens` for an outbound transfer packet, unescrowing or reminting the tokens it
+// sent, and returns their value.
+async fn revert_send_tokens<S: StateWrite + ?Sized>(
+    state: &mut S,
+    packet: &Packet,
+) -> Result<Value> {
+    let packet_data: FungibleTokenPacketData = serde_json::from_slice(packet.data.as_slice())?;
+    let denom: asset::Metadata = packet_data // CRITICAL: verify that this denom is validated in upstream timeout and acknowledgement handling
+        .denom
+        .as_str()
+        .try_into()
+        .context("couldn't decode denom in ics20 transfer refund")?;
+    let amount: Amount = packet_data
+        .amount
+        .try_into()
+        .context("couldn't decode amount in ics20 transfer refund")?;
+
+    let value: Value = Value {
+        amount,
+        asset_id: denom.id(),
+    };
+
+    let value_balance_key =
+        state_key::ics20_value_balance::by_asset_id(&packet.chan_on_a, &denom.id());
+    let value_balance: Amount = state
+        .get(&value_balance_key)
+        .await?
+        .unwrap_or_else(Amount::zero);
+
+    if is_source(&packet.port_on_a, &packet.chan_on_a, &denom, true) {
+        // sender was source chain, unescrow tokens
+        if value_balance < amount {
+            anyhow::bail!("couldn't return coins in refund: not enough value balance");
+        }
+
+        let new_value_balance = value_balance
+            .checked_sub(&amount)
+            .context("underflow in ics20 refund value balance subtraction")?;
+        state.put(value_balance_key, new_value_balance);
+    } else {
+        // receiver was source chain, mint vouchers back
+        state.increase_token_supply(value).await?;
+
+        let new_value_balance = value_balance.saturating_add(&value.amount);
+        state.put(value_balance_key, new_value_balance);
+    }
+
+    Ok(value)
+}
+
+// returns the penumbra denom of the tokens in an inbound transfer packet, and whether they are
+// returning to penumbra (i.e. we are the source of the denom) rather than vouchers for a
+// counterparty denom.
+//
+// see this part of the spec for this logic:
+//
+// https://github.com/cosmos/ibc/tree/main/spec/app/ics-020-fungible-token-transfer (onRecvPacket)
+fn inbound_denom(
+    packet: &Packet,
+    packet_data: &FungibleTokenPacketData,
+) -> Result<(asset::Metadata, bool)> {
+    let denom: asset::Metadata = packet_data
+        .denom
+        .as_str()
+        .try_into()
+        .context("couldnt decode denom in ICS20 transfer")?;
+
+    // NOTE: here we assume we are chain A.
+    if is_source(&packet.port_on_a, &packet.chan_on_a, &denom, false) {
+        // the denom is ours, with the source removed
+        let prefix = format!(
+            "{source_port}/{source_chan}/",
+            source_port = packet.port_on_a,
+            source_chan = packet.chan_on_a
+        );
+
+        let unprefixed_denom: asset::Metadata = packet_data
+            .denom
+            .strip_prefix(&prefix)
+            .context(format!(
+                "denom in packet didn't begin with expected prefix {}",
+                prefix
+            ))?
+            .try_into()
+            .context("couldnt decode denom in ICS20 transfer")?;
+
+        Ok((unprefixed_denom, true))
+    } else {
+        // create new denom:
+        //
+        // prefix = "{packet.destPort}/{packet.destChannel}/"
+        // prefixedDenomination = prefix + data.denom
+        let prefixed_denomination = format!(
+            "{}/{}/{}",
+            packet.port_on_b, packet.chan_on_b, packet_data.denom
+        );
+
+        let denom: asset::Metadata = prefixed_denomination
+            .as_str()
+            .try_into()
+            .context("unable to parse denom in ics20 transfer as DenomMetadata")?;
+
+        Ok((denom, false))
+    }
+}
+
+// unescrows or mints the tokens received in an inbound transfer packet, without paying them out
+// to anyone, and returns their penumbra denom and value.
+async fn receive_tokens<S: StateWrite + ?Sized>(
+    state: &mut S,
+    packet: &Packet,
+    packet_data: &FungibleTokenPacketData,
+) -> Result<(asset::Metadata, Value)> {
+    let (denom, returning) = inbound_denom(packet, packet_data)?;
+    let amount: Amount = packet_data
+        .amount
+        .clone()
+        .try_into()
+        .context("couldnt decode amount in ICS20 transfer")?;
+    let value = Value {
+        amount,
+        asset_id: denom.id(),
+    };
+
+    // inbound transfers over the rate limit are rejected with an error acknowledgement.
+    state
+        .check_ics20_rate_limit(Ics20Flow::Inbound, &packet.chan_on_b, value)
+        .await?;
+
+    let value_balance_key =
+        state_key::ics20_value_balance::by_asset_id(&packet.chan_on_b, &denom.id());
+    let value_balance: Amount = state
+        .get(&value_balance_key)
+        .await?
+        .unwrap_or_else(Amount::zero);
+
+    if returning {
+        // check if we have enough balance to unescrow tokens to receiver
+        if value_balance < amount {
+            // error text here is from the ics20 spec
+            anyhow::bail!("transfer coins failed");
+        }
+
+        let new_value_balance = value_balance
+            .checked_sub(&amount)
+            .context("underflow subtracing value balance in ics20 transfer")?;
+        state.put(value_balance_key, new_value_balance);
+    } else {
+        state.register_denom(&denom).await;
+        state.increase_token_supply(value).await?;
+
+        let new_value_balance = value_balance.saturating_add(&amount);
+        state.put(value_balance_key, new_value_balance);
+    }
+
+    state
+        .record_ics20_flow(Ics20Flow::Inbound, &packet.chan_on_b, value)
+        .await?;
+
+    Ok((denom, value))
+}
+
+// reverses `receive_tokens` for an inbound transfer packet, escrowing or burning the tokens it
+// received again so that they can be returned to the counterparty.
+async fn revert_receive_tokens<S: StateWrite + ?Sized>(
+    state: &mut S,
+    packet: &Packet,
+    packet_data: &FungibleTokenPacketData,
+) -> Result<()> {
+    let (denom, returning) = inbound_denom(packet, packet_data)?;
+    let amount: Amount = packet_data
+        .amount
+        .clone()
+        .try_into()
+        .context("couldnt decode amount in ICS20 transfer")?;
+
+    let value_balance_key =
+        state_key::ics20_value_balance::by_asset_id(&packet.chan_on_b, &denom.id());
+    let value_balance: Amount = state
+        .get(&value_balance_key)
+        .await?
+        .unwrap_or_else(Amount::zero);
+
+    if returning {
+        let new_value_balance = value_balance
+            .checked_add(&amount)
+            .context("overflow adding value balance in ics20 transfer")?;
+        state.put(value_balance_key, new_value_balance);
+    } else {
+        let new_value_balance = value_balance
+            .checked_sub(&amount)
+            .context("underflow subtracing value balance in ics20 transfer")?;
+        state.put(value_balance_key, new_value_balance);
+        state
+            .decrease_token_supply(Value {
+                amount,
+                asset_id: denom.id(),
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+// forwards the tokens received in an inbound transfer packet over another channel, as directed
+// by its memo. the inbound packet is stored so that it can be acknowledged once the forwarded
+// packet is.
+async fn forward_tokens<S: StateWrite + ?Sized>(
+    state: &mut S,
+    inbound: &Packet,
+    inbound_data: &FungibleTokenPacketData,
+    forward: &ForwardMemo,
+    denom: &asset::Metadata,
+    amount: Amount,
+) -> Result<()> {
+    let port_id = forward.port_id()?;
+    if port_id != PortId::transfer() {
+        anyhow::bail!("ics20 transfers can only be forwarded over the transfer port");
+    }
+    let channel_id = forward.channel_id()?;
+
+    let packet_data = FungibleTokenPacketData {
+        denom: denom.to_string(),
+        amount: amount.to_string(),
+        // the receiver of the inbound transfer is the sender of the forwarded one.
+        sender: inbound_data.receiver.clone(),
+        receiver: forward.receiver.clone(),
+        memo: forward.next_memo(),
+    };
+
+    let timeout_height = forward_timeout_height(state, &port_id, &channel_id).await?;
+    let timeout_timestamp = (state
+        .get_current_block_timestamp()
+        .await?
+        .unix_timestamp_nanos() as u64)
+        .saturating_add(forward.timeout_nanos());
+
+    // In violation of the ICS20 spec, ibc-go encodes transfer packets as JSON.
+    let packet = IBCPacket::new(
+        port_id.clone(),
+        channel_id.clone(),
+        timeout_height,
+        timeout_timestamp,
+        serde_json::to_vec(&packet_data)?,
+    );
+    let checked_packet = state.send_packet_check(packet).await?;
+
+    let value = Value {
+        amount,
+        asset_id: denom.id(),
+    };
+    state
+        .check_ics20_rate_limit(Ics20Flow::Outbound, &channel_id, value)
+        .await?;
+    send_tokens(state, &channel_id, denom, amount).await?;
+    state
+        .record_ics20_flow(Ics20Flow::Outbound, &channel_id, value)
+        .await?;
+
+    // the forwarded packet is sent with the channel's next send sequence.
+    let sequence = state.get_send_sequence(&channel_id, &port_id).await?;
+    state.send_packet_execute(checked_packet).await;
+    state.put(
+        ics20_forward::by_packet(&port_id, &channel_id, sequence),
+        inbound.clone(),
+    );
+
+    Ok(())
+}
+
+// returns the timeout height for a transfer forwarded over `channel_id`, some way past the latest
+// height of the counterparty known to the channel's client.
+async fn forward_timeout_height<S: StateRead + ?Sized>(
+    state: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Height> {
+    let channel = state
+        .get_channel(channel_id, port_id)
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!("channel {} on port {} does not exist", channel_id, port_id)
+        })?;
+    let connection = state
+        .get_connection(&channel.connection_hops[0])
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!("connection {} does not exist", channel.connection_hops[0])
+        })?;
+    let latest_height = state
+        .get_client_state(&connection.client_id)
+        .await?
+        .latest_height();
+
+    Ok(Height {
+        revision_number: latest_height.revision_number,
+        revision_height: latest_height.revision_height + FORWARD_TIMEOUT_BLOCKS,
+    })
+}
+
+// if `packet` forwarded an inbound transfer, completes the inbound transfer according to the
+// outcome of the forwarded one and returns true. `failure` is the reason the forwarded transfer
+// failed, if it did, in which case the tokens are returned to the chain they came from by
+// acknowledging the inbound transfer with an error.
+async fn complete_forward<S: StateWrite + ?Sized>(
+    state: &mut S,
+    packet: &Packet,
+    failure: Option<String>,
+) -> Result<bool> {
+    let key = ics20_forward::by_packet(&packet.port_on_a, &packet.chan_on_a, packet.sequence.0);
+    let Some(inbound) = state.get::<Packet>(&key).await? else {
+        return Ok(false);
+    };
+    state.delete(key);
+
+    let ack: Vec<u8> = match failure {
+        None => TokenTransferAcknowledgement::success().into(),
+        Some(reason) => {
+            tracing::debug!(%reason, "forwarded ics20 transfer failed, returning funds");
+            let inbound_data: FungibleTokenPacketData =
+                serde_json::from_slice(inbound.data.as_slice())?;
+            revert_send_tokens(state, packet).await?;
+            revert_receive_tokens(state, &inbound, &inbound_data).await?;
+            TokenTransferAcknowledgement::Error(reason).into()
+        }
+    };
+
+    state
+        .write_acknowledgement(&inbound, &ack)
+        .await
+        .context("able to acknowledge forwarded transfer")?;
+
+    Ok(true)
+}
+
 // see: https://github.com/cosmos/ibc/tree/master/spec/app/ics-020-fungible-token-transfer
 #[async_trait]
 impl AppHandlerCheck for Ics20Transfer {
@@ -220,289 +577,143 @@ impl AppHandlerCheck for Ics20Transfer {
     }
 
     async fn timeout_packet_check<S: StateRead>(state: S, msg: &MsgTimeout) -> Result<()> {
-        let packet_data: FungibleTokenPacketData =
-            serde_json::from_slice(msg.packet.data.as_slice())?;
-        let denom: asset::Metadata = packet_data.denom.as_str().try_into()?;
-
-        if is_source(&msg.packet.port_on_a, &msg.packet.chan_on_a, &denom, true) {
-            // check if we have enough balance to refund tokens to sender
-            let value_balance: Amount = state
-                .get(&state_key::ics20_value_balance::by_asset_id(
-                    &msg.packet.chan_on_a,
-                    &denom.id(),
-                ))
-                .await?
-                .unwrap_or_else(Amount::zero);
-
-            let amount_penumbra: Amount = packet_data.amount.try_into()?;
-            if value_balance < amount_penumbra {
-                anyhow::bail!("insufficient balance to refund tokens to sender");
-            }
-        }
-
-        Ok(())
+        refund_tokens_check(state, &msg.packet).await
     }
 
     async fn acknowledge_packet_check<S: StateRead>(
-        _state: S,
-        _msg: &MsgAcknowledgement,
+        state: S,
+        msg: &MsgAcknowledgement,
     ) -> Result<()> {
+        let ack: TokenTransferAcknowledgement =
+            serde_json::from_slice(msg.acknowledgement.as_slice())
+                .context("couldn't decode ics20 packet acknowledgement")?;
+
+        if let TokenTransferAcknowledgement::Error(_) = ack {
+            refund_tokens_check(state, &msg.packet).await?;
+        }
+
         Ok(())
     }
 }
 
-// the main entry point for ICS20 transfer packet handling
-async fn recv_transfer_packet_inner<S: StateWrite>(
-    mut state: S,
-    msg: &MsgRecvPacket,
-) -> Result<()> {
-    // parse if we are source or dest, and mint or burn accordingly
-    //
-    // see this part of the spec for this logic:
-    //
-    // https://github.com/cosmos/ibc/tree/main/spec/app/ics-020-fungible-token-transfer (onRecvPacket)
-    //
-    // NOTE: spec says proto but this is actually JSON according to the ibc-go implementation
-    let packet_data: FungibleTokenPacketData = serde_json::from_slice(msg.packet.data.as_slice())
-        .with_context(|| "failed to decode FTPD packet")?;
-    let denom: asset::Metadata = packet_data
-        .denom
-        .as_str()
-        .try_into()
-        .context("couldnt decode denom in ICS20 transfer")?;
-    let receiver_amount: Amount = packet_data
-        .amount
-        .try_into()
-        .context("couldnt decode amount in ICS20 transfer")?;
-    let receiver_address = Address::from_str(&packet_data.receiver)?;
-
-    // NOTE: here we assume we are chain A.
-
-    // 2. check if we are the source chain for the denom.
-    if is_source(&msg.packet.port_on_a, &msg.packet.chan_on_a, &denom, false) {
-        // mint tokens to receiver in the amount of packet_data.amount in the denom of denom (with
-        // the source removed, since we're the source)
-        let prefix = format!(
-            "{source_port}/{source_chan}/",
-            source_port = msg.packet.port_on_a,
-            source_chan = msg.packet.chan_on_a
-        );
-
-        let unprefixed_denom: asset::Metadata = packet_data
-            .denom
-            .strip_prefix(&prefix)
-            .context(format!(
-                "denom in packet didn't begin with expected prefix {}",
-                prefix
-            ))?
-            .try_into()
-            .context("couldnt decode denom in ICS20 transfer")?;
-
-        let value: Value = Value {
-            amount: receiver_amount,
-            asset_id: unprefixed_denom.id(),
-        };
+// checks that the tokens sent in an outbound transfer packet can be refunded to the sender.
+async fn refund_tokens_check<S: StateRead>(state: S, packet: &Packet) -> Result<()> {
+    let packet_data: FungibleTokenPacketData = serde_json::from_slice(packet.data.as_slice())?;
+    let denom: asset::Metadata = packet_data.denom.as_str().try_into()?;
 
-        // assume AppHandlerCheck has already been called, and we have enough balance to mint tokens to receiver
-        // check if we have enough balance to unescrow tokens to receiver
+    if is_source(&packet.port_on_a, &packet.chan_on_a, &denom, true) {
+        // check if we have enough balance to refund tokens to sender
         let value_balance: Amount = state
             .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_b,
-                &unprefixed_denom.id(),
+                &packet.chan_on_a,
+                &denom.id(),
             ))
             .await?
             .unwrap_or_else(Amount::zero);
 
-        if value_balance < receiver_amount {
-            // error text here is from the ics20 spec
-            anyhow::bail!("transfer coins failed");
+        let amount_penumbra: Amount = packet_data.amount.try_into()?;
+        if value_balance < amount_penumbra {
+            anyhow::bail!("insufficient balance to refund tokens to sender");
         }
+    }
 
-        state
-            .mint_note(
-                value,
-                &receiver_address,
-                CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
-                    // We are chain A
-                    channel_id: msg.packet.chan_on_a.0.clone(),
-                    sender: packet_data.sender.clone(),
-                },
-            )
-            .await
-            .context("unable to mint note when receiving ics20 transfer packet")?;
-
-        // update the value balance
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_b,
-                &unprefixed_denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
-        // note: this arithmetic was checked above, but we do it again anyway.
-        let new_value_balance = value_balance
-            .checked_sub(&receiver_amount)
-            .context("underflow subtracing value balance in ics20 transfer")?;
-        state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_b, &denom.id()),
-            new_value_balance,
-        );
-    } else {
-        // create new denom:
-        //
-        // prefix = "{packet.destPort}/{packet.destChannel}/"
-        // prefixedDenomination = prefix + data.denom
-        //
-        // then mint that denom to packet_data.receiver in packet_data.amount
-        let prefixed_denomination = format!(
-            "{}/{}/{}",
-            msg.packet.port_on_b, msg.packet.chan_on_b, packet_data.denom
-        );
+    Ok(())
+}
 
-        let denom: asset::Metadata = prefixed_denomination
-            .as_str()
-            .try_into()
-            .context("unable to parse denom in ics20 transfer as DenomMetadata")?;
-        state.register_denom(&denom).await;
+// the result of handling an inbound transfer packet.
+#[derive(Debug)]
+enum RecvOutcome {
+    // the transfer is complete, and the packet can be acknowledged.
+    Acknowledge,
+    // the tokens were forwarded over another channel, and the packet will be acknowledged once
+    // the forwarded transfer is.
+    Forwarded,
+}
 
-        let value = Value {
-            amount: receiver_amount,
-            asset_id: denom.id(),
-        };
+// the main entry point for ICS20 transfer packet handling
+async fn recv_transfer_packet_inner<S: StateWrite>(
+    mut state: S,
+    packet: &Packet,
+) -> Result<RecvOutcome> {
+    // NOTE: spec says proto but this is actually JSON according to the ibc-go implementation
+    let packet_data: FungibleTokenPacketData = serde_json::from_slice(packet.data.as_slice())
+        .with_context(|| "failed to decode FTPD packet")?;
+    let receiver_address = Address::from_str(&packet_data.receiver)?;
+    let memo = Ics20Memo::parse(&packet_data.memo)?;
+
+    let (denom, value) = receive_tokens(&mut state, packet, &packet_data).await?;
+    let source = CommitmentSource::Ics20Transfer {
+        packet_seq: packet.sequence.0,
+        // We are chain A
+        channel_id: packet.chan_on_a.0.clone(),
+        sender: packet_data.sender.clone(),
+    };
 
-        state
-            .mint_note(
-                value,
-                &receiver_address,
-                CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
-                    // We are chain A
-                    channel_id: msg.packet.chan_on_a.0.clone(),
-                    sender: packet_data.sender.clone(),
-                },
+    match memo {
+        None => {
+            state
+                .mint_note(value, &receiver_address, source)
+                .await
+                .context("unable to mint note when receiving ics20 transfer packet")?;
+            Ok(RecvOutcome::Acknowledge)
+        }
+        Some(Ics20Memo::Forward(forward)) => {
+            forward_tokens(
+                &mut state,
+                packet,
+                &packet_data,
+                &forward,
+                &denom,
+                value.amount,
             )
             .await
-            .context("failed to mint notes in ibc transfer")?;
-
-        // update the value balance
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_b,
-                &denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
-        let new_value_balance = value_balance.saturating_add(&value.amount);
-        state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_b, &denom.id()),
-            new_value_balance,
-        );
+            .context("unable to forward ics20 transfer")?;
+            Ok(RecvOutcome::Forwarded)
+        }
+        Some(Ics20Memo::Swap(swap)) => {
+            let output_asset_id = swap.output_asset_id()?;
+            if output_asset_id == value.asset_id {
+                anyhow::bail!("swap memo must swap into a different asset");
+            }
+            let min_output = swap.min_output()?;
+            state.queue_ics20_swap(Ics20Swap {
+                input: value,
+                output_asset_id,
+                min_output,
+                claim_address: receiver_address,
+                source,
+            });
+            Ok(RecvOutcome::Acknowledge)
+        }
     }
-
-    Ok(())
 }
 
 // see: https://github.com/cosmos/ibc/blob/8326e26e7e1188b95c32481ff00348a705b23700/spec/app/ics-020-fungible-token-transfer/README.md?plain=1#L297
-async fn timeout_packet_inner<S: StateWrite>(mut state: S, msg: &MsgTimeout) -> Result<()> {
-    let packet_data: FungibleTokenPacketData = serde_json::from_slice(msg.packet.data.as_slice())?;
-    let denom: asset::Metadata = packet_data // CRITICAL: verify that this denom is validated in upstream timeout handling
-        .denom
-        .as_str()
-        .try_into()
-        .context("couldn't decode denom in ics20 transfer timeout")?;
-    // receiver was source chain, mint vouchers back to sender
-    let amount: Amount = packet_data
-        .amount
-        .try_into()
-        .context("couldn't decode amount in ics20 transfer timeout")?;
+//
+// refunds the tokens sent in an outbound transfer packet to the sender. this happens both when
+// the packet times out and when the counterparty acknowledges it with an error.
+async fn refund_tokens<S: StateWrite>(mut state: S, packet: &Packet) -> Result<()> {
+    let value = revert_send_tokens(&mut state, packet).await?;
 
     // packet_data.sender is the original sender for this packet that was not committed on the
     // other chain but was sent from penumbra. so, the penumbra refund receiver address is the
     // sender
+    let packet_data: FungibleTokenPacketData = serde_json::from_slice(packet.data.as_slice())?;
     let receiver = Address::from_str(&packet_data.sender)
-        .context("couldn't decode receiver address in ics20 timeout")?;
-
-    let value: Value = Value {
-        amount,
-        asset_id: denom.id(),
-    };
-
-    if is_source(&msg.packet.port_on_a, &msg.packet.chan_on_a, &denom, true) {
-        // sender was source chain, unescrow tokens back to sender
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_a,
-                &denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
-        if value_balance < amount {
-            anyhow::bail!("couldn't return coins in timeout: not enough value balance");
-        }
-
-        state
-            .mint_note(
-                value,
-                &receiver,
-                CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
-                    channel_id: msg.packet.chan_on_a.0.clone(),
-                    sender: packet_data.sender.clone(),
-                },
-            )
-            .await
-            .context("couldn't mint note in timeout_packet_inner")?;
-
-        // update the value balance
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_a,
-                &denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
-        // note: this arithmetic was checked above, but we do it again anyway.
-        let new_value_balance = value_balance
-            .checked_sub(&amount)
-            .context("underflow in ics20 timeout packet value balance subtraction")?;
-        state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_a, &denom.id()),
-            new_value_balance,
-        );
-    } else {
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_a,
-                &denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
-        state
-            .mint_note(
-                value,
-                &receiver,
-                // NOTE: should this be Ics20TransferTimeout?
-                CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
-                    channel_id: msg.packet.chan_on_a.0.clone(),
-                    sender: packet_data.sender.clone(),
-                },
-            )
-            .await
-            .context("failed to mint return voucher in ics20 transfer timeout")?;
-
-        let new_value_balance = value_balance.saturating_add(&value.amount);
-        state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_a, &denom.id()),
-            new_value_balance,
-        );
-    }
+        .context("couldn't decode receiver address in ics20 transfer refund")?;
+
+    state
+        .mint_note(
+            value,
+            &receiver,
+            CommitmentSource::Ics20Transfer {
+                packet_seq: packet.sequence.0,
+                channel_id: packet.chan_on_a.0.clone(),
+                sender: packet_data.sender.clone(),
+            },
+        )
+        .await
+        .context("couldn't mint note in ics20 transfer refund")?;
 
     Ok(())
 }
@@ -518,36 +729,596 @@ impl AppHandlerExecute for Ics20Transfer {
     async fn chan_close_init_execute<S: StateWrite>(_state: S, _msg: &MsgChannelCloseInit) {}
     async fn recv_packet_execute<S: StateWrite>(mut state: S, msg: &MsgRecvPacket) -> Result<()> {
         // recv packet should never fail a transaction, but it should record a failure acknowledgement.
-        let ack: Vec<u8> = match recv_transfer_packet_inner(&mut state, msg).await {
-            Ok(_) => {
-                // record packet acknowledgement without error
-                TokenTransferAcknowledgement::success().into()
+        //
+        // the transfer is handled in its own delta, so that a failure leaves no trace besides
+        // the acknowledgement.
+        let mut delta = StateDelta::new(&mut state);
+        let ack: Option<Vec<u8>> = match recv_transfer_packet_inner(&mut delta, &msg.packet).await {
+            Ok(outcome) => {
+                let (state, events) = delta.apply();
+                for event in events {
+                    state.record(event);
+                }
+                match outcome {
+                    // record packet acknowledgement without error
+                    RecvOutcome::Acknowledge => {
+                        Some(TokenTransferAcknowledgement::success().into())
+                    }
+                    // the packet is acknowledged along with the forwarded transfer
+                    RecvOutcome::Forwarded => None,
+                }
             }
             Err(e) => {
+                drop(delta);
                 tracing::debug!("couldnt execute transfer: {:#}", e);
                 // record packet acknowledgement with error
-                TokenTransferAcknowledgement::Error(e.to_string()).into()
+                Some(TokenTransferAcknowledgement::Error(e.to_string()).into())
             }
         };
 
-        state
-            .write_acknowledgement(&msg.packet, &ack)
-            .await
-            .context("able to write acknowledgement")?;
+        if let Some(ack) = ack {
+            state
+                .write_acknowledgement(&msg.packet, &ack)
+                .await
+                .context("able to write acknowledgement")?;
+        }
 
         Ok(())
     }
 
     async fn timeout_packet_execute<S: StateWrite>(mut state: S, msg: &MsgTimeout) -> Result<()> {
+        // a forwarded transfer that timed out is returned to the chain it came from.
+        if complete_forward(
+            &mut state,
+            &msg.packet,
+            Some("forwarded transfer timed out".to_string()),
+        )
+        .await
+        .context("able to timeout forwarded transfer")?
+        {
+            return Ok(());
+        }
+
         // timeouts may fail due to counterparty chains sending transfers of u128-1
-        timeout_packet_inner(&mut state, msg)
+        refund_tokens(&mut state, &msg.packet)
             .await
             .context("able to timeout packet")?;
 
         Ok(())
     }
 
-    async fn acknowledge_packet_execute<S: StateWrite>(_state: S, _msg: &MsgAcknowledgement) {}
+    async fn acknowledge_packet_execute<S: StateWrite>(
+        mut state: S,
+        msg: &MsgAcknowledgement,
+    ) -> Result<()> {
+        let ack: TokenTransferAcknowledgement =
+            serde_json::from_slice(msg.acknowledgement.as_slice())
+                .context("couldn't decode ics20 packet acknowledgement")?;
+
+        // the outcome of a forwarded transfer is relayed to the chain it came from.
+        let failure = match &ack {
+            TokenTransferAcknowledgement::Error(reason) => {
+                Some(format!("forwarded transfer failed: {reason}"))
+            }
+            _ => None,
+        };
+        if complete_forward(&mut state, &msg.packet, failure)
+            .await
+            .context("able to acknowledge forwarded transfer")?
+        {
+            return Ok(());
+        }
+
+        // the counterparty failed to process the transfer, so the funds are returned
+        // to the sender exactly as if the packet had timed out.
+        if let TokenTransferAcknowledgement::Error(reason) = ack {
+            tracing::debug!(%reason, "ics20 transfer failed on counterparty, refunding");
+            refund_tokens(&mut state, &msg.packet)
+                .await
+                .context("able to refund packet on error acknowledgement")?;
+        }
+
+        Ok(())
+    }
 }
 
 impl AppHandler for Ics20Transfer {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use base64::prelude::*;
+    use cnidarium::{ArcStateDeltaExt, StateDelta};
+    use ibc_types::core::channel::{
+        channel::State as ChannelState, ChannelEnd, Counterparty as ChannelCounterparty,
+    };
+    use ibc_types::core::client::{msgs::MsgCreateClient, ClientId};
+    use ibc_types::core::connection::{
+        ConnectionEnd, ConnectionId, Counterparty as ConnectionCounterparty,
+        State as ConnectionState,
+    };
+    use ibc_types::core::{client::Height, commitment::MerkleProof};
+    use ibc_types::timestamp::Timestamp;
+    use ibc_types::DomainType as _;
+    use penumbra_keys::test_keys;
+    use penumbra_sct::component::clock::EpochManager as _;
+    use tendermint::Time;
+
+    use penumbra_ibc::component::{
+        ChannelStateReadExt as _, ChannelStateWriteExt as _, ConnectionStateWriteExt as _,
+        HostInterface,
+    };
+    use penumbra_ibc::{
+        params::IBCParameters, IbcRelay, StateWriteExt as _, IBC_COMMITMENT_PREFIX,
+    };
+
+    use super::*;
+    use crate::component::{Ics20SwapRead as _, SupplyRead as _};
+
+    const CHANNEL: u64 = 0;
+    const FORWARD_CHANNEL: u64 = 1;
+    const AMOUNT: u64 = 100;
+    const FORWARD_MEMO: &str = r#"{"forward": {"receiver": "osmo1next", "channel": "channel-1"}}"#;
+
+    struct MockHost {}
+
+    #[async_trait]
+    impl HostInterface for MockHost {
+        async fn get_chain_id<S: StateRead>(_state: S) -> Result<String> {
+            Ok("mock_chain_id".to_string())
+        }
+
+        async fn get_revision_number<S: StateRead>(_state: S) -> Result<u64> {
+            Ok(0u64)
+        }
+
+        async fn get_block_height<S: StateRead>(state: S) -> Result<u64> {
+            Ok(state.get_block_height().await?)
+        }
+
+        async fn get_block_timestamp<S: StateRead>(state: S) -> Result<tendermint::Time> {
+            state.get_current_block_timestamp().await
+        }
+    }
+
+    fn transfer_packet(denom: &str) -> Packet {
+        let packet_data = FungibleTokenPacketData {
+            denom: denom.to_string(),
+            amount: AMOUNT.to_string(),
+            sender: test_keys::ADDRESS_0_STR.to_string(),
+            receiver: "osmo1receiver".to_string(),
+            memo: String::new(),
+        };
+
+        Packet {
+            sequence: 1u64.into(),
+            port_on_a: PortId::transfer(),
+            chan_on_a: ChannelId::new(CHANNEL),
+            port_on_b: PortId::transfer(),
+            chan_on_b: ChannelId::new(CHANNEL),
+            data: serde_json::to_vec(&packet_data).expect("can serialize packet data"),
+            timeout_height_on_b: Height::new(0, 1_000).expect("valid height").into(),
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(0).expect("valid timestamp"),
+        }
+    }
+
+    fn inbound_packet(denom: &str, memo: &str) -> Packet {
+        let packet_data = FungibleTokenPacketData {
+            denom: denom.to_string(),
+            amount: AMOUNT.to_string(),
+            sender: "osmo1sender".to_string(),
+            receiver: test_keys::ADDRESS_0_STR.to_string(),
+            memo: memo.to_string(),
+        };
+
+        Packet {
+            data: serde_json::to_vec(&packet_data).expect("can serialize packet data"),
+            ..transfer_packet(denom)
+        }
+    }
+
+    fn acknowledgement(packet: Packet, ack: TokenTransferAcknowledgement) -> MsgAcknowledgement {
+        MsgAcknowledgement {
+            packet,
+            acknowledgement: ack.into(),
+            proof_acked_on_b: MerkleProof { proofs: vec![] },
+            proof_height_on_b: Height::new(0, 1).expect("valid height"),
+            signer: String::new(),
+        }
+    }
+
+    fn value_balance_key(denom: &str) -> String {
+        channel_value_balance_key(CHANNEL, denom)
+    }
+
+    fn channel_value_balance_key(channel: u64, denom: &str) -> String {
+        let denom: asset::Metadata = denom.try_into().expect("valid denom");
+        state_key::ics20_value_balance::by_asset_id(&ChannelId::new(channel), &denom.id())
+    }
+
+    /// The packet forwarding the vouchers received by `inbound_packet("uosmo", FORWARD_MEMO)`.
+    fn forwarded_packet() -> Packet {
+        let packet_data = FungibleTokenPacketData {
+            denom: "transfer/channel-0/uosmo".to_string(),
+            amount: AMOUNT.to_string(),
+            sender: test_keys::ADDRESS_0_STR.to_string(),
+            receiver: "osmo1next".to_string(),
+            memo: String::new(),
+        };
+
+        Packet {
+            chan_on_a: ChannelId::new(FORWARD_CHANNEL),
+            chan_on_b: ChannelId::new(FORWARD_CHANNEL + 10),
+            data: serde_json::to_vec(&packet_data).expect("can serialize packet data"),
+            ..transfer_packet("transfer/channel-0/uosmo")
+        }
+    }
+
+    /// Opens the channel that transfers are received over, and the channel they are
+    /// forwarded over, along with the light client and connection backing them.
+    async fn setup_forwarding<S: StateWrite>(mut state: S) -> anyhow::Result<()> {
+        state.put_ibc_params(IBCParameters::default());
+        state.put_block_height(1);
+        let timestamp = Time::parse_from_rfc3339("2022-02-11T17:30:50.425417198Z")?;
+        state.put_block_timestamp(1, timestamp);
+
+        // The light client used by the ibc component tests.
+        let msg_create_client = BASE64_STANDARD.decode(
+            include_str!("../../../ibc/src/component/test/create_client.msg").replace('\n', ""),
+        )?;
+        IbcRelay::CreateClient(MsgCreateClient::decode(msg_create_client.as_slice())?)
+            .with_handler::<Ics20Transfer, MockHost>()
+            .check_and_execute(&mut state)
+            .await?;
+        let client_id = ClientId::from_str("07-tendermint-0")?;
+
+        let connection_id = ConnectionId::new(0);
+        state
+            .put_new_connection(
+                &connection_id,
+                ConnectionEnd {
+                    state: ConnectionState::Open,
+                    client_id: client_id.clone(),
+                    counterparty: ConnectionCounterparty {
+                        client_id,
+                        connection_id: Some(connection_id.clone()),
+                        prefix: IBC_COMMITMENT_PREFIX.clone(),
+                    },
+                    versions: vec![],
+                    delay_period: Duration::ZERO,
+                },
+            )
+            .await?;
+
+        for channel in [CHANNEL, FORWARD_CHANNEL] {
+            let channel_id = ChannelId::new(channel);
+            state.put_channel(
+                &channel_id,
+                &PortId::transfer(),
+                ChannelEnd {
+                    state: ChannelState::Open,
+                    ordering: ChannelOrder::Unordered,
+                    remote: ChannelCounterparty::new(
+                        PortId::transfer(),
+                        Some(ChannelId::new(channel + 10)),
+                    ),
+                    connection_hops: vec![connection_id.clone()],
+                    version: Version::new("ics20-1".to_string()),
+                },
+            );
+            state.put_send_sequence(&channel_id, &PortId::transfer(), 1);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the acknowledgement written for `inbound_packet`, if any.
+    async fn inbound_ack<S: StateRead>(
+        state: S,
+    ) -> anyhow::Result<Option<TokenTransferAcknowledgement>> {
+        let ack = state
+            .get_packet_acknowledgement(&PortId::transfer(), &ChannelId::new(CHANNEL), 1)
+            .await?;
+        Ok(match ack {
+            Some(ack) => Some(serde_json::from_slice(&ack)?),
+            None => None,
+        })
+    }
+
+    /// Returns the value balances of the voucher on the inbound and forwarding channels, and
+    /// its supply.
+    async fn forwarded_balances<S: StateRead>(
+        state: S,
+    ) -> anyhow::Result<(Amount, Amount, Amount)> {
+        let denom = "transfer/channel-0/uosmo";
+        let inbound: Amount = state
+            .get(&channel_value_balance_key(CHANNEL, denom))
+            .await?
+            .unwrap_or_default();
+        let escrowed: Amount = state
+            .get(&channel_value_balance_key(FORWARD_CHANNEL, denom))
+            .await?
+            .unwrap_or_default();
+        let denom: asset::Metadata = denom.try_into()?;
+        let supply = state.token_supply(&denom.id()).await?;
+        Ok((inbound, escrowed, supply))
+    }
+
+    /// Applies an error acknowledgement for a withdrawal of `denom`, starting from the
+    /// given value balance, and returns the resulting value balance, refund notes and
+    /// token supply.
+    async fn refund_on_error_ack(
+        denom: &str,
+        value_balance: Amount,
+    ) -> anyhow::Result<(Amount, usize, Amount)> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put(value_balance_key(denom), value_balance);
+
+        let msg = acknowledgement(
+            transfer_packet(denom),
+            TokenTransferAcknowledgement::Error("counterparty rejected transfer".to_string()),
+        );
+        Ics20Transfer::acknowledge_packet_check(&mut state_tx, &msg).await?;
+        Ics20Transfer::acknowledge_packet_execute(&mut state_tx, &msg).await?;
+
+        let notes = state_tx.pending_note_payloads();
+        for (_, _, source) in notes.iter() {
+            assert_eq!(
+                source,
+                &CommitmentSource::Ics20Transfer {
+                    packet_seq: 1,
+                    channel_id: ChannelId::new(CHANNEL).0,
+                    sender: test_keys::ADDRESS_0_STR.to_string(),
+                }
+            );
+        }
+
+        let value_balance: Amount = state_tx
+            .get(&value_balance_key(denom))
+            .await?
+            .unwrap_or_default();
+        let denom: asset::Metadata = denom.try_into()?;
+        let supply = state_tx.token_supply(&denom.id()).await?;
+        Ok((value_balance, notes.len(), supply))
+    }
+
+    #[tokio::test]
+    /// Tokens escrowed by a withdrawal of a native asset are unescrowed on an error ack.
+    async fn error_ack_unescrows_source_denom() -> anyhow::Result<()> {
+        let (value_balance, refunds, supply) =
+            refund_on_error_ack("upenumbra", AMOUNT.into()).await?;
+
+        assert_eq!(value_balance, Amount::zero());
+        assert_eq!(refunds, 1);
+        // Unescrowing moves existing tokens, so the supply is unchanged.
+        assert_eq!(supply, Amount::zero());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// Vouchers burned by a withdrawal of a bridged asset are minted back on an error ack.
+    async fn error_ack_remints_sink_denom() -> anyhow::Result<()> {
+        let (value_balance, refunds, supply) =
+            refund_on_error_ack("transfer/channel-0/uosmo", Amount::zero()).await?;
+
+        assert_eq!(value_balance, AMOUNT.into());
+        assert_eq!(refunds, 1);
+        assert_eq!(supply, AMOUNT.into());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A refund can't unescrow more of a native asset than the channel holds.
+    async fn error_ack_rejects_refund_exceeding_escrow() -> anyhow::Result<()> {
+        refund_on_error_ack("upenumbra", Amount::zero())
+            .await
+            .expect_err("should not refund more than was escrowed");
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// Successful acknowledgements leave the withdrawn funds with the counterparty.
+    async fn success_ack_does_not_refund() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put(value_balance_key("upenumbra"), Amount::from(AMOUNT));
+
+        let msg = acknowledgement(
+            transfer_packet("upenumbra"),
+            TokenTransferAcknowledgement::success(),
+        );
+        Ics20Transfer::acknowledge_packet_check(&mut state_tx, &msg).await?;
+        Ics20Transfer::acknowledge_packet_execute(&mut state_tx, &msg).await?;
+
+        let value_balance: Amount = state_tx
+            .get(&value_balance_key("upenumbra"))
+            .await?
+            .unwrap_or_default();
+        assert_eq!(value_balance, Amount::from(AMOUNT));
+        assert!(state_tx.pending_note_payloads().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// Returning native tokens are unescrowed from the channel and paid to the receiver.
+    async fn recv_unescrows_returning_denom() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(IBCParameters::default());
+        state_tx.put(value_balance_key("upenumbra"), Amount::from(AMOUNT));
+
+        let packet = inbound_packet("transfer/channel-0/upenumbra", "");
+        let outcome = recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+        assert!(matches!(outcome, RecvOutcome::Acknowledge));
+
+        let value_balance: Amount = state_tx
+            .get(&value_balance_key("upenumbra"))
+            .await?
+            .unwrap_or_default();
+        assert_eq!(value_balance, Amount::zero());
+        assert_eq!(state_tx.pending_note_payloads().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A swap memo queues the received tokens for the DEX instead of paying them out.
+    async fn recv_with_swap_memo_queues_swap() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(IBCParameters::default());
+
+        let packet = inbound_packet("uosmo", r#"{"swap": {"output_denom": "upenumbra"}}"#);
+        let outcome = recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+        assert!(matches!(outcome, RecvOutcome::Acknowledge));
+        assert!(state_tx.pending_note_payloads().is_empty());
+
+        let swaps = state_tx.pending_ics20_swaps();
+        assert_eq!(swaps.len(), 1);
+        let voucher: asset::Metadata = "transfer/channel-0/uosmo".try_into()?;
+        assert_eq!(
+            swaps[0].input,
+            Value {
+                amount: AMOUNT.into(),
+                asset_id: voucher.id(),
+            }
+        );
+        assert_eq!(
+            swaps[0].output_asset_id,
+            *penumbra_asset::STAKING_TOKEN_ASSET_ID
+        );
+        assert_eq!(swaps[0].claim_address, *test_keys::ADDRESS_0);
+        assert_eq!(swaps[0].min_output, Amount::zero());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// Transfers with malformed memos are rejected before any tokens are received.
+    async fn recv_rejects_malformed_memo() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(IBCParameters::default());
+
+        let packet = inbound_packet("uosmo", r#"{"forward": {"receiver": "osmo1receiver"}}"#);
+        recv_transfer_packet_inner(&mut state_tx, &packet)
+            .await
+            .expect_err("forward memo without a channel");
+
+        let value_balance: Amount = state_tx
+            .get(&value_balance_key("transfer/channel-0/uosmo"))
+            .await?
+            .unwrap_or_default();
+        assert_eq!(value_balance, Amount::zero());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A forwarded transfer escrows the received vouchers on the next channel, and the
+    /// inbound transfer is only acknowledged once the forwarded one is.
+    async fn forward_acknowledged_on_success() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        setup_forwarding(&mut state_tx).await?;
+
+        let packet = inbound_packet("uosmo", FORWARD_MEMO);
+        let outcome = recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+        assert!(matches!(outcome, RecvOutcome::Forwarded));
+        assert!(state_tx.pending_note_payloads().is_empty());
+        assert!(inbound_ack(&state_tx).await?.is_none());
+        let amount = Amount::from(AMOUNT);
+        assert_eq!(forwarded_balances(&state_tx).await?, (amount, amount, amount));
+
+        let msg = acknowledgement(forwarded_packet(), TokenTransferAcknowledgement::success());
+        Ics20Transfer::acknowledge_packet_check(&mut state_tx, &msg).await?;
+        Ics20Transfer::acknowledge_packet_execute(&mut state_tx, &msg).await?;
+
+        let ack = inbound_ack(&state_tx).await?.expect("inbound transfer is acknowledged");
+        assert!(!matches!(ack, TokenTransferAcknowledgement::Error(_)));
+        // The vouchers stay escrowed for the next chain.
+        assert_eq!(forwarded_balances(&state_tx).await?, (amount, amount, amount));
+        assert!(state_tx.pending_note_payloads().is_empty());
+        let key =
+            ics20_forward::by_packet(&PortId::transfer(), &ChannelId::new(FORWARD_CHANNEL), 1);
+        assert!(state_tx.get::<Packet>(&key).await?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A forwarded transfer that fails on the next chain is returned to the previous one.
+    async fn forward_refunded_on_error_ack() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        setup_forwarding(&mut state_tx).await?;
+
+        let packet = inbound_packet("uosmo", FORWARD_MEMO);
+        recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+
+        let msg = acknowledgement(
+            forwarded_packet(),
+            TokenTransferAcknowledgement::Error("counterparty rejected transfer".to_string()),
+        );
+        Ics20Transfer::acknowledge_packet_check(&mut state_tx, &msg).await?;
+        Ics20Transfer::acknowledge_packet_execute(&mut state_tx, &msg).await?;
+
+        assert!(matches!(
+            inbound_ack(&state_tx).await?,
+            Some(TokenTransferAcknowledgement::Error(_))
+        ));
+        // The vouchers are unescrowed and burned again, rather than paid out on Penumbra.
+        let zero = Amount::zero();
+        assert_eq!(forwarded_balances(&state_tx).await?, (zero, zero, zero));
+        assert!(state_tx.pending_note_payloads().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A forwarded transfer that times out is returned to the previous chain.
+    async fn forward_refunded_on_timeout() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        setup_forwarding(&mut state_tx).await?;
+
+        let packet = inbound_packet("uosmo", FORWARD_MEMO);
+        recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+
+        let msg = MsgTimeout {
+            packet: forwarded_packet(),
+            next_seq_recv_on_b: 1u64.into(),
+            proof_unreceived_on_b: MerkleProof { proofs: vec![] },
+            proof_height_on_b: Height::new(0, 1).expect("valid height"),
+            signer: String::new(),
+        };
+        Ics20Transfer::timeout_packet_check(&mut state_tx, &msg).await?;
+        Ics20Transfer::timeout_packet_execute(&mut state_tx, &msg).await?;
+
+        assert!(matches!(
+            inbound_ack(&state_tx).await?,
+            Some(TokenTransferAcknowledgement::Error(_))
+        ));
+        let zero = Amount::zero();
+        assert_eq!(forwarded_balances(&state_tx).await?, (zero, zero, zero));
+        assert!(state_tx.pending_note_payloads().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A swap memo's minimum output is queued along with the swap.
+    async fn recv_with_swap_memo_queues_min_output() -> anyhow::Result<()> {
+        let mut state = Arc::new(StateDelta::new(()));
+        let mut state_tx = state.try_begin_transaction().unwrap();
+        state_tx.put_ibc_params(IBCParameters::default());
+
+        let packet = inbound_packet(
+            "uosmo",
+            r#"{"swap": {"output_denom": "upenumbra", "min_output": "90"}}"#,
+        );
+        recv_transfer_packet_inner(&mut state_tx, &packet).await?;
+
+        let swaps = state_tx.pending_ics20_swaps();
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].min_output, Amount::from(90u64));
+        Ok(())
+    }
+}
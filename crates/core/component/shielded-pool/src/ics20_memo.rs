@@ -0,0 +1,203 @@
+//! Memos understood by Penumbra's ICS-20 transfer application.
+//!
+//! The memo of an inbound transfer can direct the transferred funds somewhere
+//! other than the packet's receiver:
+//!
+//! - `{"forward": {...}}` sends them on over another ICS-20 channel, following
+//!   the conventions of the packet-forward middleware;
+//! - `{"swap": {...}}` swaps them on the DEX, paying the outputs to the receiver.
+//!
+//! Any other memo, including JSON memos intended for other applications, is
+//! ignored and the funds are paid to the receiver as usual.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use ibc_types::core::channel::{ChannelId, PortId};
+use penumbra_asset::asset;
+use penumbra_num::Amount;
+use serde::{Deserialize, Serialize};
+
+/// How long a forwarded transfer has to reach the next chain if the memo
+/// doesn't say otherwise, in nanoseconds.
+pub const DEFAULT_FORWARD_TIMEOUT_NANOS: u64 = 10 * 60 * 1_000_000_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ics20Memo {
+    Forward(ForwardMemo),
+    Swap(SwapMemo),
+}
+
+impl Ics20Memo {
+    /// Parses the memo of an inbound transfer.
+    ///
+    /// Returns `None` if the memo is not addressed to Penumbra, and an error if
+    /// it is but is malformed.
+    pub fn parse(memo: &str) -> Result<Option<Self>> {
+        let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(memo) else {
+            return Ok(None);
+        };
+        if !fields.contains_key("forward") && !fields.contains_key("swap") {
+            return Ok(None);
+        }
+
+        serde_json::from_value(serde_json::Value::Object(fields))
+            .map(Some)
+            .context("invalid ics20 memo")
+    }
+}
+
+/// Forwards the transferred funds over another ICS-20 channel.
+///
+/// The inbound transfer is only acknowledged once the forwarded one is. If the
+/// forwarded transfer fails or times out, the funds are returned to the
+/// previous chain by acknowledging the inbound transfer with an error.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardMemo {
+    /// The receiver on the next chain.
+    pub receiver: String,
+    /// The port to forward over.
+    #[serde(default = "default_port")]
+    pub port: String,
+    /// The channel to forward over.
+    pub channel: String,
+    /// How long the forwarded transfer has to reach the next chain, in nanoseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// The memo of the forwarded transfer, either a string or a JSON object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<serde_json::Value>,
+}
+
+fn default_port() -> String {
+    PortId::transfer().to_string()
+}
+
+impl ForwardMemo {
+    pub fn port_id(&self) -> Result<PortId> {
+        PortId::from_str(&self.port).context("invalid port in forward memo")
+    }
+
+    pub fn channel_id(&self) -> Result<ChannelId> {
+        ChannelId::from_str(&self.channel).context("invalid channel in forward memo")
+    }
+
+    pub fn timeout_nanos(&self) -> u64 {
+        self.timeout.unwrap_or(DEFAULT_FORWARD_TIMEOUT_NANOS)
+    }
+
+    /// The memo to attach to the forwarded transfer.
+    pub fn next_memo(&self) -> String {
+        match &self.next {
+            None => String::new(),
+            Some(serde_json::Value::String(memo)) => memo.clone(),
+            Some(next) => next.to_string(),
+        }
+    }
+}
+
+/// Swaps the transferred funds on the DEX.
+///
+/// The swap is executed in the batch at the end of the block, and its outputs,
+/// along with any unfilled input, are minted to the receiver of the transfer.
+/// If the batch doesn't meet the swap's minimum output, the whole input is
+/// minted back to the receiver instead.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapMemo {
+    /// The denomination to swap into.
+    pub output_denom: String,
+    /// The minimum amount of the output denomination to accept, as a string
+    /// like the transfer's amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_output: Option<String>,
+}
+
+impl SwapMemo {
+    pub fn output_asset_id(&self) -> Result<asset::Id> {
+        asset::REGISTRY
+            .parse_denom(&self.output_denom)
+            .map(|denom| denom.id())
+            .ok_or_else(|| {
+                anyhow::anyhow!("invalid output denom {} in swap memo", self.output_denom)
+            })
+    }
+
+    /// The minimum output of the swap, which is zero if the memo doesn't set one.
+    pub fn min_output(&self) -> Result<Amount> {
+        match &self.min_output {
+            None => Ok(Amount::zero()),
+            Some(min_output) => min_output
+                .parse::<u128>()
+                .map(Amount::from)
+                .context("invalid minimum output in swap memo"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_memos_for_other_applications() {
+        assert_eq!(Ics20Memo::parse("").unwrap(), None);
+        assert_eq!(Ics20Memo::parse("thanks for the tokens").unwrap(), None);
+        assert_eq!(
+            Ics20Memo::parse(r#"{"wasm": {"contract": "osmo1contract"}}"#).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_forward_memo() {
+        let memo = Ics20Memo::parse(
+            r#"{"forward": {"receiver": "osmo1receiver", "channel": "channel-1", "next": {"forward": {"receiver": "noble1receiver", "channel": "channel-2"}}}}"#,
+        )
+        .unwrap();
+        let Some(Ics20Memo::Forward(forward)) = memo else {
+            panic!("expected a forward memo");
+        };
+        assert_eq!(forward.port_id().unwrap(), PortId::transfer());
+        assert_eq!(forward.channel_id().unwrap(), ChannelId::new(1));
+        assert_eq!(forward.timeout_nanos(), DEFAULT_FORWARD_TIMEOUT_NANOS);
+        let next = Ics20Memo::parse(&forward.next_memo()).unwrap();
+        let Some(Ics20Memo::Forward(next)) = next else {
+            panic!("expected a forward memo for the next hop");
+        };
+        assert_eq!(next.receiver, "noble1receiver");
+        assert_eq!(next.channel_id().unwrap(), ChannelId::new(2));
+    }
+
+    #[test]
+    fn rejects_malformed_memo() {
+        Ics20Memo::parse(r#"{"forward": {"receiver": "osmo1receiver"}}"#)
+            .expect_err("forward memo without a channel");
+        Ics20Memo::parse(r#"{"swap": {}}"#).expect_err("swap memo without an output denom");
+    }
+
+    #[test]
+    fn parses_swap_memo_min_output() {
+        let memo = Ics20Memo::parse(r#"{"swap": {"output_denom": "upenumbra"}}"#).unwrap();
+        let Some(Ics20Memo::Swap(swap)) = memo else {
+            panic!("expected a swap memo");
+        };
+        assert_eq!(swap.min_output().unwrap(), Amount::zero());
+
+        let memo =
+            Ics20Memo::parse(r#"{"swap": {"output_denom": "upenumbra", "min_output": "250"}}"#)
+                .unwrap();
+        let Some(Ics20Memo::Swap(swap)) = memo else {
+            panic!("expected a swap memo");
+        };
+        assert_eq!(swap.min_output().unwrap(), Amount::from(250u64));
+
+        let memo =
+            Ics20Memo::parse(r#"{"swap": {"output_denom": "upenumbra", "min_output": "-1"}}"#)
+                .unwrap();
+        let Some(Ics20Memo::Swap(swap)) = memo else {
+            panic!("expected a swap memo");
+        };
+        swap.min_output().expect_err("negative minimum output");
+    }
+}
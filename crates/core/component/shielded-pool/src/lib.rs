@@ -6,6 +6,9 @@ pub mod component;
 pub mod ics20_withdrawal;
 pub use ics20_withdrawal::Ics20Withdrawal;
 
+pub mod ics20_memo;
+pub use ics20_memo::Ics20Memo;
+
 pub mod event;
 pub mod fmd;
 pub mod genesis;
@@ -11,6 +11,47 @@ pub mod denom_metadata_by_asset {
     }
 }
 
+pub mod token_supply {
+    use penumbra_asset::asset;
+    use std::string::String;
+
+    pub fn prefix() -> &'static str {
+        "shielded_pool/supply/"
+    }
+
+    pub fn by_asset_id(asset_id: &asset::Id) -> String {
+        format!("shielded_pool/supply/{asset_id}")
+    }
+}
+
+pub mod ics20_flow {
+    use ibc_types::core::channel::ChannelId;
+    use penumbra_asset::asset;
+    use std::string::String;
+
+    pub fn prefix(direction: &str, channel_id: &ChannelId, asset_id: &asset::Id) -> String {
+        format!("shielded_pool/ics20_flow/{direction}/{channel_id}/{asset_id}/")
+    }
+
+    pub fn by_height(
+        direction: &str,
+        channel_id: &ChannelId,
+        asset_id: &asset::Id,
+        height: u64,
+    ) -> String {
+        format!("{}{height:020}", prefix(direction, channel_id, asset_id))
+    }
+}
+
+pub mod ics20_forward {
+    use ibc_types::core::channel::{ChannelId, PortId};
+    use std::string::String;
+
+    pub fn by_packet(port_id: &PortId, channel_id: &ChannelId, sequence: u64) -> String {
+        format!("shielded_pool/ics20_forward/{port_id}/{channel_id}/{sequence}")
+    }
+}
+
 // State keys used to temporarily store payloads and nullifiers to be inserted into the compact
 // block
 pub fn pending_notes() -> &'static str {
@@ -21,6 +62,10 @@ pub fn pending_rolled_up_payloads() -> &'static str {
     "shielded_pool/pending_rolled_up_payloads"
 }
 
+pub fn pending_ics20_swaps() -> &'static str {
+    "shielded_pool/pending_ics20_swaps"
+}
+
 pub fn shielded_pool_params() -> &'static str {
     "shielded_pool/params"
 }
@@ -5,6 +5,7 @@ use cnidarium_component::ActionHandler;
 use penumbra_num::Amount;
 use penumbra_proto::StateWriteProto;
 use penumbra_sct::component::clock::EpochRead;
+use penumbra_shielded_pool::component::SupplyWrite;
 
 use crate::{
     component::validator_handler::ValidatorDataRead, event, validator::State::*, Delegate,
@@ -129,6 +130,11 @@ impl ActionHandler for Delegate {
             }
         }
 
+        // The delegation converts unbonded stake into delegation tokens right away,
+        // even though the validator's pool only changes at the epoch boundary.
+        state.decrease_token_supply(self.unbonded_value()).await?;
+        state.increase_token_supply(self.delegation_value()).await?;
+
         // We queue the delegation so it can be processed at the epoch boundary.
         tracing::debug!(?self, "queuing delegation for next epoch");
         state.push_delegation(self.clone());
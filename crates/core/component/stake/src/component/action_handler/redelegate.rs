@@ -0,0 +1,134 @@
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+use cnidarium::StateWrite;
+use cnidarium_component::ActionHandler;
+use penumbra_num::Amount;
+use penumbra_proto::StateWriteProto;
+use penumbra_sct::component::clock::EpochRead;
+use penumbra_shielded_pool::component::SupplyWrite;
+
+use crate::{
+    component::validator_handler::ValidatorDataRead,
+    event,
+    validator::State::*,
+    Redelegate, StateReadExt as _, StateWriteExt as _,
+};
+
+#[async_trait]
+impl ActionHandler for Redelegate {
+    type CheckStatelessContext = ();
+    async fn check_stateless(&self, _context: ()) -> Result<()> {
+        ensure!(
+            self.from_validator != self.to_validator,
+            "cannot redelegate from validator {} to itself",
+            self.from_validator,
+        );
+        Ok(())
+    }
+
+    async fn check_and_execute<S: StateWrite>(&self, mut state: S) -> Result<()> {
+        let r = self;
+
+        // Check that the redelegation was prepared for the current epoch, so that
+        // both exchange rates are current and the source validator's slashing
+        // liability is tracked from the right epoch.
+        let current_epoch = state.get_current_epoch().await?;
+        ensure!(
+            r.from_epoch == current_epoch,
+            "redelegation was prepared for epoch {} but the current epoch is {}",
+            r.from_epoch.index,
+            current_epoch.index
+        );
+
+        // A redelegation is an undelegation followed by a delegation, so we
+        // enforce the same directions of computation as those actions: from the
+        // source delegation tokens to the unbonded amount, and from the unbonded
+        // amount to the destination delegation tokens.
+        let from_rate = state
+            .get_validator_rate(&r.from_validator)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("unknown validator identity {}", r.from_validator))?;
+        let expected_unbonded_amount = from_rate.unbonded_amount(r.from_delegation_amount);
+        ensure!(
+            r.unbonded_amount == expected_unbonded_amount,
+            "given {} delegation tokens, expected {} unbonded stake but description produces {}",
+            r.from_delegation_amount,
+            expected_unbonded_amount,
+            r.unbonded_amount,
+        );
+
+        let to_rate = state
+            .get_validator_rate(&r.to_validator)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("unknown validator identity {}", r.to_validator))?;
+        let expected_to_delegation_amount = to_rate.delegation_amount(r.unbonded_amount);
+        ensure!(
+            r.to_delegation_amount == expected_to_delegation_amount,
+            "given {} unbonded stake, expected {} delegation tokens but description produces {}",
+            r.unbonded_amount,
+            expected_to_delegation_amount,
+            r.to_delegation_amount,
+        );
+
+        // The destination must accept delegations, under the same conditions as a `Delegate`.
+        let to_validator = state
+            .get_validator_definition(&r.to_validator)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("missing definition for validator"))?;
+        let to_validator_state = state
+            .get_validator_state(&r.to_validator)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("missing state for validator"))?;
+
+        ensure!(
+            to_validator.enabled,
+            "redelegations are only allowed to enabled validators, but {} is disabled",
+            r.to_validator,
+        );
+
+        ensure!(
+            matches!(to_validator_state, Defined | Inactive | Active),
+            "redelegations are only allowed to active or inactive validators, but {} is in state {:?}",
+            r.to_validator,
+            to_validator_state,
+        );
+
+        // See the `Delegate` action handler: the first delegation to a `Defined`
+        // validator must meet the minimum validator stake.
+        if to_validator_state == Defined {
+            let to_validator_pool_size = state
+                .get_validator_pool_size(&r.to_validator)
+                .await
+                .unwrap_or_else(Amount::zero);
+
+            if to_validator_pool_size == Amount::zero() {
+                let min_stake = state.get_stake_params().await?.min_validator_stake;
+                ensure!(
+                    r.unbonded_amount >= min_stake,
+                    "first delegation to a `Defined` validator must be at least {min_stake}"
+                );
+            }
+        }
+
+        /* ----- execution ------ */
+
+        // Unlike an undelegation, the stake never leaves the delegation pools,
+        // so the supply only moves from the source delegation token to the
+        // redelegation token, which stays liable for the source validator's
+        // penalties until it is claimed.
+        state
+            .decrease_token_supply(self.from_delegation_value())
+            .await?;
+        state.increase_token_supply(self.redelegation_value()).await?;
+
+        tracing::debug!(?self, "queuing redelegation for next epoch");
+        state.push_redelegation(self.clone());
+
+        state.record_proto(event::redelegate(self));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
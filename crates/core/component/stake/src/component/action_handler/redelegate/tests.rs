@@ -0,0 +1,264 @@
+use cnidarium::{StateDelta, StateWrite, TempStorage};
+use cnidarium_component::ActionHandler as _;
+use decaf377_rdsa::{SigningKey, SpendAuth, VerificationKey};
+use penumbra_num::Amount;
+use penumbra_proto::StateWriteProto as _;
+use penumbra_sct::{component::clock::EpochManager as _, epoch::Epoch};
+use penumbra_shielded_pool::component::{SupplyRead as _, SupplyWrite as _};
+use rand_core::OsRng;
+
+use crate::{
+    component::{
+        stake::SlashingData as _,
+        validator_handler::{ValidatorDataWrite as _, ValidatorPoolTracker as _},
+    },
+    params::StakeParameters,
+    rate::RateData,
+    state_key,
+    validator::{BondingState, State, Validator},
+    FundingStreams, GovernanceKey, IdentityKey, Penalty, RedelegateClaim, StateReadExt as _,
+    StateWriteExt as _,
+};
+
+const UNBONDING_DELAY: u64 = 100;
+
+/// The epoch in which the redelegations under test are performed.
+const REDELEGATION_EPOCH: Epoch = Epoch {
+    index: 1,
+    start_height: 10,
+};
+
+/// The epoch containing the height at which the redelegations finish unbonding.
+const UNBONDING_EPOCH: Epoch = Epoch {
+    index: 2,
+    start_height: 100,
+};
+
+/// Defines a validator with the given state and exchange rate, and a pool of
+/// `pool_size` delegation tokens.
+fn define_validator<S: StateWrite>(
+    state: &mut S,
+    validator_state: State,
+    enabled: bool,
+    exchange_rate: u128,
+    pool_size: Amount,
+) -> RateData {
+    let sk = SigningKey::<SpendAuth>::new(OsRng);
+    let identity_key = IdentityKey(VerificationKey::from(&sk).into());
+    let consensus_key = tendermint::PublicKey::from_raw_ed25519(
+        &ed25519_consensus::SigningKey::new(OsRng)
+            .verification_key()
+            .to_bytes(),
+    )
+    .expect("consensus key is valid");
+
+    let validator = Validator {
+        identity_key,
+        consensus_key,
+        governance_key: GovernanceKey(VerificationKey::from(&sk)),
+        enabled,
+        sequence_number: 0,
+        name: "test validator".to_string(),
+        website: String::default(),
+        description: String::default(),
+        funding_streams: FundingStreams::default(),
+    };
+    let rate_data = RateData {
+        identity_key,
+        validator_reward_rate: 0u128.into(),
+        validator_exchange_rate: exchange_rate.into(),
+    };
+
+    state.put(state_key::validators::definitions::by_id(&identity_key), validator);
+    state.put(state_key::validators::state::by_id(&identity_key), validator_state);
+    state.set_validator_rate_data(&identity_key, rate_data.clone());
+    state.set_validator_bonding_state(&identity_key, BondingState::Bonded);
+    state.set_validator_pool_size(&identity_key, pool_size);
+
+    rate_data
+}
+
+/// Sets the current height, and the epoch it belongs to.
+fn set_height<S: StateWrite>(state: &mut S, height: u64, epoch: Epoch) {
+    state.put_block_height(height);
+    state.put_epoch_by_height(height, epoch);
+}
+
+fn setup<S: StateWrite>(state: &mut S) {
+    state.put_stake_params(StakeParameters {
+        unbonding_delay: UNBONDING_DELAY,
+        ..Default::default()
+    });
+    set_height(state, REDELEGATION_EPOCH.start_height, REDELEGATION_EPOCH);
+}
+
+#[tokio::test]
+async fn redelegate_moves_stake_into_redelegation_tokens() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+
+    let from = define_validator(&mut state, State::Active, true, 2_0000_0000, 1000u64.into());
+    let to = define_validator(&mut state, State::Active, true, 1_2500_0000, 1000u64.into());
+    let redelegate = from.build_redelegate(&to, REDELEGATION_EPOCH, 100u64.into());
+    state
+        .increase_token_supply(redelegate.from_delegation_value())
+        .await?;
+
+    redelegate.check_stateless(()).await?;
+    redelegate.check_and_execute(&mut state).await?;
+
+    let from_token_id = redelegate.from_delegation_token().id();
+    let redelegation_token_id = redelegate.redelegation_token().id();
+    assert_eq!(state.token_supply(&from_token_id).await?, Amount::zero());
+    assert_eq!(state.token_supply(&redelegation_token_id).await?, 160u64.into());
+    assert_eq!(state.get_delegation_changes_tally().redelegations, vec![redelegate]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelegate_rejects_mismatched_rates() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+
+    let from = define_validator(&mut state, State::Active, true, 2_0000_0000, 1000u64.into());
+    let to = define_validator(&mut state, State::Active, true, 1_2500_0000, 1000u64.into());
+    let redelegate = from.build_redelegate(&to, REDELEGATION_EPOCH, 100u64.into());
+
+    let mut wrong_unbonded_amount = redelegate.clone();
+    wrong_unbonded_amount.unbonded_amount = 201u64.into();
+    assert!(wrong_unbonded_amount.check_and_execute(&mut state).await.is_err());
+
+    let mut wrong_to_delegation_amount = redelegate.clone();
+    wrong_to_delegation_amount.to_delegation_amount = 161u64.into();
+    assert!(wrong_to_delegation_amount.check_and_execute(&mut state).await.is_err());
+
+    let mut wrong_epoch = redelegate;
+    wrong_epoch.from_epoch = UNBONDING_EPOCH;
+    assert!(wrong_epoch.check_and_execute(&mut state).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelegate_rejects_disabled_destination() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+
+    let from = define_validator(&mut state, State::Active, true, 1_0000_0000, 1000u64.into());
+    let to = define_validator(&mut state, State::Active, false, 1_0000_0000, 1000u64.into());
+    let redelegate = from.build_redelegate(&to, REDELEGATION_EPOCH, 100u64.into());
+
+    assert!(redelegate.check_and_execute(&mut state).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelegate_to_defined_validator_requires_minimum_stake() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+    let min_stake = state.get_stake_params().await?.min_validator_stake;
+
+    let from = define_validator(&mut state, State::Active, true, 1_0000_0000, min_stake);
+    let to = define_validator(&mut state, State::Defined, true, 1_0000_0000, Amount::zero());
+
+    let too_small = from.build_redelegate(&to, REDELEGATION_EPOCH, min_stake - 1u64.into());
+    assert!(too_small.check_and_execute(&mut state).await.is_err());
+
+    let enough = from.build_redelegate(&to, REDELEGATION_EPOCH, min_stake);
+    enough.check_and_execute(&mut state).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelegate_claim_waits_for_unbonding_height() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+
+    let from = define_validator(&mut state, State::Active, true, 1_0000_0000, 1000u64.into());
+    let to = define_validator(&mut state, State::Active, true, 1_0000_0000, 1000u64.into());
+    let redelegate = from.build_redelegate(&to, REDELEGATION_EPOCH, 100u64.into());
+    redelegate.check_and_execute(&mut state).await?;
+
+    let claim = RedelegateClaim::new(
+        &redelegate.redelegation_token(),
+        Penalty::from_percent(0),
+        redelegate.to_delegation_amount,
+    );
+    claim.check_stateless(()).await?;
+
+    let unbonding_height = REDELEGATION_EPOCH.start_height + UNBONDING_DELAY;
+    set_height(&mut state, unbonding_height - 1, UNBONDING_EPOCH);
+    assert!(claim.check_and_execute(&mut state).await.is_err());
+
+    set_height(&mut state, unbonding_height, UNBONDING_EPOCH);
+    claim.check_and_execute(&mut state).await?;
+
+    assert_eq!(state.token_supply(&claim.redelegation_token().id()).await?, Amount::zero());
+    assert_eq!(state.token_supply(&claim.delegation_token().id()).await?, 100u64.into());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelegate_claim_charges_source_penalty_to_redelegated_stake_only() -> anyhow::Result<()> {
+    let storage = TempStorage::new().await?;
+    let mut state = StateDelta::new(storage.latest_snapshot());
+    setup(&mut state);
+
+    let from = define_validator(&mut state, State::Active, true, 1_0000_0000, 1000u64.into());
+    let to = define_validator(&mut state, State::Active, true, 1_0000_0000, 1000u64.into());
+    let redelegate = from.build_redelegate(&to, REDELEGATION_EPOCH, 1000u64.into());
+    redelegate.check_and_execute(&mut state).await?;
+
+    // The source validator is slashed during the unbonding window.
+    let penalty = Penalty::from_percent(10);
+    state.put(
+        state_key::penalty::for_id_in_epoch(&from.identity_key, REDELEGATION_EPOCH.index),
+        penalty,
+    );
+    set_height(&mut state, REDELEGATION_EPOCH.start_height + UNBONDING_DELAY, UNBONDING_EPOCH);
+
+    // A claim ignoring the penalty is rejected.
+    let unpenalized = RedelegateClaim::new(
+        &redelegate.redelegation_token(),
+        Penalty::from_percent(0),
+        redelegate.to_delegation_amount,
+    );
+    assert!(unpenalized.check_and_execute(&mut state).await.is_err());
+
+    // A claim with a delegation amount inconsistent with its penalty is rejected.
+    let mut inconsistent = RedelegateClaim::new(
+        &redelegate.redelegation_token(),
+        penalty,
+        redelegate.to_delegation_amount,
+    );
+    inconsistent.delegation_amount = redelegate.to_delegation_amount;
+    assert!(inconsistent.check_stateless(()).await.is_err());
+
+    let claim = RedelegateClaim::new(
+        &redelegate.redelegation_token(),
+        penalty,
+        redelegate.to_delegation_amount,
+    );
+    claim.check_stateless(()).await?;
+    claim.check_and_execute(&mut state).await?;
+
+    // The forfeited delegation tokens are queued for removal from the destination
+    // pool, and no penalty is recorded against the destination validator.
+    assert_eq!(claim.delegation_amount, 900u64.into());
+    assert_eq!(claim.forfeited_amount(), 100u64.into());
+    assert_eq!(state.get_delegation_changes_tally().redelegation_claims, vec![claim]);
+    for epoch_index in [REDELEGATION_EPOCH.index, UNBONDING_EPOCH.index] {
+        assert_eq!(state.get_penalty_in_epoch(&to.identity_key, epoch_index).await, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+use cnidarium::StateWrite;
+use cnidarium_component::ActionHandler;
+use penumbra_sct::component::clock::EpochRead;
+use penumbra_shielded_pool::component::SupplyWrite;
+
+use crate::{
+    component::{validator_handler::ValidatorDataRead, SlashingData},
+    RedelegateClaim, StateWriteExt as _,
+};
+
+#[async_trait]
+impl ActionHandler for RedelegateClaim {
+    type CheckStatelessContext = ();
+    async fn check_stateless(&self, _context: ()) -> Result<()> {
+        ensure!(
+            self.from_validator != self.to_validator,
+            "cannot claim a redelegation from validator {} to itself",
+            self.from_validator,
+        );
+
+        let expected_delegation_amount = self.penalty.apply_to_amount(self.redelegation_amount);
+        ensure!(
+            self.delegation_amount == expected_delegation_amount,
+            "given {} redelegation tokens and penalty (kept_rate: {}), expected {} delegation tokens but description produces {}",
+            self.redelegation_amount,
+            self.penalty.kept_rate(),
+            expected_delegation_amount,
+            self.delegation_amount,
+        );
+
+        Ok(())
+    }
+
+    async fn check_and_execute<S: StateWrite>(&self, mut state: S) -> Result<()> {
+        // The redelegated stake is liable for the source validator's penalties
+        // over the same window as an undelegation started at the same height, so
+        // these checks mirror those of an `UndelegateClaim`.
+        let current_height = state.get_block_height().await?;
+        let redelegation_start_height = self.redelegation_start_height;
+        ensure!(
+            current_height >= redelegation_start_height,
+            "the redelegation start height must be less than or equal to the current height"
+        );
+
+        // If the source pool is `Unbonded` or unbonding at an already elapsed
+        // height, we default to the current height.
+        let allowed_unbonding_height = state
+            .compute_unbonding_height(&self.from_validator, redelegation_start_height)
+            .await?
+            .unwrap_or(current_height);
+
+        let wait_blocks = allowed_unbonding_height.saturating_sub(current_height);
+
+        ensure!(
+            current_height >= allowed_unbonding_height,
+            "cannot claim redelegation tokens before height {} (currently at {}, wait {} blocks)",
+            allowed_unbonding_height,
+            current_height,
+            wait_blocks
+        );
+
+        let redelegation_epoch_start = state
+            .get_epoch_by_height(redelegation_start_height)
+            .await?;
+        let redelegation_epoch_end = state.get_epoch_by_height(allowed_unbonding_height).await?;
+
+        ensure!(
+            redelegation_epoch_end.index >= redelegation_epoch_start.index,
+            "unbonding epoch end must be greater than or equal to unbonding epoch start"
+        );
+
+        let expected_penalty = state
+            .compounded_penalty_over_range(
+                &self.from_validator,
+                redelegation_epoch_start.index,
+                redelegation_epoch_end.index,
+            )
+            .await?;
+
+        ensure!(
+            self.penalty == expected_penalty,
+            "penalty (kept_rate: {}) does not match expected penalty (kept_rate: {})",
+            self.penalty.kept_rate(),
+            expected_penalty.kept_rate(),
+        );
+
+        /* ---------- execution ----------- */
+
+        state
+            .decrease_token_supply(self.redelegation_value())
+            .await?;
+        state.increase_token_supply(self.delegation_value()).await?;
+
+        // The forfeited delegation tokens are removed from the destination pool
+        // at the end of the epoch.
+        tracing::debug!(?self, "queuing redelegation claim for next epoch");
+        state.push_redelegation_claim(self.clone());
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
+use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
 use penumbra_proto::StateWriteProto;
 use penumbra_sct::component::clock::EpochRead;
-use penumbra_shielded_pool::component::AssetRegistry;
+use penumbra_shielded_pool::component::{AssetRegistry, SupplyWrite};
 
 use crate::{
     component::action_handler::ActionHandler,
@@ -82,6 +83,18 @@ impl ActionHandler for Undelegate {
         // Register the undelegation's denom, so clients can look it up later.
         state.register_denom(&self.unbonding_token().denom()).await;
 
+        // The amounts converted by an `UndelegateClaim` are shielded, so the
+        // supply can't follow unbonding tokens back into staking tokens. Instead,
+        // we count the unbonded stake as staking tokens as soon as the undelegation
+        // starts. Penalties applied during the unbonding window are not reflected.
+        state.decrease_token_supply(self.delegation_value()).await?;
+        state
+            .increase_token_supply(Value {
+                amount: self.unbonded_amount,
+                asset_id: *STAKING_TOKEN_ASSET_ID,
+            })
+            .await?;
+
         tracing::debug!(?self, "queuing undelegation for next epoch");
         state.push_undelegation(self.clone());
 
@@ -38,6 +38,8 @@ pub trait EpochHandler: StateWriteExt + ConsensusIndexRead {
         let end_height = self.get_block_height().await?;
         let mut num_delegations = 0usize;
         let mut num_undelegations = 0usize;
+        let mut num_redelegations = 0usize;
+        let mut num_redelegation_claims = 0usize;
 
         // Performance: see #3874.
         for height in epoch_to_end.start_height..=end_height {
@@ -51,6 +53,9 @@ pub trait EpochHandler: StateWriteExt + ConsensusIndexRead {
 
             num_delegations = num_delegations.saturating_add(changes.delegations.len());
             num_undelegations = num_undelegations.saturating_add(changes.undelegations.len());
+            num_redelegations = num_redelegations.saturating_add(changes.redelegations.len());
+            num_redelegation_claims =
+                num_redelegation_claims.saturating_add(changes.redelegation_claims.len());
 
             for d in changes.delegations {
                 let validator_identity = d.validator_identity.clone();
@@ -69,11 +74,37 @@ pub trait EpochHandler: StateWriteExt + ConsensusIndexRead {
 
                 undelegations_by_validator.insert(validator_identity, undelegation_tally);
             }
+            // A redelegation contracts the source pool and expands the destination pool,
+            // just like an undelegation followed by a delegation.
+            for r in changes.redelegations {
+                let undelegation_tally = undelegations_by_validator
+                    .entry(r.from_validator)
+                    .or_default()
+                    .saturating_add(&r.from_delegation_amount);
+                undelegations_by_validator.insert(r.from_validator, undelegation_tally);
+
+                let delegation_tally = delegations_by_validator
+                    .entry(r.to_validator)
+                    .or_default()
+                    .saturating_add(&r.to_delegation_amount);
+                delegations_by_validator.insert(r.to_validator, delegation_tally);
+            }
+            // The delegation tokens forfeited by a redelegation claim to the source
+            // validator's penalties leave the destination pool.
+            for c in changes.redelegation_claims {
+                let undelegation_tally = undelegations_by_validator
+                    .entry(c.to_validator)
+                    .or_default()
+                    .saturating_add(&c.forfeited_amount());
+                undelegations_by_validator.insert(c.to_validator, undelegation_tally);
+            }
         }
 
         tracing::debug!(
             num_delegations,
             num_undelegations,
+            num_redelegations,
+            num_redelegation_claims,
             epoch_start = epoch_to_end.start_height,
             epoch_end = end_height,
             epoch_index = epoch_to_end.index,
@@ -6,7 +6,7 @@ use crate::rate::BaseRateData;
 use crate::validator::{self, Validator};
 use crate::{
     state_key, CurrentConsensusKeys, Delegate, DelegationChanges, FundingStreams, IdentityKey,
-    Penalty, Undelegate,
+    Penalty, Redelegate, RedelegateClaim, Undelegate,
 };
 use anyhow::Context;
 use anyhow::{anyhow, Result};
@@ -304,6 +304,20 @@ pub trait StateWriteExt: StateWrite {
         self.put_delegation_changes(changes);
     }
 
+    /// Push an entry in the redelegation queue for the current block (object-storage).
+    fn push_redelegation(&mut self, redelegation: Redelegate) {
+        let mut changes = self.get_delegation_changes_tally();
+        changes.redelegations.push(redelegation);
+        self.put_delegation_changes(changes);
+    }
+
+    /// Push an entry in the redelegation claim queue for the current block (object-storage).
+    fn push_redelegation_claim(&mut self, claim: RedelegateClaim) {
+        let mut changes = self.get_delegation_changes_tally();
+        changes.redelegation_claims.push(claim);
+        self.put_delegation_changes(changes);
+    }
+
     #[instrument(skip(self))]
     fn queue_staking_rewards(
         &mut self,
@@ -493,6 +507,8 @@ pub(crate) trait RateDataWrite: StateWrite {
             %height,
             delegations = ?changes.delegations,
             undelegations = ?changes.undelegations,
+            redelegations = ?changes.redelegations,
+            redelegation_claims = ?changes.redelegation_claims,
         )
     )]
     async fn set_delegation_changes(&mut self, height: block::Height, changes: DelegationChanges) {
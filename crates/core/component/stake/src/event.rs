@@ -1,7 +1,7 @@
 use crate::{
     rate,
     validator::{BondingState, State, Validator},
-    Delegate, IdentityKey, Penalty, Undelegate,
+    Delegate, IdentityKey, Penalty, Redelegate, Undelegate,
 };
 use penumbra_num::Amount;
 use penumbra_proto::core::component::stake::v1 as pb;
@@ -73,6 +73,14 @@ pub fn undelegate(undelegate: &Undelegate) -> pb::EventUndelegate {
     }
 }
 
+pub fn redelegate(redelegate: &Redelegate) -> pb::EventRedelegate {
+    pb::EventRedelegate {
+        from_identity_key: Some(redelegate.from_validator.into()),
+        to_identity_key: Some(redelegate.to_validator.into()),
+        amount: Some(redelegate.unbonded_amount.into()),
+    }
+}
+
 pub fn tombstone_validator(
     current_height: u64,
     identity_key: IdentityKey,
@@ -10,6 +10,7 @@ mod event;
 mod governance_key;
 mod identity_key;
 mod penalty;
+mod redelegation_token;
 mod unbonding_token;
 mod uptime;
 
@@ -18,6 +19,8 @@ pub mod funding_stream;
 pub mod genesis;
 pub mod params;
 pub mod rate;
+pub mod redelegate;
+pub mod redelegate_claim;
 pub mod state_key;
 pub mod undelegate;
 pub mod undelegate_claim;
@@ -33,6 +36,8 @@ pub static BPS_SQUARED_SCALING_FACTOR: once_cell::sync::Lazy<penumbra_num::fixpo
     once_cell::sync::Lazy::new(|| 1_0000_0000u128.into());
 
 pub use self::delegate::Delegate;
+pub use self::redelegate::Redelegate;
+pub use self::redelegate_claim::RedelegateClaim;
 pub use self::undelegate::Undelegate;
 pub use self::undelegate_claim::{
     UndelegateClaim, UndelegateClaimBody, UndelegateClaimPlan, UndelegateClaimProof,
@@ -42,6 +47,7 @@ pub use self::delegation_token::DelegationToken;
 pub use self::governance_key::GovernanceKey;
 pub use self::identity_key::IdentityKey;
 pub use self::penalty::Penalty;
+pub use self::redelegation_token::RedelegationToken;
 pub use self::unbonding_token::UnbondingToken;
 
 pub use self::changes::DelegationChanges;
@@ -8,7 +8,7 @@ use penumbra_sct::epoch::Epoch;
 use serde::{Deserialize, Serialize};
 
 use crate::{validator::State, FundingStream, IdentityKey};
-use crate::{Delegate, Penalty, Undelegate, BPS_SQUARED_SCALING_FACTOR};
+use crate::{Delegate, Penalty, Redelegate, Undelegate, BPS_SQUARED_SCALING_FACTOR};
 
 /// Describes a validator's reward rate and voting power in some epoch.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -251,6 +251,26 @@ impl RateData {
             validator_identity: self.identity_key.clone(),
         }
     }
+
+    /// Uses this `RateData` and the destination validator's `to_rate_data` to
+    /// build a `Redelegate` transaction action that moves `delegation_amount`
+    /// of this validator's delegation tokens to the destination validator.
+    pub fn build_redelegate(
+        &self,
+        to_rate_data: &RateData,
+        epoch: Epoch,
+        delegation_amount: Amount,
+    ) -> Redelegate {
+        let unbonded_amount = self.unbonded_amount(delegation_amount);
+        Redelegate {
+            from_validator: self.identity_key.clone(),
+            to_validator: to_rate_data.identity_key.clone(),
+            from_epoch: epoch,
+            from_delegation_amount: delegation_amount,
+            unbonded_amount,
+            to_delegation_amount: to_rate_data.delegation_amount(unbonded_amount),
+        }
+    }
 }
 
 /// Describes the base reward and exchange rates in some epoch.
@@ -396,6 +416,7 @@ impl TryFrom<CurrentValidatorRateResponse> for RateData {
 mod tests {
     use super::*;
     use decaf377_rdsa as rdsa;
+    use penumbra_asset::Balance;
     use rand_core::OsRng;
 
     #[test]
@@ -413,4 +434,36 @@ mod tests {
         let slashed = rate_data.slash(penalty);
         assert_eq!(slashed.validator_exchange_rate, 1_8000_0000u128.into());
     }
+
+    #[test]
+    fn build_redelegate_uses_both_rates() {
+        let from_vk = rdsa::VerificationKey::from(rdsa::SigningKey::new(OsRng));
+        let to_vk = rdsa::VerificationKey::from(rdsa::SigningKey::new(OsRng));
+
+        let from_rate_data = RateData {
+            identity_key: IdentityKey(from_vk.into()),
+            validator_reward_rate: 1_0000_0000u128.into(),
+            validator_exchange_rate: 2_0000_0000u128.into(),
+        };
+        let to_rate_data = RateData {
+            identity_key: IdentityKey(to_vk.into()),
+            validator_reward_rate: 1_0000_0000u128.into(),
+            validator_exchange_rate: 1_2500_0000u128.into(),
+        };
+        let epoch = Epoch {
+            index: 3,
+            start_height: 300,
+        };
+
+        let redelegate = from_rate_data.build_redelegate(&to_rate_data, epoch, 100u128.into());
+        assert_eq!(redelegate.from_validator, from_rate_data.identity_key);
+        assert_eq!(redelegate.to_validator, to_rate_data.identity_key);
+        assert_eq!(redelegate.unbonded_amount, 200u128.into());
+        assert_eq!(redelegate.to_delegation_amount, 160u128.into());
+        assert_eq!(
+            redelegate.balance(),
+            Balance::from(redelegate.redelegation_value())
+                - Balance::from(redelegate.from_delegation_value())
+        );
+    }
 }
@@ -0,0 +1,145 @@
+use penumbra_asset::{Balance, Value};
+use penumbra_num::Amount;
+use penumbra_proto::{penumbra::core::component::stake::v1 as pb, DomainType};
+use penumbra_sct::epoch::Epoch;
+use penumbra_txhash::{EffectHash, EffectingData};
+use serde::{Deserialize, Serialize};
+
+use crate::{DelegationToken, IdentityKey, RedelegationToken};
+
+/// A transaction action moving stake from one validator's delegation pool to
+/// another's, without waiting out the unbonding delay.
+///
+/// The redelegated stake remains liable for slashing penalties applied to the
+/// source validator until the unbonding delay has passed. Rather than the
+/// destination validator's delegation tokens, the action produces
+/// [`RedelegationToken`]s carrying that liability, which a
+/// [`RedelegateClaim`](crate::RedelegateClaim) converts into delegation tokens
+/// once the unbonding delay has passed, net of any penalty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "pb::Redelegate", into = "pb::Redelegate")]
+pub struct Redelegate {
+    /// The identity key of the validator to redelegate from.
+    pub from_validator: IdentityKey,
+    /// The identity key of the validator to redelegate to.
+    pub to_validator: IdentityKey,
+    /// The epoch at which the redelegation was performed.
+    ///
+    /// Both exchange rates are taken from this epoch, and the source
+    /// validator's slashing liability extends over the unbonding window
+    /// starting at this epoch.
+    pub from_epoch: Epoch,
+    /// The amount of the source validator's delegation tokens consumed by this action.
+    pub from_delegation_amount: Amount,
+    /// The amount of stake moved, in units of the staking token.
+    ///
+    /// This is implied by the source validator's exchange rate in the specified epoch
+    /// (and should be checked in transaction validation!), but including it allows
+    /// stateless verification that the transaction is internally consistent.
+    pub unbonded_amount: Amount,
+    /// The amount of the destination validator's delegation tokens the
+    /// redelegation tokens produced by this action are worth, before penalties.
+    ///
+    /// This is implied by the destination validator's exchange rate in the
+    /// specified epoch (and should be checked in transaction validation!).
+    pub to_delegation_amount: Amount,
+}
+
+impl EffectingData for Redelegate {
+    fn effect_hash(&self) -> EffectHash {
+        // For redelegations, the entire action is considered effecting data.
+        EffectHash::from_proto_effecting_data(&self.to_proto())
+    }
+}
+
+impl Redelegate {
+    /// Return the balance after consuming the source validator's delegation
+    /// tokens, and producing redelegation tokens.
+    pub fn balance(&self) -> Balance {
+        let from_delegation: Balance = self.from_delegation_value().into();
+        let redelegation: Balance = self.redelegation_value().into();
+
+        redelegation - from_delegation
+    }
+
+    pub fn from_delegation_token(&self) -> DelegationToken {
+        DelegationToken::new(self.from_validator.clone())
+    }
+
+    pub fn to_delegation_token(&self) -> DelegationToken {
+        DelegationToken::new(self.to_validator.clone())
+    }
+
+    /// Returns the [`Value`] of the consumed delegation [`Amount`].
+    pub fn from_delegation_value(&self) -> Value {
+        Value {
+            amount: self.from_delegation_amount,
+            asset_id: self.from_delegation_token().id(),
+        }
+    }
+
+    pub fn redelegation_token(&self) -> RedelegationToken {
+        RedelegationToken::new(
+            self.from_validator.clone(),
+            self.to_validator.clone(),
+            self.from_epoch.start_height,
+        )
+    }
+
+    /// Returns the [`Value`] of the produced redelegation tokens.
+    pub fn redelegation_value(&self) -> Value {
+        Value {
+            amount: self.to_delegation_amount,
+            asset_id: self.redelegation_token().id(),
+        }
+    }
+}
+
+impl DomainType for Redelegate {
+    type Proto = pb::Redelegate;
+}
+
+impl From<Redelegate> for pb::Redelegate {
+    fn from(r: Redelegate) -> Self {
+        pb::Redelegate {
+            from_validator: Some(r.from_validator.into()),
+            to_validator: Some(r.to_validator.into()),
+            from_epoch: Some(r.from_epoch.into()),
+            from_delegation_amount: Some(r.from_delegation_amount.into()),
+            unbonded_amount: Some(r.unbonded_amount.into()),
+            to_delegation_amount: Some(r.to_delegation_amount.into()),
+        }
+    }
+}
+
+impl TryFrom<pb::Redelegate> for Redelegate {
+    type Error = anyhow::Error;
+    fn try_from(r: pb::Redelegate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            from_validator: r
+                .from_validator
+                .ok_or_else(|| anyhow::anyhow!("missing from_validator"))?
+                .try_into()?,
+            to_validator: r
+                .to_validator
+                .ok_or_else(|| anyhow::anyhow!("missing to_validator"))?
+                .try_into()?,
+            from_epoch: r
+                .from_epoch
+                .ok_or_else(|| anyhow::anyhow!("missing from_epoch"))?
+                .try_into()?,
+            from_delegation_amount: r
+                .from_delegation_amount
+                .ok_or_else(|| anyhow::anyhow!("missing from_delegation_amount"))?
+                .try_into()?,
+            unbonded_amount: r
+                .unbonded_amount
+                .ok_or_else(|| anyhow::anyhow!("missing unbonded_amount"))?
+                .try_into()?,
+            to_delegation_amount: r
+                .to_delegation_amount
+                .ok_or_else(|| anyhow::anyhow!("missing to_delegation_amount"))?
+                .try_into()?,
+        })
+    }
+}
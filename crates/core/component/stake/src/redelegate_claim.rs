@@ -0,0 +1,172 @@
+use penumbra_asset::{Balance, Value};
+use penumbra_num::Amount;
+use penumbra_proto::{penumbra::core::component::stake::v1 as pb, DomainType};
+use penumbra_txhash::{EffectHash, EffectingData};
+use serde::{Deserialize, Serialize};
+
+use crate::{DelegationToken, IdentityKey, Penalty, RedelegationToken};
+
+/// A transaction action finishing a redelegation, converting (slashable)
+/// redelegation tokens into delegation tokens of the destination validator.
+///
+/// The claim applies the penalties incurred by the source validator over the
+/// unbonding window of the redelegation, so that the redelegated stake bears
+/// them as if it had been undelegated. The delegation tokens forfeited this
+/// way are removed from the destination validator's pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "pb::RedelegateClaim", into = "pb::RedelegateClaim")]
+pub struct RedelegateClaim {
+    /// The identity key of the validator the stake was redelegated from.
+    pub from_validator: IdentityKey,
+    /// The identity key of the validator the stake was redelegated to.
+    pub to_validator: IdentityKey,
+    /// The height of the epoch in which the redelegation was performed.
+    pub redelegation_start_height: u64,
+    /// The penalty applied to the source validator over the unbonding window.
+    pub penalty: Penalty,
+    /// The amount of redelegation tokens consumed by this action.
+    pub redelegation_amount: Amount,
+    /// The amount of the destination validator's delegation tokens produced by this action.
+    ///
+    /// This is implied by the penalty (and should be checked in transaction
+    /// validation!), but including it allows stateless verification that the
+    /// transaction is internally consistent.
+    pub delegation_amount: Amount,
+}
+
+impl EffectingData for RedelegateClaim {
+    fn effect_hash(&self) -> EffectHash {
+        // For redelegation claims, the entire action is considered effecting data.
+        EffectHash::from_proto_effecting_data(&self.to_proto())
+    }
+}
+
+impl RedelegateClaim {
+    /// Build a claim for `redelegation_amount` of the given redelegation
+    /// tokens, applying the `penalty` incurred by the source validator over
+    /// the unbonding window of the redelegation.
+    pub fn new(token: &RedelegationToken, penalty: Penalty, redelegation_amount: Amount) -> Self {
+        Self {
+            from_validator: token.from_validator(),
+            to_validator: token.to_validator(),
+            redelegation_start_height: token.redelegation_start_height(),
+            penalty,
+            redelegation_amount,
+            delegation_amount: penalty.apply_to_amount(redelegation_amount),
+        }
+    }
+
+    /// Return the balance after consuming the redelegation tokens, and producing
+    /// the destination validator's delegation tokens.
+    pub fn balance(&self) -> Balance {
+        let redelegation: Balance = self.redelegation_value().into();
+        let delegation: Balance = self.delegation_value().into();
+
+        delegation - redelegation
+    }
+
+    pub fn redelegation_token(&self) -> RedelegationToken {
+        RedelegationToken::new(
+            self.from_validator.clone(),
+            self.to_validator.clone(),
+            self.redelegation_start_height,
+        )
+    }
+
+    pub fn delegation_token(&self) -> DelegationToken {
+        DelegationToken::new(self.to_validator.clone())
+    }
+
+    /// Returns the [`Value`] of the consumed redelegation tokens.
+    pub fn redelegation_value(&self) -> Value {
+        Value {
+            amount: self.redelegation_amount,
+            asset_id: self.redelegation_token().id(),
+        }
+    }
+
+    /// Returns the [`Value`] of the produced delegation [`Amount`].
+    pub fn delegation_value(&self) -> Value {
+        Value {
+            amount: self.delegation_amount,
+            asset_id: self.delegation_token().id(),
+        }
+    }
+
+    /// Returns the amount of the destination validator's delegation tokens
+    /// forfeited to the penalty.
+    pub fn forfeited_amount(&self) -> Amount {
+        self.redelegation_amount
+            .saturating_sub(&self.delegation_amount)
+    }
+}
+
+impl DomainType for RedelegateClaim {
+    type Proto = pb::RedelegateClaim;
+}
+
+impl From<RedelegateClaim> for pb::RedelegateClaim {
+    fn from(c: RedelegateClaim) -> Self {
+        pb::RedelegateClaim {
+            from_validator: Some(c.from_validator.into()),
+            to_validator: Some(c.to_validator.into()),
+            redelegation_start_height: c.redelegation_start_height,
+            penalty: Some(c.penalty.into()),
+            redelegation_amount: Some(c.redelegation_amount.into()),
+            delegation_amount: Some(c.delegation_amount.into()),
+        }
+    }
+}
+
+impl TryFrom<pb::RedelegateClaim> for RedelegateClaim {
+    type Error = anyhow::Error;
+    fn try_from(c: pb::RedelegateClaim) -> Result<Self, Self::Error> {
+        Ok(Self {
+            from_validator: c
+                .from_validator
+                .ok_or_else(|| anyhow::anyhow!("missing from_validator"))?
+                .try_into()?,
+            to_validator: c
+                .to_validator
+                .ok_or_else(|| anyhow::anyhow!("missing to_validator"))?
+                .try_into()?,
+            redelegation_start_height: c.redelegation_start_height,
+            penalty: c
+                .penalty
+                .ok_or_else(|| anyhow::anyhow!("missing penalty"))?
+                .try_into()?,
+            redelegation_amount: c
+                .redelegation_amount
+                .ok_or_else(|| anyhow::anyhow!("missing redelegation_amount"))?
+                .try_into()?,
+            delegation_amount: c
+                .delegation_amount
+                .ok_or_else(|| anyhow::anyhow!("missing delegation_amount"))?
+                .try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decaf377_rdsa::{SigningKey, VerificationKey};
+    use rand_core::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn claim_forfeits_penalized_delegation_tokens() {
+        let from = IdentityKey(VerificationKey::from(SigningKey::new(OsRng)).into());
+        let to = IdentityKey(VerificationKey::from(SigningKey::new(OsRng)).into());
+        let token = RedelegationToken::new(from, to, 300);
+
+        let claim = RedelegateClaim::new(&token, Penalty::from_percent(10), 1000u64.into());
+        assert_eq!(claim.redelegation_token(), token);
+        assert_eq!(claim.delegation_amount, 900u64.into());
+        assert_eq!(claim.forfeited_amount(), 100u64.into());
+        assert_eq!(
+            claim.balance(),
+            Balance::from(claim.delegation_value()) - Balance::from(claim.redelegation_value())
+        );
+    }
+}
@@ -22,6 +22,43 @@ pub enum State {
     Unbonding { unbonds_at_height: u64 },
 }
 
+impl State {
+    /// Compute the unbonding height for an undelegation initiated at
+    /// `start_height` from a pool in this state, given the chain's
+    /// `unbonding_delay`.
+    ///
+    /// Returns `None` if the undelegation is not subject to any delay, because
+    /// the pool is unbonded or has finished unbonding.
+    pub fn unbonding_height(&self, start_height: u64, unbonding_delay: u64) -> Option<u64> {
+        let upper_bound_height = start_height.saturating_add(unbonding_delay);
+
+        match self {
+            // The pool is bonded, so the unbonding height is the start height plus the delay.
+            State::Bonded => Some(upper_bound_height),
+            // The pool is unbonding at a specific height, so we can use that.
+            State::Unbonding { unbonds_at_height } => {
+                if *unbonds_at_height > start_height {
+                    // The unbonding height is the minimum of the unbonding height and the upper bound.
+                    // There are a couple reasons:
+                    // - The unbonding delay parameter can change, and in particular, it can decrease.
+                    // - We might be processing an undelegation that was initiated before the validator
+                    //   began unbonding, and the unbonding height is in the past.
+                    Some((*unbonds_at_height).min(upper_bound_height))
+                } else {
+                    // In some cases, the allowed unbonding height can be smaller than
+                    // undelgation start height, for example if the unbonding delay has
+                    // changed in a parameter update, or if the unbonding has finished
+                    // and the validator is not indexed by the staking module anymore.
+                    // This is functionally equivalent to dealing with an `Unbonded` pool.
+                    None
+                }
+            }
+            // The pool is unbonded, so the unbonding height can be decided by the caller.
+            State::Unbonded => None,
+        }
+    }
+}
+
 impl std::fmt::Display for State {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -79,3 +116,32 @@ impl TryFrom<pb::BondingState> for State {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonded_pools_unbond_after_the_delay() {
+        assert_eq!(State::Bonded.unbonding_height(100, 50), Some(150));
+        assert_eq!(State::Bonded.unbonding_height(u64::MAX, 50), Some(u64::MAX));
+    }
+
+    #[test]
+    fn unbonding_pools_unbond_at_the_earliest_height() {
+        let unbonding = State::Unbonding {
+            unbonds_at_height: 120,
+        };
+        assert_eq!(unbonding.unbonding_height(100, 50), Some(120));
+        // The delay may have decreased since the pool started unbonding.
+        assert_eq!(unbonding.unbonding_height(100, 10), Some(110));
+        // Undelegations started after the pool unbonded aren't delayed.
+        assert_eq!(unbonding.unbonding_height(120, 50), None);
+        assert_eq!(unbonding.unbonding_height(130, 50), None);
+    }
+
+    #[test]
+    fn unbonded_pools_do_not_delay_undelegations() {
+        assert_eq!(State::Unbonded.unbonding_height(100, 50), None);
+    }
+}
@@ -48,3 +48,30 @@ impl std::fmt::Display for WalletId {
         ))
     }
 }
+
+impl std::str::FromStr for WalletId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        pb::WalletId {
+            inner: bech32str::decode(
+                s,
+                bech32str::wallet_id::BECH32_PREFIX,
+                bech32str::Bech32m,
+            )?,
+        }
+        .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wallet_id_roundtrips_through_bech32() {
+        let wallet_id = crate::test_keys::FULL_VIEWING_KEY.wallet_id();
+        let parsed: WalletId = wallet_id.to_string().parse().unwrap();
+        assert_eq!(parsed, wallet_id);
+    }
+}
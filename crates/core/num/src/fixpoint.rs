@@ -432,6 +432,34 @@ impl U128x128Var {
         other: &U128x128Var,
         ordering: std::cmp::Ordering,
     ) -> Result<(), SynthesisError> {
+        let (gt, lt) = self.cmp_bits(other)?;
+
+        match ordering {
+            std::cmp::Ordering::Greater => {
+                gt.enforce_equal(&Boolean::constant(true))?;
+                lt.enforce_equal(&Boolean::constant(false))?;
+            }
+            std::cmp::Ordering::Less => {
+                gt.enforce_equal(&Boolean::constant(false))?;
+                lt.enforce_equal(&Boolean::constant(true))?;
+            }
+            std::cmp::Ordering::Equal => {
+                unimplemented!("use EqGadget for efficiency");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a boolean constraint indicating whether `self` is strictly greater than `other`.
+    pub fn is_gt(&self, other: &U128x128Var) -> Result<Boolean<Fq>, SynthesisError> {
+        let (gt, _lt) = self.cmp_bits(other)?;
+        Ok(gt)
+    }
+
+    /// Compares `self` and `other` bitwise, returning whether `self > other`
+    /// and whether `self < other`.
+    fn cmp_bits(&self, other: &U128x128Var) -> Result<(Boolean<Fq>, Boolean<Fq>), SynthesisError> {
         // Collect bits from each limb to be compared.
         let self_bits: Vec<Boolean<Fq>> = self.to_bits_le().into_iter().rev().collect();
         let other_bits: Vec<Boolean<Fq>> = other.to_bits_le().into_iter().rev().collect();
@@ -452,21 +480,7 @@ impl U128x128Var {
             lt = lt.or(&gt.not().and(&q)?.and(&p.not())?)?;
         }
 
-        match ordering {
-            std::cmp::Ordering::Greater => {
-                gt.enforce_equal(&Boolean::constant(true))?;
-                lt.enforce_equal(&Boolean::constant(false))?;
-            }
-            std::cmp::Ordering::Less => {
-                gt.enforce_equal(&Boolean::constant(false))?;
-                lt.enforce_equal(&Boolean::constant(true))?;
-            }
-            std::cmp::Ordering::Equal => {
-                unimplemented!("use EqGadget for efficiency");
-            }
-        }
-
-        Ok(())
+        Ok((gt, lt))
     }
 
     pub fn checked_div(
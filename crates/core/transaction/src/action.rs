@@ -35,6 +35,8 @@ pub enum Action {
     Delegate(penumbra_stake::Delegate),
     Undelegate(penumbra_stake::Undelegate),
     UndelegateClaim(penumbra_stake::UndelegateClaim),
+    Redelegate(penumbra_stake::Redelegate),
+    RedelegateClaim(penumbra_stake::RedelegateClaim),
 
     Ics20Withdrawal(penumbra_shielded_pool::Ics20Withdrawal),
 
@@ -55,6 +57,8 @@ impl EffectingData for Action {
             Action::Delegate(delegate) => delegate.effect_hash(),
             Action::Undelegate(undelegate) => undelegate.effect_hash(),
             Action::UndelegateClaim(claim) => claim.effect_hash(),
+            Action::Redelegate(redelegate) => redelegate.effect_hash(),
+            Action::RedelegateClaim(claim) => claim.effect_hash(),
             Action::ProposalSubmit(submit) => submit.effect_hash(),
             Action::ProposalWithdraw(withdraw) => withdraw.effect_hash(),
             Action::ProposalDepositClaim(claim) => claim.effect_hash(),
@@ -114,6 +118,8 @@ impl Action {
             Action::Delegate(_) => tracing::info_span!("Delegate", ?idx),
             Action::Undelegate(_) => tracing::info_span!("Undelegate", ?idx),
             Action::UndelegateClaim(_) => tracing::info_span!("UndelegateClaim", ?idx),
+            Action::Redelegate(_) => tracing::info_span!("Redelegate", ?idx),
+            Action::RedelegateClaim(_) => tracing::info_span!("RedelegateClaim", ?idx),
             Action::Ics20Withdrawal(_) => tracing::info_span!("Ics20Withdrawal", ?idx),
             Action::CommunityPoolDeposit(_) => tracing::info_span!("CommunityPoolDeposit", ?idx),
             Action::CommunityPoolSpend(_) => tracing::info_span!("CommunityPoolSpend", ?idx),
@@ -148,6 +154,8 @@ impl Action {
             Action::Delegate(_) => 40,
             Action::Undelegate(_) => 41,
             Action::UndelegateClaim(_) => 42,
+            Action::Redelegate(_) => 43,
+            Action::RedelegateClaim(_) => 44,
             Action::CommunityPoolSpend(_) => 50,
             Action::CommunityPoolOutput(_) => 51,
             Action::CommunityPoolDeposit(_) => 52,
@@ -167,6 +175,8 @@ impl IsAction for Action {
             Action::Delegate(delegate) => delegate.balance_commitment(),
             Action::Undelegate(undelegate) => undelegate.balance_commitment(),
             Action::UndelegateClaim(undelegate_claim) => undelegate_claim.balance_commitment(),
+            Action::Redelegate(redelegate) => redelegate.balance_commitment(),
+            Action::RedelegateClaim(claim) => claim.balance_commitment(),
             Action::Swap(swap) => swap.balance_commitment(),
             Action::SwapClaim(swap_claim) => swap_claim.balance_commitment(),
             Action::ProposalSubmit(submit) => submit.balance_commitment(),
@@ -200,6 +210,8 @@ impl IsAction for Action {
             Action::Delegate(x) => x.view_from_perspective(txp),
             Action::Undelegate(x) => x.view_from_perspective(txp),
             Action::UndelegateClaim(x) => x.view_from_perspective(txp),
+            Action::Redelegate(x) => x.view_from_perspective(txp),
+            Action::RedelegateClaim(x) => x.view_from_perspective(txp),
             Action::ProposalSubmit(x) => x.view_from_perspective(txp),
             Action::ProposalWithdraw(x) => x.view_from_perspective(txp),
             Action::DelegatorVote(x) => x.view_from_perspective(txp),
@@ -243,6 +255,12 @@ impl From<Action> for pb::Action {
             Action::UndelegateClaim(inner) => pb::Action {
                 action: Some(pb::action::Action::UndelegateClaim(inner.into())),
             },
+            Action::Redelegate(inner) => pb::Action {
+                action: Some(pb::action::Action::Redelegate(inner.into())),
+            },
+            Action::RedelegateClaim(inner) => pb::Action {
+                action: Some(pb::action::Action::RedelegateClaim(inner.into())),
+            },
             Action::ValidatorDefinition(inner) => pb::Action {
                 action: Some(pb::action::Action::ValidatorDefinition(inner.into())),
             },
@@ -321,6 +339,10 @@ impl TryFrom<pb::Action> for Action {
             pb::action::Action::UndelegateClaim(inner) => {
                 Ok(Action::UndelegateClaim(inner.try_into()?))
             }
+            pb::action::Action::Redelegate(inner) => Ok(Action::Redelegate(inner.try_into()?)),
+            pb::action::Action::RedelegateClaim(inner) => {
+                Ok(Action::RedelegateClaim(inner.try_into()?))
+            }
             pb::action::Action::ValidatorDefinition(inner) => {
                 Ok(Action::ValidatorDefinition(inner.try_into()?))
             }
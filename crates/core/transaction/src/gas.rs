@@ -7,7 +7,8 @@ use penumbra_fee::Gas;
 use penumbra_ibc::IbcRelay;
 use penumbra_shielded_pool::{Ics20Withdrawal, Output, Spend};
 use penumbra_stake::{
-    validator::Definition as ValidatorDefinition, Delegate, Undelegate, UndelegateClaim,
+    validator::Definition as ValidatorDefinition, Delegate, Redelegate, RedelegateClaim, Undelegate,
+    UndelegateClaim,
 };
 
 use penumbra_governance::{
@@ -110,6 +111,38 @@ fn undelegate_gas_cost(undelegate: &Undelegate) -> Gas {
     }
 }
 
+fn redelegate_gas_cost(redelegate: &Redelegate) -> Gas {
+    Gas {
+        // The block space measured as the byte length of the encoded action.
+        block_space: redelegate.encode_to_vec().len() as u64,
+        // The compact block space cost is based on the byte size of the data the [`Action`] adds
+        // to the compact block.
+        // For a Redelegate, nothing is added to the compact block directly. The associated [`Action::Spend`]
+        // and [`Action::Output`] actions will add their costs, but there's nothing to add here.
+        compact_block_space: 0,
+        // Does not include a zk-SNARK proof, so there's no verification cost.
+        verification: 0,
+        // Execution cost is currently hardcoded at 10 for all Action variants.
+        execution: 10,
+    }
+}
+
+fn redelegate_claim_gas_cost(claim: &RedelegateClaim) -> Gas {
+    Gas {
+        // The block space measured as the byte length of the encoded action.
+        block_space: claim.encode_to_vec().len() as u64,
+        // The compact block space cost is based on the byte size of the data the [`Action`] adds
+        // to the compact block.
+        // For a RedelegateClaim, nothing is added to the compact block directly. The associated [`Action::Spend`]
+        // and [`Action::Output`] actions will add their costs, but there's nothing to add here.
+        compact_block_space: 0,
+        // Does not include a zk-SNARK proof, so there's no verification cost.
+        verification: 0,
+        // Execution cost is currently hardcoded at 10 for all Action variants.
+        execution: 10,
+    }
+}
+
 fn undelegate_claim_gas_cost() -> Gas {
     Gas {
         // penumbra.core.keys.v1.IdentityKey `validator_identity`         = 32 bytes
@@ -327,6 +360,8 @@ impl GasCost for ActionPlan {
 
             ActionPlan::Delegate(d) => d.gas_cost(),
             ActionPlan::Undelegate(u) => u.gas_cost(),
+            ActionPlan::Redelegate(r) => r.gas_cost(),
+            ActionPlan::RedelegateClaim(c) => c.gas_cost(),
             ActionPlan::ValidatorDefinition(vd) => vd.gas_cost(),
             ActionPlan::IbcAction(i) => i.gas_cost(),
             ActionPlan::ProposalSubmit(ps) => ps.gas_cost(),
@@ -351,6 +386,8 @@ impl GasCost for Action {
             Action::Delegate(delegate) => delegate.gas_cost(),
             Action::Undelegate(undelegate) => undelegate.gas_cost(),
             Action::UndelegateClaim(undelegate_claim) => undelegate_claim.gas_cost(),
+            Action::Redelegate(redelegate) => redelegate.gas_cost(),
+            Action::RedelegateClaim(claim) => claim.gas_cost(),
             Action::Swap(swap) => swap.gas_cost(),
             Action::SwapClaim(swap_claim) => swap_claim.gas_cost(),
             Action::ProposalSubmit(submit) => submit.gas_cost(),
@@ -404,6 +441,18 @@ impl GasCost for Undelegate {
     }
 }
 
+impl GasCost for Redelegate {
+    fn gas_cost(&self) -> Gas {
+        redelegate_gas_cost(&self)
+    }
+}
+
+impl GasCost for RedelegateClaim {
+    fn gas_cost(&self) -> Gas {
+        redelegate_claim_gas_cost(&self)
+    }
+}
+
 impl GasCost for UndelegateClaim {
     fn gas_cost(&self) -> Gas {
         undelegate_claim_gas_cost()
@@ -20,7 +20,7 @@ use penumbra_governance::{
 };
 use penumbra_ibc::IbcRelay;
 use penumbra_shielded_pool::{Ics20Withdrawal, Note, Output, OutputView, Spend, SpendView};
-use penumbra_stake::{Delegate, Undelegate, UndelegateClaim};
+use penumbra_stake::{Delegate, Redelegate, RedelegateClaim, Undelegate, UndelegateClaim};
 
 use crate::{Action, ActionView, TransactionPerspective};
 
@@ -189,6 +189,26 @@ impl IsAction for Undelegate {
     }
 }
 
+impl IsAction for Redelegate {
+    fn balance_commitment(&self) -> balance::Commitment {
+        self.balance().commit(Fr::zero())
+    }
+
+    fn view_from_perspective(&self, _txp: &TransactionPerspective) -> ActionView {
+        ActionView::Redelegate(self.to_owned())
+    }
+}
+
+impl IsAction for RedelegateClaim {
+    fn balance_commitment(&self) -> balance::Commitment {
+        self.balance().commit(Fr::zero())
+    }
+
+    fn view_from_perspective(&self, _txp: &TransactionPerspective) -> ActionView {
+        ActionView::RedelegateClaim(self.to_owned())
+    }
+}
+
 impl IsAction for UndelegateClaim {
     fn balance_commitment(&self) -> balance::Commitment {
         self.body.balance_commitment
@@ -401,8 +421,10 @@ impl IsAction for Swap {
                     Some(bsod) => {
                         // If we can find a matching BSOD, use it to compute the output notes
                         // for the swap.
-                        let (lambda_1_i, lambda_2_i) =
-                            bsod.pro_rata_outputs((self.body.delta_1_i, self.body.delta_2_i));
+                        let (lambda_1_i, lambda_2_i) = bsod.swap_outputs(
+                            (self.body.delta_1_i, self.body.delta_2_i),
+                            self.body.min_output,
+                        );
                         SwapView::Opaque {
                             swap: self.to_owned(),
                             batch_swap_output_data: Some(bsod.clone()),
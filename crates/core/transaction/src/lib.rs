@@ -40,7 +40,7 @@ pub use error::Error;
 pub use is_action::IsAction;
 pub use parameters::TransactionParameters;
 pub use penumbra_txhash as txhash;
-pub use plan::{ActionPlan, TransactionPlan};
+pub use plan::{ActionPlan, BuildProgress, TransactionPlan};
 pub use transaction::{Transaction, TransactionBody};
 pub use view::{ActionView, MemoPlaintextView, MemoView, TransactionPerspective, TransactionView};
 pub use witness_data::WitnessData;
@@ -27,7 +27,7 @@ use penumbra_ibc::IbcRelay;
 use penumbra_keys::{symmetric::PayloadKey, FullViewingKey};
 use penumbra_proto::{core::transaction::v1 as pb_t, DomainType};
 use penumbra_shielded_pool::{Ics20Withdrawal, OutputPlan, SpendPlan};
-use penumbra_stake::{Delegate, Undelegate, UndelegateClaimPlan};
+use penumbra_stake::{Delegate, Redelegate, RedelegateClaim, Undelegate, UndelegateClaimPlan};
 use serde::{Deserialize, Serialize};
 
 /// A declaration of a planned [`Action`], for use in transaction creation.
@@ -50,6 +50,10 @@ pub enum ActionPlan {
     /// because we don't yet use flow encryption.
     Undelegate(Undelegate),
     UndelegateClaim(UndelegateClaimPlan),
+    /// Redelegations don't need any extra information either.
+    Redelegate(Redelegate),
+    /// Redelegation claims don't need any extra information either.
+    RedelegateClaim(RedelegateClaim),
     ValidatorDefinition(penumbra_stake::validator::Definition),
     /// Describes a proposed swap.
     Swap(SwapPlan),
@@ -138,6 +142,8 @@ impl ActionPlan {
             Delegate(plan) => Action::Delegate(plan.clone()),
             Undelegate(plan) => Action::Undelegate(plan.clone()),
             UndelegateClaim(plan) => Action::UndelegateClaim(plan.undelegate_claim()),
+            Redelegate(plan) => Action::Redelegate(plan.clone()),
+            RedelegateClaim(plan) => Action::RedelegateClaim(plan.clone()),
             ValidatorDefinition(plan) => Action::ValidatorDefinition(plan.clone()),
             // Fixme: action name
             IbcAction(plan) => Action::IbcRelay(plan.clone()),
@@ -188,6 +194,8 @@ impl ActionPlan {
             ActionPlan::Delegate(_) => 40,
             ActionPlan::Undelegate(_) => 41,
             ActionPlan::UndelegateClaim(_) => 42,
+            ActionPlan::Redelegate(_) => 43,
+            ActionPlan::RedelegateClaim(_) => 44,
             ActionPlan::CommunityPoolSpend(_) => 50,
             ActionPlan::CommunityPoolOutput(_) => 51,
             ActionPlan::CommunityPoolDeposit(_) => 52,
@@ -207,6 +215,8 @@ impl ActionPlan {
             Delegate(delegate) => delegate.balance(),
             Undelegate(undelegate) => undelegate.balance(),
             UndelegateClaim(undelegate_claim) => undelegate_claim.balance(),
+            Redelegate(redelegate) => redelegate.balance(),
+            RedelegateClaim(claim) => claim.balance(),
             Swap(swap) => swap.balance(),
             SwapClaim(swap_claim) => swap_claim.balance(),
             ProposalSubmit(proposal_submit) => proposal_submit.balance(),
@@ -238,6 +248,8 @@ impl ActionPlan {
             Delegate(_) => Fr::zero(),
             Undelegate(_) => Fr::zero(),
             UndelegateClaim(undelegate_claim) => undelegate_claim.balance_blinding,
+            Redelegate(_) => Fr::zero(),
+            RedelegateClaim(_) => Fr::zero(),
             ValidatorDefinition(_) => Fr::zero(),
             Swap(swap) => swap.fee_blinding,
             SwapClaim(_) => Fr::zero(),
@@ -270,6 +282,8 @@ impl ActionPlan {
             Delegate(plan) => plan.effect_hash(),
             Undelegate(plan) => plan.effect_hash(),
             UndelegateClaim(plan) => plan.undelegate_claim_body().effect_hash(),
+            Redelegate(plan) => plan.effect_hash(),
+            RedelegateClaim(plan) => plan.effect_hash(),
             ValidatorDefinition(plan) => plan.effect_hash(),
             Swap(plan) => plan.swap_body(fvk).effect_hash(),
             SwapClaim(plan) => plan.swap_claim_body(fvk).effect_hash(),
@@ -331,6 +345,18 @@ impl From<Undelegate> for ActionPlan {
     }
 }
 
+impl From<Redelegate> for ActionPlan {
+    fn from(inner: Redelegate) -> ActionPlan {
+        ActionPlan::Redelegate(inner)
+    }
+}
+
+impl From<RedelegateClaim> for ActionPlan {
+    fn from(inner: RedelegateClaim) -> ActionPlan {
+        ActionPlan::RedelegateClaim(inner)
+    }
+}
+
 impl From<UndelegateClaimPlan> for ActionPlan {
     fn from(inner: UndelegateClaimPlan) -> ActionPlan {
         ActionPlan::UndelegateClaim(inner)
@@ -461,6 +487,12 @@ impl From<ActionPlan> for pb_t::ActionPlan {
             ActionPlan::UndelegateClaim(inner) => pb_t::ActionPlan {
                 action: Some(pb_t::action_plan::Action::UndelegateClaim(inner.into())),
             },
+            ActionPlan::Redelegate(inner) => pb_t::ActionPlan {
+                action: Some(pb_t::action_plan::Action::Redelegate(inner.into())),
+            },
+            ActionPlan::RedelegateClaim(inner) => pb_t::ActionPlan {
+                action: Some(pb_t::action_plan::Action::RedelegateClaim(inner.into())),
+            },
             ActionPlan::ValidatorDefinition(inner) => pb_t::ActionPlan {
                 action: Some(pb_t::action_plan::Action::ValidatorDefinition(inner.into())),
             },
@@ -559,6 +591,12 @@ impl TryFrom<pb_t::ActionPlan> for ActionPlan {
             pb_t::action_plan::Action::UndelegateClaim(inner) => {
                 Ok(ActionPlan::UndelegateClaim(inner.try_into()?))
             }
+            pb_t::action_plan::Action::Redelegate(inner) => {
+                Ok(ActionPlan::Redelegate(inner.try_into()?))
+            }
+            pb_t::action_plan::Action::RedelegateClaim(inner) => {
+                Ok(ActionPlan::RedelegateClaim(inner.try_into()?))
+            }
             pb_t::action_plan::Action::ValidatorDefinition(inner) => {
                 Ok(ActionPlan::ValidatorDefinition(inner.try_into()?))
             }
@@ -9,6 +9,25 @@ use super::TransactionPlan;
 use crate::ActionPlan;
 use crate::{action::Action, AuthorizationData, Transaction, TransactionBody, WitnessData};
 
+/// The progress of building a [`TransactionPlan`], reported as its actions are built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuildProgress {
+    /// The number of actions built so far.
+    pub built_actions: usize,
+    /// The number of actions in the transaction.
+    pub total_actions: usize,
+}
+
+impl BuildProgress {
+    /// The fraction of the transaction built so far, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.total_actions == 0 {
+            return 1.0;
+        }
+        self.built_actions as f32 / self.total_actions as f32
+    }
+}
+
 impl TransactionPlan {
     /// Builds a [`TransactionPlan`] by slotting in the
     /// provided prebuilt actions instead of using the
@@ -119,17 +138,40 @@ impl TransactionPlan {
         witness_data: &WitnessData,
         auth_data: &AuthorizationData,
     ) -> Result<Transaction> {
+        self.build_with_progress(full_viewing_key, witness_data, auth_data, |_| {})
+    }
+
+    /// Build the serial transaction this plan describes, calling `progress`
+    /// after each action is built.
+    ///
+    /// Building an action includes generating its proof, if any, so this can be
+    /// used to report progress while building large transactions.
+    pub fn build_with_progress(
+        self,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+        mut progress: impl FnMut(BuildProgress),
+    ) -> Result<Transaction> {
+        let total_actions = self.actions.len();
+
         // 1. Build each action.
         let actions = self
             .actions
             .iter()
-            .map(|action_plan| {
-                ActionPlan::build_unauth(
+            .enumerate()
+            .map(|(i, action_plan)| {
+                let action = ActionPlan::build_unauth(
                     action_plan.clone(),
                     full_viewing_key,
                     witness_data,
                     self.memo_key(),
-                )
+                )?;
+                progress(BuildProgress {
+                    built_actions: i + 1,
+                    total_actions,
+                });
+                Ok(action)
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -153,6 +195,23 @@ impl TransactionPlan {
         full_viewing_key: &FullViewingKey,
         witness_data: &WitnessData,
         auth_data: &AuthorizationData,
+    ) -> Result<Transaction> {
+        self.build_concurrent_with_progress(full_viewing_key, witness_data, auth_data, |_| {})
+            .await
+    }
+
+    #[cfg(feature = "parallel")]
+    /// Build the transaction this plan describes while proving concurrently,
+    /// calling `progress` as actions are built.
+    ///
+    /// Actions are reported in the order they appear in the plan, so progress
+    /// is reported once all the preceding actions are built as well.
+    pub async fn build_concurrent_with_progress(
+        self,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+        mut progress: impl FnMut(BuildProgress),
     ) -> Result<Transaction> {
         // Clone the witness data into an Arc so it can be shared between tasks.
         let witness_data = std::sync::Arc::new(witness_data.clone());
@@ -173,9 +232,14 @@ impl TransactionPlan {
             .collect::<Vec<_>>();
 
         // 1.5. Collect all of the actions.
+        let total_actions = action_handles.len();
         let mut actions = Vec::new();
         for handle in action_handles {
             actions.push(handle.await??);
+            progress(BuildProgress {
+                built_actions: actions.len(),
+                total_actions,
+            });
         }
 
         // 2. Pass in the prebuilt actions to the build method.
@@ -211,3 +275,69 @@ impl TransactionPlan {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_fee::Fee;
+    use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
+    use penumbra_shielded_pool::OutputPlan;
+    use penumbra_tct as tct;
+    use rand_core::OsRng;
+
+    use super::BuildProgress;
+    use crate::{plan::TransactionPlan, ActionPlan, TransactionParameters, WitnessData};
+
+    #[test]
+    fn build_reports_progress_for_each_action() {
+        let seed_phrase = SeedPhrase::generate(OsRng);
+        let sk = SpendKey::from_seed_phrase_bip44(seed_phrase, &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let (addr, _dtk) = fvk.incoming().payment_address(0u32.into());
+
+        let output = |amount: u64| -> ActionPlan {
+            OutputPlan::new(
+                &mut OsRng,
+                Value {
+                    amount: amount.into(),
+                    asset_id: *STAKING_TOKEN_ASSET_ID,
+                },
+                addr.clone(),
+            )
+            .into()
+        };
+        let plan = TransactionPlan {
+            actions: vec![output(1000), output(2000), output(3000)],
+            transaction_parameters: TransactionParameters {
+                expiry_height: 0,
+                fee: Fee::default(),
+                chain_id: "penumbra-test".to_string(),
+            },
+            detection_data: None,
+            memo: None,
+        };
+
+        let auth_data = plan.authorize(OsRng, &sk).unwrap();
+        let witness_data = WitnessData {
+            anchor: tct::Tree::new().root(),
+            state_commitment_proofs: Default::default(),
+        };
+
+        let mut reported = Vec::new();
+        let transaction = plan
+            .build_with_progress(fvk, &witness_data, &auth_data, |progress| {
+                reported.push(progress)
+            })
+            .unwrap();
+
+        let expected: Vec<_> = (1..=3)
+            .map(|built_actions| BuildProgress {
+                built_actions,
+                total_actions: 3,
+            })
+            .collect();
+        assert_eq!(reported, expected);
+        assert_eq!(reported.last().unwrap().fraction(), 1.0);
+        assert_eq!(transaction.actions().count(), 3);
+    }
+}
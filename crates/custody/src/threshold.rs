@@ -10,11 +10,13 @@ use penumbra_proto::{custody::v1 as pb, DomainType};
 use crate::{AuthorizeRequest, AuthorizeValidatorDefinitionRequest, AuthorizeValidatorVoteRequest};
 
 pub use self::config::Config;
+pub use self::relay::{RelayRole, RelayServer, RelayTerminal};
 use self::sign::no_signature_response;
 pub use crate::terminal::{SigningRequest, Terminal};
 
 mod config;
 mod dkg;
+mod relay;
 mod sign;
 
 /// Authorization data returned in response to some signing request, which may be a request to
@@ -362,6 +364,9 @@ impl<T: Terminal + Sync + Send + 'static> pb::custody_service_server::CustodySer
 mod test {
     use std::collections::HashMap;
 
+    use penumbra_governance::{ValidatorVoteBody, ValidatorVoteReason, Vote};
+    use penumbra_proto::custody::threshold::v1::relay_service_server::RelayServiceServer;
+    use penumbra_stake::{GovernanceKey, IdentityKey};
     use penumbra_transaction::TransactionPlan;
 
     use tokio::sync;
@@ -664,4 +669,95 @@ mod test {
         }
         Ok(())
     }
+
+    /// A terminal which accepts every request and keeps quiet, to wrap in a relay terminal.
+    fn quiet_terminal() -> FollowerTerminal {
+        let (outgoing, incoming) = sync::mpsc::channel(1);
+        FollowerTerminal {
+            incoming: sync::Mutex::new(incoming),
+            outgoing,
+        }
+    }
+
+    /// Run a relay on a free local port, returning its URL.
+    async fn run_relay() -> Result<String> {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(RelayServiceServer::new(RelayServer::new()))
+                .serve(addr),
+        );
+        while tokio::net::TcpStream::connect(addr).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(format!("http://{addr}"))
+    }
+
+    fn relay_terminal(
+        url: &str,
+        session: &str,
+        participant: &str,
+        role: RelayRole,
+    ) -> Result<RelayTerminal<FollowerTerminal>> {
+        RelayTerminal::new(
+            quiet_terminal(),
+            url.to_owned(),
+            session.to_owned(),
+            participant.to_owned(),
+            role,
+            std::time::Duration::from_secs(10),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dkg_and_signing_through_relay() -> Result<()> {
+        const T: u16 = 3;
+        const N: u16 = 3;
+        let url = run_relay().await?;
+
+        let mut handles = Vec::new();
+        for i in 0..N {
+            let terminal = relay_terminal(&url, "dkg", &format!("peer{i}"), RelayRole::Peer)?;
+            handles.push(tokio::spawn(async move { dkg(T, N, &terminal).await }));
+        }
+        let mut configs = Vec::new();
+        for handle in handles {
+            configs.push(handle.await??);
+        }
+        let coordinator_config = configs.pop().unwrap();
+        let fvk = coordinator_config.fvk().clone();
+        for config in &configs {
+            assert_eq!(config.fvk(), &fvk);
+        }
+
+        let coordinator = Threshold::new(
+            coordinator_config,
+            relay_terminal(&url, "sign", "coordinator", RelayRole::Coordinator)?,
+        );
+        let vote = ValidatorVoteBody {
+            proposal: 1,
+            vote: Vote::Yes,
+            identity_key: IdentityKey(fvk.spend_verification_key().clone().into()),
+            governance_key: GovernanceKey(fvk.spend_verification_key().clone()),
+            reason: ValidatorVoteReason("signed through a relay".to_owned()),
+        };
+        // The coordinator signs each request in its own sub-session.
+        for session in ["sign", "sign/2"] {
+            for (i, config) in configs.iter().enumerate() {
+                let config = config.clone();
+                let terminal =
+                    relay_terminal(&url, session, &format!("signer{i}"), RelayRole::Follower)?;
+                tokio::spawn(async move { follow(Some(&config), None, &terminal).await });
+            }
+            let SigningResponse::ValidatorVote(sig) = coordinator
+                .authorize(SigningRequest::ValidatorVote(vote.clone()))
+                .await?
+            else {
+                panic!("expected validator vote signature");
+            };
+            fvk.spend_verification_key()
+                .verify(&vote.encode_to_vec(), &sig)?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,647 @@
+//! A relay forwarding the messages of the threshold protocols between participants.
+//!
+//! Instead of copying every protocol message between people by hand, all the
+//! participants can connect to a [`RelayServer`], and exchange messages through it
+//! by using a [`RelayTerminal`] in place of their usual terminal.
+//!
+//! The relay just forwards the JSON messages the protocols already produce, tagging
+//! them with the round they belong to, so that participants which are ahead of the
+//! others don't confuse the participants which are behind them.
+//!
+//! Each session carries a single run of a protocol. A coordinator handling several
+//! signing requests runs each of them in its own sub-session, derived from the session
+//! it was given.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use penumbra_proto::custody::threshold::v1::{
+    self as pb, relay_service_client::RelayServiceClient, relay_service_server::RelayService,
+};
+use tonic::{async_trait, transport::Channel, Request, Response, Status};
+
+use crate::terminal::{SigningRequest, Terminal};
+
+/// How long a session is kept after its last activity.
+const SESSION_EXPIRY: Duration = Duration::from_secs(60 * 60);
+/// The maximum number of messages a single session can hold.
+const MAX_SESSION_MESSAGES: usize = 1024;
+/// How often to ask the relay for new messages.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How often to report the status of the other participants while waiting on them.
+const STATUS_INTERVAL: Duration = Duration::from_secs(30);
+/// How many requests to the relay can fail in a row before giving up.
+const MAX_CONSECUTIVE_FAILURES: usize = 5;
+/// The number of rounds in which each participant posts a message, in both the signing
+/// and DKG protocols.
+const PROTOCOL_ROUNDS: u32 = 2;
+
+struct Participant {
+    coordinator: bool,
+    rounds_posted: u32,
+    last_seen: Instant,
+}
+
+struct Session {
+    messages: Vec<pb::RelayMessage>,
+    participants: BTreeMap<String, Participant>,
+    last_active: Instant,
+}
+
+impl Session {
+    fn new(now: Instant) -> Self {
+        Self {
+            messages: Vec::new(),
+            participants: BTreeMap::new(),
+            last_active: now,
+        }
+    }
+
+    /// Record that a participant contacted the relay, checking that its role is consistent.
+    fn touch(
+        &mut self,
+        participant: &str,
+        coordinator: bool,
+        now: Instant,
+    ) -> Result<&mut Participant, Status> {
+        if coordinator {
+            if let Some(name) = self
+                .participants
+                .iter()
+                .find(|(name, p)| p.coordinator && name.as_str() != participant)
+                .map(|(name, _)| name)
+            {
+                return Err(Status::failed_precondition(format!(
+                    "{name} is already the coordinator of this session"
+                )));
+            }
+        }
+        self.last_active = now;
+        let state = self
+            .participants
+            .entry(participant.to_owned())
+            .or_insert(Participant {
+                coordinator,
+                rounds_posted: 0,
+                last_seen: now,
+            });
+        if state.coordinator != coordinator {
+            return Err(Status::failed_precondition(format!(
+                "{participant} already joined this session with another role"
+            )));
+        }
+        state.last_seen = now;
+        Ok(state)
+    }
+}
+
+fn check_names(session: &str, participant: &str) -> Result<(), Status> {
+    if session.is_empty() {
+        return Err(Status::invalid_argument("missing session"));
+    }
+    if participant.is_empty() {
+        return Err(Status::invalid_argument("missing participant name"));
+    }
+    Ok(())
+}
+
+/// A relay server, forwarding messages between the participants of threshold signing
+/// and DKG sessions.
+///
+/// Sessions are created when they're first used, and kept in memory until they've
+/// been inactive for an hour.
+#[derive(Default)]
+pub struct RelayServer {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl RelayServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lock the sessions, dropping the ones which have expired.
+    fn sessions(&self, now: Instant) -> Result<MutexGuard<'_, HashMap<String, Session>>, Status> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|_| Status::internal("relay state is poisoned"))?;
+        sessions.retain(|_, session| now.duration_since(session.last_active) < SESSION_EXPIRY);
+        Ok(sessions)
+    }
+}
+
+#[async_trait]
+impl RelayService for RelayServer {
+    async fn post(
+        &self,
+        request: Request<pb::PostRequest>,
+    ) -> Result<Response<pb::PostResponse>, Status> {
+        let request = request.into_inner();
+        check_names(&request.session, &request.participant)?;
+        if request.round > PROTOCOL_ROUNDS {
+            return Err(Status::invalid_argument(format!(
+                "sessions only have {PROTOCOL_ROUNDS} rounds"
+            )));
+        }
+        let now = Instant::now();
+        let mut sessions = self.sessions(now)?;
+        let session = sessions
+            .entry(request.session)
+            .or_insert_with(|| Session::new(now));
+        if session.messages.len() >= MAX_SESSION_MESSAGES {
+            return Err(Status::resource_exhausted(
+                "session has reached its maximum number of messages",
+            ));
+        }
+        let participant = session.touch(&request.participant, request.coordinator, now)?;
+        if request.round != participant.rounds_posted + 1 {
+            return Err(Status::failed_precondition(format!(
+                "{} has posted {} rounds, and cannot post round {}",
+                request.participant, participant.rounds_posted, request.round
+            )));
+        }
+        participant.rounds_posted = request.round;
+
+        let index = session.messages.len() as u64;
+        session.messages.push(pb::RelayMessage {
+            index,
+            participant: request.participant,
+            coordinator: request.coordinator,
+            round: request.round,
+            message: request.message,
+        });
+        Ok(Response::new(pb::PostResponse { index }))
+    }
+
+    async fn receive(
+        &self,
+        request: Request<pb::ReceiveRequest>,
+    ) -> Result<Response<pb::ReceiveResponse>, Status> {
+        let request = request.into_inner();
+        check_names(&request.session, &request.participant)?;
+        let now = Instant::now();
+        let mut sessions = self.sessions(now)?;
+        let session = sessions
+            .entry(request.session)
+            .or_insert_with(|| Session::new(now));
+        session.touch(&request.participant, request.coordinator, now)?;
+
+        let messages = session
+            .messages
+            .iter()
+            .skip(usize::try_from(request.cursor).unwrap_or(usize::MAX))
+            .filter(|message| message.participant != request.participant)
+            .cloned()
+            .collect();
+        Ok(Response::new(pb::ReceiveResponse { messages }))
+    }
+
+    async fn session_status(
+        &self,
+        request: Request<pb::SessionStatusRequest>,
+    ) -> Result<Response<pb::SessionStatusResponse>, Status> {
+        let request = request.into_inner();
+        let now = Instant::now();
+        let sessions = self.sessions(now)?;
+        let participants = sessions
+            .get(&request.session)
+            .map(|session| {
+                session
+                    .participants
+                    .iter()
+                    .map(|(name, participant)| pb::ParticipantStatus {
+                        participant: name.clone(),
+                        coordinator: participant.coordinator,
+                        rounds_posted: participant.rounds_posted,
+                        seconds_since_seen: now.duration_since(participant.last_seen).as_secs(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Response::new(pb::SessionStatusResponse { participants }))
+    }
+}
+
+/// The part a participant plays in a relay session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelayRole {
+    /// The coordinator of a signing session, exchanging messages with the followers.
+    Coordinator,
+    /// A follower in a signing session, exchanging messages with the coordinator.
+    Follower,
+    /// A participant in a DKG session, exchanging messages with every other participant.
+    Peer,
+}
+
+impl RelayRole {
+    /// The round of the messages we're waiting on, after posting a given number of rounds.
+    ///
+    /// Followers reply to the coordinator, whereas the other roles wait on replies
+    /// to what they've posted.
+    fn expected_round(self, rounds_posted: u32) -> u32 {
+        match self {
+            RelayRole::Follower => rounds_posted + 1,
+            RelayRole::Coordinator | RelayRole::Peer => rounds_posted,
+        }
+    }
+
+    /// Whether messages from a participant with a given role are meant for us.
+    fn accepts(self, from_coordinator: bool) -> bool {
+        match self {
+            RelayRole::Follower => from_coordinator,
+            RelayRole::Coordinator | RelayRole::Peer => !from_coordinator,
+        }
+    }
+}
+
+struct Inbox {
+    /// The session the current run of the protocol uses.
+    session: String,
+    /// The number of signing requests the coordinator has started.
+    requests: u64,
+    /// The index of the next message to fetch from the relay.
+    cursor: u64,
+    /// The number of rounds we've posted a message for.
+    rounds_posted: u32,
+    /// Messages fetched from the relay, but not read yet.
+    pending: Vec<pb::RelayMessage>,
+    /// The participants whose messages we've read in the current round.
+    senders: HashSet<String>,
+    /// If set, the only participants whose messages we read in the current round.
+    allowed_senders: Option<HashSet<String>>,
+}
+
+impl Inbox {
+    fn new(session: String) -> Self {
+        Self {
+            session,
+            requests: 0,
+            cursor: 0,
+            rounds_posted: 0,
+            pending: Vec::new(),
+            senders: HashSet::new(),
+            allowed_senders: None,
+        }
+    }
+
+    /// Start a new signing request, in a fresh sub-session of `base`.
+    ///
+    /// The first request uses `base` itself, and the following ones are numbered, so that
+    /// a coordinator signing a single request doesn't need to tell anyone about it.
+    fn start_request(&mut self, base: &str) {
+        let requests = self.requests + 1;
+        let session = match requests {
+            1 => base.to_owned(),
+            n => format!("{base}/{n}"),
+        };
+        *self = Self {
+            requests,
+            ..Self::new(session)
+        };
+    }
+
+    /// Take the next message meant for us in the current round, if we've fetched it.
+    fn take_next(&mut self, role: RelayRole) -> Option<String> {
+        let round = role.expected_round(self.rounds_posted);
+        // Messages from earlier rounds were either read or meant for someone else.
+        self.pending.retain(|message| message.round >= round);
+        let position = self.pending.iter().position(|message| {
+            role.accepts(message.coordinator)
+                && message.round == round
+                && !self.senders.contains(&message.participant)
+                && self
+                    .allowed_senders
+                    .as_ref()
+                    .map_or(true, |allowed| allowed.contains(&message.participant))
+        })?;
+        let message = self.pending.remove(position);
+        self.senders.insert(message.participant);
+        Some(message.message)
+    }
+
+    /// Move on to the next round, after posting our message for it.
+    fn posted(&mut self, role: RelayRole) {
+        self.rounds_posted += 1;
+        let senders = std::mem::take(&mut self.senders);
+        // The coordinator needs replies from the same followers in every round.
+        if role == RelayRole::Coordinator && !senders.is_empty() {
+            self.allowed_senders = Some(senders);
+        }
+    }
+}
+
+/// A terminal exchanging protocol messages through a relay, rather than by having
+/// the user copy and paste them.
+///
+/// Everything else, like confirming signing requests, is handled by the inner terminal.
+///
+/// Each session carries a single run of a protocol. Followers and DKG participants need a
+/// new session for every signature or key, whereas a coordinator runs each signing request
+/// in its own sub-session: the first uses the session it was given, and the `n`th one uses
+/// `<session>/<n>`.
+#[derive(Clone)]
+pub struct RelayTerminal<T> {
+    inner: T,
+    client: RelayServiceClient<Channel>,
+    session: String,
+    participant: String,
+    role: RelayRole,
+    inbox: Arc<tokio::sync::Mutex<Inbox>>,
+}
+
+impl<T: Terminal> RelayTerminal<T> {
+    /// Use the relay at `url` to take part in `session` as `participant`.
+    ///
+    /// The connection to the relay is only made once it's needed, and each request
+    /// to the relay fails if it takes longer than `timeout`.
+    pub fn new(
+        inner: T,
+        url: String,
+        session: String,
+        participant: String,
+        role: RelayRole,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let channel = Channel::from_shared(url)
+            .context("could not parse relay URL")?
+            .timeout(timeout)
+            .connect_timeout(timeout)
+            .connect_lazy();
+        Ok(Self {
+            inner,
+            client: RelayServiceClient::new(channel),
+            inbox: Arc::new(tokio::sync::Mutex::new(Inbox::new(session.clone()))),
+            session,
+            participant,
+            role,
+        })
+    }
+
+    async fn fetch(&self, session: &str, cursor: u64) -> Result<Vec<pb::RelayMessage>> {
+        let response = self
+            .client
+            .clone()
+            .receive(pb::ReceiveRequest {
+                session: session.to_owned(),
+                participant: self.participant.clone(),
+                coordinator: self.role == RelayRole::Coordinator,
+                cursor,
+            })
+            .await?;
+        Ok(response.into_inner().messages)
+    }
+
+    /// Tell the user which participants have posted what so far.
+    async fn explain_status(&self, session: &str) -> Result<()> {
+        let participants = self
+            .client
+            .clone()
+            .session_status(pb::SessionStatusRequest {
+                session: session.to_owned(),
+            })
+            .await?
+            .into_inner()
+            .participants;
+        self.inner.explain(&format!(
+            "Still waiting on the relay; participants in session {session}:"
+        ))?;
+        for p in participants {
+            self.inner.explain(&format!(
+                "  {}{}: posted {} rounds, last seen {}s ago",
+                p.participant,
+                if p.coordinator { " (coordinator)" } else { "" },
+                p.rounds_posted,
+                p.seconds_since_seen
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Terminal + Send> Terminal for RelayTerminal<T> {
+    async fn confirm_request(&self, request: &SigningRequest) -> Result<bool> {
+        self.inner.confirm_request(request).await
+    }
+
+    fn explain(&self, msg: &str) -> Result<()> {
+        self.inner.explain(msg)
+    }
+
+    async fn broadcast(&self, data: &str) -> Result<()> {
+        let mut inbox = self.inbox.lock().await;
+        if self.role == RelayRole::Coordinator && starts_signing_request(data) {
+            inbox.start_request(&self.session);
+            self.inner.explain(&format!(
+                "The other signers should join relay session {} to sign this request.",
+                inbox.session
+            ))?;
+        }
+        let round = inbox.rounds_posted + 1;
+        if round > PROTOCOL_ROUNDS {
+            anyhow::bail!(
+                "relay session {} has already been used, start a new session to sign again",
+                inbox.session
+            );
+        }
+        self.client
+            .clone()
+            .post(pb::PostRequest {
+                session: inbox.session.clone(),
+                participant: self.participant.clone(),
+                coordinator: self.role == RelayRole::Coordinator,
+                round,
+                message: data.to_owned(),
+            })
+            .await
+            .context("could not post message to the relay")?;
+        inbox.posted(self.role);
+        self.inner.explain(&format!(
+            "Posted the round {round} message to relay session {}.",
+            inbox.session
+        ))
+    }
+
+    async fn read_line_raw(&self) -> Result<String> {
+        let mut inbox = self.inbox.lock().await;
+        let mut failures = 0;
+        let mut last_status = Instant::now();
+        loop {
+            if let Some(message) = inbox.take_next(self.role) {
+                return Ok(message);
+            }
+            match self.fetch(&inbox.session, inbox.cursor).await {
+                Ok(messages) => {
+                    failures = 0;
+                    if let Some(last) = messages.last() {
+                        inbox.cursor = last.index + 1;
+                    }
+                    if !messages.is_empty() {
+                        inbox.pending.extend(messages);
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        return Err(e.context("could not receive messages from the relay"));
+                    }
+                    self.inner
+                        .explain(&format!("Could not reach the relay, retrying: {e:#}"))?;
+                }
+            }
+            if last_status.elapsed() >= STATUS_INTERVAL {
+                // The status is purely informational, so failing to get it isn't fatal.
+                if let Err(e) = self.explain_status(&inbox.session).await {
+                    self.inner
+                        .explain(&format!("Could not get the session status: {e:#}"))?;
+                }
+                last_status = Instant::now();
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn get_password(&self) -> Result<String> {
+        self.inner.get_password().await
+    }
+}
+
+/// Whether a message posted by the coordinator starts a new signing request.
+///
+/// Only the first message of the signing protocol carries the request being signed.
+fn starts_signing_request(data: &str) -> bool {
+    serde_json::from_str::<pb::CoordinatorRound1>(data)
+        .map_or(false, |message| message.request.is_some())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message(index: u64, participant: &str, coordinator: bool, round: u32) -> pb::RelayMessage {
+        pb::RelayMessage {
+            index,
+            participant: participant.to_owned(),
+            coordinator,
+            round,
+            message: format!("{participant}-{round}"),
+        }
+    }
+
+    fn post(participant: &str, coordinator: bool, round: u32) -> Request<pb::PostRequest> {
+        Request::new(pb::PostRequest {
+            session: "session".to_owned(),
+            participant: participant.to_owned(),
+            coordinator,
+            round,
+            message: format!("{participant}-{round}"),
+        })
+    }
+
+    #[test]
+    fn coordinator_reads_each_round_from_the_same_followers() {
+        let role = RelayRole::Coordinator;
+        let mut inbox = Inbox::new("session".to_owned());
+        inbox.posted(role);
+        inbox
+            .pending
+            .extend([message(1, "alice", false, 1), message(2, "bob", false, 1)]);
+        assert_eq!(inbox.take_next(role).as_deref(), Some("alice-1"));
+
+        inbox.posted(role);
+        // A late reply to the first round, and a reply from a follower we didn't pick.
+        inbox.pending.extend([
+            message(4, "carol", false, 1),
+            message(5, "bob", false, 2),
+            message(6, "alice", false, 2),
+        ]);
+        assert_eq!(inbox.take_next(role).as_deref(), Some("alice-2"));
+        assert_eq!(inbox.take_next(role), None);
+    }
+
+    #[test]
+    fn followers_only_read_the_coordinator() {
+        let role = RelayRole::Follower;
+        let mut inbox = Inbox::new("session".to_owned());
+        inbox.pending.extend([
+            message(0, "coordinator", true, 1),
+            message(1, "bob", false, 1),
+            message(2, "coordinator", true, 2),
+        ]);
+        assert_eq!(inbox.take_next(role).as_deref(), Some("coordinator-1"));
+        // We haven't replied to the first round yet.
+        assert_eq!(inbox.take_next(role), None);
+
+        inbox.posted(role);
+        assert_eq!(inbox.take_next(role).as_deref(), Some("coordinator-2"));
+    }
+
+    #[test]
+    fn coordinator_starts_a_sub_session_per_request() {
+        let role = RelayRole::Coordinator;
+        let mut inbox = Inbox::new("session".to_owned());
+        inbox.start_request("session");
+        assert_eq!(inbox.session, "session");
+        inbox.posted(role);
+        inbox.pending.push(message(1, "alice", false, 1));
+        assert_eq!(inbox.take_next(role).as_deref(), Some("alice-1"));
+
+        // An abandoned request doesn't hold up the next one.
+        inbox.start_request("session");
+        assert_eq!(inbox.session, "session/2");
+        assert_eq!((inbox.cursor, inbox.rounds_posted), (0, 0));
+        assert!(inbox.allowed_senders.is_none());
+        inbox.start_request("session");
+        assert_eq!(inbox.session, "session/3");
+    }
+
+    #[test]
+    fn only_the_first_signing_message_starts_a_request() {
+        assert!(starts_signing_request(r#"{"validatorVote": {"proposal": "1"}}"#));
+        assert!(!starts_signing_request(r#"{"signingPackages": []}"#));
+        assert!(!starts_signing_request("not json"));
+    }
+
+    #[tokio::test]
+    async fn server_forwards_messages_in_order() -> Result<()> {
+        let server = RelayServer::new();
+        server.post(post("coordinator", true, 1)).await?;
+        server.post(post("alice", false, 1)).await?;
+        // Participants can only post once per round, and sessions have a single coordinator.
+        assert!(server.post(post("alice", false, 1)).await.is_err());
+        assert!(server.post(post("alice", false, 3)).await.is_err());
+        assert!(server.post(post("bob", true, 1)).await.is_err());
+
+        let messages = server
+            .receive(Request::new(pb::ReceiveRequest {
+                session: "session".to_owned(),
+                participant: "alice".to_owned(),
+                coordinator: false,
+                cursor: 0,
+            }))
+            .await?
+            .into_inner()
+            .messages;
+        assert_eq!(messages, vec![message(0, "coordinator", true, 1)]);
+
+        let participants = server
+            .session_status(Request::new(pb::SessionStatusRequest {
+                session: "session".to_owned(),
+            }))
+            .await?
+            .into_inner()
+            .participants;
+        let rounds_posted: Vec<_> = participants
+            .iter()
+            .map(|p| (p.participant.as_str(), p.rounds_posted))
+            .collect();
+        assert_eq!(rounds_posted, vec![("alice", 1), ("coordinator", 1)]);
+        Ok(())
+    }
+}
@@ -92,6 +92,48 @@ impl ::prost::Name for AuctionStateByIdsResponse {
         ::prost::alloc::format!("penumbra.core.component.auction.v1.{}", Self::NAME)
     }
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DutchAuctionsRequest {
+    /// If set, only auctions selling `pair.start` for `pair.end` are returned.
+    #[prost(message, optional, tag = "1")]
+    pub pair: ::core::option::Option<super::super::dex::v1::DirectedTradingPair>,
+    /// If nonzero, only auctions ending at or after this height are returned.
+    #[prost(uint64, tag = "2")]
+    pub start_height: u64,
+    /// If nonzero, only auctions starting at or before this height are returned.
+    #[prost(uint64, tag = "3")]
+    pub end_height: u64,
+}
+impl ::prost::Name for DutchAuctionsRequest {
+    const NAME: &'static str = "DutchAuctionsRequest";
+    const PACKAGE: &'static str = "penumbra.core.component.auction.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.auction.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DutchAuctionsResponse {
+    /// The auction ID of the returned auction.
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<AuctionId>,
+    /// The description and state of the returned auction.
+    #[prost(message, optional, tag = "2")]
+    pub auction: ::core::option::Option<DutchAuction>,
+    /// The state of any DEX positions relevant to the returned auction.
+    ///
+    /// Could be empty, depending on the auction state.
+    #[prost(message, repeated, tag = "3")]
+    pub positions: ::prost::alloc::vec::Vec<super::super::dex::v1::Position>,
+}
+impl ::prost::Name for DutchAuctionsResponse {
+    const NAME: &'static str = "DutchAuctionsResponse";
+    const PACKAGE: &'static str = "penumbra.core.component.auction.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.auction.v1.{}", Self::NAME)
+    }
+}
 /// A unique identifier for an auction, obtained from hashing a domain separator
 /// along with the immutable part of an auction description.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -641,6 +683,37 @@ pub mod query_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Get the open Dutch auctions selling along a trading pair, filtered by height range.
+        pub async fn dutch_auctions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DutchAuctionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::DutchAuctionsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.core.component.auction.v1.QueryService/DutchAuctions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "penumbra.core.component.auction.v1.QueryService",
+                        "DutchAuctions",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -676,6 +749,23 @@ pub mod query_service_server {
             tonic::Response<Self::AuctionStateByIdsStream>,
             tonic::Status,
         >;
+        /// Server streaming response type for the DutchAuctions method.
+        type DutchAuctionsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::DutchAuctionsResponse,
+                    tonic::Status,
+                >,
+            >
+            + Send
+            + 'static;
+        /// Get the open Dutch auctions selling along a trading pair, filtered by height range.
+        async fn dutch_auctions(
+            &self,
+            request: tonic::Request<super::DutchAuctionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::DutchAuctionsStream>,
+            tonic::Status,
+        >;
     }
     /// Query operations for the auction component.
     #[derive(Debug)]
@@ -853,6 +943,55 @@ pub mod query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/penumbra.core.component.auction.v1.QueryService/DutchAuctions" => {
+                    #[allow(non_camel_case_types)]
+                    struct DutchAuctionsSvc<T: QueryService>(pub Arc<T>);
+                    impl<
+                        T: QueryService,
+                    > tonic::server::ServerStreamingService<
+                        super::DutchAuctionsRequest,
+                    > for DutchAuctionsSvc<T> {
+                        type Response = super::DutchAuctionsResponse;
+                        type ResponseStream = T::DutchAuctionsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DutchAuctionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as QueryService>::dutch_auctions(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DutchAuctionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -123,6 +123,12 @@ pub struct SwapBody {
     /// The swap commitment and encryption of the swap data.
     #[prost(message, optional, tag = "5")]
     pub payload: ::core::option::Option<SwapPayload>,
+    /// The minimum output the swap must receive to be filled, if any.
+    ///
+    /// This is only set for swaps with exactly one nonzero input, and is
+    /// denominated in the other asset of the trading pair.
+    #[prost(message, optional, tag = "6")]
+    pub min_output: ::core::option::Option<super::super::super::num::v1::Amount>,
 }
 impl ::prost::Name for SwapBody {
     const NAME: &'static str = "SwapBody";
@@ -169,6 +175,10 @@ pub struct SwapPlaintext {
     /// Swap rseed (blinding factors are derived from this)
     #[prost(bytes = "vec", tag = "6")]
     pub rseed: ::prost::alloc::vec::Vec<u8>,
+    /// The minimum output the swap must receive to be filled, if any.
+    /// If the batch would not pay this much, the swap's input is refunded instead.
+    #[prost(message, optional, tag = "7")]
+    pub min_output: ::core::option::Option<super::super::super::num::v1::Amount>,
 }
 impl ::prost::Name for SwapPlaintext {
     const NAME: &'static str = "SwapPlaintext";
@@ -514,6 +524,20 @@ pub struct BatchSwapOutputData {
     /// The prefix (epoch, block) of the position where this batch swap occurred.
     #[prost(uint64, tag = "10")]
     pub sct_position_prefix: u64,
+    /// The highest limit price, in asset 2 per unit of asset 1, of the 1=>2 swaps
+    /// that were filled, encoded as a 128.128 fixed-point number.
+    ///
+    /// Swaps with a minimum output whose limit price exceeds this were refunded.
+    /// If empty, no such swaps were filled.
+    #[prost(bytes = "vec", tag = "11")]
+    pub limit_price_1_for_2: ::prost::alloc::vec::Vec<u8>,
+    /// The highest limit price, in asset 1 per unit of asset 2, of the 2=>1 swaps
+    /// that were filled, encoded as a 128.128 fixed-point number.
+    ///
+    /// Swaps with a minimum output whose limit price exceeds this were refunded.
+    /// If empty, no such swaps were filled.
+    #[prost(bytes = "vec", tag = "12")]
+    pub limit_price_2_for_1: ::prost::alloc::vec::Vec<u8>,
 }
 impl ::prost::Name for BatchSwapOutputData {
     const NAME: &'static str = "BatchSwapOutputData";
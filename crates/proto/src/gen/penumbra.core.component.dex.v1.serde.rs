@@ -617,6 +617,12 @@ impl serde::Serialize for BatchSwapOutputData {
         if self.sct_position_prefix != 0 {
             len += 1;
         }
+        if !self.limit_price_1_for_2.is_empty() {
+            len += 1;
+        }
+        if !self.limit_price_2_for_1.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.dex.v1.BatchSwapOutputData", len)?;
         if let Some(v) = self.delta_1.as_ref() {
             struct_ser.serialize_field("delta1", v)?;
@@ -651,6 +657,14 @@ impl serde::Serialize for BatchSwapOutputData {
             #[allow(clippy::needless_borrow)]
             struct_ser.serialize_field("sctPositionPrefix", ToString::to_string(&self.sct_position_prefix).as_str())?;
         }
+        if !self.limit_price_1_for_2.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("limitPrice1For2", pbjson::private::base64::encode(&self.limit_price_1_for_2).as_str())?;
+        }
+        if !self.limit_price_2_for_1.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("limitPrice2For1", pbjson::private::base64::encode(&self.limit_price_2_for_1).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -680,6 +694,10 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
             "epochStartingHeight",
             "sct_position_prefix",
             "sctPositionPrefix",
+            "limit_price_1_for_2",
+            "limitPrice1For2",
+            "limit_price_2_for_1",
+            "limitPrice2For1",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -694,6 +712,8 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
             TradingPair,
             EpochStartingHeight,
             SctPositionPrefix,
+            LimitPrice1For2,
+            LimitPrice2For1,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -726,6 +746,8 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
                             "tradingPair" | "trading_pair" => Ok(GeneratedField::TradingPair),
                             "epochStartingHeight" | "epoch_starting_height" => Ok(GeneratedField::EpochStartingHeight),
                             "sctPositionPrefix" | "sct_position_prefix" => Ok(GeneratedField::SctPositionPrefix),
+                            "limitPrice1For2" | "limit_price_1_for_2" => Ok(GeneratedField::LimitPrice1For2),
+                            "limitPrice2For1" | "limit_price_2_for_1" => Ok(GeneratedField::LimitPrice2For1),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -755,6 +777,8 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
                 let mut trading_pair__ = None;
                 let mut epoch_starting_height__ = None;
                 let mut sct_position_prefix__ = None;
+                let mut limit_price_1_for_2__ = None;
+                let mut limit_price_2_for_1__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Delta1 => {
@@ -823,6 +847,22 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
                                 Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::LimitPrice1For2 => {
+                            if limit_price_1_for_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("limitPrice1For2"));
+                            }
+                            limit_price_1_for_2__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::LimitPrice2For1 => {
+                            if limit_price_2_for_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("limitPrice2For1"));
+                            }
+                            limit_price_2_for_1__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -839,6 +879,8 @@ impl<'de> serde::Deserialize<'de> for BatchSwapOutputData {
                     trading_pair: trading_pair__,
                     epoch_starting_height: epoch_starting_height__.unwrap_or_default(),
                     sct_position_prefix: sct_position_prefix__.unwrap_or_default(),
+                    limit_price_1_for_2: limit_price_1_for_2__.unwrap_or_default(),
+                    limit_price_2_for_1: limit_price_2_for_1__.unwrap_or_default(),
                 })
             }
         }
@@ -6710,6 +6752,9 @@ impl serde::Serialize for SwapBody {
         if self.payload.is_some() {
             len += 1;
         }
+        if self.min_output.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.dex.v1.SwapBody", len)?;
         if let Some(v) = self.trading_pair.as_ref() {
             struct_ser.serialize_field("tradingPair", v)?;
@@ -6726,6 +6771,9 @@ impl serde::Serialize for SwapBody {
         if let Some(v) = self.payload.as_ref() {
             struct_ser.serialize_field("payload", v)?;
         }
+        if let Some(v) = self.min_output.as_ref() {
+            struct_ser.serialize_field("minOutput", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -6745,6 +6793,8 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
             "fee_commitment",
             "feeCommitment",
             "payload",
+            "min_output",
+            "minOutput",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -6754,6 +6804,7 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
             Delta2I,
             FeeCommitment,
             Payload,
+            MinOutput,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -6781,6 +6832,7 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
                             "delta2I" | "delta_2_i" => Ok(GeneratedField::Delta2I),
                             "feeCommitment" | "fee_commitment" => Ok(GeneratedField::FeeCommitment),
                             "payload" => Ok(GeneratedField::Payload),
+                            "minOutput" | "min_output" => Ok(GeneratedField::MinOutput),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -6805,6 +6857,7 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
                 let mut delta_2_i__ = None;
                 let mut fee_commitment__ = None;
                 let mut payload__ = None;
+                let mut min_output__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::TradingPair => {
@@ -6837,6 +6890,12 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
                             }
                             payload__ = map_.next_value()?;
                         }
+                        GeneratedField::MinOutput => {
+                            if min_output__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("minOutput"));
+                            }
+                            min_output__ = map_.next_value()?;
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -6848,6 +6907,7 @@ impl<'de> serde::Deserialize<'de> for SwapBody {
                     delta_2_i: delta_2_i__,
                     fee_commitment: fee_commitment__,
                     payload: payload__,
+                    min_output: min_output__,
                 })
             }
         }
@@ -8559,6 +8619,9 @@ impl serde::Serialize for SwapPlaintext {
         if !self.rseed.is_empty() {
             len += 1;
         }
+        if self.min_output.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.dex.v1.SwapPlaintext", len)?;
         if let Some(v) = self.trading_pair.as_ref() {
             struct_ser.serialize_field("tradingPair", v)?;
@@ -8579,6 +8642,9 @@ impl serde::Serialize for SwapPlaintext {
             #[allow(clippy::needless_borrow)]
             struct_ser.serialize_field("rseed", pbjson::private::base64::encode(&self.rseed).as_str())?;
         }
+        if let Some(v) = self.min_output.as_ref() {
+            struct_ser.serialize_field("minOutput", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -8600,6 +8666,8 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
             "claim_address",
             "claimAddress",
             "rseed",
+            "min_output",
+            "minOutput",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -8610,6 +8678,7 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
             ClaimFee,
             ClaimAddress,
             Rseed,
+            MinOutput,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -8638,6 +8707,7 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
                             "claimFee" | "claim_fee" => Ok(GeneratedField::ClaimFee),
                             "claimAddress" | "claim_address" => Ok(GeneratedField::ClaimAddress),
                             "rseed" => Ok(GeneratedField::Rseed),
+                            "minOutput" | "min_output" => Ok(GeneratedField::MinOutput),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -8663,6 +8733,7 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
                 let mut claim_fee__ = None;
                 let mut claim_address__ = None;
                 let mut rseed__ = None;
+                let mut min_output__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::TradingPair => {
@@ -8703,6 +8774,12 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
                                 Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::MinOutput => {
+                            if min_output__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("minOutput"));
+                            }
+                            min_output__ = map_.next_value()?;
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -8715,6 +8792,7 @@ impl<'de> serde::Deserialize<'de> for SwapPlaintext {
                     claim_fee: claim_fee__,
                     claim_address: claim_address__,
                     rseed: rseed__.unwrap_or_default(),
+                    min_output: min_output__,
                 })
             }
         }
@@ -5,6 +5,9 @@ pub struct DistributionsParameters {
     /// The amount of staking token issued per block.
     #[prost(uint64, tag = "1")]
     pub staking_issuance_per_block: u64,
+    /// The amount of staking token issued per block to liquidity providers.
+    #[prost(uint64, tag = "2")]
+    pub liquidity_provider_issuance_per_block: u64,
 }
 impl ::prost::Name for DistributionsParameters {
     const NAME: &'static str = "DistributionsParameters";
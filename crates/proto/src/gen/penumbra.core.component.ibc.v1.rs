@@ -175,6 +175,9 @@ pub struct IbcParameters {
     /// Whether outbound ICS-20 transfers are enabled
     #[prost(bool, tag = "3")]
     pub outbound_ics20_transfers_enabled: bool,
+    /// Limits on the value of ICS-20 transfers over individual channels.
+    #[prost(message, repeated, tag = "4")]
+    pub ics20_rate_limits: ::prost::alloc::vec::Vec<Ics20RateLimit>,
 }
 impl ::prost::Name for IbcParameters {
     const NAME: &'static str = "IbcParameters";
@@ -183,6 +186,36 @@ impl ::prost::Name for IbcParameters {
         ::prost::alloc::format!("penumbra.core.component.ibc.v1.{}", Self::NAME)
     }
 }
+/// A limit on the amount of an asset that can be transferred over an ICS-20
+/// channel within a rolling window of blocks.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Ics20RateLimit {
+    /// The Penumbra-side identifier of the channel, e.g. `channel-0`.
+    #[prost(string, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// The denomination of the limited asset, as it is known on Penumbra.
+    #[prost(string, tag = "2")]
+    pub denom: ::prost::alloc::string::String,
+    /// The maximum amount that can be received over the channel within the window.
+    /// If unset, inbound transfers are not limited.
+    #[prost(message, optional, tag = "3")]
+    pub max_inbound: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The maximum amount that can be sent over the channel within the window.
+    /// If unset, outbound transfers are not limited.
+    #[prost(message, optional, tag = "4")]
+    pub max_outbound: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The length of the rolling window, in blocks.
+    #[prost(uint64, tag = "5")]
+    pub window_blocks: u64,
+}
+impl ::prost::Name for Ics20RateLimit {
+    const NAME: &'static str = "Ics20RateLimit";
+    const PACKAGE: &'static str = "penumbra.core.component.ibc.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.ibc.v1.{}", Self::NAME)
+    }
+}
 /// IBC genesis state.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
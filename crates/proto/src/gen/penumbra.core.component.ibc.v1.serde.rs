@@ -814,6 +814,9 @@ impl serde::Serialize for IbcParameters {
         if self.outbound_ics20_transfers_enabled {
             len += 1;
         }
+        if !self.ics20_rate_limits.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.ibc.v1.IbcParameters", len)?;
         if self.ibc_enabled {
             struct_ser.serialize_field("ibcEnabled", &self.ibc_enabled)?;
@@ -824,6 +827,9 @@ impl serde::Serialize for IbcParameters {
         if self.outbound_ics20_transfers_enabled {
             struct_ser.serialize_field("outboundIcs20TransfersEnabled", &self.outbound_ics20_transfers_enabled)?;
         }
+        if !self.ics20_rate_limits.is_empty() {
+            struct_ser.serialize_field("ics20RateLimits", &self.ics20_rate_limits)?;
+        }
         struct_ser.end()
     }
 }
@@ -840,6 +846,8 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
             "inboundIcs20TransfersEnabled",
             "outbound_ics20_transfers_enabled",
             "outboundIcs20TransfersEnabled",
+            "ics20_rate_limits",
+            "ics20RateLimits",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -847,6 +855,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
             IbcEnabled,
             InboundIcs20TransfersEnabled,
             OutboundIcs20TransfersEnabled,
+            Ics20RateLimits,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -872,6 +881,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                             "ibcEnabled" | "ibc_enabled" => Ok(GeneratedField::IbcEnabled),
                             "inboundIcs20TransfersEnabled" | "inbound_ics20_transfers_enabled" => Ok(GeneratedField::InboundIcs20TransfersEnabled),
                             "outboundIcs20TransfersEnabled" | "outbound_ics20_transfers_enabled" => Ok(GeneratedField::OutboundIcs20TransfersEnabled),
+                            "ics20RateLimits" | "ics20_rate_limits" => Ok(GeneratedField::Ics20RateLimits),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -894,6 +904,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                 let mut ibc_enabled__ = None;
                 let mut inbound_ics20_transfers_enabled__ = None;
                 let mut outbound_ics20_transfers_enabled__ = None;
+                let mut ics20_rate_limits__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::IbcEnabled => {
@@ -914,6 +925,12 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                             }
                             outbound_ics20_transfers_enabled__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Ics20RateLimits => {
+                            if ics20_rate_limits__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ics20RateLimits"));
+                            }
+                            ics20_rate_limits__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -923,6 +940,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                     ibc_enabled: ibc_enabled__.unwrap_or_default(),
                     inbound_ics20_transfers_enabled: inbound_ics20_transfers_enabled__.unwrap_or_default(),
                     outbound_ics20_transfers_enabled: outbound_ics20_transfers_enabled__.unwrap_or_default(),
+                    ics20_rate_limits: ics20_rate_limits__.unwrap_or_default(),
                 })
             }
         }
@@ -1025,6 +1043,176 @@ impl<'de> serde::Deserialize<'de> for IbcRelay {
         deserializer.deserialize_struct("penumbra.core.component.ibc.v1.IbcRelay", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for Ics20RateLimit {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.channel_id.is_empty() {
+            len += 1;
+        }
+        if !self.denom.is_empty() {
+            len += 1;
+        }
+        if self.max_inbound.is_some() {
+            len += 1;
+        }
+        if self.max_outbound.is_some() {
+            len += 1;
+        }
+        if self.window_blocks != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.ibc.v1.Ics20RateLimit", len)?;
+        if !self.channel_id.is_empty() {
+            struct_ser.serialize_field("channelId", &self.channel_id)?;
+        }
+        if !self.denom.is_empty() {
+            struct_ser.serialize_field("denom", &self.denom)?;
+        }
+        if let Some(v) = self.max_inbound.as_ref() {
+            struct_ser.serialize_field("maxInbound", v)?;
+        }
+        if let Some(v) = self.max_outbound.as_ref() {
+            struct_ser.serialize_field("maxOutbound", v)?;
+        }
+        if self.window_blocks != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("windowBlocks", ToString::to_string(&self.window_blocks).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Ics20RateLimit {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "channel_id",
+            "channelId",
+            "denom",
+            "max_inbound",
+            "maxInbound",
+            "max_outbound",
+            "maxOutbound",
+            "window_blocks",
+            "windowBlocks",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ChannelId,
+            Denom,
+            MaxInbound,
+            MaxOutbound,
+            WindowBlocks,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "channelId" | "channel_id" => Ok(GeneratedField::ChannelId),
+                            "denom" => Ok(GeneratedField::Denom),
+                            "maxInbound" | "max_inbound" => Ok(GeneratedField::MaxInbound),
+                            "maxOutbound" | "max_outbound" => Ok(GeneratedField::MaxOutbound),
+                            "windowBlocks" | "window_blocks" => Ok(GeneratedField::WindowBlocks),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Ics20RateLimit;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.ibc.v1.Ics20RateLimit")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<Ics20RateLimit, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut channel_id__ = None;
+                let mut denom__ = None;
+                let mut max_inbound__ = None;
+                let mut max_outbound__ = None;
+                let mut window_blocks__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::ChannelId => {
+                            if channel_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("channelId"));
+                            }
+                            channel_id__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Denom => {
+                            if denom__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("denom"));
+                            }
+                            denom__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::MaxInbound => {
+                            if max_inbound__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxInbound"));
+                            }
+                            max_inbound__ = map_.next_value()?;
+                        }
+                        GeneratedField::MaxOutbound => {
+                            if max_outbound__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxOutbound"));
+                            }
+                            max_outbound__ = map_.next_value()?;
+                        }
+                        GeneratedField::WindowBlocks => {
+                            if window_blocks__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("windowBlocks"));
+                            }
+                            window_blocks__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(Ics20RateLimit {
+                    channel_id: channel_id__.unwrap_or_default(),
+                    denom: denom__.unwrap_or_default(),
+                    max_inbound: max_inbound__,
+                    max_outbound: max_outbound__,
+                    window_blocks: window_blocks__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.ibc.v1.Ics20RateLimit", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for Ics20Withdrawal {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
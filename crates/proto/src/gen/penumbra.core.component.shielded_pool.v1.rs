@@ -732,6 +732,83 @@ impl ::prost::Name for AssetMetadataByIdsResponse {
         )
     }
 }
+/// Requests the total supply of an asset.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssetSupplyRequest {
+    /// The asset ID to request the supply of.
+    #[prost(message, optional, tag = "1")]
+    pub asset_id: ::core::option::Option<super::super::super::asset::v1::AssetId>,
+}
+impl ::prost::Name for AssetSupplyRequest {
+    const NAME: &'static str = "AssetSupplyRequest";
+    const PACKAGE: &'static str = "penumbra.core.component.shielded_pool.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!(
+            "penumbra.core.component.shielded_pool.v1.{}", Self::NAME
+        )
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssetSupplyResponse {
+    /// The total supply of the asset. Assets that were never minted have a zero supply.
+    #[prost(message, optional, tag = "1")]
+    pub supply: ::core::option::Option<super::super::super::num::v1::Amount>,
+}
+impl ::prost::Name for AssetSupplyResponse {
+    const NAME: &'static str = "AssetSupplyResponse";
+    const PACKAGE: &'static str = "penumbra.core.component.shielded_pool.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!(
+            "penumbra.core.component.shielded_pool.v1.{}", Self::NAME
+        )
+    }
+}
+/// Requests the remaining ICS-20 transfer quota for an asset on a channel.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Ics20QuotaRequest {
+    /// The Penumbra-side identifier of the channel, e.g. `channel-0`.
+    #[prost(string, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// The asset ID to request the quota of.
+    #[prost(message, optional, tag = "2")]
+    pub asset_id: ::core::option::Option<super::super::super::asset::v1::AssetId>,
+}
+impl ::prost::Name for Ics20QuotaRequest {
+    const NAME: &'static str = "Ics20QuotaRequest";
+    const PACKAGE: &'static str = "penumbra.core.component.shielded_pool.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!(
+            "penumbra.core.component.shielded_pool.v1.{}", Self::NAME
+        )
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Ics20QuotaResponse {
+    /// The amount that can still be received over the channel in the current window.
+    /// If absent, inbound transfers of the asset are not rate limited.
+    #[prost(message, optional, tag = "1")]
+    pub inbound_remaining: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The amount that can still be sent over the channel in the current window.
+    /// If absent, outbound transfers of the asset are not rate limited.
+    #[prost(message, optional, tag = "2")]
+    pub outbound_remaining: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The length of the rolling window in blocks, or zero if there is no rate limit.
+    #[prost(uint64, tag = "3")]
+    pub window_blocks: u64,
+}
+impl ::prost::Name for Ics20QuotaResponse {
+    const NAME: &'static str = "Ics20QuotaResponse";
+    const PACKAGE: &'static str = "penumbra.core.component.shielded_pool.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!(
+            "penumbra.core.component.shielded_pool.v1.{}", Self::NAME
+        )
+    }
+}
 /// Generated client implementations.
 #[cfg(feature = "rpc")]
 pub mod query_service_client {
@@ -884,6 +961,69 @@ pub mod query_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Requests the total supply of an asset. This includes value held by the
+        /// protocol itself, e.g. in liquidity positions, auctions, or IBC escrow.
+        pub async fn asset_supply(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AssetSupplyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AssetSupplyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.core.component.shielded_pool.v1.QueryService/AssetSupply",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "penumbra.core.component.shielded_pool.v1.QueryService",
+                        "AssetSupply",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Requests the remaining ICS-20 transfer quota for an asset on a channel.
+        pub async fn ics20_quota(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Ics20QuotaRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::Ics20QuotaResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.core.component.shielded_pool.v1.QueryService/Ics20Quota",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "penumbra.core.component.shielded_pool.v1.QueryService",
+                        "Ics20Quota",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -922,6 +1062,23 @@ pub mod query_service_server {
             tonic::Response<Self::AssetMetadataByIdsStream>,
             tonic::Status,
         >;
+        /// Requests the total supply of an asset. This includes value held by the
+        /// protocol itself, e.g. in liquidity positions, auctions, or IBC escrow.
+        async fn asset_supply(
+            &self,
+            request: tonic::Request<super::AssetSupplyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AssetSupplyResponse>,
+            tonic::Status,
+        >;
+        /// Requests the remaining ICS-20 transfer quota for an asset on a channel.
+        async fn ics20_quota(
+            &self,
+            request: tonic::Request<super::Ics20QuotaRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::Ics20QuotaResponse>,
+            tonic::Status,
+        >;
     }
     /// Query operations for the shielded pool component.
     #[derive(Debug)]
@@ -1099,6 +1256,100 @@ pub mod query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/penumbra.core.component.shielded_pool.v1.QueryService/AssetSupply" => {
+                    #[allow(non_camel_case_types)]
+                    struct AssetSupplySvc<T: QueryService>(pub Arc<T>);
+                    impl<
+                        T: QueryService,
+                    > tonic::server::UnaryService<super::AssetSupplyRequest>
+                    for AssetSupplySvc<T> {
+                        type Response = super::AssetSupplyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AssetSupplyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as QueryService>::asset_supply(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AssetSupplySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/penumbra.core.component.shielded_pool.v1.QueryService/Ics20Quota" => {
+                    #[allow(non_camel_case_types)]
+                    struct Ics20QuotaSvc<T: QueryService>(pub Arc<T>);
+                    impl<
+                        T: QueryService,
+                    > tonic::server::UnaryService<super::Ics20QuotaRequest>
+                    for Ics20QuotaSvc<T> {
+                        type Response = super::Ics20QuotaResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Ics20QuotaRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as QueryService>::ics20_quota(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = Ics20QuotaSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -438,6 +438,87 @@ impl ::prost::Name for Undelegate {
         ::prost::alloc::format!("penumbra.core.component.stake.v1.{}", Self::NAME)
     }
 }
+/// A transaction action moving stake from one validator's delegation pool to
+/// another's, without waiting out the unbonding delay.
+///
+/// The action produces redelegation tokens, which remain liable for penalties
+/// applied to the source validator until they are claimed with a `RedelegateClaim`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Redelegate {
+    /// The identity key of the validator to redelegate from.
+    #[prost(message, optional, tag = "1")]
+    pub from_validator: ::core::option::Option<
+        super::super::super::keys::v1::IdentityKey,
+    >,
+    /// The identity key of the validator to redelegate to.
+    #[prost(message, optional, tag = "2")]
+    pub to_validator: ::core::option::Option<super::super::super::keys::v1::IdentityKey>,
+    /// The epoch in which this redelegation was performed.
+    #[prost(message, optional, tag = "3")]
+    pub from_epoch: ::core::option::Option<super::super::sct::v1::Epoch>,
+    /// The amount of delegation tokens of the source validator consumed by this action.
+    #[prost(message, optional, tag = "4")]
+    pub from_delegation_amount: ::core::option::Option<
+        super::super::super::num::v1::Amount,
+    >,
+    /// The amount of stake moved, in units of the staking token.
+    ///
+    /// This is implied by the source validator's exchange rate in the specified
+    /// epoch (and should be checked in transaction validation!).
+    #[prost(message, optional, tag = "5")]
+    pub unbonded_amount: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The amount of delegation tokens of the destination validator produced by this action.
+    ///
+    /// This is implied by the destination validator's exchange rate in the
+    /// specified epoch (and should be checked in transaction validation!).
+    #[prost(message, optional, tag = "6")]
+    pub to_delegation_amount: ::core::option::Option<
+        super::super::super::num::v1::Amount,
+    >,
+}
+impl ::prost::Name for Redelegate {
+    const NAME: &'static str = "Redelegate";
+    const PACKAGE: &'static str = "penumbra.core.component.stake.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.stake.v1.{}", Self::NAME)
+    }
+}
+/// A transaction action finishing a redelegation, converting (slashable)
+/// redelegation tokens into delegation tokens of the destination validator.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RedelegateClaim {
+    /// The identity key of the validator the stake was redelegated from.
+    #[prost(message, optional, tag = "1")]
+    pub from_validator: ::core::option::Option<
+        super::super::super::keys::v1::IdentityKey,
+    >,
+    /// The identity key of the validator the stake was redelegated to.
+    #[prost(message, optional, tag = "2")]
+    pub to_validator: ::core::option::Option<super::super::super::keys::v1::IdentityKey>,
+    /// The height of the epoch in which the redelegation was performed.
+    #[prost(uint64, tag = "3")]
+    pub redelegation_start_height: u64,
+    /// The penalty applied to the source validator over the unbonding window.
+    #[prost(message, optional, tag = "4")]
+    pub penalty: ::core::option::Option<Penalty>,
+    /// The amount of redelegation tokens consumed by this action.
+    #[prost(message, optional, tag = "5")]
+    pub redelegation_amount: ::core::option::Option<
+        super::super::super::num::v1::Amount,
+    >,
+    /// The amount of delegation tokens of the destination validator produced by this action.
+    #[prost(message, optional, tag = "6")]
+    pub delegation_amount: ::core::option::Option<super::super::super::num::v1::Amount>,
+}
+impl ::prost::Name for RedelegateClaim {
+    const NAME: &'static str = "RedelegateClaim";
+    const PACKAGE: &'static str = "penumbra.core.component.stake.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.stake.v1.{}", Self::NAME)
+    }
+}
 /// A transaction action finishing an undelegation, converting (slashable)
 /// "unbonding tokens" to (unslashable) staking tokens.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -535,6 +616,10 @@ pub struct DelegationChanges {
     pub delegations: ::prost::alloc::vec::Vec<Delegate>,
     #[prost(message, repeated, tag = "2")]
     pub undelegations: ::prost::alloc::vec::Vec<Undelegate>,
+    #[prost(message, repeated, tag = "3")]
+    pub redelegations: ::prost::alloc::vec::Vec<Redelegate>,
+    #[prost(message, repeated, tag = "4")]
+    pub redelegation_claims: ::prost::alloc::vec::Vec<RedelegateClaim>,
 }
 impl ::prost::Name for DelegationChanges {
     const NAME: &'static str = "DelegationChanges";
@@ -974,6 +1059,30 @@ impl ::prost::Name for EventUndelegate {
         ::prost::alloc::format!("penumbra.core.component.stake.v1.{}", Self::NAME)
     }
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventRedelegate {
+    /// The identity key of the validator redelegated from.
+    #[prost(message, optional, tag = "1")]
+    pub from_identity_key: ::core::option::Option<
+        super::super::super::keys::v1::IdentityKey,
+    >,
+    /// The identity key of the validator redelegated to.
+    #[prost(message, optional, tag = "2")]
+    pub to_identity_key: ::core::option::Option<
+        super::super::super::keys::v1::IdentityKey,
+    >,
+    /// The amount of stake redelegated, in the staking token.
+    #[prost(message, optional, tag = "3")]
+    pub amount: ::core::option::Option<super::super::super::num::v1::Amount>,
+}
+impl ::prost::Name for EventRedelegate {
+    const NAME: &'static str = "EventRedelegate";
+    const PACKAGE: &'static str = "penumbra.core.component.stake.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.stake.v1.{}", Self::NAME)
+    }
+}
 /// Indicates a slashing penalty was applied to a validator's reward rates.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -803,6 +803,12 @@ impl serde::Serialize for DelegationChanges {
         if !self.undelegations.is_empty() {
             len += 1;
         }
+        if !self.redelegations.is_empty() {
+            len += 1;
+        }
+        if !self.redelegation_claims.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.stake.v1.DelegationChanges", len)?;
         if !self.delegations.is_empty() {
             struct_ser.serialize_field("delegations", &self.delegations)?;
@@ -810,6 +816,12 @@ impl serde::Serialize for DelegationChanges {
         if !self.undelegations.is_empty() {
             struct_ser.serialize_field("undelegations", &self.undelegations)?;
         }
+        if !self.redelegations.is_empty() {
+            struct_ser.serialize_field("redelegations", &self.redelegations)?;
+        }
+        if !self.redelegation_claims.is_empty() {
+            struct_ser.serialize_field("redelegationClaims", &self.redelegation_claims)?;
+        }
         struct_ser.end()
     }
 }
@@ -822,12 +834,17 @@ impl<'de> serde::Deserialize<'de> for DelegationChanges {
         const FIELDS: &[&str] = &[
             "delegations",
             "undelegations",
+            "redelegations",
+            "redelegation_claims",
+            "redelegationClaims",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Delegations,
             Undelegations,
+            Redelegations,
+            RedelegationClaims,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -852,6 +869,8 @@ impl<'de> serde::Deserialize<'de> for DelegationChanges {
                         match value {
                             "delegations" => Ok(GeneratedField::Delegations),
                             "undelegations" => Ok(GeneratedField::Undelegations),
+                            "redelegations" => Ok(GeneratedField::Redelegations),
+                            "redelegationClaims" | "redelegation_claims" => Ok(GeneratedField::RedelegationClaims),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -873,6 +892,8 @@ impl<'de> serde::Deserialize<'de> for DelegationChanges {
             {
                 let mut delegations__ = None;
                 let mut undelegations__ = None;
+                let mut redelegations__ = None;
+                let mut redelegation_claims__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Delegations => {
@@ -887,6 +908,18 @@ impl<'de> serde::Deserialize<'de> for DelegationChanges {
                             }
                             undelegations__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Redelegations => {
+                            if redelegations__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegations"));
+                            }
+                            redelegations__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::RedelegationClaims => {
+                            if redelegation_claims__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegationClaims"));
+                            }
+                            redelegation_claims__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -895,6 +928,8 @@ impl<'de> serde::Deserialize<'de> for DelegationChanges {
                 Ok(DelegationChanges {
                     delegations: delegations__.unwrap_or_default(),
                     undelegations: undelegations__.unwrap_or_default(),
+                    redelegations: redelegations__.unwrap_or_default(),
+                    redelegation_claims: redelegation_claims__.unwrap_or_default(),
                 })
             }
         }
@@ -1128,6 +1163,137 @@ impl<'de> serde::Deserialize<'de> for EventRateDataChange {
         deserializer.deserialize_struct("penumbra.core.component.stake.v1.EventRateDataChange", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for EventRedelegate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.from_identity_key.is_some() {
+            len += 1;
+        }
+        if self.to_identity_key.is_some() {
+            len += 1;
+        }
+        if self.amount.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.stake.v1.EventRedelegate", len)?;
+        if let Some(v) = self.from_identity_key.as_ref() {
+            struct_ser.serialize_field("fromIdentityKey", v)?;
+        }
+        if let Some(v) = self.to_identity_key.as_ref() {
+            struct_ser.serialize_field("toIdentityKey", v)?;
+        }
+        if let Some(v) = self.amount.as_ref() {
+            struct_ser.serialize_field("amount", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EventRedelegate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "from_identity_key",
+            "fromIdentityKey",
+            "to_identity_key",
+            "toIdentityKey",
+            "amount",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FromIdentityKey,
+            ToIdentityKey,
+            Amount,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "fromIdentityKey" | "from_identity_key" => Ok(GeneratedField::FromIdentityKey),
+                            "toIdentityKey" | "to_identity_key" => Ok(GeneratedField::ToIdentityKey),
+                            "amount" => Ok(GeneratedField::Amount),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EventRedelegate;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.stake.v1.EventRedelegate")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<EventRedelegate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut from_identity_key__ = None;
+                let mut to_identity_key__ = None;
+                let mut amount__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::FromIdentityKey => {
+                            if from_identity_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fromIdentityKey"));
+                            }
+                            from_identity_key__ = map_.next_value()?;
+                        }
+                        GeneratedField::ToIdentityKey => {
+                            if to_identity_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("toIdentityKey"));
+                            }
+                            to_identity_key__ = map_.next_value()?;
+                        }
+                        GeneratedField::Amount => {
+                            if amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("amount"));
+                            }
+                            amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(EventRedelegate {
+                    from_identity_key: from_identity_key__,
+                    to_identity_key: to_identity_key__,
+                    amount: amount__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.stake.v1.EventRedelegate", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for EventSlashingPenaltyApplied {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -2971,6 +3137,380 @@ impl<'de> serde::Deserialize<'de> for RateData {
         deserializer.deserialize_struct("penumbra.core.component.stake.v1.RateData", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for Redelegate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.from_validator.is_some() {
+            len += 1;
+        }
+        if self.to_validator.is_some() {
+            len += 1;
+        }
+        if self.from_epoch.is_some() {
+            len += 1;
+        }
+        if self.from_delegation_amount.is_some() {
+            len += 1;
+        }
+        if self.unbonded_amount.is_some() {
+            len += 1;
+        }
+        if self.to_delegation_amount.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.stake.v1.Redelegate", len)?;
+        if let Some(v) = self.from_validator.as_ref() {
+            struct_ser.serialize_field("fromValidator", v)?;
+        }
+        if let Some(v) = self.to_validator.as_ref() {
+            struct_ser.serialize_field("toValidator", v)?;
+        }
+        if let Some(v) = self.from_epoch.as_ref() {
+            struct_ser.serialize_field("fromEpoch", v)?;
+        }
+        if let Some(v) = self.from_delegation_amount.as_ref() {
+            struct_ser.serialize_field("fromDelegationAmount", v)?;
+        }
+        if let Some(v) = self.unbonded_amount.as_ref() {
+            struct_ser.serialize_field("unbondedAmount", v)?;
+        }
+        if let Some(v) = self.to_delegation_amount.as_ref() {
+            struct_ser.serialize_field("toDelegationAmount", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Redelegate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "from_validator",
+            "fromValidator",
+            "to_validator",
+            "toValidator",
+            "from_epoch",
+            "fromEpoch",
+            "from_delegation_amount",
+            "fromDelegationAmount",
+            "unbonded_amount",
+            "unbondedAmount",
+            "to_delegation_amount",
+            "toDelegationAmount",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FromValidator,
+            ToValidator,
+            FromEpoch,
+            FromDelegationAmount,
+            UnbondedAmount,
+            ToDelegationAmount,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "fromValidator" | "from_validator" => Ok(GeneratedField::FromValidator),
+                            "toValidator" | "to_validator" => Ok(GeneratedField::ToValidator),
+                            "fromEpoch" | "from_epoch" => Ok(GeneratedField::FromEpoch),
+                            "fromDelegationAmount" | "from_delegation_amount" => Ok(GeneratedField::FromDelegationAmount),
+                            "unbondedAmount" | "unbonded_amount" => Ok(GeneratedField::UnbondedAmount),
+                            "toDelegationAmount" | "to_delegation_amount" => Ok(GeneratedField::ToDelegationAmount),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Redelegate;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.stake.v1.Redelegate")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<Redelegate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut from_validator__ = None;
+                let mut to_validator__ = None;
+                let mut from_epoch__ = None;
+                let mut from_delegation_amount__ = None;
+                let mut unbonded_amount__ = None;
+                let mut to_delegation_amount__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::FromValidator => {
+                            if from_validator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fromValidator"));
+                            }
+                            from_validator__ = map_.next_value()?;
+                        }
+                        GeneratedField::ToValidator => {
+                            if to_validator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("toValidator"));
+                            }
+                            to_validator__ = map_.next_value()?;
+                        }
+                        GeneratedField::FromEpoch => {
+                            if from_epoch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fromEpoch"));
+                            }
+                            from_epoch__ = map_.next_value()?;
+                        }
+                        GeneratedField::FromDelegationAmount => {
+                            if from_delegation_amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fromDelegationAmount"));
+                            }
+                            from_delegation_amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::UnbondedAmount => {
+                            if unbonded_amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("unbondedAmount"));
+                            }
+                            unbonded_amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::ToDelegationAmount => {
+                            if to_delegation_amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("toDelegationAmount"));
+                            }
+                            to_delegation_amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(Redelegate {
+                    from_validator: from_validator__,
+                    to_validator: to_validator__,
+                    from_epoch: from_epoch__,
+                    from_delegation_amount: from_delegation_amount__,
+                    unbonded_amount: unbonded_amount__,
+                    to_delegation_amount: to_delegation_amount__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.stake.v1.Redelegate", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RedelegateClaim {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.from_validator.is_some() {
+            len += 1;
+        }
+        if self.to_validator.is_some() {
+            len += 1;
+        }
+        if self.redelegation_start_height != 0 {
+            len += 1;
+        }
+        if self.penalty.is_some() {
+            len += 1;
+        }
+        if self.redelegation_amount.is_some() {
+            len += 1;
+        }
+        if self.delegation_amount.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.stake.v1.RedelegateClaim", len)?;
+        if let Some(v) = self.from_validator.as_ref() {
+            struct_ser.serialize_field("fromValidator", v)?;
+        }
+        if let Some(v) = self.to_validator.as_ref() {
+            struct_ser.serialize_field("toValidator", v)?;
+        }
+        if self.redelegation_start_height != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("redelegationStartHeight", ToString::to_string(&self.redelegation_start_height).as_str())?;
+        }
+        if let Some(v) = self.penalty.as_ref() {
+            struct_ser.serialize_field("penalty", v)?;
+        }
+        if let Some(v) = self.redelegation_amount.as_ref() {
+            struct_ser.serialize_field("redelegationAmount", v)?;
+        }
+        if let Some(v) = self.delegation_amount.as_ref() {
+            struct_ser.serialize_field("delegationAmount", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RedelegateClaim {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "from_validator",
+            "fromValidator",
+            "to_validator",
+            "toValidator",
+            "redelegation_start_height",
+            "redelegationStartHeight",
+            "penalty",
+            "redelegation_amount",
+            "redelegationAmount",
+            "delegation_amount",
+            "delegationAmount",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FromValidator,
+            ToValidator,
+            RedelegationStartHeight,
+            Penalty,
+            RedelegationAmount,
+            DelegationAmount,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "fromValidator" | "from_validator" => Ok(GeneratedField::FromValidator),
+                            "toValidator" | "to_validator" => Ok(GeneratedField::ToValidator),
+                            "redelegationStartHeight" | "redelegation_start_height" => Ok(GeneratedField::RedelegationStartHeight),
+                            "penalty" => Ok(GeneratedField::Penalty),
+                            "redelegationAmount" | "redelegation_amount" => Ok(GeneratedField::RedelegationAmount),
+                            "delegationAmount" | "delegation_amount" => Ok(GeneratedField::DelegationAmount),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RedelegateClaim;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.stake.v1.RedelegateClaim")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<RedelegateClaim, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut from_validator__ = None;
+                let mut to_validator__ = None;
+                let mut redelegation_start_height__ = None;
+                let mut penalty__ = None;
+                let mut redelegation_amount__ = None;
+                let mut delegation_amount__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::FromValidator => {
+                            if from_validator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fromValidator"));
+                            }
+                            from_validator__ = map_.next_value()?;
+                        }
+                        GeneratedField::ToValidator => {
+                            if to_validator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("toValidator"));
+                            }
+                            to_validator__ = map_.next_value()?;
+                        }
+                        GeneratedField::RedelegationStartHeight => {
+                            if redelegation_start_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegationStartHeight"));
+                            }
+                            redelegation_start_height__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Penalty => {
+                            if penalty__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("penalty"));
+                            }
+                            penalty__ = map_.next_value()?;
+                        }
+                        GeneratedField::RedelegationAmount => {
+                            if redelegation_amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegationAmount"));
+                            }
+                            redelegation_amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::DelegationAmount => {
+                            if delegation_amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("delegationAmount"));
+                            }
+                            delegation_amount__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(RedelegateClaim {
+                    from_validator: from_validator__,
+                    to_validator: to_validator__,
+                    redelegation_start_height: redelegation_start_height__.unwrap_or_default(),
+                    penalty: penalty__,
+                    redelegation_amount: redelegation_amount__,
+                    delegation_amount: delegation_amount__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.stake.v1.RedelegateClaim", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for StakeParameters {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -94,7 +94,7 @@ impl ::prost::Name for DetectionData {
 pub struct Action {
     #[prost(
         oneof = "action::Action",
-        tags = "1, 2, 3, 4, 16, 17, 18, 19, 20, 21, 22, 30, 31, 32, 34, 40, 41, 42, 50, 51, 52, 53, 54, 55, 200"
+        tags = "1, 2, 3, 4, 16, 17, 18, 19, 20, 21, 22, 30, 31, 32, 34, 40, 41, 42, 43, 50, 51, 52, 53, 54, 55, 200"
     )]
     pub action: ::core::option::Option<action::Action>,
 }
@@ -151,6 +151,10 @@ pub mod action {
         Undelegate(super::super::super::component::stake::v1::Undelegate),
         #[prost(message, tag = "42")]
         UndelegateClaim(super::super::super::component::stake::v1::UndelegateClaim),
+        #[prost(message, tag = "43")]
+        Redelegate(super::super::super::component::stake::v1::Redelegate),
+        #[prost(message, tag = "44")]
+        RedelegateClaim(super::super::super::component::stake::v1::RedelegateClaim),
         /// Community Pool
         #[prost(message, tag = "50")]
         CommunityPoolSpend(
@@ -400,7 +404,7 @@ impl ::prost::Name for TransactionBodyView {
 pub struct ActionView {
     #[prost(
         oneof = "action_view::ActionView",
-        tags = "1, 2, 3, 4, 21, 16, 17, 18, 19, 20, 22, 30, 31, 32, 34, 41, 42, 50, 51, 52, 53, 54, 55, 43, 200"
+        tags = "1, 2, 3, 4, 21, 16, 17, 18, 19, 20, 22, 30, 31, 32, 34, 41, 42, 44, 50, 51, 52, 53, 54, 55, 43, 200"
     )]
     pub action_view: ::core::option::Option<action_view::ActionView>,
 }
@@ -454,6 +458,10 @@ pub mod action_view {
         Delegate(super::super::super::component::stake::v1::Delegate),
         #[prost(message, tag = "42")]
         Undelegate(super::super::super::component::stake::v1::Undelegate),
+        #[prost(message, tag = "44")]
+        Redelegate(super::super::super::component::stake::v1::Redelegate),
+        #[prost(message, tag = "45")]
+        RedelegateClaim(super::super::super::component::stake::v1::RedelegateClaim),
         /// Community Pool
         #[prost(message, tag = "50")]
         CommunityPoolSpend(
@@ -594,7 +602,7 @@ impl ::prost::Name for DetectionDataPlan {
 pub struct ActionPlan {
     #[prost(
         oneof = "action_plan::Action",
-        tags = "1, 2, 3, 4, 16, 17, 18, 19, 20, 21, 22, 200, 30, 31, 32, 34, 40, 41, 42, 50, 51, 52, 53, 54, 55"
+        tags = "1, 2, 3, 4, 16, 17, 18, 19, 20, 21, 22, 200, 30, 31, 32, 34, 40, 41, 42, 43, 50, 51, 52, 53, 54, 55"
     )]
     pub action: ::core::option::Option<action_plan::Action>,
 }
@@ -657,6 +665,12 @@ pub mod action_plan {
         Undelegate(super::super::super::component::stake::v1::Undelegate),
         #[prost(message, tag = "42")]
         UndelegateClaim(super::super::super::component::stake::v1::UndelegateClaimPlan),
+        /// Redelegations don't need any extra information either.
+        #[prost(message, tag = "43")]
+        Redelegate(super::super::super::component::stake::v1::Redelegate),
+        /// Redelegation claims don't need any extra information either.
+        #[prost(message, tag = "44")]
+        RedelegateClaim(super::super::super::component::stake::v1::RedelegateClaim),
         /// Community Pool
         #[prost(message, tag = "50")]
         CommunityPoolSpend(
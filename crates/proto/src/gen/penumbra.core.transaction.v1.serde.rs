@@ -66,6 +66,12 @@ impl serde::Serialize for Action {
                 action::Action::UndelegateClaim(v) => {
                     struct_ser.serialize_field("undelegateClaim", v)?;
                 }
+                action::Action::Redelegate(v) => {
+                    struct_ser.serialize_field("redelegate", v)?;
+                }
+                action::Action::RedelegateClaim(v) => {
+                    struct_ser.serialize_field("redelegateClaim", v)?;
+                }
                 action::Action::CommunityPoolSpend(v) => {
                     struct_ser.serialize_field("communityPoolSpend", v)?;
                 }
@@ -130,6 +136,9 @@ impl<'de> serde::Deserialize<'de> for Action {
             "undelegate",
             "undelegate_claim",
             "undelegateClaim",
+            "redelegate",
+            "redelegate_claim",
+            "redelegateClaim",
             "community_pool_spend",
             "communityPoolSpend",
             "community_pool_output",
@@ -166,6 +175,8 @@ impl<'de> serde::Deserialize<'de> for Action {
             Delegate,
             Undelegate,
             UndelegateClaim,
+            Redelegate,
+            RedelegateClaim,
             CommunityPoolSpend,
             CommunityPoolOutput,
             CommunityPoolDeposit,
@@ -213,6 +224,8 @@ impl<'de> serde::Deserialize<'de> for Action {
                             "delegate" => Ok(GeneratedField::Delegate),
                             "undelegate" => Ok(GeneratedField::Undelegate),
                             "undelegateClaim" | "undelegate_claim" => Ok(GeneratedField::UndelegateClaim),
+                            "redelegate" => Ok(GeneratedField::Redelegate),
+                            "redelegateClaim" | "redelegate_claim" => Ok(GeneratedField::RedelegateClaim),
                             "communityPoolSpend" | "community_pool_spend" => Ok(GeneratedField::CommunityPoolSpend),
                             "communityPoolOutput" | "community_pool_output" => Ok(GeneratedField::CommunityPoolOutput),
                             "communityPoolDeposit" | "community_pool_deposit" => Ok(GeneratedField::CommunityPoolDeposit),
@@ -366,6 +379,20 @@ impl<'de> serde::Deserialize<'de> for Action {
                                 return Err(serde::de::Error::duplicate_field("undelegateClaim"));
                             }
                             action__ = map_.next_value::<::std::option::Option<_>>()?.map(action::Action::UndelegateClaim)
+;
+                        }
+                        GeneratedField::Redelegate => {
+                            if action__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegate"));
+                            }
+                            action__ = map_.next_value::<::std::option::Option<_>>()?.map(action::Action::Redelegate)
+;
+                        }
+                        GeneratedField::RedelegateClaim => {
+                            if action__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegateClaim"));
+                            }
+                            action__ = map_.next_value::<::std::option::Option<_>>()?.map(action::Action::RedelegateClaim)
 ;
                         }
                         GeneratedField::CommunityPoolSpend => {
@@ -501,6 +528,12 @@ impl serde::Serialize for ActionPlan {
                 action_plan::Action::UndelegateClaim(v) => {
                     struct_ser.serialize_field("undelegateClaim", v)?;
                 }
+                action_plan::Action::Redelegate(v) => {
+                    struct_ser.serialize_field("redelegate", v)?;
+                }
+                action_plan::Action::RedelegateClaim(v) => {
+                    struct_ser.serialize_field("redelegateClaim", v)?;
+                }
                 action_plan::Action::CommunityPoolSpend(v) => {
                     struct_ser.serialize_field("communityPoolSpend", v)?;
                 }
@@ -564,6 +597,9 @@ impl<'de> serde::Deserialize<'de> for ActionPlan {
             "undelegate",
             "undelegate_claim",
             "undelegateClaim",
+            "redelegate",
+            "redelegate_claim",
+            "redelegateClaim",
             "community_pool_spend",
             "communityPoolSpend",
             "community_pool_output",
@@ -599,6 +635,8 @@ impl<'de> serde::Deserialize<'de> for ActionPlan {
             Delegate,
             Undelegate,
             UndelegateClaim,
+            Redelegate,
+            RedelegateClaim,
             CommunityPoolSpend,
             CommunityPoolOutput,
             CommunityPoolDeposit,
@@ -646,6 +684,8 @@ impl<'de> serde::Deserialize<'de> for ActionPlan {
                             "delegate" => Ok(GeneratedField::Delegate),
                             "undelegate" => Ok(GeneratedField::Undelegate),
                             "undelegateClaim" | "undelegate_claim" => Ok(GeneratedField::UndelegateClaim),
+                            "redelegate" => Ok(GeneratedField::Redelegate),
+                            "redelegateClaim" | "redelegate_claim" => Ok(GeneratedField::RedelegateClaim),
                             "communityPoolSpend" | "community_pool_spend" => Ok(GeneratedField::CommunityPoolSpend),
                             "communityPoolOutput" | "community_pool_output" => Ok(GeneratedField::CommunityPoolOutput),
                             "communityPoolDeposit" | "community_pool_deposit" => Ok(GeneratedField::CommunityPoolDeposit),
@@ -805,6 +845,20 @@ impl<'de> serde::Deserialize<'de> for ActionPlan {
                                 return Err(serde::de::Error::duplicate_field("undelegateClaim"));
                             }
                             action__ = map_.next_value::<::std::option::Option<_>>()?.map(action_plan::Action::UndelegateClaim)
+;
+                        }
+                        GeneratedField::Redelegate => {
+                            if action__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegate"));
+                            }
+                            action__ = map_.next_value::<::std::option::Option<_>>()?.map(action_plan::Action::Redelegate)
+;
+                        }
+                        GeneratedField::RedelegateClaim => {
+                            if action__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegateClaim"));
+                            }
+                            action__ = map_.next_value::<::std::option::Option<_>>()?.map(action_plan::Action::RedelegateClaim)
 ;
                         }
                         GeneratedField::CommunityPoolSpend => {
@@ -927,6 +981,12 @@ impl serde::Serialize for ActionView {
                 action_view::ActionView::Undelegate(v) => {
                     struct_ser.serialize_field("undelegate", v)?;
                 }
+                action_view::ActionView::Redelegate(v) => {
+                    struct_ser.serialize_field("redelegate", v)?;
+                }
+                action_view::ActionView::RedelegateClaim(v) => {
+                    struct_ser.serialize_field("redelegateClaim", v)?;
+                }
                 action_view::ActionView::CommunityPoolSpend(v) => {
                     struct_ser.serialize_field("communityPoolSpend", v)?;
                 }
@@ -992,6 +1052,9 @@ impl<'de> serde::Deserialize<'de> for ActionView {
             "positionRewardClaim",
             "delegate",
             "undelegate",
+            "redelegate",
+            "redelegate_claim",
+            "redelegateClaim",
             "community_pool_spend",
             "communityPoolSpend",
             "community_pool_output",
@@ -1029,6 +1092,8 @@ impl<'de> serde::Deserialize<'de> for ActionView {
             PositionRewardClaim,
             Delegate,
             Undelegate,
+            Redelegate,
+            RedelegateClaim,
             CommunityPoolSpend,
             CommunityPoolOutput,
             CommunityPoolDeposit,
@@ -1076,6 +1141,8 @@ impl<'de> serde::Deserialize<'de> for ActionView {
                             "positionRewardClaim" | "position_reward_claim" => Ok(GeneratedField::PositionRewardClaim),
                             "delegate" => Ok(GeneratedField::Delegate),
                             "undelegate" => Ok(GeneratedField::Undelegate),
+                            "redelegate" => Ok(GeneratedField::Redelegate),
+                            "redelegateClaim" | "redelegate_claim" => Ok(GeneratedField::RedelegateClaim),
                             "communityPoolSpend" | "community_pool_spend" => Ok(GeneratedField::CommunityPoolSpend),
                             "communityPoolOutput" | "community_pool_output" => Ok(GeneratedField::CommunityPoolOutput),
                             "communityPoolDeposit" | "community_pool_deposit" => Ok(GeneratedField::CommunityPoolDeposit),
@@ -1223,6 +1290,20 @@ impl<'de> serde::Deserialize<'de> for ActionView {
                                 return Err(serde::de::Error::duplicate_field("undelegate"));
                             }
                             action_view__ = map_.next_value::<::std::option::Option<_>>()?.map(action_view::ActionView::Undelegate)
+;
+                        }
+                        GeneratedField::Redelegate => {
+                            if action_view__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegate"));
+                            }
+                            action_view__ = map_.next_value::<::std::option::Option<_>>()?.map(action_view::ActionView::Redelegate)
+;
+                        }
+                        GeneratedField::RedelegateClaim => {
+                            if action_view__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("redelegateClaim"));
+                            }
+                            action_view__ = map_.next_value::<::std::option::Option<_>>()?.map(action_view::ActionView::RedelegateClaim)
 ;
                         }
                         GeneratedField::CommunityPoolSpend => {
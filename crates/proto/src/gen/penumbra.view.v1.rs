@@ -77,6 +77,14 @@ pub struct AuthorizeAndBuildRequest {
     pub transaction_plan: ::core::option::Option<
         super::super::core::transaction::v1::TransactionPlan,
     >,
+    /// Optionally, pre-authorization data, if required by the custodian.
+    ///
+    /// These are passed along with the transaction plan in the view service's
+    /// authorization request to its custody service.
+    #[prost(message, repeated, tag = "2")]
+    pub pre_authorizations: ::prost::alloc::vec::Vec<
+        super::super::custody::v1::PreAuthorization,
+    >,
 }
 impl ::prost::Name for AuthorizeAndBuildRequest {
     const NAME: &'static str = "AuthorizeAndBuildRequest";
@@ -1270,6 +1278,29 @@ impl ::prost::Name for UnclaimedSwapsResponse {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapClaimsRequest {}
+impl ::prost::Name for SwapClaimsRequest {
+    const NAME: &'static str = "SwapClaimsRequest";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapClaimsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub claim: ::core::option::Option<SwapClaimRecord>,
+}
+impl ::prost::Name for SwapClaimsResponse {
+    const NAME: &'static str = "SwapClaimsResponse";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NullifierStatusRequest {
     #[prost(message, optional, tag = "2")]
     pub nullifier: ::core::option::Option<
@@ -1501,6 +1532,40 @@ impl ::prost::Name for SwapRecord {
         ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
     }
 }
+/// A claim of a swap submitted automatically by the view service.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapClaimRecord {
+    /// The commitment to the claimed swap.
+    #[prost(message, optional, tag = "1")]
+    pub swap_commitment: ::core::option::Option<
+        super::super::crypto::tct::v1::StateCommitment,
+    >,
+    /// The sync height at which the latest claim was submitted.
+    #[prost(uint64, tag = "2")]
+    pub height_submitted: u64,
+    /// The ID of the latest claim transaction, if it was broadcast.
+    #[prost(message, optional, tag = "3")]
+    pub transaction_id: ::core::option::Option<
+        super::super::core::txhash::v1::TransactionId,
+    >,
+    /// If the latest claim could not be authorized, built or broadcast, the reason why.
+    #[prost(string, tag = "4")]
+    pub error: ::prost::alloc::string::String,
+    /// The height at which the swap was claimed, or 0 if it has not been claimed yet.
+    #[prost(uint64, tag = "5")]
+    pub height_claimed: u64,
+    /// The number of claims submitted for the swap, including retries.
+    #[prost(uint32, tag = "6")]
+    pub attempts: u32,
+}
+impl ::prost::Name for SwapClaimRecord {
+    const NAME: &'static str = "SwapClaimRecord";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OwnedPositionIdsRequest {
@@ -2688,6 +2753,36 @@ pub mod view_service_client {
                 .insert(GrpcMethod::new("penumbra.view.v1.ViewService", "Auctions"));
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Query for the swap claims submitted automatically by the view service.
+        ///
+        /// View services configured to claim swaps automatically submit a claim for
+        /// each unclaimed swap once its batch swap output data is detected. This
+        /// reports the outcome of each of those claims.
+        pub async fn swap_claims(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SwapClaimsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SwapClaimsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.view.v1.ViewService/SwapClaims",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("penumbra.view.v1.ViewService", "SwapClaims"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -3047,6 +3142,21 @@ pub mod view_service_server {
             &self,
             request: tonic::Request<super::AuctionsRequest>,
         ) -> std::result::Result<tonic::Response<Self::AuctionsStream>, tonic::Status>;
+        /// Server streaming response type for the SwapClaims method.
+        type SwapClaimsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SwapClaimsResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Query for the swap claims submitted automatically by the view service.
+        ///
+        /// View services configured to claim swaps automatically submit a claim for
+        /// each unclaimed swap once its batch swap output data is detected. This
+        /// reports the outcome of each of those claims.
+        async fn swap_claims(
+            &self,
+            request: tonic::Request<super::SwapClaimsRequest>,
+        ) -> std::result::Result<tonic::Response<Self::SwapClaimsStream>, tonic::Status>;
     }
     /// The view RPC is used by a view client, who wants to do some
     /// transaction-related actions, to request data from a view service, which is
@@ -4509,6 +4619,53 @@ pub mod view_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/penumbra.view.v1.ViewService/SwapClaims" => {
+                    #[allow(non_camel_case_types)]
+                    struct SwapClaimsSvc<T: ViewService>(pub Arc<T>);
+                    impl<
+                        T: ViewService,
+                    > tonic::server::ServerStreamingService<super::SwapClaimsRequest>
+                    for SwapClaimsSvc<T> {
+                        type Response = super::SwapClaimsResponse;
+                        type ResponseStream = T::SwapClaimsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SwapClaimsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ViewService>::swap_claims(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SwapClaimsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
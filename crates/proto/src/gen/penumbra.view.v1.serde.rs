@@ -1176,10 +1176,16 @@ impl serde::Serialize for AuthorizeAndBuildRequest {
         if self.transaction_plan.is_some() {
             len += 1;
         }
+        if !self.pre_authorizations.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.view.v1.AuthorizeAndBuildRequest", len)?;
         if let Some(v) = self.transaction_plan.as_ref() {
             struct_ser.serialize_field("transactionPlan", v)?;
         }
+        if !self.pre_authorizations.is_empty() {
+            struct_ser.serialize_field("preAuthorizations", &self.pre_authorizations)?;
+        }
         struct_ser.end()
     }
 }
@@ -1192,11 +1198,14 @@ impl<'de> serde::Deserialize<'de> for AuthorizeAndBuildRequest {
         const FIELDS: &[&str] = &[
             "transaction_plan",
             "transactionPlan",
+            "pre_authorizations",
+            "preAuthorizations",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             TransactionPlan,
+            PreAuthorizations,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -1220,6 +1229,7 @@ impl<'de> serde::Deserialize<'de> for AuthorizeAndBuildRequest {
                     {
                         match value {
                             "transactionPlan" | "transaction_plan" => Ok(GeneratedField::TransactionPlan),
+                            "preAuthorizations" | "pre_authorizations" => Ok(GeneratedField::PreAuthorizations),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -1240,6 +1250,7 @@ impl<'de> serde::Deserialize<'de> for AuthorizeAndBuildRequest {
                     V: serde::de::MapAccess<'de>,
             {
                 let mut transaction_plan__ = None;
+                let mut pre_authorizations__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::TransactionPlan => {
@@ -1248,6 +1259,12 @@ impl<'de> serde::Deserialize<'de> for AuthorizeAndBuildRequest {
                             }
                             transaction_plan__ = map_.next_value()?;
                         }
+                        GeneratedField::PreAuthorizations => {
+                            if pre_authorizations__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("preAuthorizations"));
+                            }
+                            pre_authorizations__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -1255,6 +1272,7 @@ impl<'de> serde::Deserialize<'de> for AuthorizeAndBuildRequest {
                 }
                 Ok(AuthorizeAndBuildRequest {
                     transaction_plan: transaction_plan__,
+                    pre_authorizations: pre_authorizations__.unwrap_or_default(),
                 })
             }
         }
@@ -5269,6 +5287,365 @@ impl<'de> serde::Deserialize<'de> for SwapByCommitmentResponse {
         deserializer.deserialize_struct("penumbra.view.v1.SwapByCommitmentResponse", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for SwapClaimRecord {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.swap_commitment.is_some() {
+            len += 1;
+        }
+        if self.height_submitted != 0 {
+            len += 1;
+        }
+        if self.transaction_id.is_some() {
+            len += 1;
+        }
+        if !self.error.is_empty() {
+            len += 1;
+        }
+        if self.height_claimed != 0 {
+            len += 1;
+        }
+        if self.attempts != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.view.v1.SwapClaimRecord", len)?;
+        if let Some(v) = self.swap_commitment.as_ref() {
+            struct_ser.serialize_field("swapCommitment", v)?;
+        }
+        if self.height_submitted != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("heightSubmitted", ToString::to_string(&self.height_submitted).as_str())?;
+        }
+        if let Some(v) = self.transaction_id.as_ref() {
+            struct_ser.serialize_field("transactionId", v)?;
+        }
+        if !self.error.is_empty() {
+            struct_ser.serialize_field("error", &self.error)?;
+        }
+        if self.height_claimed != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("heightClaimed", ToString::to_string(&self.height_claimed).as_str())?;
+        }
+        if self.attempts != 0 {
+            struct_ser.serialize_field("attempts", &self.attempts)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SwapClaimRecord {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "swap_commitment",
+            "swapCommitment",
+            "height_submitted",
+            "heightSubmitted",
+            "transaction_id",
+            "transactionId",
+            "error",
+            "height_claimed",
+            "heightClaimed",
+            "attempts",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            SwapCommitment,
+            HeightSubmitted,
+            TransactionId,
+            Error,
+            HeightClaimed,
+            Attempts,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "swapCommitment" | "swap_commitment" => Ok(GeneratedField::SwapCommitment),
+                            "heightSubmitted" | "height_submitted" => Ok(GeneratedField::HeightSubmitted),
+                            "transactionId" | "transaction_id" => Ok(GeneratedField::TransactionId),
+                            "error" => Ok(GeneratedField::Error),
+                            "heightClaimed" | "height_claimed" => Ok(GeneratedField::HeightClaimed),
+                            "attempts" => Ok(GeneratedField::Attempts),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SwapClaimRecord;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.view.v1.SwapClaimRecord")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SwapClaimRecord, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut swap_commitment__ = None;
+                let mut height_submitted__ = None;
+                let mut transaction_id__ = None;
+                let mut error__ = None;
+                let mut height_claimed__ = None;
+                let mut attempts__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::SwapCommitment => {
+                            if swap_commitment__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("swapCommitment"));
+                            }
+                            swap_commitment__ = map_.next_value()?;
+                        }
+                        GeneratedField::HeightSubmitted => {
+                            if height_submitted__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("heightSubmitted"));
+                            }
+                            height_submitted__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::TransactionId => {
+                            if transaction_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionId"));
+                            }
+                            transaction_id__ = map_.next_value()?;
+                        }
+                        GeneratedField::Error => {
+                            if error__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("error"));
+                            }
+                            error__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::HeightClaimed => {
+                            if height_claimed__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("heightClaimed"));
+                            }
+                            height_claimed__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Attempts => {
+                            if attempts__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempts"));
+                            }
+                            attempts__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(SwapClaimRecord {
+                    swap_commitment: swap_commitment__,
+                    height_submitted: height_submitted__.unwrap_or_default(),
+                    transaction_id: transaction_id__,
+                    error: error__.unwrap_or_default(),
+                    height_claimed: height_claimed__.unwrap_or_default(),
+                    attempts: attempts__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.view.v1.SwapClaimRecord", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SwapClaimsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser = serializer.serialize_struct("penumbra.view.v1.SwapClaimsRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SwapClaimsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                            Ok(GeneratedField::__SkipField__)
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SwapClaimsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.view.v1.SwapClaimsRequest")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SwapClaimsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                while map_.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(SwapClaimsRequest {
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.view.v1.SwapClaimsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SwapClaimsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.claim.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.view.v1.SwapClaimsResponse", len)?;
+        if let Some(v) = self.claim.as_ref() {
+            struct_ser.serialize_field("claim", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SwapClaimsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "claim",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Claim,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "claim" => Ok(GeneratedField::Claim),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SwapClaimsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.view.v1.SwapClaimsResponse")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SwapClaimsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut claim__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Claim => {
+                            if claim__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("claim"));
+                            }
+                            claim__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(SwapClaimsResponse {
+                    claim: claim__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.view.v1.SwapClaimsResponse", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for SwapRecord {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
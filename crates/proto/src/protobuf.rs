@@ -157,11 +157,12 @@ impl TryFrom<crate::core::keys::v1::ConsensusKey> for tendermint::PublicKey {
 extern crate ibc_types;
 
 use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
 use ibc_proto::ibc::core::client::v1::Height as RawHeight;
 use ibc_proto::ibc::core::connection::v1::ClientPaths as RawClientPaths;
 use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
 
-use ibc_types::core::channel::ChannelEnd;
+use ibc_types::core::channel::{ChannelEnd, Packet};
 use ibc_types::core::client::Height;
 use ibc_types::core::connection::{ClientPaths, ConnectionEnd};
 use ibc_types::lightclients::tendermint::client_state::ClientState;
@@ -178,6 +179,9 @@ impl DomainType for ConnectionEnd {
 impl DomainType for ChannelEnd {
     type Proto = RawChannel;
 }
+impl DomainType for Packet {
+    type Proto = RawPacket;
+}
 impl DomainType for Height {
     type Proto = RawHeight;
 }
@@ -7,8 +7,10 @@ use {
     tap::{Tap, TapFallible},
     tendermint::{
         abci::types::CommitInfo,
+        account,
         block::Header,
         v0_37::abci::{request, response, ConsensusRequest, ConsensusResponse},
+        Hash,
     },
     tower::{BoxError, Service},
     tracing::{error, instrument, trace},
@@ -36,6 +38,47 @@ where
             .tap_ok(|_| trace!("consensus service is now ready"))
     }
 
+    /// Sends a [`ConsensusRequest::PrepareProposal`] request to the ABCI application.
+    ///
+    /// The proposal is prepared for the next block, with the given candidate transactions
+    /// and payload size limit. This does not execute a block, use [`TestNode::block()`] to
+    /// execute the transactions returned by the application.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn prepare_proposal(
+        &mut self,
+        txs: Vec<Bytes>,
+        max_tx_bytes: i64,
+    ) -> Result<response::PrepareProposal, anyhow::Error> {
+        let request = ConsensusRequest::PrepareProposal(request::PrepareProposal {
+            max_tx_bytes,
+            txs,
+            local_last_commit: None,
+            misbehavior: Default::default(),
+            height: self.height.increment(),
+            time: tendermint::Time::now(),
+            next_validators_hash: Hash::None,
+            proposer_address: account::Id::new([0; 20]),
+        });
+        let service = self.service().await?;
+        match service
+            .tap(|_| trace!("sending PrepareProposal request"))
+            .call(request)
+            .await
+            .tap_err(|error| error!(?error, "consensus service returned error"))
+            .map_err(|_| anyhow!("consensus service returned error"))?
+        {
+            ConsensusResponse::PrepareProposal(response) => {
+                let response::PrepareProposal { txs } = &response;
+                trace!(txs = %txs.len(), "received PrepareProposal response");
+                Ok(response)
+            }
+            response => {
+                error!(?response, "unexpected PrepareProposal response");
+                Err(anyhow!("unexpected PrepareProposal response"))
+            }
+        }
+    }
+
     /// Sends a [`ConsensusRequest::BeginBlock`] request to the ABCI application.
     #[instrument(level = "debug", skip_all)]
     pub async fn begin_block(
@@ -12,6 +12,10 @@ struct FmdCluesExample {}
 
 #[async_trait]
 impl AppView for FmdCluesExample {
+    fn name(&self) -> String {
+        "fmd_clues_example".to_string()
+    }
+
     async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
         sqlx::query(
             "
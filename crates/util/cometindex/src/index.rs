@@ -8,8 +8,26 @@ pub type PgTransaction<'a> = Transaction<'a, Postgres>;
 /// Represents a specific index of raw event data.
 #[async_trait]
 pub trait AppView: std::fmt::Debug {
+    /// A unique, stable name for this view.
+    ///
+    /// The indexer tracks how far each view has progressed under this name,
+    /// so changing it causes the view to be rebuilt from scratch.
+    fn name(&self) -> String;
+
     async fn init_chain(&self, dbtx: &mut PgTransaction) -> Result<(), anyhow::Error>;
 
+    /// Removes all data written by this view, so that it can be rebuilt from the first event.
+    ///
+    /// This is called before [`AppView::init_chain`] when the view is reindexed, so it should
+    /// tolerate the view's tables not existing yet. Views that do not support reindexing can
+    /// keep the default implementation, which refuses.
+    async fn reset(&self, _dbtx: &mut PgTransaction) -> Result<(), anyhow::Error> {
+        Err(anyhow::anyhow!(
+            "view {} does not support reindexing",
+            self.name()
+        ))
+    }
+
     fn is_relevant(&self, type_str: &str) -> bool;
 
     async fn index_event(
@@ -1,6 +1,9 @@
-use std::pin::Pin;
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use futures::{Stream, StreamExt, TryStreamExt};
 use sqlx::PgPool;
@@ -13,6 +16,8 @@ use crate::{opt::Options, AppView, ContextualizedEvent, PgTransaction};
 pub struct Indexer {
     opts: Options,
     indexes: Vec<Box<dyn AppView>>,
+    /// Names of the views that were indexed under the global watermark of earlier versions.
+    legacy_indexes: HashSet<String>,
 }
 
 impl Indexer {
@@ -20,6 +25,7 @@ impl Indexer {
         Self {
             opts: Options::parse(),
             indexes: Vec::new(),
+            legacy_indexes: HashSet::new(),
         }
     }
 
@@ -28,6 +34,16 @@ impl Indexer {
         self
     }
 
+    /// Registers a view that was already indexed by versions tracking a single, global
+    /// watermark, so that it resumes from that watermark instead of being rebuilt.
+    ///
+    /// Views added since then should be registered with [`Indexer::with_index`], so that
+    /// they are built from the first event.
+    pub fn with_legacy_index(mut self, index: impl AppView + 'static) -> Self {
+        self.legacy_indexes.insert(index.name());
+        self.with_index(index)
+    }
+
     pub fn with_default_tracing(self) -> Self {
         tracing_subscriber::fmt::init();
         self
@@ -42,6 +58,75 @@ impl Indexer {
         Ok(())
     }
 
+    /// Creates the table of per-view watermarks, migrating the single global watermark
+    /// used by earlier versions to the `legacy_indexes` if it is present.
+    async fn create_watermark_table(pool: &PgPool, legacy_indexes: &HashSet<String>) -> Result<()> {
+        let mut dbtx = pool.begin().await?;
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS index_watermarks (
+    index_name TEXT PRIMARY KEY,
+    events_rowid BIGINT NOT NULL
+)
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+
+        let (has_legacy_watermark,): (bool,) =
+            sqlx::query_as("SELECT to_regclass('index_watermark') IS NOT NULL")
+                .fetch_one(dbtx.as_mut())
+                .await?;
+        if has_legacy_watermark {
+            // The views registered when the global watermark was written had indexed
+            // up to it, so carry it over rather than rebuilding them. Any other view
+            // is new, and starts from the first event.
+            let legacy_watermark: Option<i64> =
+                sqlx::query_as("SELECT events_rowid FROM index_watermark")
+                    .fetch_optional(dbtx.as_mut())
+                    .await?
+                    .map(|(w,)| w);
+            if let Some(watermark) = legacy_watermark {
+                for name in legacy_indexes {
+                    sqlx::query(
+                        "INSERT INTO index_watermarks (index_name, events_rowid) VALUES ($1, $2) ON CONFLICT (index_name) DO NOTHING",
+                    )
+                    .bind(name)
+                    .bind(watermark)
+                    .execute(dbtx.as_mut())
+                    .await?;
+                }
+                info!(%watermark, "migrated global index watermark to per-view watermarks");
+            }
+            sqlx::query("DROP TABLE index_watermark")
+                .execute(dbtx.as_mut())
+                .await?;
+        }
+
+        dbtx.commit().await?;
+        Ok(())
+    }
+
+    /// Clears the named views and rewinds their watermarks, so they are rebuilt from the first event.
+    async fn reset_indexes(
+        pool: &PgPool,
+        indexes: &[Box<dyn AppView>],
+        names: &[String],
+    ) -> Result<()> {
+        for name in names {
+            let index = indexes
+                .iter()
+                .find(|index| index.name() == *name)
+                .ok_or_else(|| anyhow!("cannot reindex unknown view {name}"))?;
+            let mut dbtx = pool.begin().await?;
+            index.reset(&mut dbtx).await?;
+            update_watermark(&mut dbtx, name, 0).await?;
+            dbtx.commit().await?;
+            info!(view = %name, "reset view for reindexing");
+        }
+        Ok(())
+    }
+
     pub async fn run(self) -> Result<(), anyhow::Error> {
         tracing::info!(?self.opts);
         let Self {
@@ -49,24 +134,40 @@ impl Indexer {
                 Options {
                     src_database_url,
                     dst_database_url,
-                    chain_id: _,
+                    chain_id,
                     poll_ms,
+                    reindex,
+                    batch_size,
                 },
             indexes,
+            legacy_indexes,
         } = self;
 
+        // Watermarks are keyed by view name, so two views sharing one would clobber each other.
+        let mut names = HashSet::new();
+        for index in &indexes {
+            if !names.insert(index.name()) {
+                anyhow::bail!("multiple views are registered as {}", index.name());
+            }
+        }
+
         let src_db = PgPool::connect(&src_database_url).await?;
         let dst_db = PgPool::connect(&dst_database_url).await?;
 
-        Self::create_dst_tables(&dst_db, &indexes).await?;
+        Self::create_watermark_table(&dst_db, &legacy_indexes).await?;
+
+        // Reindexing is a one-off operation: reset the views and exit, so that
+        // restarting the indexer does not throw their progress away again.
+        if !reindex.is_empty() {
+            Self::reset_indexes(&dst_db, &indexes, &reindex).await?;
+            info!("views will be rebuilt the next time the indexer runs without --reindex");
+            return Ok(());
+        }
 
-        // Create the index_watermark table if it does not exist
-        sqlx::query("CREATE TABLE IF NOT EXISTS index_watermark (events_rowid BIGINT NOT NULL)")
-            .execute(&dst_db)
-            .await?;
+        Self::create_dst_tables(&dst_db, &indexes).await?;
 
         loop {
-            Self::tick(&src_db, &dst_db, &indexes).await?;
+            Self::tick(&src_db, &dst_db, &indexes, chain_id.as_deref(), batch_size).await?;
             tokio::time::sleep(poll_ms).await;
         }
     }
@@ -75,38 +176,42 @@ impl Indexer {
         src_db: &PgPool,
         dst_db: &PgPool,
         indexes: &[Box<dyn AppView>],
+        chain_id: Option<&str>,
+        batch_size: u64,
     ) -> Result<(), anyhow::Error> {
-        // Fetch the highest rowid processed so far (the watermark)
-        let current_watermark: Option<i64> =
-            sqlx::query_as("SELECT events_rowid FROM index_watermark")
-                .fetch_optional(dst_db)
-                .await?
-                .map(|(w,)| w)
-                .tap_some(|row_id| debug!(%row_id, "fetched index watermark"))
-                .tap_none(|| debug!("no index watermark was present"));
-
-        // Insert initial watermark if not present, so we can use a SET query later
-        if current_watermark.is_none() {
-            sqlx::query("INSERT INTO index_watermark (events_rowid) VALUES (0)")
-                .execute(dst_db)
-                .await?
-                .tap(|_| debug!("set index watermark to 0"));
-        }
+        // Fetch the highest rowid processed so far by each view (its watermark)
+        let mut watermarks = fetch_watermarks(dst_db, indexes).await?;
 
-        let watermark = current_watermark.unwrap_or(0);
+        // Read from the least advanced view's watermark; views that are further
+        // along skip the events they have already indexed.
+        let watermark = watermarks.iter().copied().min().unwrap_or(0);
 
         // Calculate new events count since the last watermark
-        sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM events WHERE rowid > $1")
-            .bind(watermark)
-            .fetch_one(src_db)
-            .await
-            .map(|(count,)| count)?
-            .tap(|count| info!(%count, %watermark, "new events since last watermark"));
+        sqlx::query_as::<_, (i64,)>(
+            "
+SELECT COUNT(*)
+FROM events
+JOIN blocks ON events.block_id = blocks.rowid
+WHERE events.rowid > $1 AND ($2::TEXT IS NULL OR blocks.chain_id = $2)
+",
+        )
+        .bind(watermark)
+        .bind(chain_id)
+        .fetch_one(src_db)
+        .await
+        .map(|(count,)| count)?
+        .tap(|count| info!(%count, %watermark, "new events since last watermark"));
 
         let mut scanned_events = 0usize;
         let mut relevant_events = 0usize;
 
-        let mut es = read_events(&src_db, watermark);
+        // Events are applied in batches, each committed together with the
+        // watermarks it advances, so a crash never double-applies an event.
+        let mut dbtx = dst_db.begin().await?;
+        let mut batch_len = 0u64;
+        let mut last_rowid = watermark;
+
+        let mut es = read_events(src_db, watermark, chain_id.map(ToOwned::to_owned));
         while let Some(event) = es.next().await.transpose()? {
             if scanned_events % 1000 == 0 {
                 tracing::info!(scanned_events, relevant_events);
@@ -122,54 +227,99 @@ impl Indexer {
 
             scanned_events += 1;
 
-            // if not relevant then skip making a db tx for the dst db
-            if !indexes
-                .iter()
-                .any(|index| index.is_relevant(&event.as_ref().kind))
-            {
-                tracing::trace!(kind = %event.as_ref().kind, "event is not relevant to any views");
-                continue;
-            }
-
-            relevant_events += 1;
-
-            // Otherwise we have something to process. Make a dbtx
-            let mut dbtx = dst_db.begin().await?;
-            for index in indexes {
-                if index.is_relevant(&event.as_ref().kind) {
+            let mut relevant = false;
+            for (index, index_watermark) in indexes.iter().zip(&watermarks) {
+                if event.local_rowid > *index_watermark && index.is_relevant(&event.as_ref().kind) {
                     tracing::debug!(?event, ?index, "relevant to index");
                     index.index_event(&mut dbtx, &event).await?;
+                    relevant = true;
                 }
             }
-            // Mark that we got to at least this event
-            update_watermark(&mut dbtx, event.local_rowid).await?;
-            dbtx.commit().await?;
+            if relevant {
+                relevant_events += 1;
+            } else {
+                tracing::trace!(kind = %event.as_ref().kind, "event is not relevant to any views");
+            }
+
+            last_rowid = event.local_rowid;
+            batch_len += 1;
+            if batch_len >= batch_size {
+                commit_batch(dbtx, indexes, &mut watermarks, last_rowid).await?;
+                dbtx = dst_db.begin().await?;
+                batch_len = 0;
+            }
+        }
+
+        if batch_len > 0 {
+            commit_batch(dbtx, indexes, &mut watermarks, last_rowid).await?;
         }
 
         Ok(())
     }
 }
 
-async fn update_watermark(dbtx: &mut PgTransaction<'_>, watermark: i64) -> Result<()> {
-    sqlx::query("UPDATE index_watermark SET events_rowid = $1")
-        .bind(watermark)
-        .execute(dbtx.as_mut()) // lol, see note on Executor trait about Transaction impl
-        .await
-        .tap_ok(|affected| {
-            debug!(%watermark, "updated index watermark");
-            debug_assert_eq!(
-                affected.rows_affected(),
-                1,
-                "only one row should be affected when updating the index watermark"
-            );
+/// Records that every view has processed all events up to `rowid`, and commits the batch.
+async fn commit_batch(
+    mut dbtx: PgTransaction<'_>,
+    indexes: &[Box<dyn AppView>],
+    watermarks: &mut [i64],
+    rowid: i64,
+) -> Result<()> {
+    for (index, watermark) in indexes.iter().zip(watermarks.iter_mut()) {
+        if rowid > *watermark {
+            update_watermark(&mut dbtx, &index.name(), rowid).await?;
+            *watermark = rowid;
+        }
+    }
+    dbtx.commit().await?;
+    Ok(())
+}
+
+/// Fetches the watermark of each view, in the order of `indexes`.
+///
+/// Views without a recorded watermark have not indexed any events yet.
+async fn fetch_watermarks(dst_db: &PgPool, indexes: &[Box<dyn AppView>]) -> Result<Vec<i64>> {
+    let recorded: HashMap<String, i64> =
+        sqlx::query_as("SELECT index_name, events_rowid FROM index_watermarks")
+            .fetch_all(dst_db)
+            .await?
+            .into_iter()
+            .collect();
+
+    Ok(indexes
+        .iter()
+        .map(|index| {
+            let name = index.name();
+            recorded
+                .get(&name)
+                .copied()
+                .tap_some(|row_id| debug!(view = %name, %row_id, "fetched index watermark"))
+                .tap_none(|| debug!(view = %name, "no index watermark was present"))
+                .unwrap_or(0)
         })
-        .map(|_| ())
-        .map_err(anyhow::Error::from)
+        .collect())
+}
+
+async fn update_watermark(dbtx: &mut PgTransaction<'_>, name: &str, watermark: i64) -> Result<()> {
+    sqlx::query(
+        "
+INSERT INTO index_watermarks (index_name, events_rowid) VALUES ($1, $2)
+ON CONFLICT (index_name) DO UPDATE SET events_rowid = excluded.events_rowid
+",
+    )
+    .bind(name)
+    .bind(watermark)
+    .execute(dbtx.as_mut()) // lol, see note on Executor trait about Transaction impl
+    .await
+    .tap_ok(|_| debug!(view = %name, %watermark, "updated index watermark"))
+    .map(|_| ())
+    .map_err(anyhow::Error::from)
 }
 
 fn read_events(
     src_db: &PgPool,
     watermark: i64,
+    chain_id: Option<String>,
 ) -> Pin<Box<dyn Stream<Item = Result<ContextualizedEvent>> + Send + '_>> {
     let event_stream = sqlx::query_as::<_, (i64, String, i64, Option<String>, serde_json::Value)>(
         r#"
@@ -189,14 +339,18 @@ LEFT JOIN
     tx_results ON events.tx_id = tx_results.rowid
 WHERE
     events.rowid > $1
+    AND ($2::TEXT IS NULL OR blocks.chain_id = $2)
 GROUP BY 
     events.rowid, 
     events.type, 
     blocks.height, 
     tx_results.tx_hash
+ORDER BY
+    events.rowid ASC
         "#,
     )
     .bind(watermark)
+    .bind(chain_id)
     .fetch(src_db)
     .map_ok(|(local_rowid, type_str, height, tx_hash, attrs)| {
         tracing::debug!(?local_rowid, type_str, height, ?tx_hash);
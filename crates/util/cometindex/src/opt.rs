@@ -26,6 +26,17 @@ pub struct Options {
     /// The rate at which to poll for changes, in milliseconds.
     #[clap(short, long, default_value = "500", value_parser = parse_poll_ms)]
     pub poll_ms: Duration,
+
+    /// Reset the named view and exit, so that it is rebuilt from the first event
+    /// the next time the indexer runs. Other views are left untouched.
+    ///
+    /// May be given multiple times.
+    #[clap(long, value_name = "VIEW")]
+    pub reindex: Vec<String>,
+
+    /// The maximum number of source events to process in a single destination transaction.
+    #[clap(long, default_value = "1000", value_parser = clap::value_parser!(u64).range(1..))]
+    pub batch_size: u64,
 }
 
 /// Parses a string containing a [`Duration`], represented as a number of milliseconds.
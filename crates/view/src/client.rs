@@ -51,11 +51,14 @@ pub(crate) type BroadcastStatusStream = Pin<
 #[allow(clippy::type_complexity)]
 pub trait ViewClient {
     /// Query the auction state
+    ///
+    /// If `auction_ids_filter` is non-empty, only the auctions with those ids are returned.
     fn auctions(
         &mut self,
         account_filter: Option<AddressIndex>,
         include_inactive: bool,
         query_latest_state: bool,
+        auction_ids_filter: Vec<AuctionId>,
     ) -> Pin<
         Box<
             dyn Future<
@@ -915,8 +918,8 @@ where
             while let Some(rsp) = rsp.try_next().await? {
                 match rsp.status {
                     Some(status) => match status {
-                        pb::witness_and_build_response::Status::BuildProgress(_) => {
-                            // TODO: should update progress here
+                        pb::witness_and_build_response::Status::BuildProgress(p) => {
+                            tracing::debug!(progress = p.progress, "building transaction");
                         }
                         pb::witness_and_build_response::Status::Complete(c) => {
                             return c.transaction
@@ -973,6 +976,7 @@ where
         account_filter: Option<AddressIndex>,
         include_inactive: bool,
         query_latest_state: bool,
+        auction_ids_filter: Vec<AuctionId>,
     ) -> Pin<
         Box<
             dyn Future<
@@ -995,7 +999,7 @@ where
                 account_filter: account_filter.map(Into::into),
                 include_inactive,
                 query_latest_state,
-                auction_ids_filter: Vec::new(), // TODO: Support `auction_ids_filter`
+                auction_ids_filter: auction_ids_filter.into_iter().map(Into::into).collect(),
             });
 
             let auctions: Vec<pb::AuctionsResponse> =
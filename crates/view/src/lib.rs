@@ -29,8 +29,10 @@ pub use crate::client::ViewClient;
 pub use crate::metrics::register_metrics;
 pub use crate::note_record::SpendableNoteRecord;
 pub use crate::planner::Planner;
-pub use crate::service::ViewServer;
+pub use crate::service::{
+    check_tx_error, ViewServer, WalletSelector, CHECK_TX_CODE_METADATA_KEY, WALLET_ID_METADATA_KEY,
+};
 pub use crate::status::StatusStreamResponse;
 pub use crate::storage::Storage;
-pub use crate::swap_record::SwapRecord;
+pub use crate::swap_record::{SwapClaimRecord, SwapRecord};
 pub use crate::transaction_info::TransactionInfo;
@@ -40,7 +40,7 @@ use penumbra_keys::{keys::AddressIndex, Address};
 use penumbra_num::Amount;
 use penumbra_proto::view::v1::{NotesForVotingRequest, NotesRequest};
 use penumbra_shielded_pool::{Ics20Withdrawal, Note, OutputPlan, SpendPlan};
-use penumbra_stake::{rate::RateData, validator, IdentityKey, UndelegateClaimPlan};
+use penumbra_stake::{rate::RateData, validator, IdentityKey, RedelegateClaim, UndelegateClaimPlan};
 use penumbra_tct as tct;
 use penumbra_transaction::{
     memo::MemoPlaintext,
@@ -188,7 +188,8 @@ impl<R: RngCore + CryptoRng> Planner<R> {
 
     /// Withdraw a liquidity position in the order book.
     ///
-    /// Note: Currently this only supports an initial withdrawal from Closed, with no rewards.
+    /// Note: Currently this only supports an initial withdrawal from Closed. Any liquidity
+    /// rewards accrued by the position are already included in its reserves.
     #[instrument(skip(self))]
     pub fn position_withdraw(
         &mut self,
@@ -261,6 +262,27 @@ impl<R: RngCore + CryptoRng> Planner<R> {
         into_asset: asset::Id,
         swap_claim_fee: Fee,
         claim_address: Address,
+    ) -> Result<&mut Self> {
+        self.swap_with_min_output(
+            input_value,
+            into_asset,
+            Amount::zero(),
+            swap_claim_fee,
+            claim_address,
+        )
+    }
+
+    /// Perform a swap based on input notes in the transaction, which is only
+    /// filled if it receives at least `min_output` of `into_asset`. Otherwise,
+    /// the input is returned when the swap is claimed.
+    #[instrument(skip(self))]
+    pub fn swap_with_min_output(
+        &mut self,
+        input_value: Value,
+        into_asset: asset::Id,
+        min_output: Amount,
+        swap_claim_fee: Fee,
+        claim_address: Address,
     ) -> Result<&mut Self> {
         // Determine the canonical order for the assets being swapped.
         // This will determine whether the input amount is assigned to delta_1 or delta_2.
@@ -289,7 +311,8 @@ impl<R: RngCore + CryptoRng> Planner<R> {
             delta_2,
             swap_claim_fee,
             claim_address,
-        );
+        )
+        .with_min_output(min_output);
 
         let swap = SwapPlan::new(&mut self.rng, swap_plaintext);
         self.action_list.push(swap);
@@ -330,6 +353,22 @@ impl<R: RngCore + CryptoRng> Planner<R> {
         self
     }
 
+    /// Add a redelegation to this transaction, moving `delegation_amount` of the
+    /// delegation tokens of the validator described by `from_rate_data` to the
+    /// validator described by `to_rate_data`.
+    #[instrument(skip(self))]
+    pub fn redelegate(
+        &mut self,
+        epoch: Epoch,
+        delegation_amount: Amount,
+        from_rate_data: RateData,
+        to_rate_data: RateData,
+    ) -> &mut Self {
+        let redelegation = from_rate_data.build_redelegate(&to_rate_data, epoch, delegation_amount);
+        self.action_list.push(redelegation);
+        self
+    }
+
     /// Add an undelegate claim to this transaction.
     #[instrument(skip(self))]
     pub fn undelegate_claim(&mut self, claim_plan: UndelegateClaimPlan) -> &mut Self {
@@ -337,6 +376,13 @@ impl<R: RngCore + CryptoRng> Planner<R> {
         self
     }
 
+    /// Add a redelegation claim to this transaction.
+    #[instrument(skip(self))]
+    pub fn redelegate_claim(&mut self, claim: RedelegateClaim) -> &mut Self {
+        self.action_list.push(claim);
+        self
+    }
+
     /// Upload a validator definition in this transaction.
     #[instrument(skip(self))]
     pub fn validator_definition(&mut self, new_validator: validator::Definition) -> &mut Self {
@@ -9,7 +9,8 @@ use async_stream::try_stream;
 use camino::Utf8Path;
 use decaf377::Fq;
 use futures::stream::{self, StreamExt, TryStreamExt};
-use penumbra_auction::auction::dutch::actions::view::ActionDutchAuctionWithdrawView;
+use penumbra_auction::auction::{dutch::actions::view::ActionDutchAuctionWithdrawView, AuctionId};
+use prost::Name as _;
 use rand::Rng;
 use rand_core::OsRng;
 use tap::{Tap, TapFallible};
@@ -19,7 +20,11 @@ use tonic::{async_trait, transport::Channel, Request, Response, Status};
 use tracing::instrument;
 use url::Url;
 
-use penumbra_asset::{asset, asset::Metadata, Value};
+use penumbra_app::CheckTxError;
+use penumbra_asset::{
+    asset, asset::Metadata, EquivalentValue, Value, ValueView, STAKING_TOKEN_DENOM,
+};
+use penumbra_custody::{AuthorizeRequest, CustodyClient, PreAuthorization};
 use penumbra_dex::{
     lp::{
         position::{self, Position},
@@ -36,6 +41,10 @@ use penumbra_keys::{
 };
 use penumbra_num::Amount;
 use penumbra_proto::{
+    core::component::stake::v1::{
+        self as pb_stake, query_service_client::QueryServiceClient as StakeQueryServiceClient,
+        GetValidatorInfoRequest, ValidatorInfoRequest,
+    },
     util::tendermint_proxy::v1::{
         tendermint_proxy_service_client::TendermintProxyServiceClient, BroadcastTxSyncRequest,
         GetStatusRequest, GetStatusResponse, SyncInfo,
@@ -52,13 +61,107 @@ use penumbra_proto::{
     },
     DomainType,
 };
-use penumbra_stake::rate::RateData;
+use penumbra_stake::{rate::RateData, validator, DelegationToken, UnbondingToken};
 use penumbra_tct::{Proof, StateCommitment};
 use penumbra_transaction::{
-    AuthorizationData, Transaction, TransactionPerspective, TransactionPlan, WitnessData,
+    txhash::TransactionId, AuthorizationData, Transaction, TransactionParameters,
+    TransactionPerspective, TransactionPlan, WitnessData,
 };
 
-use crate::{worker::Worker, Planner, Storage};
+use crate::{worker::Worker, Planner, Storage, SwapClaimRecord, SwapRecord};
+
+/// The gRPC metadata key used to report the node's `CheckTx` rejection code
+/// when a transaction is rejected by [`ViewService::broadcast_transaction`].
+pub const CHECK_TX_CODE_METADATA_KEY: &str = "penumbra-check-tx-code";
+
+/// The gRPC metadata key used to select which wallet a [`ViewService`] request
+/// is addressed to, when a [`ViewServer`] serves several wallets.
+///
+/// The value is the bech32m encoding of the wallet's [`WalletId`]. Requests
+/// without it are served by the server's default wallet.
+pub const WALLET_ID_METADATA_KEY: &str = "penumbra-wallet-id";
+
+/// How many blocks to wait before retrying a swap claim that couldn't be
+/// submitted. The delay doubles with each failed attempt, up to
+/// [`MAX_SWAP_CLAIM_RETRY_BLOCKS`].
+const SWAP_CLAIM_RETRY_BLOCKS: u64 = 2;
+
+/// The longest delay between retries of a swap claim that couldn't be submitted.
+const MAX_SWAP_CLAIM_RETRY_BLOCKS: u64 = 720;
+
+/// How many blocks to wait for a submitted swap claim to be detected before
+/// submitting it again, e.g. because it was dropped from the mempool.
+const SWAP_CLAIM_RESUBMIT_BLOCKS: u64 = 32;
+
+/// A [`tonic::service::Interceptor`] that addresses every request made by a
+/// [`ViewServiceClient`] to the given wallet.
+///
+/// See [`WALLET_ID_METADATA_KEY`].
+#[derive(Clone, Copy, Debug)]
+pub struct WalletSelector(pub WalletId);
+
+impl tonic::service::Interceptor for WalletSelector {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let wallet_id = self
+            .0
+            .to_string()
+            .parse()
+            .map_err(|_| Status::internal("wallet id is not valid metadata"))?;
+        request
+            .metadata_mut()
+            .insert(WALLET_ID_METADATA_KEY, wallet_id);
+        Ok(request)
+    }
+}
+
+/// Recovers the `CheckTx` rejection reported in a `broadcast_transaction` error, if any.
+pub fn check_tx_error(status: &tonic::Status) -> Option<CheckTxError> {
+    status
+        .metadata()
+        .get(CHECK_TX_CODE_METADATA_KEY)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .and_then(CheckTxError::from_code)
+}
+
+/// Builds the gRPC status for a transaction rejected by `CheckTx`, recording the
+/// rejection code in the status metadata.
+fn check_tx_status(rejection: CheckTxError, message: String) -> tonic::Status {
+    let code = match rejection {
+        CheckTxError::Decode
+        | CheckTxError::InvalidBindingSignature
+        | CheckTxError::ClueCountMismatch
+        | CheckTxError::InvalidMemo
+        | CheckTxError::InvalidAction
+        | CheckTxError::CommunityPoolNotPermitted => tonic::Code::InvalidArgument,
+        CheckTxError::WrongChainId
+        | CheckTxError::Expired
+        | CheckTxError::UnknownAnchor
+        | CheckTxError::InvalidCluePrecision
+        | CheckTxError::InvalidActionState
+        | CheckTxError::FeeRejected
+        | CheckTxError::FeeTooLow
+        | CheckTxError::WrongFeeToken
+        | CheckTxError::ExecutionFailed
+        | CheckTxError::SpentNullifier => tonic::Code::FailedPrecondition,
+        CheckTxError::Other => tonic::Code::Internal,
+    };
+    let mut status = tonic::Status::new(code, message);
+    status
+        .metadata_mut()
+        .insert(CHECK_TX_CODE_METADATA_KEY, rejection.code().into());
+    status
+}
+
+/// Packs a validator's info as the `extended_metadata` of a [`ValueView`].
+fn validator_info_any(info: validator::Info) -> pbjson_types::Any {
+    pbjson_types::Any {
+        type_url: pb_stake::ValidatorInfo::type_url(),
+        value: info.encode_to_vec().into(),
+    }
+}
 
 /// A [`futures::Stream`] of broadcast transaction responses.
 ///
@@ -75,8 +178,14 @@ type BroadcastTransactionStream = Pin<
 /// internally that performs synchronization and scanning.  The
 /// [`ViewServer`] can be cloned; each clone will read from the same shared
 /// state, but there will only be a single scanning task.
+///
+/// A single server can serve several wallets, each with its own [`Storage`],
+/// scanning every compact block once for all of them. Requests select a wallet
+/// with the [`WALLET_ID_METADATA_KEY`] metadata, see [`WalletSelector`].
 #[derive(Clone)]
 pub struct ViewServer {
+    // The ID, storage, SCT, sync height and custody of the wallet requests are served from.
+    wallet_id: WalletId,
     storage: Storage,
     // A shared error slot for errors bubbled up by the worker. This is a regular Mutex
     // rather than a Tokio Mutex because it should be uncontended.
@@ -87,6 +196,22 @@ pub struct ViewServer {
     node: Url,
     /// Used to watch for changes to the sync height.
     sync_height_rx: watch::Receiver<u64>,
+    /// Used to authorize transactions in [`ViewService::authorize_and_build`], if configured.
+    custody: Option<SharedCustody>,
+    // Every wallet served by this server, by wallet ID.
+    wallets: Arc<BTreeMap<WalletId, Wallet>>,
+}
+
+/// A custody service shared between clones of a [`ViewServer`].
+type SharedCustody = Arc<Mutex<Box<dyn CustodyClient + Send>>>;
+
+/// The per-wallet state of a [`ViewServer`].
+#[derive(Clone)]
+struct Wallet {
+    storage: Storage,
+    state_commitment_tree: Arc<RwLock<penumbra_tct::Tree>>,
+    sync_height_rx: watch::Receiver<u64>,
+    custody: Option<SharedCustody>,
 }
 
 impl ViewServer {
@@ -123,6 +248,28 @@ impl ViewServer {
     /// will be backed by the same scanning task, rather than each spawning its own.
     #[instrument(skip_all)]
     pub async fn new(storage: Storage, node: Url) -> anyhow::Result<Self> {
+        Self::with_wallets(vec![storage], node).await
+    }
+
+    /// Constructs a new [`ViewService`] serving several wallets, spawning a
+    /// single sync task that scans for all of them.
+    ///
+    /// The first storage is the default wallet, which serves requests that
+    /// don't select a wallet.
+    #[instrument(skip_all)]
+    pub async fn with_wallets(storages: Vec<Storage>, node: Url) -> anyhow::Result<Self> {
+        let mut wallet_ids = Vec::with_capacity(storages.len());
+        for storage in &storages {
+            let wallet_id = storage.full_viewing_key().await?.wallet_id();
+            anyhow::ensure!(
+                !wallet_ids.contains(&wallet_id),
+                "wallet {} is configured more than once",
+                wallet_id
+            );
+            wallet_ids.push(wallet_id);
+        }
+        anyhow::ensure!(!wallet_ids.is_empty(), "no wallets to serve");
+
         let channel = Channel::from_shared(node.to_string())
             .with_context(|| "could not parse node URI")?
             .connect()
@@ -130,20 +277,208 @@ impl ViewServer {
             .with_context(|| "could not connect to grpc server")
             .tap_err(|error| tracing::error!(?error, "could not connect to grpc server"))?;
 
-        let (worker, state_commitment_tree, error_slot, sync_height_rx) =
-            Worker::new(storage.clone(), channel)
-                .tap(|_| tracing::trace!("constructing view server worker"))
-                .await?
-                .tap(|_| tracing::debug!("constructed view server worker"));
+        let (worker, handles, error_slot) = Worker::new(storages.clone(), channel)
+            .tap(|_| tracing::trace!("constructing view server worker"))
+            .await?
+            .tap(|_| tracing::debug!("constructed view server worker"));
 
         tokio::spawn(worker.run()).tap(|_| tracing::debug!("spawned view server worker"));
 
+        let wallets: BTreeMap<WalletId, Wallet> = wallet_ids
+            .iter()
+            .copied()
+            .zip(storages.into_iter().zip(handles).map(
+                |(storage, (state_commitment_tree, sync_height_rx))| Wallet {
+                    storage,
+                    state_commitment_tree,
+                    sync_height_rx,
+                    custody: None,
+                },
+            ))
+            .collect();
+        let default = wallets[&wallet_ids[0]].clone();
+
         Ok(Self {
-            storage,
+            wallet_id: wallet_ids[0],
+            storage: default.storage,
             error_slot,
-            sync_height_rx,
-            state_commitment_tree,
+            sync_height_rx: default.sync_height_rx,
+            state_commitment_tree: default.state_commitment_tree,
             node,
+            custody: None,
+            wallets: Arc::new(wallets),
+        })
+    }
+
+    /// Configures a custody service for the wallet this server serves by
+    /// default, so that it can authorize transactions requested with
+    /// [`ViewService::authorize_and_build`].
+    ///
+    /// This should be called before the server is cloned, since existing
+    /// clones are not affected.
+    pub fn with_custody(mut self, custody: impl CustodyClient + Send + 'static) -> Self {
+        let custody: SharedCustody = Arc::new(Mutex::new(Box::new(custody)));
+        if let Some(wallet) = Arc::make_mut(&mut self.wallets).get_mut(&self.wallet_id) {
+            wallet.custody = Some(custody.clone());
+        }
+        self.custody = Some(custody);
+        self
+    }
+
+    /// Claims the default wallet's swaps automatically, as soon as the worker
+    /// detects their batch swap output data.
+    ///
+    /// This spawns a task that, whenever the sync height advances, plans a
+    /// claim of each unclaimed swap paying the swap's prepaid claim fee, then
+    /// authorizes it with the configured custody service, builds it and
+    /// broadcasts it. The outcome of each claim is recorded in storage and
+    /// served by [`ViewService::swap_claims`]. Claims that fail are retried
+    /// with exponential backoff, and claims that aren't detected within
+    /// [`SWAP_CLAIM_RESUBMIT_BLOCKS`] are submitted again.
+    ///
+    /// This requires a custody service, see [`Self::with_custody`].
+    pub fn with_auto_claim(self) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            self.custody.is_some(),
+            "claiming swaps automatically requires a custody service"
+        );
+        tokio::spawn(self.clone().claim_swaps())
+            .tap(|_| tracing::debug!("spawned automatic swap claim task"));
+        Ok(self)
+    }
+
+    /// Claims unclaimed swaps each time the sync height advances, until the
+    /// worker shuts down.
+    async fn claim_swaps(self) {
+        let mut sync_height_rx = self.sync_height_rx.clone();
+        loop {
+            if let Err(error) = self.claim_unclaimed_swaps().await {
+                tracing::warn!(?error, "failed to claim swaps");
+            }
+            if sync_height_rx.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Submits a claim for each unclaimed swap that is due one, see
+    /// [`swap_claim_due`].
+    #[instrument(skip(self))]
+    async fn claim_unclaimed_swaps(&self) -> anyhow::Result<()> {
+        // Swaps detected while catching up may have been claimed in blocks we
+        // haven't scanned yet, so only claim once we're synced.
+        let status = self.status().await?;
+        if status.catching_up {
+            return Ok(());
+        }
+
+        let claims = self
+            .storage
+            .swap_claims()
+            .await?
+            .into_iter()
+            .map(|claim| (claim.swap_commitment, claim))
+            .collect::<BTreeMap<_, _>>();
+
+        for swap in self.storage.unclaimed_swaps().await? {
+            let swap_commitment = swap.swap_commitment;
+            if !swap_claim_due(claims.get(&swap_commitment), status.full_sync_height) {
+                continue;
+            }
+
+            let result = self
+                .claim_swap(swap)
+                .await
+                .tap(|result| match result {
+                    Ok(id) => tracing::info!(?swap_commitment, %id, "submitted swap claim"),
+                    Err(error) => tracing::warn!(?swap_commitment, ?error, "failed to claim swap"),
+                })
+                .map_err(|e| format!("{e:#}"));
+            self.storage
+                .record_swap_claim(swap_commitment, status.full_sync_height, result)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Plans, authorizes, builds and broadcasts a claim of `swap`, returning
+    /// the ID of the claim transaction.
+    async fn claim_swap(&self, swap: SwapRecord) -> anyhow::Result<TransactionId> {
+        let app_params = self.storage.app_params().await?;
+
+        let plan = TransactionPlan {
+            transaction_parameters: TransactionParameters {
+                chain_id: app_params.chain_id,
+                fee: swap.swap.claim_fee.clone(),
+                ..Default::default()
+            },
+            actions: vec![SwapClaimPlan {
+                swap_plaintext: swap.swap,
+                position: swap.position,
+                output_data: swap.output_data,
+                epoch_duration: app_params.sct_params.epoch_duration,
+                proof_blinding_r: Fq::rand(&mut OsRng),
+                proof_blinding_s: Fq::rand(&mut OsRng),
+            }
+            .into()],
+            // The transaction doesn't need a memo, because it's to ourselves.
+            memo: None,
+            ..Default::default()
+        };
+
+        let mut responses = self
+            .authorize_and_build(tonic::Request::new(pb::AuthorizeAndBuildRequest {
+                transaction_plan: Some(plan.into()),
+                pre_authorizations: Vec::new(),
+            }))
+            .await?
+            .into_inner();
+        let transaction: Transaction = loop {
+            use pb::authorize_and_build_response::{Complete, Status};
+
+            let response = responses
+                .try_next()
+                .await?
+                .ok_or_else(|| anyhow!("claim was not built"))?;
+            if let Some(Status::Complete(Complete { transaction })) = response.status {
+                break transaction
+                    .ok_or_else(|| anyhow!("missing claim transaction"))?
+                    .try_into()?;
+            }
+        };
+
+        let id = transaction.id();
+        let mut broadcast = self.broadcast_transaction(transaction, false);
+        while broadcast.try_next().await?.is_some() {}
+
+        Ok(id)
+    }
+
+    /// Returns a view of this server scoped to the wallet selected by the
+    /// request's [`WALLET_ID_METADATA_KEY`] metadata, if any.
+    fn select_wallet<T>(&self, request: &Request<T>) -> Result<Self, tonic::Status> {
+        let Some(wallet_id) = request.metadata().get(WALLET_ID_METADATA_KEY) else {
+            return Ok(self.clone());
+        };
+        let wallet_id: WalletId = wallet_id
+            .to_str()
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| tonic::Status::invalid_argument("invalid wallet id"))?;
+        let wallet = self
+            .wallets
+            .get(&wallet_id)
+            .ok_or_else(|| tonic::Status::not_found(format!("unknown wallet {wallet_id}")))?
+            .clone();
+
+        Ok(Self {
+            wallet_id,
+            storage: wallet.storage,
+            state_commitment_tree: wallet.state_commitment_tree,
+            sync_height_rx: wallet.sync_height_rx,
+            custody: wallet.custody,
+            ..self.clone()
         })
     }
 
@@ -177,6 +512,35 @@ impl ViewServer {
         Ok(()).tap(|_| tracing::trace!("view server worker is healthy"))
     }
 
+    /// Returns the unspent balances of every address of `account`.
+    async fn account_balances(
+        &self,
+        account: u32,
+    ) -> Result<BTreeMap<asset::Id, Amount>, tonic::Status> {
+        let entries = self
+            .storage
+            .balances(None, None)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("could not get balances: {e:#}")))?;
+
+        let mut balances = BTreeMap::new();
+        for entry in entries {
+            if entry.address_index.account != account {
+                continue;
+            }
+            let amount: &mut Amount = balances.entry(entry.id).or_default();
+            *amount += Amount::from(entry.amount);
+        }
+        Ok(balances)
+    }
+
+    /// Connects to the stake query service of the node.
+    async fn stake_query_client(&self) -> Result<StakeQueryServiceClient<Channel>, tonic::Status> {
+        StakeQueryServiceClient::connect(self.node.to_string())
+            .await
+            .map_err(|e| tonic::Status::unavailable(format!("couldn't connect to node: {e:#}")))
+    }
+
     #[instrument(skip(self, transaction), fields(id = %transaction.id()))]
     fn broadcast_transaction(
         &self,
@@ -192,10 +556,10 @@ impl ViewServer {
                     .check_stateless(())
                     .await
                     .map_err(|e| {
-                        tonic::Status::unavailable(format!(
-                            "transaction pre-submission checks failed: {:#?}",
-                            e
-                        ))
+                        check_tx_status(
+                            CheckTxError::from_error(&e),
+                            format!("transaction pre-submission checks failed: {:#?}", e),
+                        )
                     })?;
 
                 // 2. Broadcast the transaction to the network.
@@ -225,14 +589,17 @@ impl ViewServer {
                 tracing::info!(?node_rsp);
                 match node_rsp.code {
                     0 => Ok(()),
-                    _ => Err(tonic::Status::new(
-                        tonic::Code::Internal,
-                        format!(
+                    code => {
+                        let message = format!(
                             "Error submitting transaction: code {}, log: {}",
-                            node_rsp.code,
+                            code,
                             node_rsp.log,
-                        ),
-                    )),
+                        );
+                        match u32::try_from(code).ok().and_then(CheckTxError::from_code) {
+                            Some(rejection) => Err(check_tx_status(rejection, message)),
+                            None => Err(tonic::Status::new(tonic::Code::Internal, message)),
+                        }
+                    }
                 }?;
 
                 // The transaction was submitted so we provide a status update
@@ -413,12 +780,15 @@ impl ViewService for ViewServer {
     >;
     type AuctionsStream =
         Pin<Box<dyn futures::Stream<Item = Result<pb::AuctionsResponse, tonic::Status>> + Send>>;
+    type SwapClaimsStream =
+        Pin<Box<dyn futures::Stream<Item = Result<pb::SwapClaimsResponse, tonic::Status>> + Send>>;
 
     #[instrument(skip_all, level = "trace")]
     async fn auctions(
         &self,
         request: tonic::Request<pb::AuctionsRequest>,
     ) -> Result<tonic::Response<Self::AuctionsStream>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         use penumbra_proto::core::component::auction::v1 as pb_auction;
         use penumbra_proto::core::component::auction::v1::query_service_client::QueryServiceClient as AuctionQueryServiceClient;
 
@@ -433,15 +803,26 @@ impl ViewService for ViewServer {
             .map_or(Ok(None), |v| v.map(Some))
             .map_err(|_| tonic::Status::invalid_argument("invalid account filter"))?;
 
-        let all_auctions = self
+        let auction_ids_filter = parameters
+            .auction_ids_filter
+            .into_iter()
+            .map(AuctionId::try_from)
+            .collect::<anyhow::Result<BTreeSet<_>>>()
+            .map_err(|_| tonic::Status::invalid_argument("invalid auction id filter"))?;
+
+        let all_auctions = server
             .storage
             .fetch_auctions_by_account(account_filter, include_inactive)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .into_iter()
+            .filter(|(auction_id, _, _)| {
+                auction_ids_filter.is_empty() || auction_ids_filter.contains(auction_id)
+            });
 
         let client = if query_latest_state {
             Some(
-                AuctionQueryServiceClient::connect(self.node.to_string())
+                AuctionQueryServiceClient::connect(server.node.to_string())
                     .await
                     .map_err(|e| tonic::Status::internal(e.to_string()))?,
             )
@@ -490,6 +871,7 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::BroadcastTransactionRequest>,
     ) -> Result<tonic::Response<Self::BroadcastTransactionStream>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let pb::BroadcastTransactionRequest {
             transaction,
             await_detection,
@@ -501,7 +883,7 @@ impl ViewService for ViewServer {
             .map_err(|e: anyhow::Error| e.context("could not decode transaction"))
             .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
 
-        let stream = self.broadcast_transaction(transaction, await_detection);
+        let stream = server.broadcast_transaction(transaction, await_detection);
 
         Ok(tonic::Response::new(stream))
     }
@@ -511,15 +893,16 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::TransactionPlannerRequest>,
     ) -> Result<tonic::Response<pb::TransactionPlannerResponse>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let prq = request.into_inner();
 
         let app_params =
-            self.storage.app_params().await.map_err(|e| {
+            server.storage.app_params().await.map_err(|e| {
                 tonic::Status::internal(format!("could not get app params: {:#}", e))
             })?;
 
         let gas_prices =
-            self.storage.gas_prices().await.map_err(|e| {
+            server.storage.gas_prices().await.map_err(|e| {
                 tonic::Status::internal(format!("could not get gas prices: {:#}", e))
             })?;
 
@@ -600,7 +983,7 @@ impl ViewService for ViewServer {
                         "Could not parse swap commitment: {e:#}"
                     ))
                 })?;
-            let swap_record = self
+            let swap_record = server
                 .storage
                 // TODO: should there be a timeout on detection here instead?
                 .swap_by_commitment(swap_commitment, false)
@@ -758,7 +1141,7 @@ impl ViewService for ViewServer {
             );
         }
 
-        let mut client_of_self = ViewServiceClient::new(ViewServiceServer::new(self.clone()));
+        let mut client_of_self = ViewServiceClient::new(ViewServiceServer::new(server.clone()));
 
         let source = prq
             .source
@@ -783,8 +1166,9 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::AddressByIndexRequest>,
     ) -> Result<tonic::Response<pb::AddressByIndexResponse>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let fvk =
-            self.storage.full_viewing_key().await.map_err(|_| {
+            server.storage.full_viewing_key().await.map_err(|_| {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
@@ -807,8 +1191,9 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::IndexByAddressRequest>,
     ) -> Result<tonic::Response<pb::IndexByAddressResponse>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let fvk =
-            self.storage.full_viewing_key().await.map_err(|_| {
+            server.storage.full_viewing_key().await.map_err(|_| {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
@@ -831,8 +1216,9 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::EphemeralAddressRequest>,
     ) -> Result<tonic::Response<pb::EphemeralAddressResponse>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let fvk =
-            self.storage.full_viewing_key().await.map_err(|_| {
+            server.storage.full_viewing_key().await.map_err(|_| {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
@@ -855,16 +1241,17 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::TransactionInfoByHashRequest>,
     ) -> Result<tonic::Response<pb::TransactionInfoByHashResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let request = request.into_inner();
 
         let fvk =
-            self.storage.full_viewing_key().await.map_err(|_| {
+            server.storage.full_viewing_key().await.map_err(|_| {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
-        let maybe_tx = self
+        let maybe_tx = server
             .storage
             .transaction_by_hash(
                 &request
@@ -908,14 +1295,14 @@ impl ViewService for ViewServer {
                     let nullifier = spend.body.nullifier;
                     // An error here indicates we don't know the nullifier, so we omit it from the Perspective.
                     if let Ok(spendable_note_record) =
-                        self.storage.note_by_nullifier(nullifier, false).await
+                        server.storage.note_by_nullifier(nullifier, false).await
                     {
                         txp.spend_nullifiers
                             .insert(nullifier, spendable_note_record.note);
                     }
                 }
                 Action::SwapClaim(claim) => {
-                    let output_1_record = self
+                    let output_1_record = server
                         .storage
                         .note_by_commitment(claim.body.output_1_commitment, false)
                         .await
@@ -925,7 +1312,7 @@ impl ViewService for ViewServer {
                                 e
                             ))
                         })?;
-                    let output_2_record = self
+                    let output_2_record = server
                         .storage
                         .note_by_commitment(claim.body.output_2_commitment, false)
                         .await
@@ -1007,7 +1394,7 @@ impl ViewService for ViewServer {
         let mut denoms = Vec::new();
 
         for id in asset_ids {
-            if let Some(asset) = self.storage.asset_by_id(&id).await.map_err(|e| {
+            if let Some(asset) = server.storage.asset_by_id(&id).await.map_err(|e| {
                 tonic::Status::internal(format!("Error retrieving asset by id: {:#}", e))
             })? {
                 denoms.push(asset);
@@ -1039,7 +1426,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::SwapByCommitmentRequest>,
     ) -> Result<tonic::Response<pb::SwapByCommitmentResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let request = request.into_inner();
 
@@ -1054,7 +1442,8 @@ impl ViewService for ViewServer {
             })?;
 
         let swap = pb::SwapRecord::from(
-            self.storage
+            server
+                .storage
                 .swap_by_commitment(swap_commitment, request.await_detection)
                 .await
                 .map_err(|e| tonic::Status::internal(format!("error: {e}")))?,
@@ -1071,6 +1460,7 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::BalancesRequest>,
     ) -> Result<tonic::Response<Self::BalancesStream>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let request = request.into_inner();
 
         let account_filter = request.account_filter.and_then(|x| {
@@ -1089,7 +1479,7 @@ impl ViewService for ViewServer {
                 .map_or(None, |x| x.into())
         });
 
-        let result = self
+        let result = server
             .storage
             .balances(account_filter, asset_id_filter)
             .await
@@ -1097,7 +1487,7 @@ impl ViewService for ViewServer {
 
         tracing::debug!(?account_filter, ?asset_id_filter, ?result);
 
-        let self2 = self.clone();
+        let self2 = server.clone();
         let stream = try_stream! {
             // retrieve balance and address views
             for element in result {
@@ -1165,7 +1555,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::NoteByCommitmentRequest>,
     ) -> Result<tonic::Response<pb::NoteByCommitmentResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let request = request.into_inner();
 
@@ -1180,7 +1571,8 @@ impl ViewService for ViewServer {
             })?;
 
         let spendable_note = pb::SpendableNoteRecord::from(
-            self.storage
+            server
+                .storage
                 .note_by_commitment(note_commitment, request.await_detection)
                 .await
                 .map_err(|e| tonic::Status::internal(format!("error: {e}")))?,
@@ -1196,7 +1588,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::NullifierStatusRequest>,
     ) -> Result<tonic::Response<pb::NullifierStatusResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let request = request.into_inner();
 
@@ -1207,7 +1600,7 @@ impl ViewService for ViewServer {
             .map_err(|_| tonic::Status::failed_precondition("Invalid nullifier in request"))?;
 
         Ok(tonic::Response::new(pb::NullifierStatusResponse {
-            spent: self
+            spent: server
                 .storage
                 .nullifier_status(nullifier, request.await_detection)
                 .await
@@ -1218,23 +1611,25 @@ impl ViewService for ViewServer {
     #[instrument(skip_all, level = "trace")]
     async fn status(
         &self,
-        _: tonic::Request<pb::StatusRequest>,
+        request: tonic::Request<pb::StatusRequest>,
     ) -> Result<tonic::Response<pb::StatusResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
-        Ok(tonic::Response::new(self.status().await.map_err(|e| {
-            tonic::Status::internal(format!("error: {e}"))
-        })?))
+        Ok(tonic::Response::new(server.status().await.map_err(
+            |e| tonic::Status::internal(format!("error: {e}")),
+        )?))
     }
 
     #[instrument(skip_all, level = "trace")]
     async fn status_stream(
         &self,
-        _: tonic::Request<pb::StatusStreamRequest>,
+        request: tonic::Request<pb::StatusStreamRequest>,
     ) -> Result<tonic::Response<Self::StatusStreamStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
-        let (latest_known_block_height, _) = self
+        let (latest_known_block_height, _) = server
             .latest_known_block_height()
             .await
             .tap_err(|error| {
@@ -1251,7 +1646,7 @@ impl ViewService for ViewServer {
 
         // Create a stream of sync height updates from our worker, and send them to the client
         // until we've reached the latest known block height at the time the request was made.
-        let mut sync_height_stream = WatchStream::new(self.sync_height_rx.clone());
+        let mut sync_height_stream = WatchStream::new(server.sync_height_rx.clone());
         let stream = try_stream! {
             while let Some(sync_height) = sync_height_stream.next().await {
                 yield pb::StatusStreamResponse {
@@ -1273,7 +1668,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::NotesRequest>,
     ) -> Result<tonic::Response<Self::NotesStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let request = request.into_inner();
 
@@ -1297,7 +1693,7 @@ impl ViewService for ViewServer {
             .map_or(Ok(None), |v| v.map(Some))
             .map_err(|_| tonic::Status::invalid_argument("invalid amount to spend"))?;
 
-        let notes = self
+        let notes = server
             .storage
             .notes(include_spent, asset_id, address_index, amount_to_spend)
             .await
@@ -1325,7 +1721,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::NotesForVotingRequest>,
     ) -> Result<tonic::Response<Self::NotesForVotingStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let address_index = request
             .get_ref()
@@ -1337,7 +1734,7 @@ impl ViewService for ViewServer {
 
         let votable_at_height = request.get_ref().votable_at_height;
 
-        let notes = self
+        let notes = server
             .storage
             .notes_for_voting(address_index, votable_at_height)
             .await
@@ -1366,7 +1763,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::AssetsRequest>,
     ) -> Result<tonic::Response<Self::AssetsStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let pb::AssetsRequest {
             filtered,
@@ -1380,7 +1778,8 @@ impl ViewService for ViewServer {
 
         // Fetch assets from storage.
         let assets = if !filtered {
-            self.storage
+            server
+                .storage
                 .all_assets()
                 .await
                 .map_err(|e| tonic::Status::unavailable(format!("error fetching assets: {e}")))?
@@ -1400,7 +1799,8 @@ impl ViewService for ViewServer {
             ] {
                 if *include {
                     assets.extend(
-                        self.storage
+                        server
+                            .storage
                             .assets_matching(pattern.to_string())
                             .await
                             .map_err(|e| {
@@ -1435,7 +1835,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::TransactionInfoRequest>,
     ) -> Result<tonic::Response<Self::TransactionInfoStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
         // Unpack optional start/end heights.
         let start_height = if request.get_ref().start_height == 0 {
             None
@@ -1449,13 +1850,13 @@ impl ViewService for ViewServer {
         };
 
         // Fetch transactions from storage.
-        let txs = self
+        let txs = server
             .storage
             .transactions(start_height, end_height)
             .await
             .map_err(|e| tonic::Status::unavailable(format!("error fetching transactions: {e}")))?;
 
-        let self2 = self.clone();
+        let self2 = server.clone();
         let stream = try_stream! {
             for tx in txs {
 
@@ -1483,11 +1884,12 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::WitnessRequest>,
     ) -> Result<tonic::Response<WitnessResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         // Acquire a read lock for the SCT that will live for the entire request,
         // so that all auth paths are relative to the same SCT root.
-        let sct = self.state_commitment_tree.read().await;
+        let sct = server.state_commitment_tree.read().await;
 
         // Read the SCT root
         let anchor = sct.root();
@@ -1564,6 +1966,7 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::WitnessAndBuildRequest>,
     ) -> Result<tonic::Response<Self::WitnessAndBuildStream>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
         let pb::WitnessAndBuildRequest {
             transaction_plan,
             authorization_data,
@@ -1585,7 +1988,7 @@ impl ViewService for ViewServer {
             transaction_plan: Some(transaction_plan.clone().into()),
         };
 
-        let witness_data: WitnessData = self
+        let witness_data: WitnessData = server
             .witness(tonic::Request::new(witness_request))
             .await?
             .into_inner()
@@ -1596,45 +1999,62 @@ impl ViewService for ViewServer {
             .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
 
         let fvk =
-            self.storage.full_viewing_key().await.map_err(|_| {
+            server.storage.full_viewing_key().await.map_err(|_| {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
-        let transaction = Some(
-            transaction_plan
-                // TODO: calling `.build` should provide some mechanism to get progress
-                // updates
-                .build(&fvk, &witness_data, &authorization_data)
-                .map_err(|_| tonic::Status::failed_precondition("Error building transaction"))?
-                .into(),
-        );
+        // Build the transaction on a blocking thread, since proving is expensive,
+        // and report progress as each action is built.
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let build = tokio::task::spawn_blocking(move || {
+            transaction_plan.build_with_progress(
+                &fvk,
+                &witness_data,
+                &authorization_data,
+                |progress| {
+                    // The receiver is only dropped if the client went away.
+                    let _ = progress_tx.send(progress.fraction());
+                },
+            )
+        });
 
         let stream = try_stream! {
+            while let Some(progress) = progress_rx.recv().await {
+                yield pb::WitnessAndBuildResponse {
+                    status: Some(pb::witness_and_build_response::Status::BuildProgress(
+                        pb::witness_and_build_response::BuildProgress { progress },
+                    )),
+                };
+            }
+
+            let transaction = build
+                .await
+                .map_err(|e| {
+                    tonic::Status::unavailable(format!("error witnessing transaction: {e}"))
+                })?
+                .map_err(|_| tonic::Status::failed_precondition("Error building transaction"))?;
             yield pb::WitnessAndBuildResponse {
                 status: Some(pb::witness_and_build_response::Status::Complete(
-                    pb::witness_and_build_response::Complete { transaction },
+                    pb::witness_and_build_response::Complete {
+                        transaction: Some(transaction.into()),
+                    },
                 )),
             }
         };
 
-        Ok(tonic::Response::new(
-            stream
-                .map_err(|e: anyhow::Error| {
-                    tonic::Status::unavailable(format!("error witnessing transaction: {e}"))
-                })
-                .boxed(),
-        ))
+        Ok(tonic::Response::new(stream.boxed()))
     }
 
     #[instrument(skip_all, level = "trace")]
     async fn app_parameters(
         &self,
-        _request: tonic::Request<pb::AppParametersRequest>,
+        request: tonic::Request<pb::AppParametersRequest>,
     ) -> Result<tonic::Response<pb::AppParametersResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let parameters =
-            self.storage.app_params().await.map_err(|e| {
+            server.storage.app_params().await.map_err(|e| {
                 tonic::Status::unavailable(format!("error getting app params: {e}"))
             })?;
 
@@ -1648,12 +2068,13 @@ impl ViewService for ViewServer {
     #[instrument(skip_all, level = "trace")]
     async fn gas_prices(
         &self,
-        _request: tonic::Request<pb::GasPricesRequest>,
+        request: tonic::Request<pb::GasPricesRequest>,
     ) -> Result<tonic::Response<pb::GasPricesResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let gas_prices =
-            self.storage.gas_prices().await.map_err(|e| {
+            server.storage.gas_prices().await.map_err(|e| {
                 tonic::Status::unavailable(format!("error getting gas prices: {e}"))
             })?;
 
@@ -1668,12 +2089,13 @@ impl ViewService for ViewServer {
     #[instrument(skip_all, level = "trace")]
     async fn fmd_parameters(
         &self,
-        _request: tonic::Request<pb::FmdParametersRequest>,
+        request: tonic::Request<pb::FmdParametersRequest>,
     ) -> Result<tonic::Response<pb::FmdParametersResponse>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let parameters =
-            self.storage.fmd_parameters().await.map_err(|e| {
+            server.storage.fmd_parameters().await.map_err(|e| {
                 tonic::Status::unavailable(format!("error getting FMD params: {e}"))
             })?;
 
@@ -1689,7 +2111,8 @@ impl ViewService for ViewServer {
         &self,
         request: tonic::Request<pb::OwnedPositionIdsRequest>,
     ) -> Result<tonic::Response<Self::OwnedPositionIdsStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
         let pb::OwnedPositionIdsRequest {
             position_state,
@@ -1708,7 +2131,7 @@ impl ViewService for ViewServer {
             .map_err(|e: anyhow::Error| e.context("could not decode trading pair"))
             .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
 
-        let ids = self
+        let ids = server
             .storage
             .owned_position_ids(position_state, trading_pair)
             .await
@@ -1734,19 +2157,86 @@ impl ViewService for ViewServer {
     #[instrument(skip_all, level = "trace")]
     async fn authorize_and_build(
         &self,
-        _request: tonic::Request<pb::AuthorizeAndBuildRequest>,
+        request: tonic::Request<pb::AuthorizeAndBuildRequest>,
     ) -> Result<tonic::Response<Self::AuthorizeAndBuildStream>, tonic::Status> {
-        unimplemented!("authorize_and_build")
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
+        let pb::AuthorizeAndBuildRequest {
+            transaction_plan,
+            pre_authorizations,
+        } = request.into_inner();
+
+        let transaction_plan: TransactionPlan = transaction_plan
+            .ok_or_else(|| tonic::Status::invalid_argument("missing transaction plan"))?
+            .try_into()
+            .map_err(|e: anyhow::Error| e.context("could not decode transaction plan"))
+            .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
+
+        let pre_authorizations = pre_authorizations
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<anyhow::Result<Vec<PreAuthorization>>>()
+            .map_err(|e| e.context("could not decode pre-authorizations"))
+            .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
+
+        let custody = server.custody.as_ref().ok_or_else(|| {
+            tonic::Status::failed_precondition("no custody service is configured for this wallet")
+        })?;
+
+        // 1. Request authorization from the custody service. The lock is only
+        // held to start the request, not while it is pending.
+        let authorization = custody
+            .lock()
+            .map_err(|_| tonic::Status::unavailable("unable to lock custody service"))?
+            .authorize(AuthorizeRequest {
+                plan: transaction_plan.clone(),
+                pre_authorizations,
+            });
+        let authorization_data: AuthorizationData = authorization
+            .await
+            .map_err(|e| tonic::Status::permission_denied(format!("authorization failed: {e:#}")))?
+            .data
+            .ok_or_else(|| tonic::Status::internal("custody returned no authorization data"))?
+            .try_into()
+            .map_err(|e: anyhow::Error| e.context("could not decode authorization data"))
+            .map_err(|e| tonic::Status::internal(format!("{:#}", e)))?;
+
+        // 2. Witness and build the authorized transaction.
+        let stream = server
+            .witness_and_build(tonic::Request::new(pb::WitnessAndBuildRequest {
+                transaction_plan: Some(transaction_plan.into()),
+                authorization_data: Some(authorization_data.into()),
+            }))
+            .await?
+            .into_inner()
+            .map_ok(|response| {
+                use pb::authorize_and_build_response as ab;
+                use pb::witness_and_build_response as wb;
+
+                let status = response.status.map(|status| match status {
+                    wb::Status::BuildProgress(wb::BuildProgress { progress }) => {
+                        ab::Status::BuildProgress(ab::BuildProgress { progress })
+                    }
+                    wb::Status::Complete(wb::Complete { transaction }) => {
+                        ab::Status::Complete(ab::Complete { transaction })
+                    }
+                });
+                pb::AuthorizeAndBuildResponse { status }
+            })
+            .boxed();
+
+        Ok(tonic::Response::new(stream))
     }
 
     #[instrument(skip_all, level = "trace")]
     async fn unclaimed_swaps(
         &self,
-        _: tonic::Request<pb::UnclaimedSwapsRequest>,
+        request: tonic::Request<pb::UnclaimedSwapsRequest>,
     ) -> Result<tonic::Response<Self::UnclaimedSwapsStream>, tonic::Status> {
-        self.check_worker().await?;
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
 
-        let swaps = self.storage.unclaimed_swaps().await.map_err(|e| {
+        let swaps = server.storage.unclaimed_swaps().await.map_err(|e| {
             tonic::Status::unavailable(format!("error fetching unclaimed swaps: {e}"))
         })?;
 
@@ -1767,12 +2257,43 @@ impl ViewService for ViewServer {
         ))
     }
 
+    #[instrument(skip_all, level = "trace")]
+    async fn swap_claims(
+        &self,
+        request: tonic::Request<pb::SwapClaimsRequest>,
+    ) -> Result<tonic::Response<Self::SwapClaimsStream>, tonic::Status> {
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
+
+        let claims =
+            server.storage.swap_claims().await.map_err(|e| {
+                tonic::Status::unavailable(format!("error fetching swap claims: {e}"))
+            })?;
+
+        let stream = try_stream! {
+            for claim in claims {
+                yield pb::SwapClaimsResponse{
+                    claim: Some(claim.into()),
+                }
+            }
+        };
+
+        Ok(tonic::Response::new(
+            stream
+                .map_err(|e: anyhow::Error| {
+                    tonic::Status::unavailable(format!("error getting swap claims: {e}"))
+                })
+                .boxed(),
+        ))
+    }
+
     #[instrument(skip_all, level = "trace")]
     async fn wallet_id(
         &self,
-        _: Request<WalletIdRequest>,
+        request: Request<WalletIdRequest>,
     ) -> Result<Response<WalletIdResponse>, Status> {
-        let fvk = self.storage.full_viewing_key().await.map_err(|e| {
+        let server = self.select_wallet(&request)?;
+        let fvk = server.storage.full_viewing_key().await.map_err(|e| {
             Status::failed_precondition(format!("Error retrieving full viewing key: {e}"))
         })?;
 
@@ -1786,6 +2307,7 @@ impl ViewService for ViewServer {
         &self,
         request: Request<AssetMetadataByIdRequest>,
     ) -> Result<Response<AssetMetadataByIdResponse>, Status> {
+        let server = self.select_wallet(&request)?;
         let asset_id = request
             .into_inner()
             .asset_id
@@ -1793,7 +2315,7 @@ impl ViewService for ViewServer {
             .try_into()
             .map_err(|e| Status::invalid_argument(format!("{e:#}")))?;
 
-        let metadata = self
+        let metadata = server
             .storage
             .asset_by_id(&asset_id)
             .await
@@ -1807,16 +2329,527 @@ impl ViewService for ViewServer {
     #[instrument(skip_all, level = "trace")]
     async fn delegations_by_address_index(
         &self,
-        _request: tonic::Request<pb::DelegationsByAddressIndexRequest>,
+        request: tonic::Request<pb::DelegationsByAddressIndexRequest>,
     ) -> Result<tonic::Response<Self::DelegationsByAddressIndexStream>, tonic::Status> {
-        unimplemented!("delegations_by_address_index")
+        use pb::delegations_by_address_index_request::Filter;
+
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
+        let request = request.into_inner();
+
+        let address_index: AddressIndex = request
+            .address_index
+            .context("missing address index")
+            .and_then(TryInto::try_into)
+            .map_err(|e| {
+                tonic::Status::invalid_argument(format!("invalid address index: {e:#}"))
+            })?;
+        let filter = Filter::try_from(request.filter)
+            .map_err(|_| tonic::Status::invalid_argument("invalid filter"))?;
+
+        let balances = server.account_balances(address_index.account).await?;
+        let as_of_height = server
+            .storage
+            .last_sync_height()
+            .await
+            .map_err(|e| tonic::Status::internal(format!("could not get sync height: {e:#}")))?
+            .unwrap_or_default();
+
+        // Only active validators are returned, unless all validators were requested.
+        let validators = server
+            .stake_query_client()
+            .await?
+            .validator_info(ValidatorInfoRequest {
+                show_inactive: filter == Filter::All,
+            })
+            .await?
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|response| {
+                response
+                    .validator_info
+                    .context("missing validator info")?
+                    .try_into()
+            })
+            .collect::<anyhow::Result<Vec<validator::Info>>>()
+            .map_err(|e| tonic::Status::internal(format!("invalid validator info: {e:#}")))?;
+
+        let mut responses = Vec::new();
+        for info in validators {
+            let delegation_token = DelegationToken::from(&info.validator.identity_key);
+            let amount = balances
+                .get(&delegation_token.id())
+                .copied()
+                .unwrap_or_default();
+            if filter == Filter::AllActiveWithNonzeroBalances && amount == Amount::zero() {
+                continue;
+            }
+
+            // Value the delegation tokens at the validator's current exchange rate.
+            let value_view = ValueView::KnownAssetId {
+                amount,
+                metadata: delegation_token.denom(),
+                equivalent_values: vec![EquivalentValue {
+                    equivalent_amount: info.rate_data.unbonded_amount(amount),
+                    numeraire: STAKING_TOKEN_DENOM.clone(),
+                    as_of_height,
+                }],
+                extended_metadata: Some(validator_info_any(info)),
+            };
+            responses.push(Ok(pb::DelegationsByAddressIndexResponse {
+                value_view: Some(value_view.into()),
+            }));
+        }
+
+        Ok(tonic::Response::new(stream::iter(responses).boxed()))
     }
 
     #[instrument(skip_all, level = "trace")]
     async fn unbonding_tokens_by_address_index(
         &self,
-        _request: tonic::Request<pb::UnbondingTokensByAddressIndexRequest>,
+        request: tonic::Request<pb::UnbondingTokensByAddressIndexRequest>,
     ) -> Result<tonic::Response<Self::UnbondingTokensByAddressIndexStream>, tonic::Status> {
-        unimplemented!("unbonding_tokens_by_address_index currently only implemented on web")
+        use pb::unbonding_tokens_by_address_index_request::Filter;
+
+        let server = self.select_wallet(&request)?;
+        server.check_worker().await?;
+        let request = request.into_inner();
+
+        let address_index: AddressIndex = request
+            .address_index
+            .context("missing address index")
+            .and_then(TryInto::try_into)
+            .map_err(|e| {
+                tonic::Status::invalid_argument(format!("invalid address index: {e:#}"))
+            })?;
+        let filter = Filter::try_from(request.filter)
+            .map_err(|_| tonic::Status::invalid_argument("invalid filter"))?;
+
+        let balances = server.account_balances(address_index.account).await?;
+        let current_height = server
+            .storage
+            .last_sync_height()
+            .await
+            .map_err(|e| tonic::Status::internal(format!("could not get sync height: {e:#}")))?
+            .unwrap_or_default();
+        let unbonding_delay = server
+            .storage
+            .app_params()
+            .await
+            .map_err(|e| tonic::Status::internal(format!("could not get app params: {e:#}")))?
+            .stake_params
+            .unbonding_delay;
+
+        let mut client = server.stake_query_client().await?;
+        let mut responses = Vec::new();
+        for (asset_id, amount) in balances {
+            let Some(metadata) = server.storage.asset_by_id(&asset_id).await.map_err(|e| {
+                tonic::Status::internal(format!("could not get asset metadata: {e:#}"))
+            })?
+            else {
+                continue;
+            };
+            let Ok(unbonding_token) = UnbondingToken::try_from(metadata.clone()) else {
+                continue;
+            };
+
+            let info: validator::Info = client
+                .get_validator_info(GetValidatorInfoRequest {
+                    identity_key: Some(unbonding_token.validator().into()),
+                })
+                .await?
+                .into_inner()
+                .validator_info
+                .context("missing validator info")
+                .and_then(TryInto::try_into)
+                .map_err(|e| tonic::Status::internal(format!("invalid validator info: {e:#}")))?;
+
+            let claimable = unbonding_claimable(
+                &info.status.bonding_state,
+                unbonding_token.unbonding_start_height(),
+                unbonding_delay,
+                current_height,
+            );
+            if !unbonding_filter_matches(filter, claimable) {
+                continue;
+            }
+
+            let value_view = ValueView::KnownAssetId {
+                amount,
+                metadata,
+                equivalent_values: vec![],
+                extended_metadata: Some(validator_info_any(info)),
+            };
+            responses.push(Ok(pb::UnbondingTokensByAddressIndexResponse {
+                value_view: Some(value_view.into()),
+                claimable,
+            }));
+        }
+
+        Ok(tonic::Response::new(stream::iter(responses).boxed()))
+    }
+}
+
+/// Returns whether the unbonding tokens of an undelegation started at
+/// `start_height` can be claimed at `current_height`.
+///
+/// Tokens are claimable once the validator's pool has unbonded, or the
+/// unbonding delay has passed, as for an `UndelegateClaim`.
+fn unbonding_claimable(
+    bonding_state: &validator::BondingState,
+    start_height: u64,
+    unbonding_delay: u64,
+    current_height: u64,
+) -> bool {
+    bonding_state
+        .unbonding_height(start_height, unbonding_delay)
+        .map_or(true, |unbonding_height| current_height >= unbonding_height)
+}
+
+/// Returns whether unbonding tokens are returned for `filter`, given whether
+/// they're `claimable`.
+fn unbonding_filter_matches(
+    filter: pb::unbonding_tokens_by_address_index_request::Filter,
+    claimable: bool,
+) -> bool {
+    use pb::unbonding_tokens_by_address_index_request::Filter;
+
+    match filter {
+        Filter::Unspecified => true,
+        Filter::Claimable => claimable,
+        Filter::NotYetClaimable => !claimable,
+    }
+}
+
+/// Returns whether an unclaimed swap should be claimed at sync height `height`,
+/// given its latest automatic claim, if any.
+///
+/// Claims that couldn't be submitted are retried after
+/// [`SWAP_CLAIM_RETRY_BLOCKS`], doubling with each attempt, and claims that
+/// were submitted are resubmitted after [`SWAP_CLAIM_RESUBMIT_BLOCKS`].
+fn swap_claim_due(claim: Option<&SwapClaimRecord>, height: u64) -> bool {
+    let Some(claim) = claim else {
+        return true;
+    };
+    let delay = match claim.result {
+        Ok(_) => SWAP_CLAIM_RESUBMIT_BLOCKS,
+        Err(_) => {
+            let backoff = 1u64 << claim.attempts.saturating_sub(1).min(16);
+            SWAP_CLAIM_RETRY_BLOCKS
+                .saturating_mul(backoff)
+                .min(MAX_SWAP_CLAIM_RETRY_BLOCKS)
+        }
+    };
+    height >= claim.height_submitted.saturating_add(delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_app::params::AppParameters;
+    use penumbra_asset::STAKING_TOKEN_ASSET_ID;
+    use penumbra_custody::soft_kms::SoftKms;
+    use penumbra_keys::{
+        keys::{Bip44Path, SpendKey},
+        test_keys,
+    };
+    use penumbra_proto::custody::v1::{
+        custody_service_client::CustodyServiceClient,
+        custody_service_server::CustodyServiceServer,
+    };
+    use penumbra_shielded_pool::{Note, OutputPlan, SpendPlan};
+
+    use super::*;
+
+    /// Derives the spend key of another account of the test wallet.
+    fn spend_key(account: u32) -> SpendKey {
+        SpendKey::from_seed_phrase_bip44(
+            test_keys::SEED_PHRASE.parse().unwrap(),
+            &Bip44Path::new(account),
+        )
+    }
+
+    async fn wallet_storage(fvk: &FullViewingKey) -> anyhow::Result<Storage> {
+        Storage::initialize(None::<&str>, fvk.clone(), AppParameters::default()).await
+    }
+
+    /// Builds a server for the wallets in `storages`, without a sync worker or
+    /// a node. The first storage is the default wallet.
+    async fn test_server(storages: Vec<Storage>) -> anyhow::Result<ViewServer> {
+        let mut wallet_ids = Vec::new();
+        let mut wallets = BTreeMap::new();
+        for storage in storages {
+            let wallet_id = storage.full_viewing_key().await?.wallet_id();
+            let sync_height = storage.last_sync_height().await?.unwrap_or(0);
+            let (_, sync_height_rx) = watch::channel(sync_height);
+            wallet_ids.push(wallet_id);
+            wallets.insert(
+                wallet_id,
+                Wallet {
+                    storage,
+                    state_commitment_tree: Default::default(),
+                    sync_height_rx,
+                    custody: None,
+                },
+            );
+        }
+        let default = wallets[&wallet_ids[0]].clone();
+
+        Ok(ViewServer {
+            wallet_id: wallet_ids[0],
+            storage: default.storage,
+            error_slot: Default::default(),
+            state_commitment_tree: default.state_commitment_tree,
+            node: "http://127.0.0.1:26657".parse()?,
+            sync_height_rx: default.sync_height_rx,
+            custody: None,
+            wallets: Arc::new(wallets),
+        })
+    }
+
+    fn for_wallet<T>(message: T, wallet_id: WalletId) -> Request<T> {
+        let mut request = Request::new(message);
+        request
+            .metadata_mut()
+            .insert(WALLET_ID_METADATA_KEY, wallet_id.to_string().parse().unwrap());
+        request
+    }
+
+    async fn served_wallet_id(
+        server: &ViewServer,
+        request: Request<WalletIdRequest>,
+    ) -> Result<WalletId, tonic::Status> {
+        let wallet_id = server
+            .wallet_id(request)
+            .await?
+            .into_inner()
+            .wallet_id
+            .expect("wallet id is set")
+            .try_into()
+            .expect("wallet id is valid");
+        Ok(wallet_id)
+    }
+
+    #[tokio::test]
+    async fn requests_are_served_from_the_selected_wallet() -> anyhow::Result<()> {
+        let default_fvk = test_keys::FULL_VIEWING_KEY.clone();
+        let other_fvk = spend_key(1).full_viewing_key().clone();
+        let server = test_server(vec![
+            wallet_storage(&default_fvk).await?,
+            wallet_storage(&other_fvk).await?,
+        ])
+        .await?;
+
+        let selected = for_wallet(WalletIdRequest {}, other_fvk.wallet_id());
+        assert_eq!(served_wallet_id(&server, selected).await?, other_fvk.wallet_id());
+        let selected = for_wallet(WalletIdRequest {}, default_fvk.wallet_id());
+        assert_eq!(served_wallet_id(&server, selected).await?, default_fvk.wallet_id());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unaddressed_requests_are_served_by_the_default_wallet() -> anyhow::Result<()> {
+        let default_fvk = test_keys::FULL_VIEWING_KEY.clone();
+        let other_fvk = spend_key(1).full_viewing_key().clone();
+        let server = test_server(vec![
+            wallet_storage(&default_fvk).await?,
+            wallet_storage(&other_fvk).await?,
+        ])
+        .await?;
+
+        assert_eq!(
+            served_wallet_id(&server, Request::new(WalletIdRequest {})).await?,
+            default_fvk.wallet_id()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn requests_for_unknown_wallets_are_not_found() -> anyhow::Result<()> {
+        let server = test_server(vec![wallet_storage(&test_keys::FULL_VIEWING_KEY).await?]).await?;
+
+        let unknown = for_wallet(WalletIdRequest {}, spend_key(2).full_viewing_key().wallet_id());
+        let status = served_wallet_id(&server, unknown).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let mut invalid = Request::new(WalletIdRequest {});
+        invalid
+            .metadata_mut()
+            .insert(WALLET_ID_METADATA_KEY, "not-a-wallet-id".parse().unwrap());
+        let status = served_wallet_id(&server, invalid).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        Ok(())
+    }
+
+    /// Returns a plan spending a note of the test wallet, inserted into the
+    /// server's SCT, to an output of the same value.
+    async fn self_transfer_plan(server: &ViewServer) -> TransactionPlan {
+        let value = Value {
+            amount: 10_000u64.into(),
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        };
+        let note = Note::generate(&mut OsRng, &test_keys::ADDRESS_0, value);
+
+        let mut sct = server.state_commitment_tree.write().await;
+        sct.insert(penumbra_tct::Witness::Keep, note.commit()).unwrap();
+        let position = sct.witness(note.commit()).unwrap().position();
+        drop(sct);
+
+        TransactionPlan {
+            actions: vec![
+                SpendPlan::new(&mut OsRng, note, position).into(),
+                OutputPlan::new(&mut OsRng, value, test_keys::ADDRESS_1.clone()).into(),
+            ],
+            transaction_parameters: TransactionParameters {
+                chain_id: "penumbra-test".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn authorize_and_build_reports_progress_then_signs() -> anyhow::Result<()> {
+        let fvk = test_keys::FULL_VIEWING_KEY.clone();
+        let custody = CustodyServiceClient::new(CustodyServiceServer::new(SoftKms::new(
+            test_keys::SPEND_KEY.clone().into(),
+        )));
+        let server = test_server(vec![wallet_storage(&fvk).await?])
+            .await?
+            .with_custody(custody);
+        let plan = self_transfer_plan(&server).await;
+
+        let responses: Vec<_> = server
+            .authorize_and_build(Request::new(pb::AuthorizeAndBuildRequest {
+                transaction_plan: Some(plan.clone().into()),
+                pre_authorizations: Vec::new(),
+            }))
+            .await?
+            .into_inner()
+            .try_collect()
+            .await?;
+
+        use pb::authorize_and_build_response::{BuildProgress, Complete, Status};
+        let (complete, progress) = responses.split_last().expect("the build completes");
+        let progress: Vec<f32> = progress
+            .iter()
+            .map(|response| match response.status {
+                Some(Status::BuildProgress(BuildProgress { progress })) => progress,
+                ref status => panic!("expected build progress, got {status:?}"),
+            })
+            .collect();
+        assert_eq!(progress, vec![0.5, 1.0]);
+
+        let Some(Status::Complete(Complete {
+            transaction: Some(transaction),
+        })) = complete.status.clone()
+        else {
+            panic!("expected a complete transaction, got {:?}", complete.status);
+        };
+        let transaction: Transaction = transaction.try_into()?;
+
+        // The transaction is the one planned, and is signed by the wallet.
+        use penumbra_transaction::txhash::{AuthorizingData as _, EffectingData as _};
+        let effect_hash = transaction.effect_hash();
+        assert_eq!(effect_hash, plan.effect_hash(&fvk)?);
+        transaction
+            .binding_verification_key()
+            .verify(transaction.auth_hash().as_bytes(), transaction.binding_sig())?;
+        for spend in transaction.spends() {
+            spend.body.rk.verify(effect_hash.as_ref(), &spend.auth_sig)?;
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn authorize_and_build_requires_custody() -> anyhow::Result<()> {
+        let server = test_server(vec![wallet_storage(&test_keys::FULL_VIEWING_KEY).await?]).await?;
+        let plan = self_transfer_plan(&server).await;
+
+        let status = server
+            .authorize_and_build(Request::new(pb::AuthorizeAndBuildRequest {
+                transaction_plan: Some(plan.into()),
+                pre_authorizations: Vec::new(),
+            }))
+            .await
+            .err()
+            .expect("building without custody fails");
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unbonding_tokens_are_claimable_after_the_unbonding_delay() {
+        let bonded = validator::BondingState::Bonded;
+        assert!(!unbonding_claimable(&bonded, 100, 50, 149));
+        assert!(unbonding_claimable(&bonded, 100, 50, 150));
+
+        // Tokens are claimable once the pool has unbonded, even before the delay.
+        let unbonding = validator::BondingState::Unbonding {
+            unbonds_at_height: 120,
+        };
+        assert!(!unbonding_claimable(&unbonding, 100, 50, 119));
+        assert!(unbonding_claimable(&unbonding, 100, 50, 120));
+
+        let unbonded = validator::BondingState::Unbonded;
+        assert!(unbonding_claimable(&unbonded, 100, 50, 100));
+    }
+
+    #[test]
+    fn unbonding_tokens_are_filtered_by_claimability() {
+        use pb::unbonding_tokens_by_address_index_request::Filter;
+
+        for claimable in [true, false] {
+            assert!(unbonding_filter_matches(Filter::Unspecified, claimable));
+            assert_eq!(unbonding_filter_matches(Filter::Claimable, claimable), claimable);
+            assert_eq!(unbonding_filter_matches(Filter::NotYetClaimable, claimable), !claimable);
+        }
+    }
+
+    fn claim(height_submitted: u64, attempts: u32, broadcast: bool) -> SwapClaimRecord {
+        SwapClaimRecord {
+            swap_commitment: StateCommitment::try_from([0; 32]).unwrap(),
+            height_submitted,
+            result: if broadcast {
+                Ok(TransactionId([0; 32]))
+            } else {
+                Err("custody rejected the claim".to_string())
+            },
+            height_claimed: None,
+            attempts,
+        }
+    }
+
+    #[test]
+    fn unclaimed_swaps_are_claimed_immediately() {
+        assert!(swap_claim_due(None, 0));
+    }
+
+    #[test]
+    fn failed_claims_are_retried_with_backoff() {
+        let first = claim(100, 1, false);
+        assert!(!swap_claim_due(Some(&first), 101));
+        assert!(swap_claim_due(Some(&first), 102));
+
+        let third = claim(100, 3, false);
+        assert!(!swap_claim_due(Some(&third), 107));
+        assert!(swap_claim_due(Some(&third), 108));
+
+        let many = claim(100, 30, false);
+        assert!(!swap_claim_due(Some(&many), 100 + MAX_SWAP_CLAIM_RETRY_BLOCKS - 1));
+        assert!(swap_claim_due(Some(&many), 100 + MAX_SWAP_CLAIM_RETRY_BLOCKS));
+    }
+
+    #[test]
+    fn undetected_claims_are_resubmitted() {
+        let submitted = claim(100, 1, true);
+        assert!(!swap_claim_due(Some(&submitted), 100 + SWAP_CLAIM_RESUBMIT_BLOCKS - 1));
+        assert!(swap_claim_due(Some(&submitted), 100 + SWAP_CLAIM_RESUBMIT_BLOCKS));
     }
 }
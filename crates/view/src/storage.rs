@@ -38,11 +38,11 @@ use penumbra_sct::{CommitmentSource, Nullifier};
 use penumbra_shielded_pool::{fmd, note, Note, Rseed};
 use penumbra_stake::{DelegationToken, IdentityKey};
 use penumbra_tct as tct;
-use penumbra_transaction::Transaction;
+use penumbra_transaction::{txhash::TransactionId, Transaction};
 use sct::TreeStore;
 use tct::StateCommitment;
 
-use crate::{sync::FilteredBlock, SpendableNoteRecord, SwapRecord};
+use crate::{sync::FilteredBlock, SpendableNoteRecord, SwapClaimRecord, SwapRecord};
 
 mod sct;
 
@@ -57,6 +57,10 @@ pub struct BalanceEntry {
 static SCHEMA_HASH: Lazy<String> =
     Lazy::new(|| hex::encode(Sha256::digest(include_str!("storage/schema.sql"))));
 
+/// The table of automatic swap claims, which is created when the database is
+/// opened rather than being part of the schema.
+static SWAP_CLAIMS_TABLE: &str = include_str!("storage/swap_claims.sql");
+
 #[derive(Clone)]
 pub struct Storage {
     pool: r2d2::Pool<SqliteConnectionManager>,
@@ -192,6 +196,8 @@ impl Storage {
                 );
             }
 
+            storage.pool.get()?.execute_batch(SWAP_CLAIMS_TABLE)?;
+
             Ok(storage)
         })
             .await?
@@ -214,6 +220,7 @@ impl Storage {
 
             // Create the tables
             tx.execute_batch(include_str!("storage/schema.sql"))?;
+            tx.execute_batch(SWAP_CLAIMS_TABLE)?;
 
             let params_bytes = params.encode_to_vec();
             tx.execute(
@@ -481,6 +488,65 @@ impl Storage {
         Ok(records)
     }
 
+    /// Records the outcome of an automatic claim of the swap with the given
+    /// commitment: either the ID of the broadcast claim transaction, or the
+    /// reason it could not be broadcast.
+    ///
+    /// This replaces the outcome of any previous claim of the swap, and counts
+    /// the new claim as another attempt.
+    pub async fn record_swap_claim(
+        &self,
+        swap_commitment: StateCommitment,
+        height_submitted: u64,
+        result: Result<TransactionId, String>,
+    ) -> anyhow::Result<()> {
+        let swap_commitment = swap_commitment.0.to_bytes().to_vec();
+        let (tx_hash, error) = match result {
+            Ok(id) => (Some(id.0.to_vec()), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            pool.get()?
+                .execute(
+                    "INSERT INTO swap_claims (swap_commitment, height_submitted, tx_hash, error)
+                    VALUES (?1, ?2, ?3, ?4)
+                    ON CONFLICT (swap_commitment) DO UPDATE SET
+                        height_submitted = excluded.height_submitted,
+                        tx_hash = excluded.tx_hash,
+                        error = excluded.error,
+                        attempts = swap_claims.attempts + 1",
+                    (swap_commitment, height_submitted, tx_hash, error),
+                )
+                .map_err(anyhow::Error::from)
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Query for all automatic swap claims, along with the height at which
+    /// each swap was claimed, if it has been detected.
+    pub async fn swap_claims(&self) -> anyhow::Result<Vec<SwapClaimRecord>> {
+        let pool = self.pool.clone();
+
+        let records = spawn_blocking(move || {
+            pool.get()?
+                .prepare(
+                    "SELECT swap_claims.*, swaps.height_claimed
+                    FROM swap_claims
+                    JOIN swaps ON swap_claims.swap_commitment = swaps.swap_commitment",
+                )?
+                .query_and_then((), |record| record.try_into())?
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await??;
+
+        Ok(records)
+    }
+
     /// Query for a nullifier's status, optionally waiting until the nullifier is detected.
     pub async fn nullifier_status(
         &self,
@@ -1733,3 +1799,49 @@ impl Storage {
         Ok(records)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use penumbra_keys::test_keys;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn swap_claims_table_is_created_lazily() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = camino::Utf8PathBuf::try_from(dir.path().join("pcli-view.sqlite"))?;
+
+        // Create a database as it was before the swap claims table existed.
+        let storage = Storage::initialize(
+            Some(&path),
+            test_keys::FULL_VIEWING_KEY.clone(),
+            AppParameters::default(),
+        )
+        .await?;
+        storage.pool.get()?.execute_batch("DROP TABLE swap_claims")?;
+        drop(storage);
+
+        let storage = Storage::load(&path).await?;
+
+        let swap_commitment = StateCommitment::try_from([1; 32])?;
+        storage
+            .record_swap_claim(swap_commitment, 10, Err("rejected".to_string()))
+            .await?;
+        storage
+            .record_swap_claim(swap_commitment, 12, Ok(TransactionId([2; 32])))
+            .await?;
+
+        let (height_submitted, tx_hash, error, attempts): (u64, Vec<u8>, Option<String>, u32) =
+            storage.pool.get()?.query_row(
+                "SELECT height_submitted, tx_hash, error, attempts FROM swap_claims",
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )?;
+        assert_eq!(height_submitted, 12);
+        assert_eq!(tx_hash, vec![2; 32]);
+        assert_eq!(error, None);
+        assert_eq!(attempts, 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+-- This table records the claims submitted automatically for swaps in the
+-- swaps table. It isn't part of schema.sql, and is instead created when the
+-- database is opened, so that existing databases can claim swaps without being
+-- reset.
+CREATE TABLE IF NOT EXISTS swap_claims (
+    swap_commitment         BLOB PRIMARY KEY NOT NULL,
+    -- the sync height at which the latest claim was submitted
+    height_submitted        BIGINT NOT NULL,
+    -- null if the latest claim could not be broadcast, otherwise its tx hash
+    tx_hash                 BLOB,
+    -- null if the latest claim was broadcast, otherwise the reason it wasn't
+    error                   TEXT,
+    -- the number of claims submitted, including retries
+    attempts                BIGINT NOT NULL DEFAULT 1
+);
@@ -2,6 +2,7 @@ use penumbra_dex::{swap::SwapPlaintext, BatchSwapOutputData};
 use penumbra_proto::{view::v1 as pb, DomainType};
 use penumbra_sct::{CommitmentSource, Nullifier};
 use penumbra_tct as tct;
+use penumbra_transaction::txhash::TransactionId;
 
 use r2d2_sqlite::rusqlite::Row;
 use serde::{Deserialize, Serialize};
@@ -83,3 +84,79 @@ impl TryFrom<&Row<'_>> for SwapRecord {
         })
     }
 }
+
+/// A claim of a swap submitted automatically by the view service.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "pb::SwapClaimRecord", into = "pb::SwapClaimRecord")]
+pub struct SwapClaimRecord {
+    pub swap_commitment: tct::StateCommitment,
+    /// The sync height at which the latest claim was submitted.
+    pub height_submitted: u64,
+    /// The latest claim transaction, or the reason it could not be broadcast.
+    pub result: Result<TransactionId, String>,
+    pub height_claimed: Option<u64>,
+    /// The number of claims submitted for the swap, including retries.
+    pub attempts: u32,
+}
+impl DomainType for SwapClaimRecord {
+    type Proto = pb::SwapClaimRecord;
+}
+impl From<SwapClaimRecord> for pb::SwapClaimRecord {
+    fn from(msg: SwapClaimRecord) -> Self {
+        let (transaction_id, error) = match msg.result {
+            Ok(id) => (Some(id.into()), String::new()),
+            Err(error) => (None, error),
+        };
+        pb::SwapClaimRecord {
+            swap_commitment: Some(msg.swap_commitment.into()),
+            height_submitted: msg.height_submitted,
+            transaction_id,
+            error,
+            height_claimed: msg.height_claimed.unwrap_or(0),
+            attempts: msg.attempts,
+        }
+    }
+}
+
+impl TryFrom<pb::SwapClaimRecord> for SwapClaimRecord {
+    type Error = anyhow::Error;
+    fn try_from(value: pb::SwapClaimRecord) -> Result<Self, Self::Error> {
+        let result = match value.transaction_id {
+            Some(id) => Ok(id.try_into()?),
+            None if !value.error.is_empty() => Err(value.error),
+            None => anyhow::bail!("missing transaction_id"),
+        };
+        Ok(Self {
+            swap_commitment: value
+                .swap_commitment
+                .ok_or_else(|| anyhow::anyhow!("missing swap_commitment"))?
+                .try_into()?,
+            height_submitted: value.height_submitted,
+            result,
+            height_claimed: if value.height_claimed > 0 {
+                Some(value.height_claimed)
+            } else {
+                None
+            },
+            attempts: value.attempts,
+        })
+    }
+}
+
+impl TryFrom<&Row<'_>> for SwapClaimRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        let result = match row.get::<_, Option<Vec<u8>>>("tx_hash")? {
+            Some(tx_hash) => Ok(TransactionId(tx_hash[..].try_into()?)),
+            None => Err(row.get::<_, Option<String>>("error")?.unwrap_or_default()),
+        };
+        Ok(Self {
+            swap_commitment: row.get::<_, Vec<u8>>("swap_commitment")?[..].try_into()?,
+            height_submitted: row.get("height_submitted")?,
+            result,
+            height_claimed: row.get("height_claimed")?,
+            attempts: row.get("attempts")?,
+        })
+    }
+}
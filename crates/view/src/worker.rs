@@ -5,6 +5,7 @@ use std::{
 };
 
 use anyhow::Context;
+use penumbra_asset::asset::{self, Metadata};
 use penumbra_auction::auction::AuctionNft;
 use penumbra_compact_block::CompactBlock;
 use penumbra_dex::lp::{position, LpNft};
@@ -21,7 +22,7 @@ use penumbra_proto::core::{
         },
         shielded_pool::v1::{
             query_service_client::QueryServiceClient as ShieldedPoolQueryServiceClient,
-            AssetMetadataByIdRequest,
+            AssetMetadataByIdRequest, AssetMetadataByIdsRequest,
         },
     },
 };
@@ -40,71 +41,95 @@ use crate::{
 // The maximum size of a compact block, in bytes (12MB).
 const MAX_CB_SIZE_BYTES: usize = 12 * 1024 * 1024;
 
-pub struct Worker {
+/// The sync state of one of the wallets scanned by a [`Worker`].
+struct WalletSync {
     storage: Storage,
     sct: Arc<RwLock<penumbra_tct::Tree>>,
     fvk: FullViewingKey, // TODO: notifications (see TODOs on ViewService)
-    error_slot: Arc<Mutex<Option<anyhow::Error>>>,
     sync_height_tx: watch::Sender<u64>,
+}
+
+/// Syncs one or more wallets with the chain.
+///
+/// Compact blocks are downloaded once and scanned with each wallet's full
+/// viewing key, so serving several wallets costs no more bandwidth than
+/// serving one.
+pub struct Worker {
+    wallets: Vec<WalletSync>,
+    error_slot: Arc<Mutex<Option<anyhow::Error>>>,
     /// Tonic channel used to create GRPC clients.
     channel: Channel,
 }
 
 impl Worker {
-    /// Creates a new worker, returning:
+    /// Creates a new worker syncing the wallets in `storages`, returning:
     ///
     /// - the worker itself;
-    /// - a shared, in-memory SCT instance;
-    /// - a shared error slot;
-    /// - a channel for notifying the client of sync progress.
+    /// - for each wallet, in order, a shared, in-memory SCT instance and a
+    ///   channel for notifying the client of sync progress;
+    /// - a shared error slot.
     #[instrument(skip_all)]
     pub async fn new(
-        storage: Storage,
+        storages: Vec<Storage>,
         channel: Channel,
     ) -> Result<
         (
             Self,
-            Arc<RwLock<penumbra_tct::Tree>>,
+            Vec<(Arc<RwLock<penumbra_tct::Tree>>, watch::Receiver<u64>)>,
             Arc<Mutex<Option<anyhow::Error>>>,
-            watch::Receiver<u64>,
         ),
         anyhow::Error,
     > {
         tracing::trace!("constructing view server worker");
-        let fvk = storage
-            .full_viewing_key()
-            .await
-            .context("failed to retrieve full viewing key from storage")?
-            .tap(|_| tracing::debug!("retrieved full viewing key"));
+        let mut wallets = Vec::with_capacity(storages.len());
+        let mut handles = Vec::with_capacity(storages.len());
+        for storage in storages {
+            let fvk = storage
+                .full_viewing_key()
+                .await
+                .context("failed to retrieve full viewing key from storage")?
+                .tap(|fvk| tracing::debug!(wallet_id = %fvk.wallet_id(), "retrieved full viewing key"));
+
+            // Create a shared, in-memory SCT.
+            let sct = Arc::new(RwLock::new(storage.state_commitment_tree().await?));
+            // Create a channel for the worker to notify of sync height changes.
+            let (sync_height_tx, mut sync_height_rx) =
+                watch::channel(storage.last_sync_height().await?.unwrap_or(0));
+            // Mark the current height as seen, since it's not new.
+            sync_height_rx.borrow_and_update();
+
+            wallets.push(WalletSync {
+                storage,
+                sct: sct.clone(),
+                fvk,
+                sync_height_tx,
+            });
+            handles.push((sct, sync_height_rx));
+        }
 
-        // Create a shared, in-memory SCT.
-        let sct = Arc::new(RwLock::new(storage.state_commitment_tree().await?));
         // Create a shared error slot
         let error_slot = Arc::new(Mutex::new(None));
-        // Create a channel for the worker to notify of sync height changes.
-        let (sync_height_tx, mut sync_height_rx) =
-            watch::channel(storage.last_sync_height().await?.unwrap_or(0));
-        // Mark the current height as seen, since it's not new.
-        sync_height_rx.borrow_and_update();
 
         Ok((
             Self {
-                storage,
-                sct: sct.clone(),
-                fvk,
+                wallets,
                 error_slot: error_slot.clone(),
-                sync_height_tx,
                 channel,
             },
-            sct,
+            handles,
             error_slot,
-            sync_height_rx,
         ))
     }
 
+    /// Fetches the transactions in `filtered_block` relevant to the wallet it
+    /// was scanned for.
+    ///
+    /// All of the block's transactions are cached in `block_transactions`, so
+    /// that they are only downloaded once however many wallets need them.
     pub async fn fetch_transactions(
         &self,
         filtered_block: &mut FilteredBlock,
+        block_transactions: &mut Option<Vec<Transaction>>,
     ) -> anyhow::Result<Vec<Transaction>> {
         let spent_nullifiers = filtered_block
             .spent_nullifiers
@@ -135,8 +160,12 @@ impl Worker {
             "fetching full transaction data"
         );
 
-        let all_transactions =
-            fetch_transactions(self.channel.clone(), filtered_block.height).await?;
+        let all_transactions = match block_transactions {
+            Some(transactions) => transactions.clone(),
+            None => block_transactions
+                .insert(fetch_transactions(self.channel.clone(), filtered_block.height).await?)
+                .clone(),
+        };
 
         let mut transactions = Vec::new();
 
@@ -188,12 +217,20 @@ impl Worker {
         // Do a single sync run, up to whatever the latest block height is
         tracing::info!("starting client sync");
 
-        let start_height = self
-            .storage
-            .last_sync_height()
-            .await?
-            .map(|h| h + 1)
-            .unwrap_or(0);
+        // Each wallet resumes from its own sync height, so start from the
+        // earliest of them and skip blocks a wallet has already scanned.
+        let mut next_heights = Vec::with_capacity(self.wallets.len());
+        for wallet in &self.wallets {
+            next_heights.push(
+                wallet
+                    .storage
+                    .last_sync_height()
+                    .await?
+                    .map(|h| h + 1)
+                    .unwrap_or(0),
+            );
+        }
+        let start_height = next_heights.iter().copied().min().unwrap_or(0);
 
         let mut client = CompactBlockQueryServiceClient::new(self.channel.clone())
             .max_decoding_message_size(MAX_CB_SIZE_BYTES);
@@ -233,198 +270,270 @@ impl Worker {
             }
             expected_height += 1;
 
-            // Lock the SCT only while processing this block.
-            let mut sct_guard = self.sct.write().await;
-
-            if !block.requires_scanning() {
-                // Optimization: if the block is empty, seal the in-memory SCT,
-                // and skip touching the database:
-                sct_guard.end_block()?;
-                // We also need to end the epoch, since if there are no funding streams, then an
-                // epoch boundary won't necessarily require scanning:
-                if block.epoch_root.is_some() {
-                    sct_guard
-                        .end_epoch()
-                        .expect("ending the epoch must succeed");
+            // Transactions are downloaded at most once per block, however
+            // many wallets need them.
+            let mut block_transactions = None;
+            for (wallet, next_height) in self.wallets.iter().zip(next_heights.iter_mut()) {
+                if height < *next_height {
+                    continue;
                 }
-                self.storage.record_empty_block(height).await?;
-                // Notify all watchers of the new height we just recorded.
-                self.sync_height_tx.send(height)?;
-            } else {
-                // Otherwise, scan the block and commit its changes:
-                let mut filtered_block =
-                    scan_block(&self.fvk, &mut sct_guard, block, &self.storage).await?;
-
-                // Download any transactions we detected.
-                let transactions = self.fetch_transactions(&mut filtered_block).await?;
-
-                // LPNFT asset IDs won't be known to the chain, so we need to pre-populate them in the local
-                // registry based on transaction contents.
-                for transaction in &transactions {
-                    for action in transaction.actions() {
-                        match action {
-                            penumbra_transaction::Action::PositionOpen(position_open) => {
-                                let position_id = position_open.position.id();
-
-                                // Record every possible permutation.
-                                let lp_nft = LpNft::new(position_id, position::State::Opened);
-                                let _id = lp_nft.asset_id();
-                                let denom = lp_nft.denom();
-                                self.storage.record_asset(denom).await?;
-
-                                let lp_nft = LpNft::new(position_id, position::State::Closed);
-                                let _id = lp_nft.asset_id();
-                                let denom = lp_nft.denom();
-                                self.storage.record_asset(denom).await?;
-
-                                let lp_nft = LpNft::new(
-                                    position_id,
-                                    position::State::Withdrawn { sequence: 0 },
-                                );
-                                let _id = lp_nft.asset_id();
-                                let denom = lp_nft.denom();
-                                self.storage.record_asset(denom).await?;
-
-                                // Record the position itself
-                                self.storage
-                                    .record_position(position_open.position.clone())
-                                    .await?;
-                            }
-                            penumbra_transaction::Action::PositionClose(position_close) => {
-                                let position_id = position_close.position_id;
-
-                                // Update the position record
-                                self.storage
-                                    .update_position(position_id, position::State::Closed)
-                                    .await?;
-                            }
-                            penumbra_transaction::Action::PositionWithdraw(position_withdraw) => {
-                                let position_id = position_withdraw.position_id;
-
-                                // Record the LPNFT for the current sequence number.
-                                let state = position::State::Withdrawn {
-                                    sequence: position_withdraw.sequence,
-                                };
-                                let lp_nft = LpNft::new(position_id, state);
-                                let denom = lp_nft.denom();
-                                self.storage.record_asset(denom).await?;
-
-                                // Update the position record
-                                self.storage.update_position(position_id, state).await?;
-                            }
-                            penumbra_transaction::Action::ActionDutchAuctionSchedule(
-                                schedule_da,
-                            ) => {
-                                let auction_id = schedule_da.description.id();
-                                let auction_nft_opened = AuctionNft::new(auction_id, 0);
-                                let nft_metadata_opened = auction_nft_opened.metadata.clone();
-
-                                self.storage.record_asset(nft_metadata_opened).await?;
-
-                                self.storage
-                                    .record_auction_with_state(
-                                        schedule_da.description.id(),
-                                        0u64, // Opened
-                                    )
-                                    .await?;
-                            }
-                            penumbra_transaction::Action::ActionDutchAuctionEnd(end_da) => {
-                                let auction_id = end_da.auction_id;
-                                let auction_nft_closed = AuctionNft::new(auction_id, 1);
-                                let nft_metadata_closed = auction_nft_closed.metadata.clone();
-
-                                self.storage.record_asset(nft_metadata_closed).await?;
-
-                                self.storage
-                                    .record_auction_with_state(end_da.auction_id, 1)
-                                    .await?;
-                            }
-                            penumbra_transaction::Action::ActionDutchAuctionWithdraw(
-                                withdraw_da,
-                            ) => {
-                                let auction_id = withdraw_da.auction_id;
-                                let auction_nft_withdrawn =
-                                    AuctionNft::new(auction_id, withdraw_da.seq);
-                                let nft_metadata_withdrawn = auction_nft_withdrawn.metadata.clone();
-
-                                self.storage.record_asset(nft_metadata_withdrawn).await?;
-                                self.storage
-                                    .record_auction_with_state(auction_id, withdraw_da.seq)
-                                    .await?;
-                            }
-                            _ => (),
-                        };
+                self.sync_block(wallet, block.clone(), &mut block_transactions)
+                    .await?;
+                *next_height = height + 1;
+            }
+
+            // Check if we should stop waiting for blocks to arrive, because the view
+            // services are dropped and we're supposed to shut down.
+            if self
+                .wallets
+                .iter()
+                .all(|wallet| wallet.sync_height_tx.is_closed())
+            {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Queries for the denom metadata of the given assets, omitting the assets
+    /// which have none.
+    ///
+    /// The assets are requested all at once, unless the node predates the batched
+    /// query, in which case they are requested one at a time.
+    async fn fetch_asset_metadata(
+        &self,
+        asset_ids: &BTreeSet<asset::Id>,
+    ) -> anyhow::Result<Vec<Metadata>> {
+        let mut client = ShieldedPoolQueryServiceClient::new(self.channel.clone());
+        let mut metadata = Vec::new();
+        match client
+            .asset_metadata_by_ids(AssetMetadataByIdsRequest {
+                asset_id: asset_ids.iter().copied().map(Into::into).collect(),
+            })
+            .await
+        {
+            Ok(responses) => {
+                let mut responses = responses.into_inner();
+                while let Some(response) = responses.message().await? {
+                    if let Some(denom_metadata) = response.denom_metadata {
+                        metadata.push(denom_metadata.try_into()?);
                     }
                 }
-
-                // Record any new assets we detected.
-                for note_record in filtered_block.new_notes.values() {
-                    // If the asset is already known, skip it, unless there's useful information
-                    // to cross-reference.
-                    if let Some(note_denom) = self
-                        .storage
-                        .asset_by_id(&note_record.note.asset_id())
+            }
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                tracing::debug!("node does not support batched asset metadata queries");
+                for asset_id in asset_ids {
+                    if let Some(denom_metadata) = client
+                        .asset_metadata_by_id(AssetMetadataByIdRequest {
+                            asset_id: Some((*asset_id).into()),
+                        })
                         .await?
+                        .into_inner()
+                        .denom_metadata
                     {
-                        // If the asset metata is for an auction, we record the associated note commitment
-                        // in the auction state table to cross reference with SNRs.
-                        if note_denom.is_auction_nft() {
-                            let note_commitment = note_record.note_commitment;
-                            let auction_nft: AuctionNft = note_denom.try_into()?;
-                            self.storage
-                                .update_auction_with_note_commitment(
-                                    auction_nft.id,
-                                    note_commitment,
+                        metadata.push(denom_metadata.try_into()?);
+                    }
+                }
+            }
+            Err(status) => return Err(status.into()),
+        }
+        Ok(metadata)
+    }
+
+    /// Scans a compact block for one wallet and records its changes.
+    async fn sync_block(
+        &self,
+        wallet: &WalletSync,
+        block: CompactBlock,
+        block_transactions: &mut Option<Vec<Transaction>>,
+    ) -> anyhow::Result<()> {
+        let height = block.height;
+
+        // Lock the SCT only while processing this block.
+        let mut sct_guard = wallet.sct.write().await;
+
+        if !block.requires_scanning() {
+            // Optimization: if the block is empty, seal the in-memory SCT,
+            // and skip touching the database:
+            sct_guard.end_block()?;
+            // We also need to end the epoch, since if there are no funding streams, then an
+            // epoch boundary won't necessarily require scanning:
+            if block.epoch_root.is_some() {
+                sct_guard
+                    .end_epoch()
+                    .expect("ending the epoch must succeed");
+            }
+            wallet.storage.record_empty_block(height).await?;
+            // Notify all watchers of the new height we just recorded.
+            wallet.sync_height_tx.send(height)?;
+        } else {
+            // Otherwise, scan the block and commit its changes:
+            let mut filtered_block =
+                scan_block(&wallet.fvk, &mut sct_guard, block, &wallet.storage).await?;
+
+            // Download any transactions we detected.
+            let transactions = self
+                .fetch_transactions(&mut filtered_block, block_transactions)
+                .await?;
+
+            // LPNFT asset IDs won't be known to the chain, so we need to pre-populate them in the local
+            // registry based on transaction contents.
+            for transaction in &transactions {
+                for action in transaction.actions() {
+                    match action {
+                        penumbra_transaction::Action::PositionOpen(position_open) => {
+                            let position_id = position_open.position.id();
+
+                            // Record every possible permutation.
+                            let lp_nft = LpNft::new(position_id, position::State::Opened);
+                            let _id = lp_nft.asset_id();
+                            let denom = lp_nft.denom();
+                            wallet.storage.record_asset(denom).await?;
+
+                            let lp_nft = LpNft::new(position_id, position::State::Closed);
+                            let _id = lp_nft.asset_id();
+                            let denom = lp_nft.denom();
+                            wallet.storage.record_asset(denom).await?;
+
+                            let lp_nft =
+                                LpNft::new(position_id, position::State::Withdrawn { sequence: 0 });
+                            let _id = lp_nft.asset_id();
+                            let denom = lp_nft.denom();
+                            wallet.storage.record_asset(denom).await?;
+
+                            // Record the position itself
+                            wallet
+                                .storage
+                                .record_position(position_open.position.clone())
+                                .await?;
+                        }
+                        penumbra_transaction::Action::PositionClose(position_close) => {
+                            let position_id = position_close.position_id;
+
+                            // Update the position record
+                            wallet
+                                .storage
+                                .update_position(position_id, position::State::Closed)
+                                .await?;
+                        }
+                        penumbra_transaction::Action::PositionWithdraw(position_withdraw) => {
+                            let position_id = position_withdraw.position_id;
+
+                            // Record the LPNFT for the current sequence number.
+                            let state = position::State::Withdrawn {
+                                sequence: position_withdraw.sequence,
+                            };
+                            let lp_nft = LpNft::new(position_id, state);
+                            let denom = lp_nft.denom();
+                            wallet.storage.record_asset(denom).await?;
+
+                            // Update the position record
+                            wallet.storage.update_position(position_id, state).await?;
+                        }
+                        penumbra_transaction::Action::ActionDutchAuctionSchedule(schedule_da) => {
+                            let auction_id = schedule_da.description.id();
+                            let auction_nft_opened = AuctionNft::new(auction_id, 0);
+                            let nft_metadata_opened = auction_nft_opened.metadata.clone();
+
+                            wallet.storage.record_asset(nft_metadata_opened).await?;
+
+                            wallet
+                                .storage
+                                .record_auction_with_state(
+                                    schedule_da.description.id(),
+                                    0u64, // Opened
                                 )
                                 .await?;
                         }
-                        continue;
-                    } else {
-                        // If the asset is unknown, we may be able to query for its denom metadata and store that.
-
-                        let mut client = ShieldedPoolQueryServiceClient::new(self.channel.clone());
-                        if let Some(denom_metadata) = client
-                            .asset_metadata_by_id(AssetMetadataByIdRequest {
-                                asset_id: Some(note_record.note.asset_id().into()),
-                            })
-                            .await?
-                            .into_inner()
-                            .denom_metadata
-                        {
-                            // If we get metadata: great, record it.
-                            self.storage
-                                .record_asset(denom_metadata.try_into()?)
+                        penumbra_transaction::Action::ActionDutchAuctionEnd(end_da) => {
+                            let auction_id = end_da.auction_id;
+                            let auction_nft_closed = AuctionNft::new(auction_id, 1);
+                            let nft_metadata_closed = auction_nft_closed.metadata.clone();
+
+                            wallet.storage.record_asset(nft_metadata_closed).await?;
+
+                            wallet
+                                .storage
+                                .record_auction_with_state(end_da.auction_id, 1)
                                 .await?;
-                        } else {
-                            tracing::warn!(asset_id = ?note_record.note.asset_id(), "received unknown asset ID with no available metadata");
                         }
-                    }
+                        penumbra_transaction::Action::ActionDutchAuctionWithdraw(withdraw_da) => {
+                            let auction_id = withdraw_da.auction_id;
+                            let auction_nft_withdrawn =
+                                AuctionNft::new(auction_id, withdraw_da.seq);
+                            let nft_metadata_withdrawn = auction_nft_withdrawn.metadata.clone();
+
+                            wallet.storage.record_asset(nft_metadata_withdrawn).await?;
+                            wallet
+                                .storage
+                                .record_auction_with_state(auction_id, withdraw_da.seq)
+                                .await?;
+                        }
+                        _ => (),
+                    };
                 }
-
-                // Commit the block to the database.
-                self.storage
-                    .record_block(
-                        filtered_block.clone(),
-                        transactions,
-                        &mut sct_guard,
-                        self.channel.clone(),
-                    )
-                    .await?;
-                // Notify all watchers of the new height we just recorded.
-                self.sync_height_tx.send(filtered_block.height)?;
             }
-            #[cfg(feature = "sct-divergence-check")]
-            sct_divergence_check(self.channel.clone(), height, sct_guard.root()).await?;
 
-            // Release the SCT RwLock
-            drop(sct_guard);
+            // Record any new assets we detected.
+            let mut unknown_assets = BTreeSet::new();
+            for note_record in filtered_block.new_notes.values() {
+                // If the asset is already known, skip it, unless there's useful information
+                // to cross-reference.
+                if let Some(note_denom) = wallet
+                    .storage
+                    .asset_by_id(&note_record.note.asset_id())
+                    .await?
+                {
+                    // If the asset metata is for an auction, we record the associated note commitment
+                    // in the auction state table to cross reference with SNRs.
+                    if note_denom.is_auction_nft() {
+                        let note_commitment = note_record.note_commitment;
+                        let auction_nft: AuctionNft = note_denom.try_into()?;
+                        wallet
+                            .storage
+                            .update_auction_with_note_commitment(auction_nft.id, note_commitment)
+                            .await?;
+                    }
+                } else {
+                    unknown_assets.insert(note_record.note.asset_id());
+                }
+            }
 
-            // Check if we should stop waiting for blocks to arrive, because the view
-            // services are dropped and we're supposed to shut down.
-            if self.sync_height_tx.is_closed() {
-                return Ok(());
+            // If any assets are unknown, we may be able to query for their denom metadata
+            // and store that, in a single request for the whole block.
+            if !unknown_assets.is_empty() {
+                for denom_metadata in self.fetch_asset_metadata(&unknown_assets).await? {
+                    unknown_assets.remove(&denom_metadata.id());
+                    // If we get metadata: great, record it.
+                    wallet.storage.record_asset(denom_metadata).await?;
+                }
+                for asset_id in unknown_assets {
+                    tracing::warn!(
+                        ?asset_id,
+                        "received unknown asset ID with no available metadata"
+                    );
+                }
             }
+
+            // Commit the block to the database.
+            wallet
+                .storage
+                .record_block(
+                    filtered_block.clone(),
+                    transactions,
+                    &mut sct_guard,
+                    self.channel.clone(),
+                )
+                .await?;
+            // Notify all watchers of the new height we just recorded.
+            wallet.sync_height_tx.send(filtered_block.height)?;
         }
+        #[cfg(feature = "sct-divergence-check")]
+        sct_divergence_check(self.channel.clone(), height, sct_guard.root()).await?;
+
+        // Release the SCT RwLock
+        drop(sct_guard);
 
         Ok(())
     }
@@ -519,3 +628,136 @@ async fn sct_divergence_check(
         Err(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use futures::StreamExt;
+    use penumbra_app::params::AppParameters;
+    use penumbra_fee::GasPrices;
+    use penumbra_keys::{
+        keys::{Bip44Path, SpendKey},
+        test_keys,
+    };
+    use penumbra_proto::core::component::compact_block::v1::{
+        self as pb,
+        query_service_server::{QueryService, QueryServiceServer},
+    };
+    use tokio_stream::wrappers::TcpListenerStream;
+
+    use super::*;
+
+    /// A node serving a fixed chain of compact blocks, recording the height of
+    /// each block it serves.
+    struct MockNode {
+        blocks: Vec<CompactBlock>,
+        served: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[tonic::async_trait]
+    impl QueryService for MockNode {
+        type CompactBlockRangeStream = Pin<
+            Box<
+                dyn futures::Stream<Item = Result<pb::CompactBlockRangeResponse, tonic::Status>>
+                    + Send,
+            >,
+        >;
+
+        async fn compact_block_range(
+            &self,
+            request: tonic::Request<pb::CompactBlockRangeRequest>,
+        ) -> Result<tonic::Response<Self::CompactBlockRangeStream>, tonic::Status> {
+            // The stream ends with the chain, rather than being kept alive.
+            let start_height = request.into_inner().start_height as usize;
+            let blocks = self.blocks[start_height..].to_vec();
+            self.served
+                .lock()
+                .unwrap()
+                .extend(blocks.iter().map(|block| block.height));
+
+            Ok(tonic::Response::new(
+                futures::stream::iter(blocks.into_iter().map(|block| {
+                    Ok(pb::CompactBlockRangeResponse {
+                        compact_block: Some(block.into()),
+                    })
+                }))
+                .boxed(),
+            ))
+        }
+
+        async fn compact_block(
+            &self,
+            _request: tonic::Request<pb::CompactBlockRequest>,
+        ) -> Result<tonic::Response<pb::CompactBlockResponse>, tonic::Status> {
+            Err(tonic::Status::unimplemented("not served by the mock node"))
+        }
+    }
+
+    /// Serves the first `count` blocks of a chain in which every block updates
+    /// the gas prices, so that every block is scanned and committed.
+    async fn mock_node(count: u64) -> anyhow::Result<(Channel, Arc<Mutex<Vec<u64>>>)> {
+        let blocks = (0..count)
+            .map(|height| CompactBlock {
+                height,
+                gas_prices: Some(GasPrices::zero()),
+                ..Default::default()
+            })
+            .collect();
+        let served = Arc::new(Mutex::new(Vec::new()));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(QueryServiceServer::new(MockNode {
+                    blocks,
+                    served: served.clone(),
+                }))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let channel = Channel::from_shared(format!("http://{addr}"))?
+            .connect()
+            .await?;
+        Ok((channel, served))
+    }
+
+    #[tokio::test]
+    async fn wallets_are_scanned_from_their_own_sync_heights() -> anyhow::Result<()> {
+        let other_fvk = SpendKey::from_seed_phrase_bip44(
+            test_keys::SEED_PHRASE.parse()?,
+            &Bip44Path::new(1),
+        )
+        .full_viewing_key()
+        .clone();
+        let behind = Storage::initialize(
+            None::<&str>,
+            test_keys::FULL_VIEWING_KEY.clone(),
+            AppParameters::default(),
+        )
+        .await?;
+        let ahead = Storage::initialize(None::<&str>, other_fvk, AppParameters::default()).await?;
+
+        // Sync one wallet up to height 2 on its own.
+        let (channel, _) = mock_node(3).await?;
+        let (mut worker, _handles, _) = Worker::new(vec![ahead.clone()], channel).await?;
+        worker.sync().await?;
+        assert_eq!(ahead.last_sync_height().await?, Some(2));
+
+        // Then sync both wallets together. Rescanning a block a wallet has
+        // already recorded would fail, so this also checks that each wallet
+        // resumes from its own height.
+        let (channel, served) = mock_node(6).await?;
+        let (mut worker, _handles, _) =
+            Worker::new(vec![behind.clone(), ahead.clone()], channel).await?;
+        worker.sync().await?;
+
+        assert_eq!(behind.last_sync_height().await?, Some(5));
+        assert_eq!(ahead.last_sync_height().await?, Some(5));
+        // Each block was downloaded once, for both wallets.
+        assert_eq!(*served.lock().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+
+        Ok(())
+    }
+}
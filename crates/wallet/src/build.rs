@@ -2,7 +2,7 @@ use anyhow::Result;
 
 use penumbra_custody::{AuthorizeRequest, CustodyClient};
 use penumbra_keys::FullViewingKey;
-use penumbra_transaction::{AuthorizationData, Transaction, TransactionPlan};
+use penumbra_transaction::{AuthorizationData, BuildProgress, Transaction, TransactionPlan};
 use penumbra_view::ViewClient;
 
 pub async fn build_transaction<V, C>(
@@ -11,6 +11,22 @@ pub async fn build_transaction<V, C>(
     custody: &mut C,
     plan: TransactionPlan,
 ) -> Result<Transaction>
+where
+    V: ViewClient,
+    C: CustodyClient,
+{
+    build_transaction_with_progress(fvk, view, custody, plan, |_| {}).await
+}
+
+/// Like [`build_transaction`], but calls `progress` as the transaction's
+/// actions are built.
+pub async fn build_transaction_with_progress<V, C>(
+    fvk: &FullViewingKey,
+    view: &mut V,
+    custody: &mut C,
+    plan: TransactionPlan,
+    progress: impl FnMut(BuildProgress),
+) -> Result<Transaction>
 where
     V: ViewClient,
     C: CustodyClient,
@@ -32,14 +48,14 @@ where
     // ... and then build the transaction:
     #[cfg(not(feature = "parallel"))]
     {
-        let tx = plan.build(fvk, &witness_data, &auth_data)?;
+        let tx = plan.build_with_progress(fvk, &witness_data, &auth_data, progress)?;
         return Ok(tx);
     }
 
     #[cfg(feature = "parallel")]
     {
         let tx = plan
-            .build_concurrent(fvk, &witness_data, &auth_data)
+            .build_concurrent_with_progress(fvk, &witness_data, &auth_data, progress)
             .await
             .map_err(|_| tonic::Status::failed_precondition("Error building transaction"))?;
 
@@ -2,7 +2,7 @@
 use std::path::PathBuf;
 use std::{
     env, fs,
-    io::{BufWriter, Result},
+    io::{BufWriter, Error, ErrorKind, Result},
 };
 
 use ark_groth16::{ProvingKey, VerifyingKey};
@@ -22,6 +22,17 @@ use penumbra_shielded_pool::{
 };
 use rand_core::OsRng;
 
+/// The names of the circuits whose parameters this tool can generate.
+const CIRCUITS: &[&str] = &[
+    "spend",
+    "output",
+    "swap",
+    "swapclaim",
+    "convert",
+    "delegator_vote",
+    "nullifier_derivation",
+];
+
 fn generate_parameters<D: DummyWitness>() -> (ProvingKey<Bls12_377>, VerifyingKey<Bls12_377>) {
     let matrices = generate_constraint_matrices::<D>();
 
@@ -73,33 +84,62 @@ fn main() -> Result<()> {
         .join("gen");
     println!("{}", target_dir.display());
 
+    // Circuit names may be passed as arguments to regenerate only those parameters,
+    // e.g. after a change to a single circuit. With no arguments, every circuit is
+    // regenerated.
+    let selected: Vec<String> = env::args().skip(1).collect();
+    let wanted = |name: &str| selected.is_empty() || selected.iter().any(|s| s == name);
+    for name in &selected {
+        if !CIRCUITS.contains(&name.as_str()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown circuit {name}, expected one of {CIRCUITS:?}"),
+            ));
+        }
+    }
+
     // Generate the parameters for the current proofs and serialize them
     // to files in the target directory.
-    let (spend_pk, spend_vk) = generate_parameters::<SpendCircuit>();
-    write_params(&target_dir, "spend", &spend_pk, &spend_vk)?;
-    let (output_pk, output_vk) = generate_parameters::<OutputCircuit>();
-    write_params(&target_dir, "output", &output_pk, &output_vk)?;
-    let (swap_pk, swap_vk) = generate_parameters::<SwapCircuit>();
-    write_params(&target_dir, "swap", &swap_pk, &swap_vk)?;
-    let (swapclaim_pk, swapclaim_vk) = generate_parameters::<SwapClaimCircuit>();
-    write_params(&target_dir, "swapclaim", &swapclaim_pk, &swapclaim_vk)?;
-    let (convert_pk, convert_vk) = generate_parameters::<ConvertCircuit>();
-    write_params(&target_dir, "convert", &convert_pk, &convert_vk)?;
-    let (delegator_vote_pk, delegator_vote_vk) = generate_parameters::<DelegatorVoteCircuit>();
-    write_params(
-        &target_dir,
-        "delegator_vote",
-        &delegator_vote_pk,
-        &delegator_vote_vk,
-    )?;
-    let (nullifier_derivation_pk, nullifier_derivation_vk) =
-        generate_parameters::<NullifierDerivationCircuit>();
-    write_params(
-        &target_dir,
-        "nullifier_derivation",
-        &nullifier_derivation_pk,
-        &nullifier_derivation_vk,
-    )?;
+    if wanted("spend") {
+        let (spend_pk, spend_vk) = generate_parameters::<SpendCircuit>();
+        write_params(&target_dir, "spend", &spend_pk, &spend_vk)?;
+    }
+    if wanted("output") {
+        let (output_pk, output_vk) = generate_parameters::<OutputCircuit>();
+        write_params(&target_dir, "output", &output_pk, &output_vk)?;
+    }
+    if wanted("swap") {
+        let (swap_pk, swap_vk) = generate_parameters::<SwapCircuit>();
+        write_params(&target_dir, "swap", &swap_pk, &swap_vk)?;
+    }
+    if wanted("swapclaim") {
+        let (swapclaim_pk, swapclaim_vk) = generate_parameters::<SwapClaimCircuit>();
+        write_params(&target_dir, "swapclaim", &swapclaim_pk, &swapclaim_vk)?;
+    }
+    if wanted("convert") {
+        let (convert_pk, convert_vk) = generate_parameters::<ConvertCircuit>();
+        write_params(&target_dir, "convert", &convert_pk, &convert_vk)?;
+    }
+    if wanted("delegator_vote") {
+        let (delegator_vote_pk, delegator_vote_vk) =
+            generate_parameters::<DelegatorVoteCircuit>();
+        write_params(
+            &target_dir,
+            "delegator_vote",
+            &delegator_vote_pk,
+            &delegator_vote_vk,
+        )?;
+    }
+    if wanted("nullifier_derivation") {
+        let (nullifier_derivation_pk, nullifier_derivation_vk) =
+            generate_parameters::<NullifierDerivationCircuit>();
+        write_params(
+            &target_dir,
+            "nullifier_derivation",
+            &nullifier_derivation_pk,
+            &nullifier_derivation_vk,
+        )?;
+    }
     // NOTE: New proofs go here following the approach above.
 
     Ok(())